@@ -28,26 +28,26 @@ pub(crate) fn cumu_method_derive(input: TokenStream) -> TokenStream {
         })
         .collect();
 
+    // pairs of rate fields and the cumulative fields they are integrated into
     let (pwr_fields, energy_fields): (Vec<TokenStream2>, Vec<TokenStream2>) = if struct_is_state {
         fields
             .iter()
             .filter_map(|x| {
                 let field_str = &x.ident.as_ref().unwrap().to_string();
-                if ENERGY_REGEX.is_match(field_str) {
-                    // the field name looks like `energy_*`
-                    let key = ENERGY_REGEX.captures(field_str).unwrap()[1].to_string();
-                    if fields
-                        .iter()
-                        .any(|x| *x.ident.as_ref().unwrap() == format!("pwr_{}", key))
-                    {
-                        // there is also a matching `pwr_*` field
-                        Some((
-                            format!("pwr_{}", key).parse().unwrap(),
-                            field_str.clone().parse().unwrap(),
-                        ))
-                    } else {
-                        None
-                    }
+                let rate_str = match ENERGY_REGEX.captures(field_str) {
+                    // the field name looks like `energy_*`, so look for a matching `pwr_*` field
+                    Some(caps) => format!("pwr_{}", &caps[1]),
+                    // otherwise, look for a matching `*_rate` field
+                    None => format!("{}_rate", field_str),
+                };
+                if fields
+                    .iter()
+                    .any(|x| *x.ident.as_ref().unwrap() == rate_str)
+                {
+                    Some((
+                        rate_str.parse().unwrap(),
+                        field_str.clone().parse().unwrap(),
+                    ))
                 } else {
                     None
                 }
@@ -70,16 +70,47 @@ pub(crate) fn cumu_method_derive(input: TokenStream) -> TokenStream {
             // this tells the compiler that the `SetCumulative` trait is not manually derived
             #[automatically_derived]
             impl SetCumulative for #ident {
-                fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+                fn set_cumulative_with<F: Fn() -> String>(
+                    &mut self,
+                    dt: si::Time,
+                    integration_method: IntegrationMethod,
+                    loc: F,
+                ) -> anyhow::Result<()> {
                     #(self
                         .#energy_fields
                         .increment(
-                            *self.#pwr_fields.get_fresh(|| format_dbg!())? * dt,
+                            match integration_method {
+                                IntegrationMethod::Rectangle => {
+                                    *self.#pwr_fields.get_fresh(|| format_dbg!())? * dt
+                                }
+                                // previous half was added by `set_cumulative_prev`
+                                IntegrationMethod::Trapezoid => {
+                                    0.5 * *self.#pwr_fields.get_fresh(|| format_dbg!())? * dt
+                                }
+                            },
                             || format_dbg!()
                         )?;
                     )*
                     Ok(())
                 }
+
+                fn set_cumulative_prev<F: Fn() -> String>(
+                    &mut self,
+                    dt: si::Time,
+                    integration_method: IntegrationMethod,
+                    loc: F,
+                ) -> anyhow::Result<()> {
+                    if integration_method == IntegrationMethod::Trapezoid {
+                        #(self
+                            .#energy_fields
+                            .increment_stale(
+                                0.5 * *self.#pwr_fields.get_stale(|| format!("{}\n{}", loc(), format_dbg!()))? * dt,
+                                || format_dbg!()
+                            )?;
+                        )*
+                    }
+                    Ok(())
+                }
             }
         });
     } else if struct_has_state {
@@ -87,9 +118,25 @@ pub(crate) fn cumu_method_derive(input: TokenStream) -> TokenStream {
             // this tells the compiler that the `SetCumulative` trait is not manually derived
             #[automatically_derived]
             impl SetCumulative for #ident {
-                fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
-                    self.state.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
-                    #(self.#fields_with_state.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;)*
+                fn set_cumulative_with<F: Fn() -> String>(
+                    &mut self,
+                    dt: si::Time,
+                    integration_method: IntegrationMethod,
+                    loc: F,
+                ) -> anyhow::Result<()> {
+                    self.state.set_cumulative_with(dt, integration_method, || format!("{}\n{}", loc(), format_dbg!()))?;
+                    #(self.#fields_with_state.set_cumulative_with(dt, integration_method, || format!("{}\n{}", loc(), format_dbg!()))?;)*
+                    Ok(())
+                }
+
+                fn set_cumulative_prev<F: Fn() -> String>(
+                    &mut self,
+                    dt: si::Time,
+                    integration_method: IntegrationMethod,
+                    loc: F,
+                ) -> anyhow::Result<()> {
+                    self.state.set_cumulative_prev(dt, integration_method, || format!("{}\n{}", loc(), format_dbg!()))?;
+                    #(self.#fields_with_state.set_cumulative_prev(dt, integration_method, || format!("{}\n{}", loc(), format_dbg!()))?;)*
                     Ok(())
                 }
             }
@@ -99,8 +146,23 @@ pub(crate) fn cumu_method_derive(input: TokenStream) -> TokenStream {
             // this tells the compiler that the `SetCumulative` trait is not manually derived
             #[automatically_derived]
             impl SetCumulative for #ident {
-                fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
-                    #(self.#fields_with_state.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;)*
+                fn set_cumulative_with<F: Fn() -> String>(
+                    &mut self,
+                    dt: si::Time,
+                    integration_method: IntegrationMethod,
+                    loc: F,
+                ) -> anyhow::Result<()> {
+                    #(self.#fields_with_state.set_cumulative_with(dt, integration_method, || format!("{}\n{}", loc(), format_dbg!()))?;)*
+                    Ok(())
+                }
+
+                fn set_cumulative_prev<F: Fn() -> String>(
+                    &mut self,
+                    dt: si::Time,
+                    integration_method: IntegrationMethod,
+                    loc: F,
+                ) -> anyhow::Result<()> {
+                    #(self.#fields_with_state.set_cumulative_prev(dt, integration_method, || format!("{}\n{}", loc(), format_dbg!()))?;)*
                     Ok(())
                 }
             }
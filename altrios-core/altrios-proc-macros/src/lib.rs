@@ -18,7 +18,9 @@ pub fn serde_api(attr: TokenStream, item: TokenStream) -> TokenStream {
 #[proc_macro_error]
 #[proc_macro_attribute]
 /// Macro for creating appropriate setters and getters for pyo3 struct
-/// attributes and other, non-python API functionality
+/// attributes and other, non-python API functionality.  `#[pyo3_api(repr)]`
+/// additionally adds a Python `__repr__` that calls the struct's `Display`
+/// implementation.
 pub fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
     pyo3_api::pyo3_api(attr, item)
 }
@@ -1,6 +1,17 @@
 use crate::imports::*;
 
-pub(crate) fn pyo3_api(_attr: TokenStream, item: TokenStream) -> TokenStream {
+pub(crate) fn pyo3_api(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let options = syn::parse_macro_input!(
+        attr with syn::punctuated::Punctuated::<Ident, syn::Token![,]>::parse_terminated
+    );
+    let mut add_repr = false;
+    for option in options {
+        if option == "repr" {
+            add_repr = true;
+        } else {
+            abort!(option.span(), "Invalid option, expected `repr`");
+        }
+    }
     let py_impl_block = syn::parse_macro_input!(item as syn::ItemImpl);
     let ident = match *py_impl_block.self_ty {
         syn::Type::Path(type_path) if type_path.path.segments.len() == 1 => {
@@ -16,6 +27,9 @@ pub(crate) fn pyo3_api(_attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     }
     add_serde_methods(&mut py_impl_block_body);
+    if add_repr {
+        add_repr_method(&mut py_impl_block_body);
+    }
     let mut new_py_impl_block: TokenStream2 = Default::default();
     new_py_impl_block.extend(quote! {
         #[allow(non_snake_case)]
@@ -205,3 +219,13 @@ fn add_serde_methods(py_impl_block: &mut TokenStream2) {
         }
     });
 }
+
+/// Adds `__repr__` based on the struct's `Display` implementation, which
+/// should be a concise summary of key fields
+fn add_repr_method(py_impl_block: &mut TokenStream2) {
+    py_impl_block.extend::<TokenStream2>(quote! {
+        fn __repr__(&self) -> String {
+            format!("{self}")
+        }
+    });
+}
@@ -172,11 +172,16 @@ pub(crate) fn serde_attrs_for_si_fields(field: &mut syn::Field) -> Option<()> {
                 uom::si::available_energy::kilojoule_per_kilogram,
                 uom::si::available_energy::megajoule_per_kilogram
             ),
+            "ElectricCurrent" => extract_units!(uom::si::electric_current::ampere),
+            "ElectricPotential" => extract_units!(uom::si::electric_potential::volt),
+            "ElectricalResistance" => extract_units!(uom::si::electrical_resistance::ohm),
             "Energy" => extract_units!(uom::si::energy::joule),
             "Force" => extract_units!(uom::si::force::newton),
             "InverseVelocity" => extract_units!(uom::si::inverse_velocity::second_per_meter),
+            "Jerk" => extract_units!(uom::si::jerk::meter_per_second_cubed),
             "Length" => extract_units!(uom::si::length::meter, uom::si::length::mile),
             "Mass" => extract_units!(uom::si::mass::kilogram),
+            "MassRate" => extract_units!(uom::si::mass_rate::kilogram_per_second),
             "MomentOfInertia" => extract_units!(uom::si::moment_of_inertia::kilogram_square_meter),
             "Power" => extract_units!(uom::si::power::watt),
             "SpecificPower" => extract_units!(uom::si::specific_power::watt_per_kilogram),
@@ -219,7 +224,9 @@ pub(crate) fn serde_attrs_for_si_fields(field: &mut syn::Field) -> Option<()> {
                 uom::si::velocity::mile_per_hour
             ),
             "Volume" => extract_units!(uom::si::volume::cubic_meter, uom::si::volume::liter),
+            "VolumeRate" => extract_units!(uom::si::volume_rate::cubic_meter_per_second),
             "MassDensity" => extract_units!(uom::si::mass_density::kilogram_per_cubic_meter),
+            "MassPerEnergy" => extract_units!(uom::si::mass_per_energy::kilogram_per_joule),
             _ => abort!(
                 inner_path.span(),
                 "Unknown si quantity! Make sure it's implemented in `impl_getters_and_setters`"
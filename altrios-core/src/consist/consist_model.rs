@@ -1,4 +1,5 @@
 use super::*;
+use crate::consist::locomotive::powertrain::electric_drivetrain::DynBrakeThermal;
 
 #[serde_api]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, SetCumulative, StateMethods)]
@@ -12,6 +13,19 @@ pub struct Consist {
 
     /// power distribution control type
     pub pdct: PowerDistributionControlType,
+    /// Optional consist-level cap on the rate of increase of total commanded
+    /// power, e.g. for gentle throttle handling to manage in-train forces.  If
+    /// `None`, the consist rate is the sum of per-locomotive rates.
+    #[serde(default)]
+    pub pwr_rate_out_max: Option<si::PowerRate>,
+    /// Distributed power command propagation delay from the lead locomotive
+    /// (i.e. first in [Self::loco_vec]) to remote locomotives.  Remote
+    /// locomotives apply the power command from `ceil(command_delay / dt)`
+    /// time steps earlier rather than the current one.  Zero (default) means
+    /// no delay.  Train motion is still solved from the requested power, and
+    /// the difference is recorded in [ConsistState::pwr_out_delay_deficit].
+    #[serde(default)]
+    pub command_delay: si::Time,
     #[serde(default = "utils::return_true")]
     // setter needs to also apply to individual locomotives
     /// whether to panic if TPC requires more power than consist can deliver
@@ -25,13 +39,38 @@ pub struct Consist {
     save_interval: Option<usize>,
     #[serde(skip)]
     n_res_equipped: Option<u8>,
+    /// Per-locomotive power commands from previous time steps, oldest first,
+    /// used for [Self::command_delay]
+    #[serde(skip)]
+    pwr_out_cmd_buffer: VecDeque<Vec<si::Power>>,
 }
 
 impl StateMethods for Vec<Locomotive> {}
 impl SetCumulative for Vec<Locomotive> {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
         for (loco_idx, loco) in self.iter_mut().enumerate() {
-            loco.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!(loco_idx)))?;
+            loco.set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!(loco_idx))
+            })?;
+        }
+        Ok(())
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        for (loco_idx, loco) in self.iter_mut().enumerate() {
+            loco.set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!(loco_idx))
+            })?;
         }
         Ok(())
     }
@@ -64,16 +103,34 @@ impl CheckAndResetState for Vec<Locomotive> {
     }
 }
 
-#[pyo3_api]
+#[pyo3_api(repr)]
 impl Consist {
     #[new]
-    #[pyo3(signature = (loco_vec, save_interval=None))]
-    fn __new__(loco_vec: Vec<Locomotive>, save_interval: Option<usize>) -> anyhow::Result<Self> {
-        Ok(Self::new(
+    #[pyo3(signature = (
+        loco_vec,
+        save_interval=None,
+        pwr_rate_out_max_watts_per_second=None,
+        command_delay_seconds=None,
+    ))]
+    fn __new__(
+        loco_vec: Vec<Locomotive>,
+        save_interval: Option<usize>,
+        pwr_rate_out_max_watts_per_second: Option<f64>,
+        command_delay_seconds: Option<f64>,
+    ) -> anyhow::Result<Self> {
+        let mut consist = Self::new(
             loco_vec,
             save_interval,
             PowerDistributionControlType::default(),
-        ))
+        );
+        consist.pwr_rate_out_max = pwr_rate_out_max_watts_per_second.map(|r| r * uc::WPS);
+        consist.command_delay = command_delay_seconds.unwrap_or_default() * uc::S;
+        ensure!(
+            consist.command_delay >= si::Time::ZERO,
+            "{}\n`command_delay_seconds` must not be negative",
+            format_dbg!()
+        );
+        Ok(consist)
     }
 
     #[staticmethod]
@@ -96,6 +153,49 @@ impl Consist {
         Ok(Pyo3VecLocoWrapper(self.drain_loco_vec(start, end)))
     }
 
+    #[pyo3(name = "insert_locomotive")]
+    fn insert_locomotive_py(&mut self, idx: usize, loco: Locomotive) -> anyhow::Result<()> {
+        self.insert_locomotive(idx, loco)
+    }
+
+    #[pyo3(name = "remove_locomotive")]
+    fn remove_locomotive_py(&mut self, idx: usize) -> anyhow::Result<Locomotive> {
+        self.remove_locomotive(idx)
+    }
+
+    #[pyo3(name = "describe_topology")]
+    /// Mermaid flowchart of locomotives in order, with types and key specs
+    fn describe_topology_py(&self) -> String {
+        self.describe_topology()
+    }
+
+    #[pyo3(name = "set_adhesion_factor")]
+    fn set_adhesion_factor_py(&mut self, adhesion_factor: f64) -> anyhow::Result<()> {
+        self.set_adhesion_factor(adhesion_factor * uc::R)
+    }
+
+    #[pyo3(name = "set_dyn_brake_thermal")]
+    #[pyo3(signature = (dyn_brake_thermal=None))]
+    /// Enables dynamic brake fade on every locomotive with `dyn_brake_thermal`
+    /// settings, or disables it if `None`
+    fn set_dyn_brake_thermal_py(
+        &mut self,
+        dyn_brake_thermal: Option<DynBrakeThermal>,
+    ) -> anyhow::Result<()> {
+        self.set_dyn_brake_thermal(dyn_brake_thermal)
+    }
+
+    #[pyo3(name = "set_initial_soc")]
+    fn set_initial_soc_py(&mut self, soc: f64) -> anyhow::Result<()> {
+        self.set_initial_soc(soc * uc::R)
+    }
+
+    #[pyo3(name = "strip_history")]
+    /// Clear history and cascade to nested components.
+    fn strip_history_py(&mut self) {
+        self.strip_history();
+    }
+
     #[pyo3(name = "set_save_interval")]
     #[pyo3(signature = (save_interval=None))]
     /// Set save interval and cascade to nested components.
@@ -110,6 +210,13 @@ impl Consist {
         Ok(self.get_save_interval())
     }
 
+    #[pyo3(name = "check_save_interval_consistency")]
+    /// Raise an error if any locomotive or nested component has a save
+    /// interval that differs from the consist's.
+    fn check_save_interval_consistency_py(&self) -> anyhow::Result<()> {
+        self.check_save_interval_consistency()
+    }
+
     // methods setting values for hct, which is not directly exposed to python because enums
     // with fields are not supported by pyo3.
 
@@ -141,6 +248,28 @@ impl Consist {
         Ok(self.get_energy_fuel()?.get::<si::joule>())
     }
 
+    #[pyo3(name = "get_co2_mass_kg")]
+    fn get_co2_mass_py(&self) -> anyhow::Result<f64> {
+        Ok(self.get_co2_mass()?.get::<si::kilogram>())
+    }
+
+    #[pyo3(name = "get_fuel_volume_cubic_meters")]
+    fn get_fuel_volume_py(&self) -> anyhow::Result<f64> {
+        Ok(self.get_fuel_volume()?.get::<si::cubic_meter>())
+    }
+
+    #[pyo3(name = "energy_by_loco_type")]
+    /// Returns dict of `(fuel_joules, res_joules)` keyed by locomotive type
+    fn energy_by_loco_type_py(&self) -> anyhow::Result<HashMap<String, (f64, f64)>> {
+        Ok(self
+            .energy_by_loco_type()?
+            .into_iter()
+            .map(|(loco_type, (fuel, res))| {
+                (loco_type, (fuel.get::<si::joule>(), res.get::<si::joule>()))
+            })
+            .collect())
+    }
+
     #[getter("force_max_lbs")]
     fn get_force_max_pounds_py(&self) -> anyhow::Result<f64> {
         Ok(self.force_max()?.get::<si::pound_force>())
@@ -155,10 +284,50 @@ impl Consist {
     fn get_mass_kg_py(&self) -> anyhow::Result<Option<f64>> {
         Ok(self.mass()?.map(|m| m.get::<si::kilogram>()))
     }
+
+    #[pyo3(name = "can_hold_grade")]
+    fn can_hold_grade_py(
+        &self,
+        grade: f64,
+        speed_meters_per_second: f64,
+        train_mass_kilograms: f64,
+    ) -> anyhow::Result<bool> {
+        self.can_hold_grade(
+            grade * uc::R,
+            speed_meters_per_second * uc::MPS,
+            train_mass_kilograms * uc::KG,
+        )
+    }
+
+    #[pyo3(name = "tractive_effort_curve_newtons")]
+    fn tractive_effort_curve_py(
+        &self,
+        speeds_meters_per_second: Vec<f64>,
+        train_mass_kilograms: f64,
+    ) -> anyhow::Result<Vec<f64>> {
+        Ok(self
+            .tractive_effort_curve(
+                &speeds_meters_per_second
+                    .iter()
+                    .map(|speed| *speed * uc::MPS)
+                    .collect::<Vec<si::Velocity>>(),
+                train_mass_kilograms * uc::KG,
+            )?
+            .iter()
+            .map(|force| force.get::<si::newton>())
+            .collect())
+    }
 }
 
 impl Init for Consist {
     fn init(&mut self) -> Result<(), Error> {
+        if self.loco_vec.is_empty() {
+            return Err(Error::InitError(format!(
+                "{}
+`loco_vec` must contain at least one locomotive",
+                format_dbg!()
+            )));
+        }
         let _mass = self
             .mass()
             .map_err(|err| Error::InitError(format_dbg!(err)))?;
@@ -169,6 +338,12 @@ impl Init for Consist {
         self.pdct.init()?;
         self.state.init()?;
         self.history.init()?;
+        if self.command_delay < si::Time::ZERO {
+            return Err(Error::InitError(format!(
+                "{}\n`command_delay` must not be negative",
+                format_dbg!()
+            )));
+        }
         Ok(())
     }
 }
@@ -185,31 +360,66 @@ impl Consist {
             history: Default::default(),
             save_interval,
             pdct,
+            pwr_rate_out_max: None,
+            command_delay: si::Time::ZERO,
             assert_limits: true,
             n_res_equipped: None,
+            pwr_out_cmd_buffer: Default::default(),
         };
-        let _ = consist.n_res_equipped();
+        consist.refresh_n_res_equipped();
         consist.set_save_interval(save_interval);
         consist
     }
 
+    /// Returns a [mermaid](https://mermaid.js.org/) flowchart of
+    /// [Self::loco_vec] in order, from the lead locomotive to the remotes, with
+    /// each node listing locomotive type, rated power, and RES energy
+    /// capacity, if any, e.g. for documentation
+    pub fn describe_topology(&self) -> String {
+        let mut lines = vec!["flowchart LR".to_string()];
+        for (i, loco) in self.loco_vec.iter().enumerate() {
+            let mut specs = vec![loco.loco_type.to_string()];
+            if let Some(pwr_rated) = loco.pwr_rated() {
+                specs.push(format!("{:.0} kW", pwr_rated.get::<si::kilowatt>()));
+            }
+            if let Some(res) = loco.reversible_energy_storage() {
+                specs.push(format!(
+                    "{:.0} kWh",
+                    res.energy_capacity.get::<si::kilowatt_hour>()
+                ));
+            }
+            lines.push(format!(
+                "    loco{i}[\"#{i} {}: {}\"]",
+                if i == 0 { "lead" } else { "remote" },
+                specs.join(", ")
+            ));
+        }
+        lines.extend((1..self.loco_vec.len()).map(|i| format!("    loco{} --- loco{i}", i - 1)));
+        lines.join("\n")
+    }
+
     /// Returns number of RES-equipped locomotives
     pub fn n_res_equipped(&mut self) -> u8 {
         match self.n_res_equipped {
             Some(n_res_equipped) => n_res_equipped,
-            None => {
-                self.n_res_equipped = Some(self.loco_vec.iter().fold(0, |acc, loco| {
-                    acc + if loco.reversible_energy_storage().is_some() {
-                        1
-                    } else {
-                        0
-                    }
-                }));
-                self.n_res_equipped.unwrap()
-            }
+            None => self.refresh_n_res_equipped(),
         }
     }
 
+    /// Recounts and caches number of RES-equipped locomotives, which must be
+    /// called whenever locomotives are added to or removed from [Self::loco_vec]
+    fn refresh_n_res_equipped(&mut self) -> u8 {
+        let n_res_equipped = self.loco_vec.iter().fold(0, |acc, loco| {
+            acc + if loco.reversible_energy_storage().is_some() {
+                1
+            } else {
+                0
+            }
+        });
+        self.n_res_equipped = Some(n_res_equipped);
+        n_res_equipped
+    }
+
     pub fn set_assert_limits(&mut self, val: bool) {
         self.assert_limits = val;
         for loco in self.loco_vec.iter_mut() {
@@ -217,11 +427,13 @@ impl Consist {
         }
     }
 
+    /// Returns sum of locomotive max tractive forces, scaled by each
+    /// locomotive's adhesion factor
     pub fn force_max(&self) -> anyhow::Result<si::Force> {
         self.loco_vec.iter().enumerate().try_fold(
             0. * uc::N,
             |f_sum, (i, loco)| -> anyhow::Result<si::Force> {
-                Ok(loco.force_max().with_context(|| {
+                Ok(loco.force_max_effective().with_context(|| {
                     format!(
                         "{}\nloco #: {}\nloco type: {}",
                         format_dbg!(),
@@ -233,6 +445,127 @@ impl Consist {
         )
     }
 
+    /// Returns one line per locomotive with its type and current power limits,
+    /// for diagnosing which unit is underpowered when limits are exceeded
+    fn loco_pwr_lims_summary(&self) -> anyhow::Result<String> {
+        Ok(self
+            .loco_vec
+            .iter()
+            .enumerate()
+            .map(|(i, loco)| -> anyhow::Result<String> {
+                let loco_type = loco.loco_type.to_string();
+                Ok(format!(
+                    "loco #: {}, loco type: {}, pwr_out_max: {} MW, pwr_regen_max: {} MW",
+                    i,
+                    loco_type,
+                    loco.state
+                        .pwr_out_max
+                        .get_unchecked(|| format_dbg!())?
+                        .get::<si::megawatt>()
+                        .format_eng(Some(5)),
+                    loco.state
+                        .pwr_regen_max
+                        .get_unchecked(|| format_dbg!())?
+                        .get::<si::megawatt>()
+                        .format_eng(Some(5)),
+                ))
+            })
+            .collect::<anyhow::Result<Vec<String>>>()?
+            .join("\n"))
+    }
+
+    /// Returns sum of locomotive max tractive forces for the current time step,
+    /// i.e. [crate::consist::locomotive::LocomotiveState::force_max_curr], which
+    /// accounts for axle load transfer.  Must be called after
+    /// [LocoTrait::set_curr_pwr_max_out].
+    pub fn force_max_curr(&self) -> anyhow::Result<si::Force> {
+        self.loco_vec.iter().enumerate().try_fold(
+            si::Force::ZERO,
+            |f_sum, (i, loco)| -> anyhow::Result<si::Force> {
+                Ok(*loco
+                    .state
+                    .force_max_curr
+                    .get_fresh(|| format!("{}\nloco #: {}", format_dbg!(), i))?
+                    + f_sum)
+            },
+        )
+    }
+
+    /// Returns tractive effort at each of `speeds`, limited by adhesion (i.e.
+    /// [Self::force_max]) at low speed and by consist power capability at high
+    /// speed.  Power capability is evaluated from the current state with a time
+    /// step equal to the longest fuel converter ramp lag so that ramp rate does
+    /// not limit the result.  `self` is not modified.
+    /// # Arguments
+    /// - `speeds`: train speeds at which to evaluate tractive effort
+    /// - `train_mass`: total mass of train, including locomotives, used for
+    ///   speed-dependent battery state of charge buffers
+    pub fn tractive_effort_curve(
+        &self,
+        speeds: &[si::Velocity],
+        train_mass: si::Mass,
+    ) -> anyhow::Result<Vec<si::Force>> {
+        ensure!(
+            train_mass >= si::Mass::ZERO,
+            "{}\n`train_mass` must be non-negative",
+            format_dbg!()
+        );
+        let force_max = self.force_max().with_context(|| format_dbg!())?;
+        let dt = self
+            .loco_vec
+            .iter()
+            .filter_map(|loco| loco.fuel_converter())
+            .fold(uc::S, |dt, fc| dt.max(fc.pwr_ramp_lag));
+        speeds
+            .iter()
+            .map(|speed| {
+                ensure!(
+                    *speed >= si::Velocity::ZERO,
+                    "{}\n`speeds` must be non-negative",
+                    format_dbg!()
+                );
+                let mut consist = self.clone();
+                consist.check_and_reset(|| format_dbg!())?;
+                consist.set_pwr_aux(Some(true))?;
+                consist.set_curr_pwr_max_out(
+                    None,
+                    None,
+                    Some(train_mass),
+                    Some(*speed),
+                    None,
+                    dt,
+                )?;
+                let pwr_out_max = *consist.state.pwr_out_max.get_fresh(|| format_dbg!())?;
+                Ok(if *speed > si::Velocity::ZERO {
+                    force_max.min(pwr_out_max / *speed)
+                } else {
+                    force_max
+                })
+            })
+            .collect()
+    }
+
+    /// Returns true if the consist can hold `speed` on a sustained `grade`
+    /// while hauling `train_mass`, i.e. if tractive effort per
+    /// [Self::tractive_effort_curve] covers grade resistance.  Other train
+    /// resistance is neglected, so this is an optimistic precheck, e.g. for the
+    /// ruling grade of a route before a long run.
+    /// # Arguments
+    /// - `grade`: grade (rise over run) to be climbed
+    /// - `speed`: minimum speed to be held
+    /// - `train_mass`: total mass of train, including locomotives
+    pub fn can_hold_grade(
+        &self,
+        grade: si::Ratio,
+        speed: si::Velocity,
+        train_mass: si::Mass,
+    ) -> anyhow::Result<bool> {
+        let force_avail = self
+            .tractive_effort_curve(&[speed], train_mass)
+            .with_context(|| format_dbg!())?[0];
+        Ok(force_avail >= train_mass * uc::ACC_GRAV * grade)
+    }
+
     pub fn get_loco_vec(&self) -> Vec<Locomotive> {
         self.loco_vec.clone()
     }
@@ -246,6 +579,50 @@ impl Consist {
         loco_vec
     }
 
+    /// Inserts `loco` at position `idx` in [Self::loco_vec], shifting later
+    /// locomotives back, and propagates [Self::save_interval] to `loco`
+    pub fn insert_locomotive(&mut self, idx: usize, mut loco: Locomotive) -> anyhow::Result<()> {
+        ensure!(
+            idx <= self.loco_vec.len(),
+            "{}
+`idx` ({}) must not exceed `loco_vec` len ({})",
+            format_dbg!(),
+            idx,
+            self.loco_vec.len()
+        );
+        loco.set_save_interval(self.save_interval);
+        self.loco_vec.insert(idx, loco);
+        for pwr_out_cmd_vec in self.pwr_out_cmd_buffer.iter_mut() {
+            pwr_out_cmd_vec.insert(idx, si::Power::ZERO);
+        }
+        self.refresh_n_res_equipped();
+        Ok(())
+    }
+
+    /// Removes and returns the locomotive at position `idx` in [Self::loco_vec],
+    /// which must not be the only locomotive
+    pub fn remove_locomotive(&mut self, idx: usize) -> anyhow::Result<Locomotive> {
+        ensure!(
+            idx < self.loco_vec.len(),
+            "{}
+`idx` ({}) must be less than `loco_vec` len ({})",
+            format_dbg!(),
+            idx,
+            self.loco_vec.len()
+        );
+        ensure!(
+            self.loco_vec.len() > 1,
+            "{}\nCannot remove the last locomotive from `loco_vec`",
+            format_dbg!()
+        );
+        let loco = self.loco_vec.remove(idx);
+        for pwr_out_cmd_vec in self.pwr_out_cmd_buffer.iter_mut() {
+            pwr_out_cmd_vec.remove(idx);
+        }
+        self.refresh_n_res_equipped();
+        Ok(loco)
+    }
+
     pub fn get_save_interval(&self) -> Option<usize> {
         self.save_interval
     }
@@ -257,6 +634,107 @@ impl Consist {
         }
     }
 
+    /// Returns an error identifying the first locomotive or powertrain
+    /// component whose `save_interval` differs from `self.save_interval`,
+    /// e.g. after a component is swapped in without calling
+    /// [Self::set_save_interval]
+    pub fn check_save_interval_consistency(&self) -> anyhow::Result<()> {
+        for (i, loco) in self.loco_vec.iter().enumerate() {
+            ensure!(
+                loco.get_save_interval() == self.save_interval,
+                "{}\n`loco_vec[{}].save_interval` ({:?}) does not match consist `save_interval` ({:?})",
+                format_dbg!(),
+                i,
+                loco.get_save_interval(),
+                self.save_interval
+            );
+            if let Some((name, save_interval)) = loco
+                .component_save_intervals()
+                .into_iter()
+                .find(|(_, save_interval)| *save_interval != self.save_interval)
+            {
+                bail!(
+                    "{}\n`loco_vec[{}].{}.save_interval` ({:?}) does not match consist `save_interval` ({:?})",
+                    format_dbg!(),
+                    i,
+                    name,
+                    save_interval,
+                    self.save_interval
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets runtime adhesion multiplier on max tractive force for all locomotives
+    pub fn set_adhesion_factor(&mut self, adhesion_factor: si::Ratio) -> anyhow::Result<()> {
+        for loco in self.loco_vec.iter_mut() {
+            loco.set_adhesion_factor(adhesion_factor)?;
+        }
+        Ok(())
+    }
+
+    /// Sets dynamic brake grid thermal model of every locomotive with an
+    /// electric drivetrain to `dyn_brake_thermal`, with `None` disabling
+    /// dynamic brake fade
+    pub fn set_dyn_brake_thermal(
+        &mut self,
+        dyn_brake_thermal: Option<DynBrakeThermal>,
+    ) -> anyhow::Result<()> {
+        for loco in self.loco_vec.iter_mut() {
+            if let Some(edrv) = loco.electric_drivetrain_mut() {
+                edrv.set_dyn_brake_thermal(dyn_brake_thermal)?;
+            }
+        }
+        self.state.pwr_dyn_brake_max.mark_stale();
+        self.set_pwr_dyn_brake_max()
+    }
+
+    /// Sets starting SOC of every RES-equipped locomotive to `soc`.  If `soc` is
+    /// outside of [`min_soc`, `max_soc`] for any locomotive, returns an error
+    /// without modifying any locomotive.
+    pub fn set_initial_soc(&mut self, soc: si::Ratio) -> anyhow::Result<()> {
+        let locos_out_of_range: Vec<String> = self
+            .loco_vec
+            .iter()
+            .enumerate()
+            .filter_map(|(i, loco)| {
+                loco.reversible_energy_storage().and_then(|res| {
+                    (soc < res.min_soc || soc > res.max_soc).then(|| {
+                        format!(
+                            "loco #: {}, min_soc: {}, max_soc: {}",
+                            i,
+                            res.min_soc.get::<si::ratio>(),
+                            res.max_soc.get::<si::ratio>()
+                        )
+                    })
+                })
+            })
+            .collect();
+        ensure!(
+            locos_out_of_range.is_empty(),
+            "{}\n`soc` ({}) is out of range for:\n{}",
+            format_dbg!(),
+            soc.get::<si::ratio>(),
+            locos_out_of_range.join("\n")
+        );
+        for loco in self.loco_vec.iter_mut() {
+            if let Some(res) = loco.reversible_energy_storage_mut() {
+                res.set_initial_soc(soc)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Clears `self.history` and history of all locomotives so that `self` can
+    /// be serialized compactly, e.g. for reuse as an input
+    pub fn strip_history(&mut self) {
+        self.history.clear();
+        for loco in self.loco_vec.iter_mut() {
+            loco.strip_history();
+        }
+    }
+
     /// Set catenary charging/discharging power limit
     pub fn set_cat_power_limit(
         &mut self,
@@ -279,18 +757,17 @@ impl Consist {
         Ok(())
     }
 
+    /// Returns cumulative fuel energy summed over
+    /// [FC](locomotive::powertrain::fuel_converter::FuelConverter)-equipped
+    /// locomotives.  [DummyLoco](locomotive::DummyLoco) locomotives have free
+    /// energy and contribute nothing to this or any other energy accounting.
     pub fn get_energy_fuel(&self) -> anyhow::Result<si::Energy> {
         let energy_fuel = self.loco_vec.iter().try_fold(
             si::Energy::ZERO,
             |acc, loco| -> anyhow::Result<si::Energy> {
-                let new = match loco.loco_type {
-                    PowertrainType::BatteryElectricLoco(_) => si::Energy::ZERO,
-                    _ => *loco
-                        .fuel_converter()
-                        .unwrap()
-                        .state
-                        .energy_fuel
-                        .get_fresh(|| format_dbg!())?,
+                let new = match loco.fuel_converter() {
+                    Some(fc) => *fc.state.energy_fuel.get_fresh(|| format_dbg!())?,
+                    None => si::Energy::ZERO,
                 } + acc;
                 Ok(new)
             },
@@ -298,6 +775,37 @@ impl Consist {
         Ok(energy_fuel)
     }
 
+    /// Returns total rated power of all locomotives, per
+    /// [Locomotive::pwr_rated]
+    pub fn pwr_rated(&self) -> si::Power {
+        self.loco_vec
+            .iter()
+            .filter_map(|loco| loco.pwr_rated())
+            .fold(si::Power::ZERO, |acc, pwr| acc + pwr)
+    }
+
+    /// Returns cumulative CO2 emitted by all
+    /// [FC](locomotive::powertrain::fuel_converter::FuelConverter)-equipped locomotives
+    pub fn get_co2_mass(&self) -> anyhow::Result<si::Mass> {
+        self.loco_vec
+            .iter()
+            .filter_map(|loco| loco.fuel_converter())
+            .try_fold(si::Mass::ZERO, |acc, fc| -> anyhow::Result<si::Mass> {
+                Ok(acc + *fc.state.co2_mass.get_fresh(|| format_dbg!())?)
+            })
+    }
+
+    /// Returns cumulative fuel volume consumed by all
+    /// [FC](locomotive::powertrain::fuel_converter::FuelConverter)-equipped locomotives
+    pub fn get_fuel_volume(&self) -> anyhow::Result<si::Volume> {
+        self.loco_vec
+            .iter()
+            .filter_map(|loco| loco.fuel_converter())
+            .try_fold(si::Volume::ZERO, |acc, fc| -> anyhow::Result<si::Volume> {
+                Ok(acc + *fc.state.fuel_volume.get_fresh(|| format_dbg!())?)
+            })
+    }
+
     pub fn get_net_energy_res(&self) -> anyhow::Result<si::Energy> {
         let energy_res = self.loco_vec.iter().try_fold(
             si::Energy::ZERO,
@@ -321,6 +829,29 @@ impl Consist {
         Ok(energy_res)
     }
 
+    /// Returns cumulative `(fuel, RES)` energy summed over locomotives of each
+    /// powertrain type, keyed by [PowertrainType::to_string], where RES energy
+    /// is net chemical energy out of the battery
+    pub fn energy_by_loco_type(&self) -> anyhow::Result<HashMap<String, (si::Energy, si::Energy)>> {
+        let mut energy_by_loco_type: HashMap<String, (si::Energy, si::Energy)> = HashMap::new();
+        for loco in self.loco_vec.iter() {
+            let energy_fuel = match loco.fuel_converter() {
+                Some(fc) => *fc.state.energy_fuel.get_fresh(|| format_dbg!())?,
+                None => si::Energy::ZERO,
+            };
+            let energy_res = match loco.reversible_energy_storage() {
+                Some(res) => *res.state.energy_out_chemical.get_fresh(|| format_dbg!())?,
+                None => si::Energy::ZERO,
+            };
+            let entry = energy_by_loco_type
+                .entry(loco.loco_type.to_string())
+                .or_insert((si::Energy::ZERO, si::Energy::ZERO));
+            entry.0 += energy_fuel;
+            entry.1 += energy_res;
+        }
+        Ok(energy_by_loco_type)
+    }
+
     pub fn set_pwr_aux(&mut self, engine_on: Option<bool>) -> anyhow::Result<()> {
         self.loco_vec
             .iter_mut()
@@ -340,7 +871,7 @@ impl Consist {
         if self.assert_limits {
             ensure!(
                 -pwr_out_req <= *self.state.pwr_dyn_brake_max.get_fresh(|| format_dbg!())?,
-                "{}\nbraking power required ({} MW)\nexceeds max DB power ({} MW)",
+                "{}\nbraking power required ({} MW)\nexceeds max DB power ({} MW)\n{}",
                 format_dbg!(),
                 (-pwr_out_req.get::<si::megawatt>()).format_eng(Some(5)),
                 self.state
@@ -348,18 +879,35 @@ impl Consist {
                     .get_fresh(|| format_dbg!())?
                     .get::<si::megawatt>()
                     .format_eng(Some(5)),
+                self.loco_pwr_lims_summary()?,
             );
             ensure!(
                 pwr_out_req <= *self.state.pwr_out_max.get_fresh(|| format_dbg!())?,
-                "{}\npower required ({} MW)\nexceeds max power ({} MW)",
+                "{}\npower required ({} MW)\nexceeds max power ({} MW)\n{}",
                 format_dbg!(),
                 pwr_out_req.get::<si::megawatt>().format_eng(Some(5)),
                 self.state
                     .pwr_out_max
                     .get_fresh(|| format_dbg!())?
                     .get::<si::megawatt>()
-                    .format_eng(Some(5))
+                    .format_eng(Some(5)),
+                self.loco_pwr_lims_summary()?,
             );
+            if let Some(pwr_rate_out_max) = self.pwr_rate_out_max {
+                let pwr_out_prev = self
+                    .state
+                    .pwr_out
+                    .get_stale(|| format_dbg!())?
+                    .max(si::Power::ZERO);
+                ensure!(
+                    almost_le_uom(&pwr_out_req, &(pwr_out_prev + pwr_rate_out_max * dt), None),
+                    "{}\npower required ({} MW)\nexceeds consist ramp rate limit from previous power ({} MW)\n{}",
+                    format_dbg!(),
+                    pwr_out_req.get::<si::megawatt>().format_eng(Some(5)),
+                    pwr_out_prev.get::<si::megawatt>().format_eng(Some(5)),
+                    self.loco_pwr_lims_summary()?,
+                );
+            }
         }
 
         self.state
@@ -376,7 +924,7 @@ impl Consist {
             || format_dbg!(),
         )?;
 
-        let pwr_out_vec: Vec<si::Power> = if pwr_out_req > si::Power::ZERO {
+        let pwr_out_cmd_vec: Vec<si::Power> = if pwr_out_req > si::Power::ZERO {
             // positive tractive power `pwr_out_vec`
             self.pdct.solve_positive_traction(
                 &self.loco_vec,
@@ -397,47 +945,55 @@ impl Consist {
             vec![si::Power::ZERO; self.loco_vec.len()]
         };
 
-        self.state.pwr_out.update(
-            pwr_out_vec
-                .iter()
-                .fold(si::Power::ZERO, |acc, &curr| acc + curr),
-            || format_dbg!(),
-        )?;
+        let pwr_out_cmd = pwr_out_cmd_vec
+            .iter()
+            .fold(si::Power::ZERO, |acc, &curr| acc + curr);
 
         if self.assert_limits {
             ensure!(
                 utils::almost_eq_uom(
                     self.state.pwr_out_req.get_fresh(|| format_dbg!())?,
-                    self.state.pwr_out.get_fresh(|| format_dbg!())?,
+                    &pwr_out_cmd,
                     None
                 ),
                 format!(
                     "{}
                     self.state.pwr_out_req: {:.6} MW
-                    self.state.pwr_out: {:.6} MW
+                    pwr_out_cmd: {:.6} MW
                     self.state.pwr_out_deficit: {:.6} MW
-                    pwr_out_vec: {:?}",
+                    pwr_out_cmd_vec: {:?}",
                     format_dbg!(),
                     &self
                         .state
                         .pwr_out_req
                         .get_fresh(|| format_dbg!())?
                         .get::<si::megawatt>(),
-                    &self
-                        .state
-                        .pwr_out
-                        .get_fresh(|| format_dbg!())?
-                        .get::<si::megawatt>(),
+                    pwr_out_cmd.get::<si::megawatt>(),
                     &self
                         .state
                         .pwr_out_deficit
                         .get_fresh(|| format_dbg!())?
                         .get::<si::megawatt>(),
-                    &pwr_out_vec,
+                    &pwr_out_cmd_vec,
                 )
             );
         }
 
+        let pwr_out_vec = self
+            .delay_remote_pwr_out_cmds(pwr_out_cmd_vec, dt)
+            .with_context(|| format_dbg!())?;
+
+        self.state.pwr_out.update(
+            pwr_out_vec
+                .iter()
+                .fold(si::Power::ZERO, |acc, &curr| acc + curr),
+            || format_dbg!(),
+        )?;
+        self.state.pwr_out_delay_deficit.update(
+            pwr_out_cmd - *self.state.pwr_out.get_fresh(|| format_dbg!())?,
+            || format_dbg!(),
+        )?;
+
         // maybe put logic for toggling `engine_on` here
 
         for (i, (loco, pwr_out)) in self.loco_vec.iter_mut().zip(pwr_out_vec.iter()).enumerate() {
@@ -466,7 +1022,8 @@ impl Consist {
                                 *hel.fc.state.pwr_fuel.get_fresh(|| format_dbg!())?
                             }
                             PowertrainType::BatteryElectricLoco(_) => si::Power::ZERO,
-                            PowertrainType::DummyLoco(_) => f64::NAN * uc::W,
+                            // dummy locos have free energy and contribute nothing
+                            PowertrainType::DummyLoco(_) => si::Power::ZERO,
                         };
                         Ok(new + acc)
                     })
@@ -490,7 +1047,8 @@ impl Consist {
                             PowertrainType::BatteryElectricLoco(bel) => {
                                 *bel.res.state.pwr_out_chemical.get_fresh(|| format_dbg!())?
                             }
-                            PowertrainType::DummyLoco(_) => f64::NAN * uc::W,
+                            // dummy locos have free energy and contribute nothing
+                            PowertrainType::DummyLoco(_) => si::Power::ZERO,
                         } + acc;
                         Ok(new)
                     })
@@ -500,6 +1058,21 @@ impl Consist {
             || format_dbg!(),
         )?;
 
+        self.state.eta_consist.update(
+            {
+                let pwr_out = *self.state.pwr_out.get_fresh(|| format_dbg!())?;
+                let pwr_in = *self.state.pwr_fuel.get_fresh(|| format_dbg!())?
+                    + *self.state.pwr_reves.get_fresh(|| format_dbg!())?;
+                if pwr_out > si::Power::ZERO && pwr_in > si::Power::ZERO {
+                    pwr_out / pwr_in
+                } else {
+                    // avoid dividing by zero when not motoring
+                    *self.state.eta_consist.get_stale(|| format_dbg!())?
+                }
+            },
+            || format_dbg!(),
+        )?;
+
         if *self.state.pwr_out.get_fresh(|| format_dbg!())? >= si::Power::ZERO {
             self.state.energy_out_pos.increment(
                 *self.state.pwr_out.get_fresh(|| format_dbg!())? * dt,
@@ -520,24 +1093,77 @@ impl Consist {
         Ok(())
     }
 
+    /// Returns power applied by each locomotive given `pwr_out_cmd_vec`
+    /// commanded this time step.  The lead locomotive applies the current
+    /// command, and remote locomotives apply the command from
+    /// `ceil(command_delay / dt)` time steps earlier (zero until that many
+    /// commands have been issued), capped at their current max propulsion
+    /// and braking power.
+    fn delay_remote_pwr_out_cmds(
+        &mut self,
+        pwr_out_cmd_vec: Vec<si::Power>,
+        dt: si::Time,
+    ) -> anyhow::Result<Vec<si::Power>> {
+        let n_steps_delay = (self.command_delay / dt).get::<si::ratio>().ceil() as usize;
+        if n_steps_delay == 0 {
+            self.pwr_out_cmd_buffer.clear();
+            return Ok(pwr_out_cmd_vec);
+        }
+        self.pwr_out_cmd_buffer.push_back(pwr_out_cmd_vec.clone());
+        let mut pwr_out_cmd_vec_delayed = None;
+        while self.pwr_out_cmd_buffer.len() > n_steps_delay {
+            pwr_out_cmd_vec_delayed = self.pwr_out_cmd_buffer.pop_front();
+        }
+        let pwr_out_cmd_vec_delayed =
+            pwr_out_cmd_vec_delayed.unwrap_or_else(|| vec![si::Power::ZERO; pwr_out_cmd_vec.len()]);
+        pwr_out_cmd_vec
+            .iter()
+            .zip(pwr_out_cmd_vec_delayed)
+            .zip(&self.loco_vec)
+            .enumerate()
+            .map(|(i, ((pwr_out_cmd, pwr_out_cmd_delayed), loco))| {
+                if i == 0 {
+                    return Ok(*pwr_out_cmd);
+                }
+                Ok(pwr_out_cmd_delayed
+                    .min(*loco.state.pwr_out_max.get_fresh(|| format_dbg!())?)
+                    .max(-pwr_brake_max(loco)?))
+            })
+            .collect()
+    }
+
+    /// Sets consist-level braking capability, including regen, which is
+    /// reduced by dynamic brake fade for locomotives with
+    /// [locomotive::powertrain::electric_drivetrain::ElectricDrivetrain::dyn_brake_thermal]
     pub fn set_pwr_dyn_brake_max(&mut self) -> anyhow::Result<()> {
         self.state.pwr_dyn_brake_max.update(
             self.loco_vec
                 .iter()
-                .map(|loco| match &loco.loco_type {
-                    PowertrainType::ConventionalLoco(conv) => conv.edrv.pwr_out_max,
-                    PowertrainType::HybridLoco(hel) => hel.edrv.pwr_out_max,
-                    PowertrainType::BatteryElectricLoco(bel) => bel.edrv.pwr_out_max,
-                    // really big number that is not inf to avoid null in json
-                    PowertrainType::DummyLoco(_) => uc::W * 1e15,
+                .try_fold(si::Power::ZERO, |acc, loco| -> anyhow::Result<si::Power> {
+                    Ok(acc + pwr_brake_max(loco)?)
                 })
-                .sum(),
+                .with_context(|| format_dbg!())?,
             || format_dbg!(),
         )?;
         Ok(())
     }
 }
 
+/// Returns braking capability of `loco`, including regen, which is reduced by
+/// dynamic brake fade if
+/// [locomotive::powertrain::electric_drivetrain::ElectricDrivetrain::dyn_brake_thermal]
+/// is provided
+fn pwr_brake_max(loco: &Locomotive) -> anyhow::Result<si::Power> {
+    Ok(match loco.electric_drivetrain() {
+        Some(edrv) => edrv.pwr_out_max.min(
+            *loco.state.pwr_regen_max.get_unchecked(|| format_dbg!())?
+                + edrv.pwr_dyn_brake_max()?,
+        ),
+        // really big number that is not inf to avoid null in json
+        None => uc::W * 1e15,
+    })
+}
+
 impl Default for Consist {
     fn default() -> Self {
         let mut consist = Self {
@@ -555,6 +1181,9 @@ impl Default for Consist {
             save_interval: Some(1),
             n_res_equipped: Default::default(),
             pdct: Default::default(),
+            pwr_rate_out_max: None,
+            command_delay: si::Time::ZERO,
+            pwr_out_cmd_buffer: Default::default(),
         };
         // ensure propagation to nested components
         consist.set_save_interval(Some(1));
@@ -571,6 +1200,7 @@ impl LocoTrait for Consist {
         elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         train_mass: Option<si::Mass>,
         train_speed: Option<si::Velocity>,
+        grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()> {
         // TODO: this will need to account for catenary power
@@ -602,7 +1232,7 @@ impl LocoTrait for Consist {
             } else {
                 None
             };
-            loco.set_curr_pwr_max_out(None, elev_and_temp, mass, train_speed, dt)
+            loco.set_curr_pwr_max_out(None, elev_and_temp, mass, train_speed, grade_ahead, dt)
                 .map_err(|err| {
                     err.context(format!(
                         "loco idx: {} loco type: {}",
@@ -634,7 +1264,11 @@ impl LocoTrait for Consist {
                         },
                     )
                     .with_context(|| format_dbg!())?;
-                pwr_rate_out_max
+                // consist-level cap, if any, on top of per-locomotive rates
+                match self.pwr_rate_out_max {
+                    Some(consist_rate_max) => pwr_rate_out_max.min(consist_rate_max),
+                    None => pwr_rate_out_max,
+                }
             },
             || format_dbg!(),
         )?;
@@ -697,6 +1331,18 @@ impl LocoTrait for Consist {
     }
 }
 
+impl fmt::Display for Consist {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Consist(n_locos={}, mass_kilograms={}, pwr_rated_kilowatts={:.1})",
+            self.loco_vec.len(),
+            utils::mass_repr(self.mass()),
+            self.pwr_rated().get::<si::kilowatt>(),
+        )
+    }
+}
+
 impl Mass for Consist {
     fn mass(&self) -> anyhow::Result<Option<si::Mass>> {
         self.derived_mass()
@@ -810,12 +1456,22 @@ pub struct ConsistState {
 
     // achieved values
     /// Total tractive power of consist.
-    /// Should always match [pwr_out_req](Self::pwr_out_req)] if `assert_limits == true`.
+    /// Should always match [pwr_out_req](Self::pwr_out_req)] if `assert_limits == true`,
+    /// except for [pwr_out_delay_deficit](Self::pwr_out_delay_deficit).
     pub pwr_out: TrackedState<si::Power>,
+    /// Power commanded to locomotives minus [pwr_out](Self::pwr_out) due to
+    /// [Consist::command_delay] to remote locomotives, which is negative when
+    /// remote locomotives deliver more than requested, e.g. while power is
+    /// being reduced.  This is not fed back into train motion.
+    pub pwr_out_delay_deficit: TrackedState<si::Power>,
     /// Total battery power of [RES](locomotive::powertrain::reversible_energy_storage::ReversibleEnergyStorage)-equppped locomotives
     pub pwr_reves: TrackedState<si::Power>,
     /// Total fuel power of [FC](locomotive::powertrain::fuel_converter::FuelConverter)-equppped locomotives
     pub pwr_fuel: TrackedState<si::Power>,
+    /// Instantaneous consist efficiency, i.e. [pwr_out](Self::pwr_out) over
+    /// the sum of [pwr_fuel](Self::pwr_fuel) and [pwr_reves](Self::pwr_reves).
+    /// Holds the previous value when not motoring.
+    pub eta_consist: TrackedState<si::Ratio>,
 
     /// Time-integrated energy form of [pwr_out](Self::pwr_out)
     pub energy_out: TrackedState<si::Energy>,
@@ -823,6 +1479,8 @@ pub struct ConsistState {
     pub energy_out_pos: TrackedState<si::Energy>,
     /// Energy out during negative traction (positive value means negative traction)
     pub energy_out_neg: TrackedState<si::Energy>,
+    /// Time-integrated energy form of [pwr_out_delay_deficit](Self::pwr_out_delay_deficit)
+    pub energy_out_delay_deficit: TrackedState<si::Energy>,
     /// Time-integrated energy form of [pwr_reves](Self::pwr_reves)
     pub energy_reves: TrackedState<si::Energy>,
     /// Time-integrated energy form of [pwr_fuel](Self::pwr_fuel)
@@ -1,3 +1,4 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::consist::locomotive::loco_sim::PowerTrace;
@@ -99,7 +100,7 @@ impl ConsistSimulation {
         };
         let dt = self.power_trace.dt_at_i(i).with_context(|| format_dbg!())?;
         self.loco_con
-            .set_curr_pwr_max_out(None, None, train_mass, train_speed, dt)
+            .set_curr_pwr_max_out(None, None, train_mass, train_speed, None, dt)
             .with_context(|| format_dbg!())?;
         self.solve_energy_consumption(
             self.power_trace.pwr[*self.loco_con.state.i.get_fresh(|| format_dbg!())?],
@@ -150,9 +151,29 @@ impl ConsistSimulation {
 impl StateMethods for ConsistSimulation {}
 
 impl SetCumulative for ConsistSimulation {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        self.loco_con
+            .set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
+        Ok(())
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
         self.loco_con
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
+            .set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
         Ok(())
     }
 }
@@ -206,10 +227,77 @@ impl Default for ConsistSimulation {
     }
 }
 
+#[serde_api]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+pub struct ConsistSimulationVec(pub Vec<ConsistSimulation>);
+impl ConsistSimulationVec {
+    pub fn new(value: Vec<ConsistSimulation>) -> Self {
+        Self(value)
+    }
+}
+
+#[pyo3_api]
+impl ConsistSimulationVec {
+    #[new]
+    /// Rust-defined `__new__` magic method for Python used exposed via PyO3.
+    fn __new__(v: Vec<ConsistSimulation>) -> Self {
+        Self(v)
+    }
+
+    #[pyo3(name = "walk")]
+    #[pyo3(signature = (b_parallelize=None))]
+    /// Exposes `walk` to Python.
+    fn walk_py(&mut self, b_parallelize: Option<bool>) -> anyhow::Result<()> {
+        let b_par = b_parallelize.unwrap_or(false);
+        self.walk(b_par)
+    }
+}
+
+impl Init for ConsistSimulationVec {
+    fn init(&mut self) -> Result<(), Error> {
+        self.0.iter_mut().try_for_each(|c| c.init())?;
+        Ok(())
+    }
+}
+impl SerdeAPI for ConsistSimulationVec {}
+impl Default for ConsistSimulationVec {
+    fn default() -> Self {
+        Self(vec![ConsistSimulation::default(); 3])
+    }
+}
+
+impl ConsistSimulationVec {
+    /// Calls `walk` for each consist simulation in vec.
+    pub fn walk(&mut self, parallelize: bool) -> anyhow::Result<()> {
+        if parallelize {
+            self.0
+                .par_iter_mut()
+                .enumerate()
+                .try_for_each(|(i, consist_sim)| {
+                    consist_sim
+                        .walk()
+                        .map_err(|err| err.context(format!("consist_sim idx:{}", i)))
+                })?;
+        } else {
+            self.0
+                .iter_mut()
+                .enumerate()
+                .try_for_each(|(i, consist_sim)| {
+                    consist_sim
+                        .walk()
+                        .map_err(|err| err.context(format!("consist_sim idx:{}", i)))
+                })?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{Consist, ConsistSimulation};
+    use super::{Consist, ConsistSimulation, ConsistSimulationVec};
     use crate::consist::locomotive::loco_sim::PowerTrace;
+    use crate::imports::*;
 
     #[test]
     fn test_consist_sim() {
@@ -218,4 +306,37 @@ mod tests {
         let mut consist_sim = ConsistSimulation::new(consist, pt, None);
         consist_sim.walk().unwrap();
     }
+
+    #[test]
+    fn test_eta_consist() {
+        let consist = Consist::default();
+        let pt = PowerTrace::default();
+        let mut consist_sim = ConsistSimulation::new(consist, pt, Some(1));
+        consist_sim.walk().unwrap();
+
+        let eta_hist: Vec<f64> = consist_sim
+            .loco_con
+            .history
+            .eta_consist
+            .iter()
+            .map(|eta| {
+                eta.get_unchecked(|| format_dbg!())
+                    .unwrap()
+                    .get::<si::ratio>()
+            })
+            .collect();
+        assert!(!eta_hist.is_empty());
+        assert!(eta_hist.iter().all(|eta| (0.0..=1.0).contains(eta)));
+        assert!(eta_hist.iter().any(|eta| *eta > 0.0));
+    }
+
+    #[test]
+    fn test_consist_sim_vec_par_matches_ser() {
+        let mut consist_sim_vec_ser = ConsistSimulationVec::default();
+        let mut consist_sim_vec_par = consist_sim_vec_ser.clone();
+        consist_sim_vec_ser.walk(false).unwrap();
+        consist_sim_vec_par.walk(true).unwrap();
+        assert!(!consist_sim_vec_ser.0[0].loco_con.history.is_empty());
+        assert_eq!(consist_sim_vec_par, consist_sim_vec_ser);
+    }
 }
@@ -10,6 +10,7 @@ pub trait LocoTrait {
     /// - `elev_and_temp`: elevation and temperature
     /// - `train_speed`: current train speed
     /// - `train_mass`: portion of total train mass handled by `self`
+    /// - `grade_ahead`: average grade over lookahead distance in front of train
     /// - `dt`: time step size
     fn set_curr_pwr_max_out(
         &mut self,
@@ -17,6 +18,7 @@ pub trait LocoTrait {
         elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         train_mass: Option<si::Mass>,
         train_speed: Option<si::Velocity>,
+        grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()>;
     /// Get energy loss in components
@@ -176,7 +178,9 @@ fn get_pwr_regen_vec(
             }
             // if the DummyLoco is present in the consist, it should be the only locomotive
             // and pwr_regen_deficit should be 0.0
-            PowertrainType::DummyLoco(_) => si::Power::ZERO,
+            PowertrainType::DummyLoco(_) => {
+                *loco.state.pwr_regen_max.get_fresh(|| format_dbg!())? * regen_frac
+            }
         })
     }
     Ok(pwr_regen_vec)
@@ -216,13 +220,14 @@ fn solve_negative_traction(
         let pwr_surplus_vec: Vec<si::Power> = loco_vec
             .iter()
             .zip(&pwr_regen_vec)
-            .map(|(loco, pwr_regen)| {
-                loco.electric_drivetrain()
-                    .expect("this `expect` might cause problems for DummyLoco")
-                    .pwr_out_max
-                    - *pwr_regen
+            .map(|(loco, pwr_regen)| -> anyhow::Result<si::Power> {
+                let edrv = loco
+                    .electric_drivetrain()
+                    .expect("this `expect` might cause problems for DummyLoco");
+                // dynamic braking is limited by grid fade as well as by drivetrain
+                Ok((edrv.pwr_out_max - *pwr_regen).min(edrv.pwr_dyn_brake_max()?))
             })
-            .collect();
+            .collect::<anyhow::Result<Vec<si::Power>>>()?;
         let pwr_surplus_sum = pwr_surplus_vec
             .iter()
             .fold(0.0 * uc::W, |acc, &curr| acc + curr);
@@ -233,9 +238,11 @@ fn solve_negative_traction(
             .get_fresh(|| format_dbg!())?
             / pwr_surplus_sum;
         ensure!(
-            surplus_frac >= si::Ratio::ZERO && surplus_frac <= uc::R,
+            surplus_frac >= si::Ratio::ZERO && utils::almost_le_uom(&surplus_frac, &uc::R, None),
             format_dbg!(surplus_frac),
         );
+        // guard against roundoff when braking demand is at the consist limit
+        let surplus_frac = surplus_frac.min(uc::R);
         // total dynamic braking, including regen
         let pwr_dyn_brake_vec: Vec<si::Power> = pwr_surplus_vec
             .iter()
@@ -0,0 +1,116 @@
+use crate::imports::*;
+
+#[serde_api]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Model of dynamic weight transfer off of the lead truck at high drawbar pull,
+/// which reduces adhesion-limited max tractive force.  Drawbar pull acting at
+/// [Self::drawbar_height] above the rail pitches the locomotive body and
+/// unloads the lead truck by `drawbar_pull * drawbar_height / truck_spacing`,
+/// and [Self::transfer_coeff] scales the resulting fractional reduction in
+/// max tractive force.  The default of zero [Self::transfer_coeff] disables
+/// the model.
+pub struct AxleLoadTransfer {
+    /// Fraction of rigid-body weight transfer that reduces max tractive force,
+    /// typically less than 1.0 for locomotives with weight transfer
+    /// compensation.  0.0 disables the model.
+    pub transfer_coeff: si::Ratio,
+    /// Height of drawbar (i.e. coupler centerline) above top of rail
+    pub drawbar_height: si::Length,
+    /// Longitudinal distance between truck centers
+    pub truck_spacing: si::Length,
+}
+
+#[pyo3_api]
+impl AxleLoadTransfer {
+    #[new]
+    #[pyo3(signature = (transfer_coeff, drawbar_height_meters=None, truck_spacing_meters=None))]
+    fn __new__(
+        transfer_coeff: f64,
+        drawbar_height_meters: Option<f64>,
+        truck_spacing_meters: Option<f64>,
+    ) -> Self {
+        let def = Self::default();
+        Self {
+            transfer_coeff: transfer_coeff * uc::R,
+            drawbar_height: drawbar_height_meters
+                .map(|h| h * uc::M)
+                .unwrap_or(def.drawbar_height),
+            truck_spacing: truck_spacing_meters
+                .map(|l| l * uc::M)
+                .unwrap_or(def.truck_spacing),
+        }
+    }
+}
+
+impl Init for AxleLoadTransfer {}
+impl SerdeAPI for AxleLoadTransfer {}
+
+impl Default for AxleLoadTransfer {
+    /// Disabled (i.e. zero transfer coefficient) with standard 34.5 inch
+    /// coupler height and 52 ft truck spacing typical of six-axle locomotives
+    fn default() -> Self {
+        Self {
+            transfer_coeff: si::Ratio::ZERO,
+            drawbar_height: 34.5 / 12.0 * uc::FT,
+            truck_spacing: 52.0 * uc::FT,
+        }
+    }
+}
+
+impl AxleLoadTransfer {
+    /// Returns true if the model has any effect
+    pub fn is_enabled(&self) -> bool {
+        self.transfer_coeff > si::Ratio::ZERO
+    }
+
+    /// Returns fraction of max tractive force available at `force_drawbar` for a
+    /// locomotive with static weight `weight`
+    pub fn force_max_ratio(&self, force_drawbar: si::Force, weight: si::Force) -> si::Ratio {
+        if !self.is_enabled() || weight <= si::Force::ZERO {
+            return uc::R;
+        }
+        let weight_transfer =
+            force_drawbar.max(si::Force::ZERO) * self.drawbar_height / self.truck_spacing;
+        (uc::R - self.transfer_coeff * weight_transfer / weight)
+            .max(si::Ratio::ZERO)
+            .min(uc::R)
+    }
+}
+
+impl ObjState for AxleLoadTransfer {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gez(&mut errors, &self.transfer_coeff, "Transfer coefficient");
+        si_chk_num_gez(&mut errors, &self.drawbar_height, "Drawbar height");
+        si_chk_num_gtz(&mut errors, &self.truck_spacing, "Truck spacing");
+        errors.make_err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_force_max_ratio() {
+        let weight = 432e3 * uc::LB * uc::ACC_GRAV;
+        let force_drawbar = 150e3 * uc::LBF;
+
+        assert_eq!(
+            AxleLoadTransfer::default().force_max_ratio(force_drawbar, weight),
+            uc::R
+        );
+
+        let alt = AxleLoadTransfer {
+            transfer_coeff: uc::R,
+            ..Default::default()
+        };
+        let ratio_low = alt.force_max_ratio(10e3 * uc::LBF, weight);
+        let ratio_high = alt.force_max_ratio(force_drawbar, weight);
+        assert!(ratio_high < ratio_low);
+        assert!(ratio_low < uc::R);
+        // braking does not unload the lead truck
+        assert_eq!(alt.force_max_ratio(-force_drawbar, weight), uc::R);
+    }
+}
@@ -127,6 +127,7 @@ impl LocoTrait for BatteryElectricLoco {
         _elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         train_mass: Option<si::Mass>,
         train_speed: Option<si::Velocity>,
+        _grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()> {
         let mass_for_loco: si::Mass = train_mass.with_context(|| {
@@ -177,9 +178,9 @@ impl LocoTrait for BatteryElectricLoco {
             disch_buffer,
             chrg_buffer,
         )?;
-        self.edrv.set_cur_pwr_max_out(
+        self.edrv.set_cur_pwr_max_out_at_speed(
             *self.res.state.pwr_prop_max.get_fresh(|| format_dbg!())?,
-            None,
+            Some(train_speed),
         )?;
         self.edrv
             .set_cur_pwr_regen_max(*self.res.state.pwr_charge_max.get_fresh(|| format_dbg!())?)?;
@@ -232,11 +233,30 @@ impl Init for BatteryPowertrainControls {
 }
 
 impl SetCumulative for BatteryPowertrainControls {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
         match self {
-            Self::RGWDB(rgwdb) => {
-                rgwdb.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?
-            }
+            Self::RGWDB(rgwdb) => rgwdb.set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?,
+        }
+        Ok(())
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::RGWDB(rgwdb) => rgwdb.set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?,
         }
         Ok(())
     }
@@ -19,21 +19,26 @@ pub struct ConventionalLoco {
     pub gen: Generator,
     #[has_state]
     pub edrv: ElectricDrivetrain,
+    /// Optional throttle notch table, as fractions of `fc.pwr_out_max` for notches 1 and up
+    /// (notch 0 is idle).  If `None`, fuel converter power is continuous.
+    #[serde(default)]
+    pub notch_pwr_fracs: Option<Vec<f64>>,
 }
 
 #[pyo3_api]
 impl ConventionalLoco {
     #[new]
+    #[pyo3(signature = (fuel_converter, generator, electric_drivetrain, notch_pwr_fracs=None))]
     pub fn __new__(
         fuel_converter: FuelConverter,
         generator: Generator,
         electric_drivetrain: ElectricDrivetrain,
-    ) -> Self {
-        Self {
-            fc: fuel_converter,
-            gen: generator,
-            edrv: electric_drivetrain,
-        }
+        notch_pwr_fracs: Option<Vec<f64>>,
+    ) -> anyhow::Result<Self> {
+        let mut loco = Self::new(fuel_converter, generator, electric_drivetrain);
+        loco.notch_pwr_fracs = notch_pwr_fracs;
+        loco.init()?;
+        Ok(loco)
     }
 }
 
@@ -47,9 +52,27 @@ impl ConventionalLoco {
             fc: fuel_converter,
             gen: generator,
             edrv: electric_drivetrain,
+            notch_pwr_fracs: None,
         }
     }
 
+    /// Returns the nearest notch and its power for `pwr_req`
+    fn get_notch(&self, pwr_req: si::Power) -> Option<(u8, si::Power)> {
+        let notch_pwr_fracs = self.notch_pwr_fracs.as_ref()?;
+        let mut notch = (0, si::Power::ZERO);
+        for (idx, frac) in notch_pwr_fracs.iter().enumerate() {
+            let pwr_notch = self.fc.pwr_out_max * *frac;
+            if (pwr_notch - pwr_req).abs() < (notch.1 - pwr_req).abs() {
+                notch = (idx as u8 + 1, pwr_notch);
+            }
+        }
+        Some(notch)
+    }
+
+    /// If [Self::notch_pwr_fracs] is provided, power at the wheel/rail interface is adjusted
+    /// from `pwr_out_req` so that [Self::fc] runs at the nearest notch, limited by its
+    /// current transient max, and the adjusted power is what [Self::gen] and [Self::edrv]
+    /// deliver.
     /// # Arguments
     /// - `pwr_out_req`: power required at the wheel/rail interface
     /// - `dt`: time step size
@@ -64,28 +87,117 @@ impl ConventionalLoco {
         pwr_aux: si::Power,
         assert_limits: bool,
     ) -> anyhow::Result<()> {
-        self.edrv.set_pwr_in_req(pwr_out_req, dt)?;
-
-        self.gen.set_pwr_in_req(
-            // TODO: maybe this should be either zero or greater than or equal to zero if not loco_on
-            *self
-                .edrv
-                .state
-                .pwr_elec_prop_in
-                .get_fresh(|| format_dbg!())?,
+        let (pwr_out_req, notch) =
+            match self.get_notch_pwr_out(pwr_out_req, pwr_aux, loco_on, dt)? {
+                Some((pwr_out_notch, notch)) => (pwr_out_notch, Some(notch)),
+                None => (pwr_out_req, None),
+            };
+        let fc_pwr_out_req = solve_drivetrain(
+            &mut self.edrv,
+            &mut self.gen,
+            pwr_out_req,
             pwr_aux,
             loco_on,
             dt,
         )?;
-
-        self.fc.solve_energy_consumption(
-            *self.gen.state.pwr_mech_in.get_fresh(|| format_dbg!())?,
-            dt,
-            loco_on,
-            assert_limits,
-        )?;
+        self.fc
+            .solve_energy_consumption(fc_pwr_out_req, dt, loco_on, notch, assert_limits)?;
         Ok(())
     }
+
+    /// Returns the power at the wheel/rail interface that loads [Self::fc] to the nearest
+    /// notch for `pwr_out_req`, or to its current transient max if that is lower, along
+    /// with that notch.  Returns `None` if [Self::notch_pwr_fracs] is `None`.  Non-positive
+    /// `pwr_out_req` is returned unchanged with notch 0 (idle).  Output power is bounded by
+    /// zero and the drivetrain's current max, so [Self::fc] may run above or below the notch
+    /// power when the notch calls for more than the drivetrain can take or less than aux
+    /// power requires.
+    fn get_notch_pwr_out(
+        &self,
+        pwr_out_req: si::Power,
+        pwr_aux: si::Power,
+        loco_on: bool,
+        dt: si::Time,
+    ) -> anyhow::Result<Option<(si::Power, u8)>> {
+        if self.notch_pwr_fracs.is_none() {
+            return Ok(None);
+        }
+        if pwr_out_req <= si::Power::ZERO {
+            return Ok(Some((pwr_out_req, 0)));
+        }
+        let pwr_mech_in = |pwr_out: si::Power| -> anyhow::Result<si::Power> {
+            solve_drivetrain(
+                &mut self.edrv.clone(),
+                &mut self.gen.clone(),
+                pwr_out,
+                pwr_aux,
+                loco_on,
+                dt,
+            )
+        };
+        let pwr_out_max = *self
+            .edrv
+            .state
+            .pwr_mech_out_max
+            .get_fresh(|| format_dbg!())?;
+        let (notch, pwr_notch) = self
+            .get_notch(pwr_mech_in(pwr_out_req)?)
+            .with_context(|| format_dbg!())?;
+        // engine ramps toward notch power at the rate allowed by its transient limit
+        let pwr_notch = pwr_notch.min(*self.fc.state.pwr_out_max.get_fresh(|| format_dbg!())?);
+
+        // secant iteration on output power, starting from the bracket of zero output power
+        // and `pwr_out_req`, until generator input matches notch power
+        let mut pwr_out_prev = si::Power::ZERO;
+        let mut pwr_mech_in_prev = pwr_mech_in(pwr_out_prev)?;
+        let mut pwr_out = pwr_out_req;
+        for _ in 0..NOTCH_SOLVER_MAX_ITER {
+            let pwr_mech_in_curr = pwr_mech_in(pwr_out)?;
+            if (pwr_notch - pwr_mech_in_curr).abs() <= NOTCH_SOLVER_TOL * self.fc.pwr_out_max
+                || pwr_mech_in_curr == pwr_mech_in_prev
+            {
+                return Ok(Some((pwr_out, notch)));
+            }
+            let pwr_out_next = (pwr_out
+                + (pwr_notch - pwr_mech_in_curr) * (pwr_out - pwr_out_prev)
+                    / (pwr_mech_in_curr - pwr_mech_in_prev))
+                .max(si::Power::ZERO)
+                .min(pwr_out_max);
+            pwr_out_prev = pwr_out;
+            pwr_mech_in_prev = pwr_mech_in_curr;
+            pwr_out = pwr_out_next;
+        }
+        bail!(
+            "{}\nFailed to find output power for notch {notch} within {NOTCH_SOLVER_MAX_ITER} iterations",
+            format_dbg!()
+        )
+    }
+}
+
+/// Max number of iterations for finding output power that matches notch power
+const NOTCH_SOLVER_MAX_ITER: usize = 20;
+/// Tolerance, as a fraction of [FuelConverter::pwr_out_max], for matching notch power
+const NOTCH_SOLVER_TOL: f64 = 1e-9;
+
+/// Solves `edrv` and `gen` for `pwr_out_req` and returns the resulting generator mechanical
+/// input power
+fn solve_drivetrain(
+    edrv: &mut ElectricDrivetrain,
+    gen: &mut Generator,
+    pwr_out_req: si::Power,
+    pwr_aux: si::Power,
+    loco_on: bool,
+    dt: si::Time,
+) -> anyhow::Result<si::Power> {
+    edrv.set_pwr_in_req(pwr_out_req, dt)?;
+    gen.set_pwr_in_req(
+        // TODO: maybe this should be either zero or greater than or equal to zero if not loco_on
+        *edrv.state.pwr_elec_prop_in.get_fresh(|| format_dbg!())?,
+        pwr_aux,
+        loco_on,
+        dt,
+    )?;
+    Ok(*gen.state.pwr_mech_in.get_fresh(|| format_dbg!())?)
 }
 
 impl Mass for ConventionalLoco {
@@ -119,6 +231,24 @@ impl Init for ConventionalLoco {
         self.fc.init()?;
         self.gen.init()?;
         self.edrv.init()?;
+        if let Some(notch_pwr_fracs) = &self.notch_pwr_fracs {
+            if notch_pwr_fracs.len() > u8::MAX as usize {
+                return Err(Error::InitError(format!(
+                    "{}\n`notch_pwr_fracs` has too many notches",
+                    format_dbg!()
+                )));
+            }
+            if notch_pwr_fracs
+                .iter()
+                .any(|frac| !(0.0..=1.0).contains(frac))
+                || notch_pwr_fracs.windows(2).any(|w| w[0] >= w[1])
+            {
+                return Err(Error::InitError(format!(
+                    "{}\n`notch_pwr_fracs` must be strictly increasing and between 0 and 1",
+                    format_dbg!()
+                )));
+            }
+        }
         Ok(())
     }
 }
@@ -132,7 +262,8 @@ impl LocoTrait for ConventionalLoco {
         pwr_aux: Option<si::Power>,
         elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         _train_mass: Option<si::Mass>,
-        _train_speed: Option<si::Velocity>,
+        train_speed: Option<si::Velocity>,
+        _grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()> {
         self.fc.set_cur_pwr_out_max(elev_and_temp, dt)?;
@@ -140,13 +271,13 @@ impl LocoTrait for ConventionalLoco {
             *self.fc.state.pwr_out_max.get_fresh(|| format_dbg!())?,
             Some(pwr_aux.with_context(|| format_dbg!("`pwr_aux` not provided"))?),
         )?;
-        self.edrv.set_cur_pwr_max_out(
+        self.edrv.set_cur_pwr_max_out_at_speed(
             *self
                 .gen
                 .state
                 .pwr_elec_prop_out_max
                 .get_fresh(|| format_dbg!())?,
-            None,
+            train_speed,
         )?;
         self.edrv.set_cur_pwr_regen_max(si::Power::ZERO)?;
         self.gen
@@ -167,3 +298,108 @@ impl LocoTrait for ConventionalLoco {
             + *self.edrv.state.energy_loss.get_stale(|| format_dbg!())?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `loco` at constant fractional power demand and returns cumulative fuel energy
+    fn run_loco(loco: &mut Locomotive, pwr_frac: f64) -> si::Energy {
+        let dt = uc::S;
+        for _ in 0..30 {
+            loco.check_and_reset(|| format_dbg!()).unwrap();
+            loco.step(|| format_dbg!()).unwrap();
+            loco.set_pwr_aux(Some(true)).unwrap();
+            loco.set_curr_pwr_max_out(None, None, None, None, None, dt)
+                .unwrap();
+            let pwr_out_req = (pwr_frac * loco.get_pwr_rated())
+                .min(*loco.state.pwr_out_max.get_fresh(|| format_dbg!()).unwrap());
+            loco.solve_energy_consumption(pwr_out_req, dt, Some(true), None, None)
+                .unwrap();
+            loco.set_cumulative(dt, || format_dbg!()).unwrap();
+        }
+        *loco
+            .fuel_converter()
+            .unwrap()
+            .state
+            .energy_fuel
+            .get_fresh(|| format_dbg!())
+            .unwrap()
+    }
+
+    #[test]
+    fn test_notch_pwr_fracs() {
+        let mut loco_cont = Locomotive::default();
+        let energy_fuel_cont = run_loco(&mut loco_cont, 0.3);
+        assert_eq!(
+            *loco_cont
+                .fuel_converter()
+                .unwrap()
+                .state
+                .notch
+                .get_fresh(|| format_dbg!())
+                .unwrap(),
+            None
+        );
+        let pwr_out_cont = *loco_cont.state.pwr_out.get_fresh(|| format_dbg!()).unwrap();
+
+        let notch_pwr_fracs: Vec<f64> = (1..=8).map(|n| n as f64 / 8.0).collect();
+        let mut loco_notch = Locomotive::default();
+        if let PowertrainType::ConventionalLoco(conv) = &mut loco_notch.loco_type {
+            conv.notch_pwr_fracs = Some(notch_pwr_fracs.clone());
+            conv.init().unwrap();
+        }
+        let energy_fuel_notch = run_loco(&mut loco_notch, 0.3);
+        assert!(energy_fuel_notch != energy_fuel_cont);
+
+        let PowertrainType::ConventionalLoco(conv) = &loco_notch.loco_type else {
+            unreachable!()
+        };
+        let notch = conv
+            .fc
+            .state
+            .notch
+            .get_fresh(|| format_dbg!())
+            .unwrap()
+            .unwrap();
+        assert!(notch > 0);
+        let pwr_notch = conv.fc.pwr_out_max * notch_pwr_fracs[notch as usize - 1];
+        let pwr_shaft = *conv.fc.state.pwr_shaft.get_fresh(|| format_dbg!()).unwrap();
+        assert!(almost_eq_uom(&pwr_shaft, &pwr_notch, None));
+        // rounded power propagates through generator and drivetrain to locomotive output
+        assert_eq!(
+            *conv
+                .gen
+                .state
+                .pwr_mech_in
+                .get_fresh(|| format_dbg!())
+                .unwrap(),
+            pwr_shaft
+        );
+        let pwr_out_notch = *loco_notch
+            .state
+            .pwr_out
+            .get_fresh(|| format_dbg!())
+            .unwrap();
+        assert_eq!(
+            *conv
+                .edrv
+                .state
+                .pwr_out_req
+                .get_fresh(|| format_dbg!())
+                .unwrap(),
+            pwr_out_notch
+        );
+        assert!(pwr_out_notch != pwr_out_cont);
+        assert!(pwr_out_notch > si::Power::ZERO);
+    }
+
+    #[test]
+    fn test_notch_pwr_fracs_init() {
+        let mut conv = ConventionalLoco {
+            notch_pwr_fracs: Some(vec![0.5, 0.25]),
+            ..Default::default()
+        };
+        assert!(conv.init().is_err());
+    }
+}
@@ -102,6 +102,7 @@ impl LocoTrait for Box<HybridLoco> {
         // amount of assigned train mass for this locomotive
         train_mass_for_loco: Option<si::Mass>,
         train_speed: Option<si::Velocity>,
+        grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()> {
         let mass_for_loco: si::Mass = train_mass_for_loco.with_context(|| {
@@ -125,6 +126,7 @@ impl LocoTrait for Box<HybridLoco> {
                         format_dbg!()
                     )
                 })?, || format_dbg!())?;
+                rgwb.handle_fc_off_causes_for_downgrade(&self.res, grade_ahead, dt)?;
             }
         };
 
@@ -170,14 +172,14 @@ impl LocoTrait for Box<HybridLoco> {
             Some(si::Power::ZERO),
         )?;
 
-        self.edrv.set_cur_pwr_max_out(
+        self.edrv.set_cur_pwr_max_out_at_speed(
             *self
                 .gen
                 .state
                 .pwr_elec_prop_out_max
                 .get_fresh(|| format_dbg!())?
                 + *self.res.state.pwr_prop_max.get_fresh(|| format_dbg!())?,
-            None,
+            Some(train_speed),
         )?;
 
         self.edrv
@@ -204,6 +206,21 @@ impl LocoTrait for Box<HybridLoco> {
 }
 
 impl HybridLoco {
+    /// Determines whether engine must remain off because it has not been off
+    /// for long enough
+    /// # Arguments:
+    /// - `time_in_engine_state`: elapsed time since engine last turned on or off
+    pub fn handle_fc_off_causes_for_min_time_off(
+        &mut self,
+        time_in_engine_state: si::Time,
+    ) -> anyhow::Result<()> {
+        match &mut self.pt_cntrl {
+            HybridPowertrainControls::RGWDB(rgwdb) => rgwdb
+                .handle_fc_off_causes_for_min_time_off(&self.fc, time_in_engine_state)
+                .with_context(|| format_dbg!()),
+        }
+    }
+
     /// Solve fc and res energy consumption
     /// # Arguments:
     /// - `pwr_out_req`: tractive power require
@@ -252,7 +269,7 @@ impl HybridLoco {
         let fc_pwr_mech_out = *self.gen.state.pwr_mech_in.get_fresh(|| format_dbg!())?;
 
         self.fc
-            .solve_energy_consumption(fc_pwr_mech_out, dt, fc_on, assert_limits)
+            .solve_energy_consumption(fc_pwr_mech_out, dt, fc_on, None, assert_limits)
             .with_context(|| {
                 format!(
                     "{}
@@ -348,11 +365,30 @@ impl CheckAndResetState for HybridPowertrainControls {
 }
 
 impl SetCumulative for HybridPowertrainControls {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
         match self {
-            Self::RGWDB(rgwdb) => {
-                rgwdb.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?
-            }
+            Self::RGWDB(rgwdb) => rgwdb.set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?,
+        }
+        Ok(())
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::RGWDB(rgwdb) => rgwdb.set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?,
         }
         Ok(())
     }
@@ -576,6 +612,11 @@ pub struct RESGreedyWithDynamicBuffers {
     pub speed_soc_fc_on_buffer: Option<si::Velocity>,
     /// Coefficient for modifying amount of [Self::speed_soc_fc_on_buffer]
     pub speed_soc_fc_on_buffer_coeff: Option<si::Ratio>,
+    /// Once [FuelConverter] has been forced on for low SOC, SOC must exceed
+    /// the buffer from [Self::speed_soc_fc_on_buffer] by this amount before
+    /// low SOC no longer forces it on, providing hysteresis
+    #[serde(default)]
+    pub soc_fc_off_buffer_delta: Option<si::Ratio>,
     /// RES energy delta from maximum SOC corresponding to kinetic energy of
     /// vehicle at current speed minus kinetic energy of vehicle at this speed
     /// triggers ramp down in RES discharge
@@ -586,6 +627,10 @@ pub struct RESGreedyWithDynamicBuffers {
     /// Minimum time engine must remain on if it was on during the previous
     /// simulation time step.
     pub fc_min_time_on: Option<si::Time>,
+    /// Minimum time engine must remain off if it was off during the previous
+    /// simulation time step, unless power demand requires it to be on.
+    #[serde(default)]
+    pub fc_min_time_off: Option<si::Time>,
     /// Speed at which [FuelConverter] is forced on.
     pub speed_fc_forced_on: Option<si::Velocity>,
     /// Fraction of total aux and powertrain rated power at which
@@ -598,6 +643,20 @@ pub struct RESGreedyWithDynamicBuffers {
     pub frac_of_max_pwr_to_run_fc: Option<si::Ratio>,
     /// Force generator, if engine is on, to run at this power to help run engine efficiently
     pub pwr_gen_elec_out_for_eff_fc: Option<si::Power>,
+    /// SOC above which [FuelConverter] is turned off when the grade ahead of
+    /// the train is a downgrade, in anticipation of sustained regen filling
+    /// the battery.  Engine is never turned off for downgrades if `None`.
+    #[serde(default)]
+    pub engine_off_downgrade_soc_thresh: Option<si::Ratio>,
+    /// Magnitude of grade ahead of the train beyond which the grade is
+    /// treated as a downgrade for [Self::engine_off_downgrade_soc_thresh]
+    #[serde(default)]
+    pub engine_off_downgrade_grade_thresh: Option<si::Ratio>,
+    /// Minimum time the grade ahead must remain a downgrade before
+    /// [FuelConverter] is turned off for it, so that short dips do not cycle
+    /// the engine
+    #[serde(default)]
+    pub engine_off_downgrade_min_time: Option<si::Time>,
     // /// temperature at which engine is forced on to warm up
     // #[serde(default)]
     // pub temp_fc_forced_on: Option<si::Temperature>,
@@ -659,9 +718,77 @@ impl RESGreedyWithDynamicBuffers {
                     * *res_state.pwr_disch_max.get_fresh(|| format_dbg!())?,
             || format_dbg!(),
         )?;
+        // demand beyond what RES alone can provide also requires the engine,
+        // even if [RESGreedyWithDynamicBuffers::fc_min_time_off] has not elapsed
         self.state.propulsion_power_demand.update(
             pwr_out_req - *gen_state.pwr_elec_out_max.get_fresh(|| format_dbg!())?
-                >= si::Power::ZERO,
+                >= si::Power::ZERO
+                || pwr_out_req > *res_state.pwr_prop_max.get_fresh(|| format_dbg!())?,
+            || format_dbg!(),
+        )?;
+        Ok(())
+    }
+
+    /// Determines whether engine should be off because upcoming downgrade
+    /// implies sustained regen that would fill the battery
+    fn handle_fc_off_causes_for_downgrade(
+        &mut self,
+        res: &ReversibleEnergyStorage,
+        grade_ahead: Option<si::Ratio>,
+        dt: si::Time,
+    ) -> anyhow::Result<()> {
+        let grade_thresh = self
+            .engine_off_downgrade_grade_thresh
+            .with_context(|| format_dbg!())?;
+        let downgrade_ahead = grade_ahead.is_some_and(|grade| grade < -grade_thresh);
+        self.state.time_downgrade_ahead.update(
+            if downgrade_ahead {
+                *self
+                    .state
+                    .time_downgrade_ahead
+                    .get_stale(|| format_dbg!())?
+                    + dt
+            } else {
+                si::Time::ZERO
+            },
+            || format_dbg!(),
+        )?;
+        self.state.downgrade_regen_expected.update(
+            match self.engine_off_downgrade_soc_thresh {
+                Some(soc_thresh) => {
+                    downgrade_ahead
+                        && *self
+                            .state
+                            .time_downgrade_ahead
+                            .get_fresh(|| format_dbg!())?
+                            >= self
+                                .engine_off_downgrade_min_time
+                                .with_context(|| format_dbg!())?
+                        && *res.state.soc.get_stale(|| format_dbg!())? >= soc_thresh
+                }
+                None => false,
+            },
+            || format_dbg!(),
+        )?;
+        Ok(())
+    }
+
+    /// Determines whether engine must remain off because it has been off for
+    /// less than [Self::fc_min_time_off]
+    fn handle_fc_off_causes_for_min_time_off(
+        &mut self,
+        fc: &FuelConverter,
+        time_in_engine_state: si::Time,
+    ) -> anyhow::Result<()> {
+        self.state.off_time_too_short.update(
+            !*fc.state.engine_on.get_stale(|| format_dbg!())?
+                && time_in_engine_state
+                    < self.fc_min_time_off.with_context(|| {
+                        anyhow!(
+                            "{}\n Expected `ResGreedyWithBuffers::init` to have been called beforehand.",
+                            format_dbg!()
+                        )
+                    })?,
             || format_dbg!(),
         )?;
         Ok(())
@@ -684,11 +811,25 @@ impl RESGreedyWithDynamicBuffers {
         train_speed: si::Velocity,
     ) -> anyhow::Result<()> {
         self.set_soc_fc_on_buffer(res, mass, train_speed)?;
-        self.state.charging_for_low_soc.update(
-            *res.state.soc.get_stale(|| format_dbg!())?
-                < *self.state.soc_fc_on_buffer.get_fresh(|| format_dbg!())?,
-            || format_dbg!(),
-        )?;
+        let soc = *res.state.soc.get_stale(|| format_dbg!())?;
+        let soc_fc_on_buffer = *self.state.soc_fc_on_buffer.get_fresh(|| format_dbg!())?;
+        // once charging, keep charging until SOC is above the on buffer plus
+        // the hysteresis delta
+        let soc_fc_off_buffer = if *self
+            .state
+            .charging_for_low_soc
+            .get_stale(|| format_dbg!())?
+        {
+            soc_fc_on_buffer
+                + self
+                    .soc_fc_off_buffer_delta
+                    .with_context(|| format_dbg!())?
+        } else {
+            soc_fc_on_buffer
+        };
+        self.state
+            .charging_for_low_soc
+            .update(soc < soc_fc_off_buffer, || format_dbg!())?;
         Ok(())
     }
 }
@@ -709,12 +850,16 @@ impl Init for RESGreedyWithDynamicBuffers {
         init_opt_default!(self, speed_soc_fc_on_buffer_coeff, 1.0 * uc::R);
         init_opt_default!(self, speed_soc_regen_buffer, 10. * uc::MPH);
         init_opt_default!(self, speed_soc_regen_buffer_coeff, 1.0 * uc::R);
+        init_opt_default!(self, soc_fc_off_buffer_delta, 0.0 * uc::R);
         init_opt_default!(self, fc_min_time_on, uc::S * 5.0);
+        init_opt_default!(self, fc_min_time_off, uc::S * 0.0);
         // Force FC to be on all the time by default
         init_opt_default!(self, speed_fc_forced_on, uc::MPH * 0.0);
         init_opt_default!(self, frac_pwr_demand_fc_forced_on, uc::R * 0.75);
         // 20% of peak power gets most of peak efficiency
         init_opt_default!(self, frac_of_max_pwr_to_run_fc, 0.2 * uc::R);
+        init_opt_default!(self, engine_off_downgrade_grade_thresh, 0.005 * uc::R);
+        init_opt_default!(self, engine_off_downgrade_min_time, 30.0 * uc::S);
         Ok(())
     }
 }
@@ -746,6 +891,9 @@ pub struct RGWDBState {
     train_speed_above_threshold: TrackedState<bool>,
     /// Engine has not been on long enough (usually 30 s)
     on_time_too_short: TrackedState<bool>,
+    /// Engine has not been off long enough, per
+    /// [RESGreedyWithDynamicBuffers::fc_min_time_off]
+    off_time_too_short: TrackedState<bool>,
     /// Powertrain power demand exceeds motor and/or battery capabilities
     propulsion_power_demand: TrackedState<bool>,
     /// Powertrain power demand exceeds optimal motor and/or battery output
@@ -755,6 +903,12 @@ pub struct RGWDBState {
     // aux_power_demand: TrackedState<bool>,
     /// SOC is below min buffer so FC is charging RES
     charging_for_low_soc: TrackedState<bool>,
+    /// Upcoming downgrade is expected to provide enough regen to fill RES, so
+    /// FC is allowed to turn off
+    downgrade_regen_expected: TrackedState<bool>,
+    /// Time for which grade ahead has been steeper than
+    /// [RESGreedyWithDynamicBuffers::engine_off_downgrade_grade_thresh]
+    time_downgrade_ahead: TrackedState<si::Time>,
 
     /// buffer at which FC is forced on
     pub soc_fc_on_buffer: TrackedState<si::Ratio>,
@@ -767,18 +921,196 @@ impl Init for RGWDBState {}
 impl SerdeAPI for RGWDBState {}
 
 impl FuelConverterOn for RGWDBState {
-    /// If any of the causes are true, engine must be on
+    /// If any of the causes are true, engine must be on.  An expected
+    /// downgrade overrides all causes except minimum on time and power demand.
+    /// Minimum off time overrides all causes except minimum on time and power
+    /// demand exceeding powertrain capability.
     fn fc_on(&self) -> anyhow::Result<bool> {
-        Ok(*self.fc_temperature_too_low.get_fresh(|| format_dbg!())?
+        let fc_required = *self.on_time_too_short.get_fresh(|| format_dbg!())?
+            || *self.propulsion_power_demand.get_fresh(|| format_dbg!())?;
+        let fc_demanded = *self
+            .propulsion_power_demand_soft
+            .get_fresh(|| format_dbg!())?;
+        let fc_forced_on = *self.fc_temperature_too_low.get_fresh(|| format_dbg!())?
             || *self
                 .train_speed_above_threshold
                 .get_fresh(|| format_dbg!())?
-            || *self.on_time_too_short.get_fresh(|| format_dbg!())?
-            || *self.propulsion_power_demand.get_fresh(|| format_dbg!())?
-            || *self
-                .propulsion_power_demand_soft
-                .get_fresh(|| format_dbg!())?
             // || *self.aux_power_demand.get_fresh(|| format_dbg!())?
-            || *self.charging_for_low_soc.get_fresh(|| format_dbg!())?)
+            || *self.charging_for_low_soc.get_fresh(|| format_dbg!())?;
+        Ok(fc_required
+            || (!*self.off_time_too_short.get_fresh(|| format_dbg!())?
+                && (fc_demanded
+                    || (fc_forced_on
+                        && !*self.downgrade_regen_expected.get_fresh(|| format_dbg!())?))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steps `loco` through a time step at constant speed with the given grade
+    /// ahead and power demand as a fraction of regen (negative) or propulsion
+    /// (positive) capability
+    fn step_loco(loco: &mut Locomotive, grade_ahead: si::Ratio, pwr_frac: f64) {
+        let train_mass = Some(2e6 * uc::KG);
+        let train_speed = Some(20.0 * uc::MPH);
+        let dt = uc::S;
+        loco.check_and_reset(|| format_dbg!()).unwrap();
+        loco.step(|| format_dbg!()).unwrap();
+        loco.set_pwr_aux(Some(true)).unwrap();
+        loco.set_curr_pwr_max_out(None, None, train_mass, train_speed, Some(grade_ahead), dt)
+            .unwrap();
+        let pwr_out_req = if pwr_frac < 0.0 {
+            pwr_frac
+                * *loco
+                    .state
+                    .pwr_regen_max
+                    .get_fresh(|| format_dbg!())
+                    .unwrap()
+        } else {
+            pwr_frac * *loco.state.pwr_out_max.get_fresh(|| format_dbg!()).unwrap()
+        };
+        loco.solve_energy_consumption(pwr_out_req, dt, Some(true), train_mass, train_speed)
+            .unwrap();
+        loco.set_cumulative(dt, || format_dbg!()).unwrap();
+        loco.save_state(|| format_dbg!()).unwrap();
+    }
+
+    fn energy_fuel(loco: &Locomotive) -> si::Energy {
+        *loco
+            .fuel_converter()
+            .unwrap()
+            .state
+            .energy_fuel
+            .get_fresh(|| format_dbg!())
+            .unwrap()
+    }
+
+    fn soc(loco: &Locomotive) -> si::Ratio {
+        *loco
+            .reversible_energy_storage()
+            .unwrap()
+            .state
+            .soc
+            .get_fresh(|| format_dbg!())
+            .unwrap()
+    }
+
+    /// Returns hybrid loco that turns engine off for downgrades steeper than
+    /// 0.5% sustained for at least 5 s when SOC is at or above `soc_thresh`
+    fn downgrade_loco(soc_thresh: si::Ratio) -> Locomotive {
+        let mut loco = Locomotive::default_hybrid_electric_loco();
+        if let PowertrainType::HybridLoco(hel) = &mut loco.loco_type {
+            let HybridPowertrainControls::RGWDB(rgwdb) = &mut hel.pt_cntrl;
+            rgwdb.engine_off_downgrade_soc_thresh = Some(soc_thresh);
+            rgwdb.engine_off_downgrade_grade_thresh = Some(0.005 * uc::R);
+            rgwdb.engine_off_downgrade_min_time = Some(5.0 * uc::S);
+        }
+        loco.save_state(|| format_dbg!()).unwrap();
+        // flat segment long enough to satisfy minimum engine on time
+        for _ in 0..10 {
+            step_loco(&mut loco, si::Ratio::ZERO, 0.1);
+        }
+        loco
+    }
+
+    #[test]
+    fn test_engine_off_on_downgrade() {
+        let soc_thresh = 0.5 * uc::R;
+        let mut loco = downgrade_loco(soc_thresh);
+        assert!(soc(&loco) > soc_thresh);
+        let energy_fuel_flat = energy_fuel(&loco);
+        assert!(energy_fuel_flat > si::Energy::ZERO);
+
+        // grade ahead shallower than the grade threshold keeps engine on
+        let mut energy_fuel_prev = energy_fuel_flat;
+        for _ in 0..10 {
+            step_loco(&mut loco, -0.002 * uc::R, -0.5);
+            assert!(energy_fuel(&loco) > energy_fuel_prev);
+            energy_fuel_prev = energy_fuel(&loco);
+        }
+
+        // steep downhill segment with regen keeps engine on until the
+        // downgrade has been sustained for the minimum time
+        for _ in 0..4 {
+            step_loco(&mut loco, -0.01 * uc::R, -0.5);
+            assert!(energy_fuel(&loco) > energy_fuel_prev);
+            energy_fuel_prev = energy_fuel(&loco);
+        }
+        step_loco(&mut loco, -0.01 * uc::R, -0.5);
+        let energy_fuel_downhill = energy_fuel(&loco);
+        for _ in 0..5 {
+            step_loco(&mut loco, -0.01 * uc::R, -0.5);
+            assert_eq!(energy_fuel(&loco), energy_fuel_downhill);
+        }
+
+        // engine turns back on after the downgrade
+        for _ in 0..10 {
+            step_loco(&mut loco, si::Ratio::ZERO, 0.1);
+        }
+        assert!(energy_fuel(&loco) > energy_fuel_downhill);
+    }
+
+    #[test]
+    fn test_engine_stays_on_on_downgrade_below_soc_thresh() {
+        let soc_thresh = 0.99 * uc::R;
+        let mut loco = downgrade_loco(soc_thresh);
+        assert!(soc(&loco) < soc_thresh);
+        let mut energy_fuel_prev = energy_fuel(&loco);
+        for _ in 0..10 {
+            step_loco(&mut loco, -0.01 * uc::R, -0.5);
+            assert!(energy_fuel(&loco) > energy_fuel_prev);
+            energy_fuel_prev = energy_fuel(&loco);
+        }
+    }
+
+    /// Returns number of times engine turns on or off while `loco` is
+    /// subjected to power demand that alternates every time step
+    fn n_engine_state_changes(
+        fc_min_time_on: si::Time,
+        fc_min_time_off: si::Time,
+        n_steps: usize,
+    ) -> usize {
+        let mut loco = Locomotive::default_hybrid_electric_loco();
+        if let PowertrainType::HybridLoco(hel) = &mut loco.loco_type {
+            let HybridPowertrainControls::RGWDB(rgwdb) = &mut hel.pt_cntrl;
+            // only power demand forces the engine on
+            rgwdb.speed_fc_forced_on = Some(100.0 * uc::MPH);
+            rgwdb.speed_soc_fc_on_buffer = Some(si::Velocity::ZERO);
+            rgwdb.frac_pwr_demand_fc_forced_on = Some(0.05 * uc::R);
+            rgwdb.fc_min_time_on = Some(fc_min_time_on);
+            rgwdb.fc_min_time_off = Some(fc_min_time_off);
+        }
+        loco.save_state(|| format_dbg!()).unwrap();
+
+        (0..n_steps)
+            .filter(|i| {
+                step_loco(
+                    &mut loco,
+                    si::Ratio::ZERO,
+                    if i % 2 == 0 { 0.0 } else { 0.1 },
+                );
+                *loco
+                    .state
+                    .time_in_engine_state
+                    .get_fresh(|| format_dbg!())
+                    .unwrap()
+                    == uc::S
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_engine_dwell_with_oscillating_load() {
+        let n_steps = 20;
+        // without dwell, engine follows the load
+        assert_eq!(
+            n_engine_state_changes(si::Time::ZERO, si::Time::ZERO, n_steps),
+            n_steps
+        );
+        let n_changes = n_engine_state_changes(5.0 * uc::S, 5.0 * uc::S, n_steps);
+        assert!(n_changes > 0);
+        assert!(n_changes <= n_steps / 5, "{n_changes}");
     }
 }
@@ -25,6 +25,10 @@ pub struct PowerTrace {
     pub train_speed: Vec<si::Velocity>,
     /// Train mass, needed only if simulating [HybridLoco]
     pub train_mass: Option<si::Mass>,
+    #[serde(default)]
+    /// Prescribed auxiliary power, e.g. measured aux load for validation.  If
+    /// provided, overrides the locomotive's aux power model.
+    pub pwr_aux: Option<Vec<si::Power>>,
 }
 
 #[pyo3_api]
@@ -44,9 +48,33 @@ impl PowerTrace {
     fn default_py() -> Self {
         Self::default()
     }
+
+    #[pyo3(name = "scale_power")]
+    fn scale_power_py(&mut self, factor: f64) -> anyhow::Result<()> {
+        self.scale_power(factor)
+    }
+
+    #[pyo3(name = "offset_time")]
+    fn offset_time_py(&mut self, dt_seconds: f64) -> anyhow::Result<()> {
+        self.offset_time(dt_seconds * uc::S)
+    }
 }
 
-impl Init for PowerTrace {}
+impl Init for PowerTrace {
+    fn init(&mut self) -> Result<(), Error> {
+        if let Some(pwr_aux) = &self.pwr_aux {
+            if pwr_aux.len() != self.len() {
+                return Err(Error::InitError(format!(
+                    "{}\n`pwr_aux` len ({}) and `time` len ({}) do not match",
+                    format_dbg!(),
+                    pwr_aux.len(),
+                    self.len()
+                )));
+            }
+        }
+        Ok(())
+    }
+}
 impl SerdeAPI for PowerTrace {}
 
 impl PowerTrace {
@@ -57,6 +85,7 @@ impl PowerTrace {
             engine_on: Vec::new(),
             train_speed: Vec::new(),
             train_mass: None,
+            pwr_aux: None,
         }
     }
 
@@ -81,6 +110,9 @@ impl PowerTrace {
         if let Some(train_speed) = pt_element.train_speed {
             self.train_speed.push(train_speed);
         }
+        if let Some(pwr_aux) = pt_element.pwr_aux {
+            self.pwr_aux.get_or_insert_with(Vec::new).push(pwr_aux);
+        }
     }
 
     pub fn trim(&mut self, start_idx: Option<usize>, end_idx: Option<usize>) -> anyhow::Result<()> {
@@ -91,6 +123,36 @@ impl PowerTrace {
         self.time = self.time[start_idx..end_idx].to_vec();
         self.pwr = self.pwr[start_idx..end_idx].to_vec();
         self.engine_on = self.engine_on[start_idx..end_idx].to_vec();
+        if let Some(pwr_aux) = &mut self.pwr_aux {
+            *pwr_aux = pwr_aux[start_idx..end_idx].to_vec();
+        }
+        Ok(())
+    }
+
+    /// Multiplies every element of `pwr` by `factor`.  Prescribed `pwr_aux`,
+    /// if any, is left unchanged.
+    pub fn scale_power(&mut self, factor: f64) -> anyhow::Result<()> {
+        ensure!(
+            factor.is_finite(),
+            "{}
+`factor` must be finite, got {}",
+            format_dbg!(),
+            factor
+        );
+        self.pwr.iter_mut().for_each(|pwr| *pwr *= factor);
+        Ok(())
+    }
+
+    /// Shifts every element of `time` by `dt`
+    pub fn offset_time(&mut self, dt: si::Time) -> anyhow::Result<()> {
+        ensure!(
+            dt.is_finite(),
+            "{}
+`dt` must be finite, got {:?}",
+            format_dbg!(),
+            dt
+        );
+        self.time.iter_mut().for_each(|time| *time += dt);
         Ok(())
     }
 
@@ -112,6 +174,7 @@ impl PowerTrace {
         if pt.is_empty() {
             bail!("Invalid PowerTrace file; PowerTrace is empty")
         } else {
+            pt.init()?;
             Ok(pt)
         }
     }
@@ -132,6 +195,7 @@ impl Default for PowerTrace {
             engine_on: vec![Some(true); time_len],
             train_speed: vec![10.0 * uc::MPH; time_len],
             train_mass: Some(1e6 * uc::LB),
+            pwr_aux: None,
         };
         pt.init().unwrap();
         pt
@@ -149,6 +213,9 @@ pub struct PowerTraceElement {
     engine_on: Option<bool>,
     /// speed at time step
     train_speed: Option<si::Velocity>,
+    /// prescribed aux power at time step
+    #[serde(default, alias = "pwr_aux_watts")]
+    pwr_aux: Option<si::Power>,
 }
 
 #[serde_api]
@@ -201,6 +268,11 @@ impl LocomotiveSimulation {
         self.trim_failed_steps()?;
         Ok(())
     }
+
+    #[pyo3(name = "set_power_trace")]
+    fn set_power_trace_py(&mut self, power_trace: PowerTrace) -> anyhow::Result<()> {
+        self.set_power_trace(power_trace)
+    }
 }
 
 impl LocomotiveSimulation {
@@ -234,6 +306,19 @@ impl LocomotiveSimulation {
         self.loco_unit.set_save_interval(save_interval);
     }
 
+    /// Replaces [Self::power_trace] and resets state and history of
+    /// [Self::loco_unit] (see [Locomotive::reset_state]) so that the same
+    /// locomotive can be rerun over a new power trace without rebuilding the
+    /// simulation
+    pub fn set_power_trace(&mut self, mut power_trace: PowerTrace) -> anyhow::Result<()> {
+        power_trace.init().with_context(|| format_dbg!())?;
+        self.loco_unit
+            .reset_state()
+            .with_context(|| format_dbg!())?;
+        self.power_trace = power_trace;
+        Ok(())
+    }
+
     pub fn get_save_interval(&self) -> Option<usize> {
         self.loco_unit.get_save_interval()
     }
@@ -247,7 +332,13 @@ impl LocomotiveSimulation {
             .get(i)
             .cloned()
             .with_context(|| format_dbg!())?;
-        self.loco_unit.set_pwr_aux(engine_on)?;
+        match &self.power_trace.pwr_aux {
+            Some(pwr_aux) => self.loco_unit.state.pwr_aux.update(
+                *pwr_aux.get(i).with_context(|| format_dbg!())?,
+                || format_dbg!(),
+            )?,
+            None => self.loco_unit.set_pwr_aux(engine_on)?,
+        }
         let train_mass = self.power_trace.train_mass;
         let train_speed = if !self.power_trace.train_speed.is_empty() {
             Some(self.power_trace.train_speed[i])
@@ -256,7 +347,7 @@ impl LocomotiveSimulation {
         };
         let dt = self.power_trace.dt_at_i(i).with_context(|| format_dbg!())?;
         self.loco_unit
-            .set_curr_pwr_max_out(None, None, train_mass, train_speed, dt)?;
+            .set_curr_pwr_max_out(None, None, train_mass, train_speed, None, dt)?;
         let pwr_out_req = self
             .power_trace
             .pwr
@@ -349,9 +440,28 @@ impl CheckAndResetState for LocomotiveSimulation {
 }
 
 impl SetCumulative for LocomotiveSimulation {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
         self.loco_unit
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))
+            .set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        self.loco_unit
+            .set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })
     }
 }
 
@@ -445,6 +555,7 @@ impl LocomotiveSimulationVec {
 mod tests {
     use super::{Locomotive, LocomotiveSimulation, LocomotiveSimulationVec, PowerTrace};
     use crate::consist::locomotive::PowertrainType;
+    use crate::imports::*;
 
     #[test]
     fn test_loco_sim_vec_par() {
@@ -484,6 +595,30 @@ mod tests {
         loco_sim.walk().unwrap();
     }
 
+    #[test]
+    fn test_prescribed_pwr_aux() {
+        let mut pt = PowerTrace::default();
+        let pwr_aux: Vec<si::Power> = (0..pt.len()).map(|i| (10e3 + i as f64) * uc::W).collect();
+        pt.pwr_aux = Some(pwr_aux.clone());
+        pt.init().unwrap();
+        let mut loco_sim = LocomotiveSimulation::new(Locomotive::default(), pt, Some(1));
+        loco_sim.walk().unwrap();
+        let hist_pwr_aux = &loco_sim.loco_unit.history.pwr_aux;
+        assert_eq!(hist_pwr_aux.len(), pwr_aux.len());
+        for (pwr_aux_hist, pwr_aux) in hist_pwr_aux.iter().zip(pwr_aux.iter()).skip(1) {
+            assert_eq!(
+                pwr_aux_hist.get_unchecked(|| format_dbg!()).unwrap(),
+                pwr_aux
+            );
+        }
+
+        let mut pt = PowerTrace {
+            pwr_aux: Some(vec![10e3 * uc::W; 2]),
+            ..Default::default()
+        };
+        assert!(pt.init().is_err());
+    }
+
     #[test]
     fn test_hybrid_locomotive_sim() {
         let hel = Locomotive::default_hybrid_electric_loco();
@@ -543,6 +678,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_set_power_trace() {
+        let mut loco_sim = LocomotiveSimulation::new(
+            Locomotive::default_hybrid_electric_loco(),
+            PowerTrace::default(),
+            Some(1),
+        );
+        loco_sim.walk().unwrap();
+        let energy_fuel_full = *loco_sim
+            .loco_unit
+            .fuel_converter()
+            .unwrap()
+            .state
+            .energy_fuel
+            .get_unchecked(|| format_dbg!())
+            .unwrap();
+
+        let mut pt_short = PowerTrace::default();
+        pt_short.trim(None, Some(10)).unwrap();
+        loco_sim.set_power_trace(pt_short.clone()).unwrap();
+        assert_eq!(loco_sim.get_save_interval(), Some(1));
+        assert!(loco_sim.loco_unit.history.is_empty());
+        assert_eq!(
+            *loco_sim
+                .loco_unit
+                .state
+                .i
+                .get_unchecked(|| format_dbg!())
+                .unwrap(),
+            0
+        );
+
+        loco_sim.walk().unwrap();
+        assert_eq!(
+            *loco_sim
+                .loco_unit
+                .state
+                .i
+                .get_fresh(|| format_dbg!())
+                .unwrap(),
+            pt_short.len() - 1
+        );
+        assert_eq!(loco_sim.loco_unit.history.len(), pt_short.len());
+        // cumulative energy restarts from zero
+        assert!(
+            *loco_sim
+                .loco_unit
+                .fuel_converter()
+                .unwrap()
+                .state
+                .energy_fuel
+                .get_unchecked(|| format_dbg!())
+                .unwrap()
+                < energy_fuel_full
+        );
+    }
+
+    #[test]
+    fn test_power_trace_scale_and_offset() {
+        let pt = PowerTrace::default();
+
+        let mut pt_scaled = pt.clone();
+        pt_scaled.scale_power(2.0).unwrap();
+        for (pwr_scaled, pwr) in pt_scaled.pwr.iter().zip(pt.pwr.iter()) {
+            assert_eq!(*pwr_scaled, 2.0 * *pwr);
+        }
+        assert_eq!(pt_scaled.time, pt.time);
+        assert!(pt_scaled.scale_power(f64::NAN).is_err());
+
+        let dt = 100.0 * uc::S;
+        let mut pt_offset = pt.clone();
+        pt_offset.offset_time(dt).unwrap();
+        for (time_offset, time) in pt_offset.time.iter().zip(pt.time.iter()) {
+            assert_eq!(*time_offset - *time, dt);
+        }
+        assert_eq!(pt_offset.pwr, pt.pwr);
+        assert!(pt_offset.offset_time(f64::INFINITY * uc::S).is_err());
+    }
+
     #[test]
     fn test_power_trace_trim() {
         let pt = PowerTrace::default();
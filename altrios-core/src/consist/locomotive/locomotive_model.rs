@@ -29,6 +29,7 @@ impl LocoTrait for PowertrainType {
         elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         train_mass_for_loco: Option<si::Mass>,
         train_speed: Option<si::Velocity>,
+        grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()> {
         match self {
@@ -37,6 +38,7 @@ impl LocoTrait for PowertrainType {
                 elev_and_temp,
                 train_mass_for_loco,
                 train_speed,
+                grade_ahead,
                 dt,
             ),
             PowertrainType::HybridLoco(hel) => hel.set_curr_pwr_max_out(
@@ -44,6 +46,7 @@ impl LocoTrait for PowertrainType {
                 elev_and_temp,
                 train_mass_for_loco,
                 train_speed,
+                grade_ahead,
                 dt,
             ),
             PowertrainType::BatteryElectricLoco(bel) => bel.set_curr_pwr_max_out(
@@ -51,6 +54,7 @@ impl LocoTrait for PowertrainType {
                 elev_and_temp,
                 train_mass_for_loco,
                 train_speed,
+                grade_ahead,
                 dt,
             ),
             PowertrainType::DummyLoco(dummy) => dummy.set_curr_pwr_max_out(
@@ -58,6 +62,7 @@ impl LocoTrait for PowertrainType {
                 elev_and_temp,
                 train_mass_for_loco,
                 train_speed,
+                grade_ahead,
                 dt,
             ),
         }
@@ -136,16 +141,49 @@ impl CheckAndResetState for PowertrainType {
 impl StateMethods for PowertrainType {}
 
 impl SetCumulative for PowertrainType {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
         match self {
             Self::ConventionalLoco(loco) => {
-                loco.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))
+                loco.set_cumulative_with(dt, integration_method, || {
+                    format!("{}\n{}", loc(), format_dbg!())
+                })
             }
-            Self::HybridLoco(loco) => {
-                loco.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))
+            Self::HybridLoco(loco) => loco.set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            }),
+            Self::BatteryElectricLoco(loco) => {
+                loco.set_cumulative_with(dt, integration_method, || {
+                    format!("{}\n{}", loc(), format_dbg!())
+                })
+            }
+            Self::DummyLoco(_loco) => Ok(()),
+        }
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        match self {
+            Self::ConventionalLoco(loco) => {
+                loco.set_cumulative_prev(dt, integration_method, || {
+                    format!("{}\n{}", loc(), format_dbg!())
+                })
             }
+            Self::HybridLoco(loco) => loco.set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            }),
             Self::BatteryElectricLoco(loco) => {
-                loco.set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))
+                loco.set_cumulative_prev(dt, integration_method, || {
+                    format!("{}\n{}", loc(), format_dbg!())
+                })
             }
             Self::DummyLoco(_loco) => Ok(()),
         }
@@ -345,6 +383,7 @@ impl LocoTrait for DummyLoco {
         _elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         _train_mass: Option<si::Mass>,
         _train_speed: Option<si::Velocity>,
+        _grade_ahead: Option<si::Ratio>,
         _dt: si::Time,
     ) -> anyhow::Result<()> {
         Ok(())
@@ -410,11 +449,27 @@ pub struct Locomotive {
     /// gain for linear model on traction power used to compute traction-power-dependent component
     /// of aux load, in terms of ratio of aux power per tractive power
     pub pwr_aux_traction_coeff: si::Ratio,
+    /// Optional aux load that replaces [Self::pwr_aux_offset] when the
+    /// locomotive is stationary, i.e. producing zero tractive power, as
+    /// returned by [Self::pwr_aux_at_idle]
+    #[serde(default)]
+    pub pwr_aux_idle_override: Option<si::Power>,
     /// maximum tractive force
     force_max: si::Force,
+    /// Runtime multiplier on [Self::force_max] reflecting rail conditions, e.g.
+    /// less than 1.0 for wet rail or leaves and greater than 1.0 for sanded rail.
+    /// Applied only where max force limits tractive effort, via
+    /// [Self::force_max_effective], so it does not affect [Self::check_force_max].
+    #[serde(default = "utils::return_one_ratio")]
+    adhesion_factor: si::Ratio,
+    /// Optional dynamic reduction of [Self::force_max_effective] due to weight
+    /// transfer off of the lead truck at high drawbar pull, applied via
+    /// [LocomotiveState::force_max_curr].  Disabled by default.
+    #[serde(default)]
+    axle_load_transfer: AxleLoadTransfer,
 }
 
-#[pyo3_api]
+#[pyo3_api(repr)]
 impl Locomotive {
     #[new]
     #[pyo3(signature = (loco_type, loco_params, save_interval=None))]
@@ -494,20 +549,15 @@ impl Locomotive {
     }
 
     #[staticmethod]
-    fn build_dummy_loco() -> Self {
-        let mut dummy = Self {
-            loco_type: PowertrainType::DummyLoco(DummyLoco::default()),
-            state: LocomotiveState::default(),
-            save_interval: None,
-            history: LocomotiveStateHistoryVec::new(),
-            assert_limits: true,
-            pwr_aux_offset: 50e3 * uc::W,
-            pwr_aux_traction_coeff: 0.01 * uc::R,
-            force_max: 50e6 * uc::N,
-            ..Default::default()
-        };
-        dummy.set_mass(None, MassSideEffect::None).unwrap();
-        dummy
+    #[pyo3(name = "build_dummy_loco")]
+    fn build_dummy_loco_py() -> Self {
+        Self::build_dummy_loco()
+    }
+
+    #[pyo3(name = "strip_history")]
+    /// Clear history and cascade to nested components.
+    fn strip_history_py(&mut self) {
+        self.strip_history();
     }
 
     #[pyo3(name = "set_save_interval")]
@@ -553,6 +603,11 @@ impl Locomotive {
         self.get_pwr_rated().get::<si::kilowatt>()
     }
 
+    #[pyo3(name = "summary")]
+    fn summary_py(&self) -> anyhow::Result<String> {
+        self.summary()
+    }
+
     #[getter("force_max_lbs")]
     fn get_force_max_pounds_py(&self) -> anyhow::Result<f64> {
         Ok(self.force_max()?.get::<si::pound_force>())
@@ -603,6 +658,61 @@ impl Locomotive {
         Ok(self.baseline_mass.map(|m| m.get::<si::kilogram>()))
     }
 
+    #[getter("adhesion_factor")]
+    fn get_adhesion_factor_py(&self) -> f64 {
+        self.adhesion_factor.get::<si::ratio>()
+    }
+
+    #[pyo3(name = "set_adhesion_factor")]
+    fn set_adhesion_factor_py(&mut self, adhesion_factor: f64) -> anyhow::Result<()> {
+        self.set_adhesion_factor(adhesion_factor * uc::R)
+    }
+
+    #[getter("force_max_effective_newtons")]
+    fn get_force_max_effective_newtons_py(&self) -> anyhow::Result<f64> {
+        Ok(self.force_max_effective()?.get::<si::newton>())
+    }
+
+    #[getter("axle_load_transfer")]
+    fn get_axle_load_transfer_py(&self) -> AxleLoadTransfer {
+        self.axle_load_transfer
+    }
+
+    #[pyo3(name = "set_axle_load_transfer")]
+    fn set_axle_load_transfer_py(
+        &mut self,
+        axle_load_transfer: AxleLoadTransfer,
+    ) -> anyhow::Result<()> {
+        self.set_axle_load_transfer(axle_load_transfer)
+    }
+
+    #[getter("pwr_aux_at_idle_watts")]
+    fn get_pwr_aux_at_idle_watts_py(&self) -> f64 {
+        self.pwr_aux_at_idle().get::<si::watt>()
+    }
+
+    #[getter("pwr_aux_idle_override_watts")]
+    fn get_pwr_aux_idle_override_watts_py(&self) -> Option<f64> {
+        self.pwr_aux_idle_override.map(|pwr| pwr.get::<si::watt>())
+    }
+
+    #[setter("pwr_aux_idle_override_watts")]
+    fn set_pwr_aux_idle_override_watts_py(
+        &mut self,
+        pwr_aux_idle_override_watts: Option<f64>,
+    ) -> anyhow::Result<()> {
+        self.pwr_aux_idle_override = pwr_aux_idle_override_watts.map(|pwr| pwr * uc::W);
+        self.init()?;
+        Ok(())
+    }
+
+    #[pyo3(name = "derate_for_altitude")]
+    fn derate_for_altitude_py(&self, elev_meters: f64) -> anyhow::Result<f64> {
+        Ok(self
+            .derate_for_altitude(elev_meters * uc::M)?
+            .get::<si::ratio>())
+    }
+
     #[getter("mu")]
     fn get_mu_py(&self) -> anyhow::Result<Option<f64>> {
         Ok(self.mu()?.map(|mu| mu.get::<si::ratio>()))
@@ -640,6 +750,7 @@ impl Default for Locomotive {
             loco_type: PowertrainType::ConventionalLoco(ConventionalLoco::default()),
             pwr_aux_offset: loco_params.pwr_aux_offset,
             pwr_aux_traction_coeff: loco_params.pwr_aux_traction_coeff,
+            pwr_aux_idle_override: None,
             mass: loco_params.mass,
             force_max: loco_params.force_max,
             state: Default::default(),
@@ -649,6 +760,8 @@ impl Default for Locomotive {
             history: Default::default(),
             assert_limits: true,
             mu: Default::default(),
+            adhesion_factor: uc::R,
+            axle_load_transfer: Default::default(),
         };
         loco.init().unwrap();
         loco.set_save_interval(Some(1));
@@ -658,15 +771,55 @@ impl Default for Locomotive {
 
 impl Init for Locomotive {
     fn init(&mut self) -> Result<(), Error> {
-        let _mass = self
+        let mass = self
             .mass()
             .map_err(|err| Error::InitError(format_dbg!(err)))?;
+        if self.adhesion_factor < si::Ratio::ZERO || !self.adhesion_factor.is_finite() {
+            return Err(Error::InitError(format!(
+                "{}\n`adhesion_factor` must be finite and non-negative, got {:?}",
+                format_dbg!(),
+                self.adhesion_factor
+            )));
+        }
+        self.axle_load_transfer
+            .validate()
+            .map_err(|err| Error::InitError(format!("{}\n{}", format_dbg!(), err)))?;
+        if let Some(pwr_aux_idle_override) = self.pwr_aux_idle_override {
+            if pwr_aux_idle_override < si::Power::ZERO || !pwr_aux_idle_override.is_finite() {
+                return Err(Error::InitError(format!(
+                    "{}\n`pwr_aux_idle_override` must be finite and non-negative, got {:?}",
+                    format_dbg!(),
+                    pwr_aux_idle_override
+                )));
+            }
+        }
+        if self.axle_load_transfer.is_enabled() && mass.is_none() {
+            return Err(Error::InitError(format!(
+                "{}\n`mass` must be set when `axle_load_transfer` is enabled",
+                format_dbg!()
+            )));
+        }
         self.loco_type.init()?;
         Ok(())
     }
 }
 impl SerdeAPI for Locomotive {}
 
+impl fmt::Display for Locomotive {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let loco_type = self.loco_type.to_string();
+        write!(
+            f,
+            "Locomotive(loco_type={}, mass_kilograms={}, pwr_rated_kilowatts={})",
+            loco_type,
+            utils::mass_repr(self.mass()),
+            self.pwr_rated()
+                .map(|pwr| format!("{:.1}", pwr.get::<si::kilowatt>()))
+                .unwrap_or_else(|| "None".into()),
+        )
+    }
+}
+
 impl Mass for Locomotive {
     fn mass(&self) -> anyhow::Result<Option<si::Mass>> {
         let derived_mass = self.derived_mass().with_context(|| format_dbg!())?;
@@ -793,6 +946,67 @@ impl Locomotive {
         Ok(self.force_max)
     }
 
+    /// Returns [Self::force_max] scaled by [Self::adhesion_factor], which is
+    /// the force limit that applies to tractive effort
+    pub fn force_max_effective(&self) -> anyhow::Result<si::Force> {
+        Ok(self.force_max().with_context(|| format_dbg!())? * self.adhesion_factor)
+    }
+
+    pub fn adhesion_factor(&self) -> si::Ratio {
+        self.adhesion_factor
+    }
+
+    /// Sets runtime adhesion multiplier on [Self::force_max]
+    pub fn set_adhesion_factor(&mut self, adhesion_factor: si::Ratio) -> anyhow::Result<()> {
+        ensure!(
+            adhesion_factor >= si::Ratio::ZERO && adhesion_factor.is_finite(),
+            "{}\n`adhesion_factor` must be finite and non-negative, got {:?}",
+            format_dbg!(),
+            adhesion_factor
+        );
+        self.adhesion_factor = adhesion_factor;
+        Ok(())
+    }
+
+    pub fn axle_load_transfer(&self) -> &AxleLoadTransfer {
+        &self.axle_load_transfer
+    }
+
+    /// Sets [Self::axle_load_transfer] model
+    pub fn set_axle_load_transfer(
+        &mut self,
+        axle_load_transfer: AxleLoadTransfer,
+    ) -> anyhow::Result<()> {
+        axle_load_transfer
+            .validate()
+            .with_context(|| format_dbg!())?;
+        ensure!(
+            !axle_load_transfer.is_enabled() || self.mass()?.is_some(),
+            "{}\n`mass` must be set when `axle_load_transfer` is enabled",
+            format_dbg!()
+        );
+        self.axle_load_transfer = axle_load_transfer;
+        Ok(())
+    }
+
+    /// Returns [Self::force_max_effective] reduced by [Self::axle_load_transfer]
+    /// at drawbar pull `force_drawbar`
+    pub fn force_max_dynamic(&self, force_drawbar: si::Force) -> anyhow::Result<si::Force> {
+        let force_max = self.force_max_effective().with_context(|| format_dbg!())?;
+        if !self.axle_load_transfer.is_enabled() {
+            return Ok(force_max);
+        }
+        let weight = self
+            .mass()
+            .with_context(|| format_dbg!())?
+            .with_context(|| format!("{}\nExpected `Some`", format_dbg!()))?
+            * uc::ACC_GRAV;
+        Ok(force_max
+            * self
+                .axle_load_transfer
+                .force_max_ratio(force_drawbar.min(force_max), weight))
+    }
+
     pub fn check_force_max(&self) -> anyhow::Result<()> {
         if let (Some(mu), Some(mass)) = (self.mu, self.mass) {
             ensure!(utils::almost_eq_uom(
@@ -813,6 +1027,23 @@ impl Locomotive {
         Ok(())
     }
 
+    /// Returns locomotive with [DummyLoco] powertrain and no mass
+    pub fn build_dummy_loco() -> Self {
+        Self {
+            loco_type: PowertrainType::DummyLoco(DummyLoco::default()),
+            mass: None,
+            mu: None,
+            state: LocomotiveState::default(),
+            save_interval: None,
+            history: LocomotiveStateHistoryVec::new(),
+            assert_limits: true,
+            pwr_aux_offset: 50e3 * uc::W,
+            pwr_aux_traction_coeff: 0.01 * uc::R,
+            force_max: 50e6 * uc::N,
+            ..Default::default()
+        }
+    }
+
     pub fn default_battery_electric_loco() -> Self {
         let mut loco = Locomotive {
             loco_type: PowertrainType::BatteryElectricLoco(Default::default()),
@@ -827,6 +1058,9 @@ impl Locomotive {
             history: Default::default(),
             save_interval: Some(1),
             assert_limits: true,
+            adhesion_factor: uc::R,
+            axle_load_transfer: Default::default(),
+            pwr_aux_idle_override: None,
         };
         loco.init().unwrap();
         loco.set_save_interval(Some(1));
@@ -845,6 +1079,13 @@ impl Locomotive {
         loco
     }
 
+    /// Returns rated power, or `None` if there is neither a fuel converter nor
+    /// reversible energy storage from which to derive it
+    pub fn pwr_rated(&self) -> Option<si::Power> {
+        (self.fuel_converter().is_some() || self.reversible_energy_storage().is_some())
+            .then(|| self.get_pwr_rated())
+    }
+
     pub fn get_pwr_rated(&self) -> si::Power {
         if self.fuel_converter().is_some() && self.reversible_energy_storage().is_some() {
             self.fuel_converter().unwrap().pwr_out_max
@@ -856,6 +1097,53 @@ impl Locomotive {
         }
     }
 
+    /// Returns a human-readable spec sheet listing locomotive type, rated
+    /// power, max tractive force, mass, and which powertrain components are
+    /// present
+    pub fn summary(&self) -> anyhow::Result<String> {
+        let yes_no = |present: bool| if present { "yes" } else { "no" };
+        let loco_type = self.loco_type.to_string();
+        let pwr_rated = match self.pwr_rated() {
+            Some(pwr_rated) => format!("{:.1} kW", pwr_rated.get::<si::kilowatt>()),
+            None => "N/A".into(),
+        };
+        let force_max = self.force_max().with_context(|| format_dbg!())?;
+        let mass = match self.mass().with_context(|| format_dbg!())? {
+            Some(mass) => format!(
+                "{:.0} kg ({:.1} short tons)",
+                mass.get::<si::kilogram>(),
+                (mass / uc::TON).get::<si::ratio>()
+            ),
+            None => "not set".into(),
+        };
+        let mut summary = format!(
+            "Locomotive type: {}\n\
+             Rated power: {}\n\
+             Max tractive force: {:.0} lbf ({:.0} N)\n\
+             Mass: {}\n\
+             Fuel converter: {}\n\
+             Generator: {}\n\
+             Reversible energy storage: {}\n\
+             Electric drivetrain: {}",
+            loco_type,
+            pwr_rated,
+            force_max.get::<si::pound_force>(),
+            force_max.get::<si::newton>(),
+            mass,
+            yes_no(self.fuel_converter().is_some()),
+            yes_no(self.generator().is_some()),
+            yes_no(self.reversible_energy_storage().is_some()),
+            yes_no(self.electric_drivetrain().is_some()),
+        );
+        if let Some(res) = self.reversible_energy_storage() {
+            summary.push_str(&format!(
+                "\nRES energy capacity: {:.1} kWh",
+                res.energy_capacity.get::<si::kilowatt_hour>()
+            ));
+        }
+        Ok(summary)
+    }
+
     pub fn get_save_interval(&self) -> Option<usize> {
         self.save_interval
     }
@@ -882,6 +1170,109 @@ impl Locomotive {
         }
     }
 
+    /// Returns `(name, save_interval)` for each powertrain component
+    pub fn component_save_intervals(&self) -> Vec<(&'static str, Option<usize>)> {
+        match &self.loco_type {
+            PowertrainType::ConventionalLoco(loco) => vec![
+                ("fc", loco.fc.save_interval),
+                ("gen", loco.gen.save_interval),
+                ("edrv", loco.edrv.save_interval),
+            ],
+            PowertrainType::HybridLoco(loco) => vec![
+                ("fc", loco.fc.save_interval),
+                ("gen", loco.gen.save_interval),
+                ("res", loco.res.save_interval),
+                ("edrv", loco.edrv.save_interval),
+            ],
+            PowertrainType::BatteryElectricLoco(loco) => vec![
+                ("res", loco.res.save_interval),
+                ("edrv", loco.edrv.save_interval),
+            ],
+            PowertrainType::DummyLoco(_) => vec![],
+        }
+    }
+
+    /// Clears `self.history` and history of all powertrain components so that
+    /// `self` can be serialized compactly, e.g. for reuse as an input
+    pub fn strip_history(&mut self) {
+        self.history.clear();
+        match &mut self.loco_type {
+            PowertrainType::ConventionalLoco(loco) => {
+                loco.fc.history.clear();
+                loco.gen.history.clear();
+                loco.edrv.history.clear();
+            }
+            PowertrainType::HybridLoco(loco) => {
+                loco.fc.history.clear();
+                loco.gen.history.clear();
+                loco.res.history.clear();
+                loco.edrv.history.clear();
+                match &mut loco.pt_cntrl {
+                    HybridPowertrainControls::RGWDB(rgwdb) => rgwdb.history.clear(),
+                }
+            }
+            PowertrainType::BatteryElectricLoco(loco) => {
+                loco.res.history.clear();
+                loco.edrv.history.clear();
+                match &mut loco.pt_cntrl {
+                    BatteryPowertrainControls::RGWDB(rgwdb) => rgwdb.history.clear(),
+                }
+            }
+            PowertrainType::DummyLoco(_) => {}
+        }
+    }
+
+    /// Resets [Self::state] and states of all components to their defaults
+    /// and clears history so that `self` can be rerun from the start, e.g.
+    /// with a different power trace.  SOC of any [ReversibleEnergyStorage] is
+    /// preserved and can be set via
+    /// [ReversibleEnergyStorage::set_initial_soc].
+    pub fn reset_state(&mut self) -> anyhow::Result<()> {
+        self.strip_history();
+        self.state = Default::default();
+        let reset_res = |res: &mut ReversibleEnergyStorage| {
+            let soc = res.state.soc.clone();
+            res.state = Default::default();
+            res.state.soc = soc;
+        };
+        match &mut self.loco_type {
+            PowertrainType::ConventionalLoco(loco) => {
+                loco.fc.state = Default::default();
+                loco.gen.state = Default::default();
+                loco.edrv.state = Default::default();
+            }
+            PowertrainType::HybridLoco(loco) => {
+                loco.fc.state = Default::default();
+                loco.gen.state = Default::default();
+                reset_res(&mut loco.res);
+                loco.edrv.state = Default::default();
+                match &mut loco.pt_cntrl {
+                    HybridPowertrainControls::RGWDB(rgwdb) => rgwdb.state = Default::default(),
+                }
+            }
+            PowertrainType::BatteryElectricLoco(loco) => {
+                reset_res(&mut loco.res);
+                loco.edrv.state = Default::default();
+                match &mut loco.pt_cntrl {
+                    BatteryPowertrainControls::RGWDB(rgwdb) => rgwdb.state = Default::default(),
+                }
+            }
+            PowertrainType::DummyLoco(_) => {}
+        }
+        self.init().with_context(|| format_dbg!())?;
+        Ok(())
+    }
+
+    /// Returns fraction of rated fuel converter power available at elevation
+    /// `elev` per [FuelConverter::altitude_derate], which is 1.0 for
+    /// locomotives without a fuel converter
+    pub fn derate_for_altitude(&self, elev: si::Length) -> anyhow::Result<si::Ratio> {
+        match self.fuel_converter() {
+            Some(fc) => fc.derate_for_altitude(elev).with_context(|| format_dbg!()),
+            None => Ok(uc::R),
+        }
+    }
+
     pub fn fuel_converter(&self) -> Option<&FuelConverter> {
         match &self.loco_type {
             PowertrainType::ConventionalLoco(loco) => Some(&loco.fc),
@@ -992,6 +1383,15 @@ impl Locomotive {
         }
     }
 
+    pub fn electric_drivetrain_mut(&mut self) -> Option<&mut ElectricDrivetrain> {
+        match &mut self.loco_type {
+            PowertrainType::ConventionalLoco(loco) => Some(&mut loco.edrv),
+            PowertrainType::HybridLoco(loco) => Some(&mut loco.edrv),
+            PowertrainType::BatteryElectricLoco(loco) => Some(&mut loco.edrv),
+            PowertrainType::DummyLoco(_) => None,
+        }
+    }
+
     pub fn set_electric_drivetrain(&mut self, edrv: ElectricDrivetrain) -> Result<()> {
         match &mut self.loco_type {
             PowertrainType::ConventionalLoco(loco) => {
@@ -1131,7 +1531,13 @@ impl Locomotive {
     ) -> anyhow::Result<()> {
         // maybe put logic for toggling `engine_on` here
 
-        self.state.pwr_out.update(pwr_out_req, || format_dbg!())?;
+        let engine_on_prev = match self.fuel_converter() {
+            Some(fc) => Some(*fc.state.engine_on.get_stale(|| format_dbg!())?),
+            None => None,
+        };
+        // power actually delivered, which differs from `pwr_out_req` only for a
+        // [ConventionalLoco] with throttle notches
+        let mut pwr_out = pwr_out_req;
         match &mut self.loco_type {
             PowertrainType::ConventionalLoco(loco) => {
                 loco.solve_energy_consumption(
@@ -1142,6 +1548,7 @@ impl Locomotive {
                     self.assert_limits,
                 )
                 .with_context(|| format_dbg!("ConventionalLoco"))?;
+                pwr_out = *loco.edrv.state.pwr_out_req.get_fresh(|| format_dbg!())?;
                 // self.state.pwr_out.update(
                 //     *loco
                 //         .edrv
@@ -1211,6 +1618,23 @@ impl Locomotive {
             }
             PowertrainType::DummyLoco(_) => { /* maybe put an error error in the future */ }
         }
+        self.state.pwr_out.update(pwr_out, || format_dbg!())?;
+
+        let engine_state_changed = match (self.fuel_converter(), engine_on_prev) {
+            (Some(fc), Some(engine_on_prev)) => {
+                *fc.state.engine_on.get_fresh(|| format_dbg!())? != engine_on_prev
+            }
+            _ => false,
+        };
+        if engine_state_changed {
+            self.state
+                .time_in_engine_state
+                .update(dt, || format_dbg!())?;
+        } else {
+            self.state
+                .time_in_engine_state
+                .increment(dt, || format_dbg!())?;
+        }
 
         Ok(())
     }
@@ -1221,11 +1645,14 @@ impl Locomotive {
     pub fn set_pwr_aux(&mut self, loco_on: Option<bool>) -> anyhow::Result<()> {
         self.state.pwr_aux.update(
             if loco_on.unwrap_or(true) {
-                // TODO: make this optionally asymmetrical to allow for locomotives that
-                // do not have an aux penalty related to dynamic braking
-                self.pwr_aux_offset
-                    + self.pwr_aux_traction_coeff
-                        * self.state.pwr_out.get_stale(|| format_dbg!())?.abs()
+                let pwr_out = self.state.pwr_out.get_stale(|| format_dbg!())?.abs();
+                if pwr_out == si::Power::ZERO {
+                    self.pwr_aux_at_idle()
+                } else {
+                    // TODO: make this optionally asymmetrical to allow for locomotives that
+                    // do not have an aux penalty related to dynamic braking
+                    self.pwr_aux_offset + self.pwr_aux_traction_coeff * pwr_out
+                }
             } else {
                 si::Power::ZERO
             },
@@ -1233,6 +1660,12 @@ impl Locomotive {
         )
     }
 
+    /// Returns aux power when the locomotive produces zero tractive power, which
+    /// is [Self::pwr_aux_idle_override] if set and [Self::pwr_aux_offset] otherwise
+    pub fn pwr_aux_at_idle(&self) -> si::Power {
+        self.pwr_aux_idle_override.unwrap_or(self.pwr_aux_offset)
+    }
+
     pub fn mu(&self) -> anyhow::Result<Option<si::Ratio>> {
         self.check_force_max().with_context(|| format_dbg!())?;
         Ok(self.mu)
@@ -1288,6 +1721,7 @@ impl LocoTrait for Locomotive {
         elev_and_temp: Option<(si::Length, si::ThermodynamicTemperature)>,
         train_mass_for_loco: Option<si::Mass>,
         train_speed: Option<si::Velocity>,
+        grade_ahead: Option<si::Ratio>,
         dt: si::Time,
     ) -> anyhow::Result<()> {
         ensure!(
@@ -1299,13 +1733,37 @@ impl LocoTrait for Locomotive {
             )
         );
 
+        if let PowertrainType::HybridLoco(loco) = &mut self.loco_type {
+            loco.handle_fc_off_causes_for_min_time_off(
+                *self
+                    .state
+                    .time_in_engine_state
+                    .get_stale(|| format_dbg!())?,
+            )
+            .with_context(|| format_dbg!())?;
+        }
         self.loco_type.set_curr_pwr_max_out(
             Some(*self.state.pwr_aux.get_fresh(|| format_dbg!())?),
             elev_and_temp,
             train_mass_for_loco,
             train_speed,
+            grade_ahead,
             dt,
         )?;
+        // drawbar pull is estimated from the previous time step, and full
+        // effort is assumed when starting from rest
+        let force_max = self.force_max_effective().with_context(|| format_dbg!())?;
+        let force_drawbar = match train_speed {
+            Some(speed) if speed > si::Velocity::ZERO => {
+                *self.state.pwr_out.get_stale(|| format_dbg!())? / speed
+            }
+            _ => force_max,
+        };
+        self.state.force_max_curr.update(
+            self.force_max_dynamic(force_drawbar)
+                .with_context(|| format_dbg!())?,
+            || format_dbg!(),
+        )?;
         match &self.loco_type {
             PowertrainType::ConventionalLoco(loco) => {
                 set_pwr_lims(&mut self.state, &loco.edrv)?;
@@ -1366,8 +1824,15 @@ pub struct LocomotiveState {
     pub pwr_out: TrackedState<si::Power>,
     /// time varying aux load
     pub pwr_aux: TrackedState<si::Power>,
+    /// max tractive force for current time step, i.e.
+    /// [Locomotive::force_max_effective] reduced by [AxleLoadTransfer]
+    pub force_max_curr: TrackedState<si::Force>,
     // todo: add variable for statemachine pwr_out_prev,
-    // time_at_or_below_idle, time_in_engine_state
+    // time_at_or_below_idle
+    /// elapsed time since the engine last turned on or off, or since the
+    /// start of simulation if it has not changed state or there is no engine
+    #[serde(default)]
+    pub time_in_engine_state: TrackedState<si::Time>,
     /// integral of [Self::pwr_out]
     pub energy_out: TrackedState<si::Energy>,
     /// integral of [Self::pwr_aux]
@@ -1,5 +1,6 @@
 //! Module containing models for consists, locomotives, and powertrain components
 
+pub mod axle_load_transfer;
 pub mod battery_electric_loco;
 pub mod conventional_loco;
 pub mod hybrid_loco;
@@ -11,6 +12,7 @@ pub mod loco_utils;
 pub use loco_utils::*;
 
 use super::*;
+pub use crate::consist::locomotive::axle_load_transfer::AxleLoadTransfer;
 pub use crate::consist::locomotive::battery_electric_loco::{
     BatteryElectricLoco, BatteryPowertrainControls, RESGreedyWithDynamicBuffersBEL, RGWDBStateBEL,
     RGWDBStateBELHistoryVec,
@@ -24,6 +24,18 @@ pub struct ElectricDrivetrain {
     pub pwr_in_frac_interp: Vec<f64>,
     /// ElectricDrivetrain maximum output power assuming that positive and negative tractive powers have same magnitude
     pub pwr_out_max: si::Power,
+    /// Optional train speed at the end of the traction motors' constant-power
+    /// region, above which field weakening reduces max output power in
+    /// proportion to `speed_field_weakening / speed`, such that max tractive
+    /// force falls with the square of speed.  If `None`, [Self::pwr_out_max]
+    /// is available at all speeds.
+    #[serde(default)]
+    pub speed_field_weakening: Option<si::Velocity>,
+    /// Optional lumped thermal model of the dynamic brake resistor grid, which
+    /// fades dynamic braking capability as the grid heats up.  If `None`,
+    /// dynamic braking is limited only by [Self::pwr_out_max].
+    #[serde(default)]
+    pub dyn_brake_thermal: Option<DynBrakeThermal>,
     // TODO: add `mass` here
     /// Time step interval between saves. 1 is a good option. If None, no saving occurs.
     pub save_interval: Option<usize>,
@@ -56,6 +68,33 @@ impl ElectricDrivetrain {
         Self::default()
     }
 
+    #[pyo3(name = "set_speed_field_weakening")]
+    #[pyo3(signature = (speed_field_weakening_meters_per_second=None))]
+    /// Sets field-weakening speed, with `None` disabling field weakening
+    fn set_speed_field_weakening_py(
+        &mut self,
+        speed_field_weakening_meters_per_second: Option<f64>,
+    ) -> anyhow::Result<()> {
+        self.set_speed_field_weakening(
+            speed_field_weakening_meters_per_second.map(|speed| speed * uc::MPS),
+        )
+    }
+
+    #[pyo3(name = "set_dyn_brake_thermal")]
+    #[pyo3(signature = (dyn_brake_thermal=None))]
+    /// Enables dynamic brake fade with `dyn_brake_thermal` settings, or disables it if `None`
+    fn set_dyn_brake_thermal_py(
+        &mut self,
+        dyn_brake_thermal: Option<DynBrakeThermal>,
+    ) -> anyhow::Result<()> {
+        self.set_dyn_brake_thermal(dyn_brake_thermal)
+    }
+
+    #[getter("pwr_dyn_brake_max_watts")]
+    fn get_pwr_dyn_brake_max_py(&self) -> anyhow::Result<f64> {
+        Ok(self.pwr_dyn_brake_max()?.get::<si::watt>())
+    }
+
     #[getter("eta_max")]
     fn get_eta_max_py(&self) -> f64 {
         self.get_eta_max()
@@ -125,6 +164,8 @@ impl ElectricDrivetrain {
             eta_interp,
             pwr_in_frac_interp: Vec::new(),
             pwr_out_max: pwr_out_max_watts,
+            speed_field_weakening: None,
+            dyn_brake_thermal: None,
             save_interval,
             history,
         };
@@ -152,6 +193,93 @@ impl ElectricDrivetrain {
         Ok(())
     }
 
+    /// Sets [Self::speed_field_weakening], with `None` disabling field weakening
+    pub fn set_speed_field_weakening(
+        &mut self,
+        speed_field_weakening: Option<si::Velocity>,
+    ) -> anyhow::Result<()> {
+        if let Some(speed) = speed_field_weakening {
+            ensure!(
+                speed > si::Velocity::ZERO,
+                "{}\n`speed_field_weakening` must be positive",
+                format_dbg!()
+            );
+        }
+        self.speed_field_weakening = speed_field_weakening;
+        Ok(())
+    }
+
+    /// Sets [Self::dyn_brake_thermal], with `None` disabling dynamic brake
+    /// fade, and resets grid temperature to ambient
+    pub fn set_dyn_brake_thermal(
+        &mut self,
+        dyn_brake_thermal: Option<DynBrakeThermal>,
+    ) -> anyhow::Result<()> {
+        if let Some(dyn_brake_thermal) = &dyn_brake_thermal {
+            dyn_brake_thermal
+                .validate()
+                .with_context(|| format_dbg!())?;
+        }
+        self.dyn_brake_thermal = dyn_brake_thermal;
+        self.state.temp_dyn_brake.update_unchecked(
+            self.dyn_brake_thermal
+                .as_ref()
+                .map(|thermal| thermal.temp_ambient),
+            || format_dbg!(),
+        )?;
+        Ok(())
+    }
+
+    /// Returns max power that can be dissipated in the dynamic brake grid at
+    /// the current grid temperature, which is [Self::pwr_out_max] unless
+    /// [Self::dyn_brake_thermal] is provided
+    pub fn pwr_dyn_brake_max(&self) -> anyhow::Result<si::Power> {
+        let temp = *self.state.temp_dyn_brake.get_unchecked(|| format_dbg!())?;
+        Ok(match (&self.dyn_brake_thermal, temp) {
+            (Some(thermal), Some(temp)) => self.pwr_out_max * thermal.fade_frac(temp),
+            _ => self.pwr_out_max,
+        })
+    }
+
+    /// Returns max output power at train `speed`, accounting for field
+    /// weakening above [Self::speed_field_weakening]
+    pub fn pwr_out_max_at_speed(&self, speed: Option<si::Velocity>) -> si::Power {
+        match (self.speed_field_weakening, speed) {
+            (Some(speed_fw), Some(speed)) if speed > speed_fw => {
+                self.pwr_out_max * (speed_fw / speed)
+            }
+            _ => self.pwr_out_max,
+        }
+    }
+
+    /// Set current max possible output power, `pwr_mech_out_max`, given
+    /// `pwr_in_max` from upstream component and train `speed`, which is used
+    /// for field weakening if [Self::speed_field_weakening] is provided
+    pub fn set_cur_pwr_max_out_at_speed(
+        &mut self,
+        pwr_in_max: si::Power,
+        speed: Option<si::Velocity>,
+    ) -> anyhow::Result<()> {
+        if self.pwr_in_frac_interp.is_empty() {
+            self.set_pwr_in_frac_interp()?;
+        }
+        let eta = uc::R
+            * interp1d(
+                &(pwr_in_max / self.pwr_out_max).get::<si::ratio>().abs(),
+                &self.pwr_in_frac_interp,
+                &self.eta_interp,
+                false,
+            )?;
+
+        self.state.pwr_mech_out_max.update(
+            self.pwr_out_max_at_speed(speed)
+                .min(pwr_in_max * eta)
+                .max(si::Power::ZERO),
+            || format_dbg!(),
+        )?;
+        Ok(())
+    }
+
     pub fn set_cur_pwr_regen_max(&mut self, pwr_max_regen_in: si::Power) -> anyhow::Result<()> {
         if self.pwr_in_frac_interp.is_empty() {
             self.set_pwr_in_frac_interp()?;
@@ -174,7 +302,7 @@ impl ElectricDrivetrain {
     }
 
     /// Set `pwr_in_req` required to achieve desired `pwr_out_req` with time step size `dt`.
-    pub fn set_pwr_in_req(&mut self, pwr_out_req: si::Power, _dt: si::Time) -> anyhow::Result<()> {
+    pub fn set_pwr_in_req(&mut self, pwr_out_req: si::Power, dt: si::Time) -> anyhow::Result<()> {
         ensure!(
             almost_le_uom(&pwr_out_req.abs(), &self.pwr_out_max, None),
             format!(
@@ -278,6 +406,19 @@ impl ElectricDrivetrain {
             || format_dbg!(),
         )?;
 
+        let temp_dyn_brake = *self.state.temp_dyn_brake.get_stale(|| format_dbg!())?;
+        self.state.temp_dyn_brake.update(
+            match (&self.dyn_brake_thermal, temp_dyn_brake) {
+                (Some(thermal), Some(temp)) => Some(thermal.temp_next(
+                    temp,
+                    *self.state.pwr_elec_dyn_brake.get_fresh(|| format_dbg!())?,
+                    dt,
+                )),
+                _ => temp_dyn_brake,
+            },
+            || format_dbg!(),
+        )?;
+
         Ok(())
     }
 
@@ -294,6 +435,32 @@ impl ElectricDrivetrain {
 impl Init for ElectricDrivetrain {
     fn init(&mut self) -> Result<(), Error> {
         self.state.init()?;
+        if let Some(speed) = self.speed_field_weakening {
+            if speed <= si::Velocity::ZERO {
+                return Err(Error::InitError(format!(
+                    "{}\n`speed_field_weakening` must be positive",
+                    format_dbg!()
+                )));
+            }
+        }
+        // grid starts at ambient unless initial temperature was provided
+        if let Some(thermal) = &self.dyn_brake_thermal {
+            thermal
+                .validate()
+                .map_err(|err| Error::InitError(format_dbg!(err)))?;
+            if self
+                .state
+                .temp_dyn_brake
+                .get_unchecked(|| format_dbg!())
+                .map_err(|err| Error::InitError(format_dbg!(err)))?
+                .is_none()
+            {
+                self.state
+                    .temp_dyn_brake
+                    .update_unchecked(Some(thermal.temp_ambient), || format_dbg!())
+                    .map_err(|err| Error::InitError(format_dbg!(err)))?;
+            }
+        }
         Ok(())
     }
 }
@@ -318,22 +485,7 @@ impl ElectricMachine for ElectricDrivetrain {
         pwr_aux: Option<si::Power>,
     ) -> anyhow::Result<()> {
         ensure!(pwr_aux.is_none(), format_dbg!(pwr_aux.is_none()));
-        if self.pwr_in_frac_interp.is_empty() {
-            self.set_pwr_in_frac_interp()?;
-        }
-        let eta = uc::R
-            * interp1d(
-                &(pwr_in_max / self.pwr_out_max).get::<si::ratio>().abs(),
-                &self.pwr_in_frac_interp,
-                &self.eta_interp,
-                false,
-            )?;
-
-        self.state.pwr_mech_out_max.update(
-            self.pwr_out_max.min(pwr_in_max * eta).max(si::Power::ZERO),
-            || format_dbg!(),
-        )?;
-        Ok(())
+        self.set_cur_pwr_max_out_at_speed(pwr_in_max, None)
     }
 
     /// Set current power out max ramp rate, `pwr_rate_out_max` given `pwr_rate_in_max`
@@ -405,6 +557,10 @@ pub struct ElectricDrivetrainState {
     pub energy_elec_dyn_brake: TrackedState<si::Energy>,
     /// Cumulative energy lost in regeneratively converting mechanical power to power that can be absorbed by the battery.
     pub energy_loss: TrackedState<si::Energy>,
+    /// lumped dynamic brake grid temperature, `None` if
+    /// [ElectricDrivetrain::dyn_brake_thermal] is `None`
+    #[serde(default)]
+    pub temp_dyn_brake: TrackedState<Option<si::ThermodynamicTemperature>>,
 }
 
 #[pyo3_api]
@@ -413,6 +569,129 @@ impl ElectricDrivetrainState {}
 impl Init for ElectricDrivetrainState {}
 impl SerdeAPI for ElectricDrivetrainState {}
 
+#[serde_api]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Simple lumped thermal model of the dynamic brake resistor grid of an
+/// [ElectricDrivetrain].  Dynamic braking power dissipated in the grid heats
+/// its [Self::thermal_mass], which cools toward [Self::temp_ambient] with time
+/// constant [Self::time_constant], so the grid cools when braking is light.
+/// Dynamic braking capability fades linearly from full at
+/// [Self::temp_fade_start] to none at [Self::temp_max].
+pub struct DynBrakeThermal {
+    /// Lumped heat capacity of resistor grid
+    pub thermal_mass: si::HeatCapacity,
+    /// Time constant of cooling toward [Self::temp_ambient]
+    pub time_constant: si::Time,
+    /// Ambient (i.e. cooling air) temperature
+    pub temp_ambient: si::ThermodynamicTemperature,
+    /// Grid temperature above which dynamic braking capability fades
+    pub temp_fade_start: si::ThermodynamicTemperature,
+    /// Grid temperature at and above which no dynamic braking is available
+    pub temp_max: si::ThermodynamicTemperature,
+}
+
+#[pyo3_api]
+impl DynBrakeThermal {
+    #[new]
+    #[pyo3(signature = (thermal_mass_joules_per_kelvin=None, time_constant_seconds=None, temp_ambient_kelvin=None, temp_fade_start_kelvin=None, temp_max_kelvin=None))]
+    fn __new__(
+        thermal_mass_joules_per_kelvin: Option<f64>,
+        time_constant_seconds: Option<f64>,
+        temp_ambient_kelvin: Option<f64>,
+        temp_fade_start_kelvin: Option<f64>,
+        temp_max_kelvin: Option<f64>,
+    ) -> Self {
+        let def = Self::default();
+        Self {
+            thermal_mass: thermal_mass_joules_per_kelvin
+                .map(|c| c * uc::JPK)
+                .unwrap_or(def.thermal_mass),
+            time_constant: time_constant_seconds
+                .map(|t| t * uc::S)
+                .unwrap_or(def.time_constant),
+            temp_ambient: temp_ambient_kelvin
+                .map(|t| t * uc::KELVIN)
+                .unwrap_or(def.temp_ambient),
+            temp_fade_start: temp_fade_start_kelvin
+                .map(|t| t * uc::KELVIN)
+                .unwrap_or(def.temp_fade_start),
+            temp_max: temp_max_kelvin
+                .map(|t| t * uc::KELVIN)
+                .unwrap_or(def.temp_max),
+        }
+    }
+
+    #[pyo3(name = "fade_frac")]
+    fn fade_frac_py(&self, temp_kelvin: f64) -> f64 {
+        self.fade_frac(temp_kelvin * uc::KELVIN).get::<si::ratio>()
+    }
+}
+
+impl Init for DynBrakeThermal {}
+impl SerdeAPI for DynBrakeThermal {}
+
+impl Default for DynBrakeThermal {
+    /// Roughly representative of a locomotive dynamic brake grid that starts
+    /// fading after about 4 minutes of full braking from a 25 °C ambient
+    fn default() -> Self {
+        Self {
+            thermal_mass: 2e6 * uc::JPK,
+            time_constant: 1_200.0 * uc::S,
+            temp_ambient: (25.0 + uc::CELSIUS_TO_KELVIN) * uc::KELVIN,
+            temp_fade_start: (500.0 + uc::CELSIUS_TO_KELVIN) * uc::KELVIN,
+            temp_max: (800.0 + uc::CELSIUS_TO_KELVIN) * uc::KELVIN,
+        }
+    }
+}
+
+impl DynBrakeThermal {
+    /// Returns fraction of dynamic braking capability available at grid
+    /// temperature `temp`
+    pub fn fade_frac(&self, temp: si::ThermodynamicTemperature) -> si::Ratio {
+        ((self.temp_max.get::<si::kelvin>() - temp.get::<si::kelvin>())
+            / (self.temp_max.get::<si::kelvin>() - self.temp_fade_start.get::<si::kelvin>()))
+        .clamp(0.0, 1.0)
+            * uc::R
+    }
+
+    /// Returns grid temperature after `dt` of dissipating `pwr_dyn_brake`,
+    /// starting from `temp`.  Uses the exact solution for constant power so
+    /// that large time steps remain stable.
+    pub fn temp_next(
+        &self,
+        temp: si::ThermodynamicTemperature,
+        pwr_dyn_brake: si::Power,
+        dt: si::Time,
+    ) -> si::ThermodynamicTemperature {
+        // steady-state temperature at which cooling balances `pwr_dyn_brake`
+        let temp_eq_kelvin = self.temp_ambient.get::<si::kelvin>()
+            + (pwr_dyn_brake * self.time_constant).get::<si::joule>()
+                / self.thermal_mass.get::<si::joule_per_kelvin>();
+        let decay = (-(dt / self.time_constant).get::<si::ratio>()).exp();
+        (temp_eq_kelvin + (temp.get::<si::kelvin>() - temp_eq_kelvin) * decay) * uc::KELVIN
+    }
+}
+
+impl ObjState for DynBrakeThermal {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gtz(&mut errors, &self.thermal_mass, "Thermal mass");
+        si_chk_num_gtz(&mut errors, &self.time_constant, "Time constant");
+        if self.temp_fade_start <= self.temp_ambient {
+            errors.push(anyhow!(
+                "Fade start temperature must be greater than ambient temperature"
+            ));
+        }
+        if self.temp_max <= self.temp_fade_start {
+            errors.push(anyhow!(
+                "Max temperature must be greater than fade start temperature"
+            ));
+        }
+        errors.make_err()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -446,6 +725,58 @@ mod tests {
         assert!(edrv.history.is_empty());
     }
 
+    #[test]
+    fn test_field_weakening() {
+        // force and power limits at each of `speeds`
+        let lims =
+            |edrv: &ElectricDrivetrain, speeds: &[si::Velocity]| -> Vec<(si::Force, si::Power)> {
+                speeds
+                    .iter()
+                    .map(|speed| {
+                        let mut edrv = edrv.clone();
+                        edrv.check_and_reset(|| format_dbg!()).unwrap();
+                        // ample electrical power so that only the drivetrain limits
+                        edrv.set_cur_pwr_max_out_at_speed(2.0 * edrv.pwr_out_max, Some(*speed))
+                            .unwrap();
+                        let pwr = *edrv
+                            .state
+                            .pwr_mech_out_max
+                            .get_fresh(|| format_dbg!())
+                            .unwrap();
+                        (pwr / *speed, pwr)
+                    })
+                    .collect()
+            };
+        let speeds: Vec<si::Velocity> = [10.0, 20.0, 30.0, 40.0]
+            .iter()
+            .map(|s| *s * uc::MPS)
+            .collect();
+
+        let mut edrv = test_edrv();
+        let lims_const_pwr = lims(&edrv, &speeds);
+        assert!(lims_const_pwr
+            .iter()
+            .all(|(_, pwr)| *pwr == edrv.pwr_out_max));
+
+        edrv.set_speed_field_weakening(Some(speeds[1])).unwrap();
+        let lims_fw = lims(&edrv, &speeds);
+        // unchanged up to field weakening speed
+        assert_eq!(lims_fw[..2], lims_const_pwr[..2]);
+        for ((force_fw, pwr_fw), (force_const_pwr, _)) in
+            lims_fw.iter().zip(&lims_const_pwr).skip(2)
+        {
+            // power stays capped even though electrical power is available,
+            // and force drops faster than with constant power
+            assert!(*pwr_fw < edrv.pwr_out_max);
+            assert!(force_fw < force_const_pwr);
+        }
+        assert!(lims_fw.windows(2).all(|w| w[1].0 < w[0].0));
+
+        assert!(edrv
+            .set_speed_field_weakening(Some(si::Velocity::ZERO))
+            .is_err());
+    }
+
     #[test]
     fn test_get_and_set_eta() {
         let mut res = test_edrv();
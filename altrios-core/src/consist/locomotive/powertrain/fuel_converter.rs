@@ -4,6 +4,16 @@ use super::*;
 
 const TOL: f64 = 1e-3;
 
+/// CO2 emitted per unit of diesel fuel energy (lower heating value), in kg/J,
+/// per the IPCC default emission factor of 74,100 kg/TJ
+const DIESEL_CO2_INTENSITY_KG_PER_JOULE: f64 = 7.41e-8;
+
+/// Lower heating value of diesel fuel, in J/kg
+const DIESEL_LHV_JOULES_PER_KG: f64 = 42.6e6;
+
+/// Density of diesel fuel, in kg/m³
+const DIESEL_DENSITY_KG_PER_M3: f64 = 846.0;
+
 #[serde_api]
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq, StateMethods, SetCumulative)]
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
@@ -41,6 +51,20 @@ pub struct FuelConverter {
     /// peak power, e.g. a value of 1 means no derating and a value of 0 means
     /// the engine is completely disabled.
     pub elev_and_temp_derate: Option<Interp2DOwned<f64, strategy::Linear>>,
+    /// Interpolator for derating dynamic engine peak power of a
+    /// naturally-aspirated engine based on elevation in meters, returning
+    /// fraction of rated power.  Applied in addition to
+    /// [Self::elev_and_temp_derate], and `None` means no altitude derating.
+    #[serde(default)]
+    pub altitude_derate: Option<Interp1DOwned<f64, strategy::Linear>>,
+    /// Optional lumped thermal model for derating efficiency during warm-up.
+    /// If `None`, the engine is always treated as warm.
+    #[serde(default)]
+    pub thermal: Option<FuelConverterThermal>,
+    /// Properties of fuel used to compute [FuelConverterState::fuel_volume]
+    /// and [FuelConverterState::co2_mass].  Defaults to diesel.
+    #[serde(default)]
+    pub fuel_type: FuelType,
     /// time step interval between saves. 1 is a good option. If None, no saving occurs.
     pub save_interval: Option<usize>,
     /// Custom vector of [Self::state]
@@ -94,6 +118,46 @@ impl FuelConverter {
         self.set_default_elev_and_temp_derate()
     }
 
+    #[pyo3(name = "set_altitude_derate")]
+    /// Sets altitude derate curve with fraction of rated power, `pwr_frac`,
+    /// at each elevation in `elev_meters`
+    fn set_altitude_derate_py(
+        &mut self,
+        elev_meters: Vec<f64>,
+        pwr_frac: Vec<f64>,
+    ) -> anyhow::Result<()> {
+        self.set_altitude_derate(
+            elev_meters.into_iter().map(|e| e * uc::M).collect(),
+            pwr_frac.into_iter().map(|f| f * uc::R).collect(),
+        )
+    }
+
+    #[pyo3(name = "derate_for_altitude")]
+    fn derate_for_altitude_py(&self, elev_meters: f64) -> anyhow::Result<f64> {
+        Ok(self
+            .derate_for_altitude(elev_meters * uc::M)?
+            .get::<si::ratio>())
+    }
+
+    #[getter("fuel_type")]
+    fn get_fuel_type_py(&self) -> FuelType {
+        self.fuel_type.clone()
+    }
+
+    #[pyo3(name = "set_fuel_type")]
+    #[pyo3(signature = (fuel_type=None))]
+    /// Sets fuel properties to `fuel_type`, or to diesel if `None`
+    fn set_fuel_type_py(&mut self, fuel_type: Option<FuelType>) -> anyhow::Result<()> {
+        self.set_fuel_type(fuel_type.unwrap_or_default())
+    }
+
+    #[pyo3(name = "set_thermal")]
+    #[pyo3(signature = (thermal=None))]
+    /// Enables warm-up thermal model with `thermal` settings, or disables it if `None`
+    fn set_thermal_py(&mut self, thermal: Option<FuelConverterThermal>) -> anyhow::Result<()> {
+        self.set_thermal(thermal)
+    }
+
     #[staticmethod]
     #[pyo3(name = "default")]
     fn default_py() -> Self {
@@ -113,6 +177,27 @@ impl Default for FuelConverter {
 impl Init for FuelConverter {
     fn init(&mut self) -> Result<(), Error> {
         self.state.init()?;
+        self.fuel_type
+            .validate()
+            .map_err(|err| Error::InitError(format_dbg!(err)))?;
+        // cold start unless initial temperature was provided
+        if let Some(thermal) = &self.thermal {
+            thermal
+                .validate()
+                .map_err(|err| Error::InitError(format_dbg!(err)))?;
+            if self
+                .state
+                .temp
+                .get_unchecked(|| format_dbg!())
+                .map_err(|err| Error::InitError(format_dbg!(err)))?
+                .is_none()
+            {
+                self.state
+                    .temp
+                    .update_unchecked(Some(thermal.temp_cold), || format_dbg!())
+                    .map_err(|err| Error::InitError(format_dbg!(err)))?;
+            }
+        }
         Ok(())
     }
 }
@@ -179,6 +264,65 @@ impl Mass for FuelConverter {
 
 // non-py methods
 impl FuelConverter {
+    /// Sets [Self::fuel_type]
+    pub fn set_fuel_type(&mut self, fuel_type: FuelType) -> anyhow::Result<()> {
+        fuel_type.validate().with_context(|| format_dbg!())?;
+        self.fuel_type = fuel_type;
+        Ok(())
+    }
+
+    /// Sets [Self::thermal], with `None` disabling the warm-up thermal model,
+    /// and resets engine temperature to a cold start
+    pub fn set_thermal(&mut self, thermal: Option<FuelConverterThermal>) -> anyhow::Result<()> {
+        if let Some(thermal) = &thermal {
+            thermal.validate().with_context(|| format_dbg!())?;
+        }
+        self.thermal = thermal;
+        self.state.temp.update_unchecked(
+            self.thermal.as_ref().map(|thermal| thermal.temp_cold),
+            || format_dbg!(),
+        )?;
+        Ok(())
+    }
+
+    /// Sets [Self::altitude_derate] with fraction of rated power, `pwr_frac`,
+    /// at each elevation in `elev`, which must be strictly increasing
+    pub fn set_altitude_derate(
+        &mut self,
+        elev: Vec<si::Length>,
+        pwr_frac: Vec<si::Ratio>,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            pwr_frac
+                .iter()
+                .all(|f| *f >= si::Ratio::ZERO && *f <= uc::R),
+            "{}\nExpected all `pwr_frac` values to be between 0 and 1",
+            format_dbg!()
+        );
+        self.altitude_derate = Some(
+            Interp1D::new(
+                elev.iter().map(|e| e.get::<si::meter>()).collect(),
+                pwr_frac.iter().map(|f| f.get::<si::ratio>()).collect(),
+                strategy::Linear,
+                Extrapolate::Clamp,
+            )
+            .with_context(|| format_dbg!())?,
+        );
+        Ok(())
+    }
+
+    /// Returns fraction of rated power available at elevation `elev` per
+    /// [Self::altitude_derate], which is 1.0 if there is no altitude derate
+    pub fn derate_for_altitude(&self, elev: si::Length) -> anyhow::Result<si::Ratio> {
+        match &self.altitude_derate {
+            Some(altitude_derate) => Ok(altitude_derate
+                .interpolate(&[elev.get::<si::meter>()])
+                .with_context(|| format_dbg!())?
+                * uc::R),
+            None => Ok(uc::R),
+        }
+    }
+
     /// Get fuel converter max power output given time step, dt
     pub fn set_cur_pwr_out_max(
         &mut self,
@@ -193,13 +337,20 @@ impl FuelConverter {
             )
         );
 
+        let altitude_derate = match elev_and_temp {
+            Some((elev, _)) => self.derate_for_altitude(elev)?,
+            None => uc::R,
+        };
         let pwr_max_derated = match (&mut self.elev_and_temp_derate, elev_and_temp) {
             (Some(elev_and_temp_derate), Some(elev_and_temp)) => {
                 elev_and_temp_derate.interpolate(&[
                     elev_and_temp.0.get::<si::meter>(),
                     elev_and_temp.1.get::<si::degree_celsius>(),
-                ])? * self.pwr_out_max
+                ])? * altitude_derate
+                    * self.pwr_out_max
             }
+            // altitude derate alone is sufficient when elevation is provided
+            (None, Some(_)) if self.altitude_derate.is_some() => altitude_derate * self.pwr_out_max,
             (None, Some(_)) => bail!(
                 "{}\nExpected (self.elev_and_temp_derate, elev_and_temp) to both be Some or None",
                 format_dbg!()
@@ -228,13 +379,21 @@ impl FuelConverter {
     }
 
     /// Solve for fuel usage for a given required fuel converter power output
+    /// # Arguments
+    /// - `pwr_out_req`: required fuel converter shaft power
+    /// - `dt`: time step size
+    /// - `engine_on`: whether engine is on
+    /// - `notch`: active throttle notch, if operating in discrete notches
+    /// - `assert_limits`: whether to fail when fuel converter limits are exceeded
     pub fn solve_energy_consumption(
         &mut self,
         pwr_out_req: si::Power,
         dt: si::Time,
         engine_on: bool,
+        notch: Option<u8>,
         assert_limits: bool,
     ) -> anyhow::Result<()> {
+        self.state.notch.update(notch, || format_dbg!())?;
         if engine_on {
             self.state.time_on.increment(dt, || format_dbg!())?;
         } else {
@@ -280,8 +439,13 @@ impl FuelConverter {
         );
 
         self.state.pwr_shaft.update(pwr_out_req, || format_dbg!())?;
+        let temp = *self.state.temp.get_stale(|| format_dbg!())?;
+        let eta_derate = match (&self.thermal, temp) {
+            (Some(thermal), Some(temp)) => thermal.eta_derate(temp),
+            _ => si::Ratio::ZERO,
+        };
         self.state.eta.update(
-            uc::R
+            (uc::R - eta_derate)
                 * interp1d(
                     &(pwr_out_req / self.pwr_out_max).get::<si::ratio>(),
                     &self.pwr_out_frac_interp,
@@ -328,7 +492,18 @@ impl FuelConverter {
             )
         );
         self.state.pwr_fuel.update(
-            pwr_out_req / *self.state.eta.get_fresh(|| format_dbg!())? + self.pwr_idle_fuel,
+            pwr_out_req / *self.state.eta.get_fresh(|| format_dbg!())?
+                + *self.state.pwr_idle_fuel.get_fresh(|| format_dbg!())?,
+            || format_dbg!(),
+        )?;
+        self.state.co2_mass_rate.update(
+            *self.state.pwr_fuel.get_fresh(|| format_dbg!())? * self.fuel_type.co2_intensity,
+            || format_dbg!(),
+        )?;
+        self.state.fuel_volume_rate.update(
+            *self.state.pwr_fuel.get_fresh(|| format_dbg!())?
+                / self.fuel_type.lhv
+                / self.fuel_type.density,
             || format_dbg!(),
         )?;
         self.state.pwr_loss.update(
@@ -354,6 +529,17 @@ impl FuelConverter {
                 )
             )
         );
+        self.state.temp.update(
+            match (&self.thermal, temp) {
+                (Some(thermal), Some(temp)) => Some(thermal.temp_next(
+                    temp,
+                    *self.state.pwr_loss.get_fresh(|| format_dbg!())?,
+                    dt,
+                )),
+                _ => temp,
+            },
+            || format_dbg!(),
+        )?;
         Ok(())
     }
 
@@ -406,10 +592,27 @@ pub struct FuelConverterState {
     pub energy_loss: TrackedState<si::Energy>,
     /// cumulative fuel energy fc has lost due to idle
     pub energy_idle_fuel: TrackedState<si::Energy>,
+    /// instantaneous CO2 emission rate, based on [FuelConverter::fuel_type]
+    #[serde(default)]
+    pub co2_mass_rate: TrackedState<si::MassRate>,
+    /// cumulative CO2 emitted
+    #[serde(default)]
+    pub co2_mass: TrackedState<si::Mass>,
+    /// instantaneous fuel volume flow rate, based on [FuelConverter::fuel_type]
+    #[serde(default)]
+    pub fuel_volume_rate: TrackedState<si::VolumeRate>,
+    /// cumulative volume of fuel consumed
+    #[serde(default)]
+    pub fuel_volume: TrackedState<si::Volume>,
     /// If true, engine is on, and if false, off (no idle)
     pub engine_on: TrackedState<bool>,
     /// elapsed time since engine was turned on
     pub time_on: TrackedState<si::Time>,
+    /// active throttle notch, `None` if operating with continuous power
+    pub notch: TrackedState<Option<u8>>,
+    /// lumped engine temperature, `None` if [FuelConverter::thermal] is `None`
+    #[serde(default)]
+    pub temp: TrackedState<Option<si::ThermodynamicTemperature>>,
 }
 
 #[pyo3_api]
@@ -431,12 +634,215 @@ impl Default for FuelConverterState {
             energy_shaft: Default::default(),
             energy_loss: Default::default(),
             energy_idle_fuel: Default::default(),
+            co2_mass_rate: Default::default(),
+            co2_mass: Default::default(),
+            fuel_volume_rate: Default::default(),
+            fuel_volume: Default::default(),
             engine_on: TrackedState::new(true),
             time_on: Default::default(),
+            notch: Default::default(),
+            temp: Default::default(),
         }
     }
 }
 
+#[serde_api]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Simple lumped thermal model of [FuelConverter] warm-up.  A fraction,
+/// [Self::heat_frac], of loss power heats the engine's [Self::thermal_mass]
+/// until the thermostat holds it at [Self::temp_warm].  Efficiency is derated
+/// by [Self::eta_derate_cold] at [Self::temp_cold], decreasing linearly to no
+/// derating at [Self::temp_warm].  Heat rejection after warm-up and cooling
+/// while off are neglected.
+pub struct FuelConverterThermal {
+    /// Lumped heat capacity of engine block and coolant
+    pub thermal_mass: si::HeatCapacity,
+    /// Fraction of loss power that heats the engine, with the remainder
+    /// rejected via exhaust
+    pub heat_frac: si::Ratio,
+    /// Cold-start engine temperature, at which derating is greatest
+    pub temp_cold: si::ThermodynamicTemperature,
+    /// Warm-up target (i.e. thermostat) temperature, at and above which
+    /// efficiency is not derated
+    pub temp_warm: si::ThermodynamicTemperature,
+    /// Fractional reduction in efficiency at [Self::temp_cold]
+    pub eta_derate_cold: si::Ratio,
+}
+
+#[pyo3_api]
+impl FuelConverterThermal {
+    #[new]
+    #[pyo3(signature = (thermal_mass_joules_per_kelvin, heat_frac=None, temp_cold_kelvin=None, temp_warm_kelvin=None, eta_derate_cold=None))]
+    fn __new__(
+        thermal_mass_joules_per_kelvin: f64,
+        heat_frac: Option<f64>,
+        temp_cold_kelvin: Option<f64>,
+        temp_warm_kelvin: Option<f64>,
+        eta_derate_cold: Option<f64>,
+    ) -> Self {
+        let def = Self::default();
+        Self {
+            thermal_mass: thermal_mass_joules_per_kelvin * uc::JPK,
+            heat_frac: heat_frac.map(|f| f * uc::R).unwrap_or(def.heat_frac),
+            temp_cold: temp_cold_kelvin
+                .map(|t| t * uc::KELVIN)
+                .unwrap_or(def.temp_cold),
+            temp_warm: temp_warm_kelvin
+                .map(|t| t * uc::KELVIN)
+                .unwrap_or(def.temp_warm),
+            eta_derate_cold: eta_derate_cold
+                .map(|d| d * uc::R)
+                .unwrap_or(def.eta_derate_cold),
+        }
+    }
+}
+
+impl Init for FuelConverterThermal {}
+impl SerdeAPI for FuelConverterThermal {}
+
+impl Default for FuelConverterThermal {
+    /// Roughly representative of a 4.4 MW diesel locomotive engine with a
+    /// 20 °C cold start, 85 °C thermostat, and 10% efficiency penalty when cold
+    fn default() -> Self {
+        Self {
+            thermal_mass: 15e6 * uc::JPK,
+            heat_frac: 0.3 * uc::R,
+            temp_cold: (20.0 + uc::CELSIUS_TO_KELVIN) * uc::KELVIN,
+            temp_warm: (85.0 + uc::CELSIUS_TO_KELVIN) * uc::KELVIN,
+            eta_derate_cold: 0.1 * uc::R,
+        }
+    }
+}
+
+impl FuelConverterThermal {
+    /// Returns fractional efficiency reduction at engine temperature `temp`
+    pub fn eta_derate(&self, temp: si::ThermodynamicTemperature) -> si::Ratio {
+        let frac_cold = ((self.temp_warm.get::<si::kelvin>() - temp.get::<si::kelvin>())
+            / (self.temp_warm.get::<si::kelvin>() - self.temp_cold.get::<si::kelvin>()))
+        .clamp(0.0, 1.0);
+        self.eta_derate_cold * frac_cold
+    }
+
+    /// Returns engine temperature after `dt` of heating by `pwr_loss`,
+    /// starting from `temp`
+    pub fn temp_next(
+        &self,
+        temp: si::ThermodynamicTemperature,
+        pwr_loss: si::Power,
+        dt: si::Time,
+    ) -> si::ThermodynamicTemperature {
+        if temp >= self.temp_warm {
+            return temp;
+        }
+        let temp_rise_kelvin = (self.heat_frac * pwr_loss * dt).get::<si::joule>()
+            / self.thermal_mass.get::<si::joule_per_kelvin>();
+        ((temp.get::<si::kelvin>() + temp_rise_kelvin) * uc::KELVIN).min(self.temp_warm)
+    }
+}
+
+impl ObjState for FuelConverterThermal {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gtz(&mut errors, &self.thermal_mass, "Thermal mass");
+        si_chk_num_gez(&mut errors, &self.heat_frac, "Heat fraction");
+        si_chk_num_gez(&mut errors, &self.eta_derate_cold, "Cold efficiency derate");
+        if self.heat_frac > uc::R {
+            errors.push(anyhow!("Heat fraction must not exceed 1.0"));
+        }
+        if self.eta_derate_cold >= uc::R {
+            errors.push(anyhow!("Cold efficiency derate must be less than 1.0"));
+        }
+        if self.temp_warm <= self.temp_cold {
+            errors.push(anyhow!(
+                "Warm temperature must be greater than cold temperature"
+            ));
+        }
+        errors.make_err()
+    }
+}
+
+#[serde_api]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Fuel properties for fuel volume and CO2 accounting, e.g. for biodiesel or
+/// renewable diesel blends.  Defaults to diesel.
+pub struct FuelType {
+    /// Fuel name, e.g. `"Diesel"` or `"B20"`
+    pub name: String,
+    /// Lower heating value, i.e. fuel energy per unit mass
+    pub lhv: si::SpecificEnergy,
+    /// Fuel density
+    pub density: si::MassDensity,
+    /// Mass of CO2 emitted per unit of fuel energy
+    pub co2_intensity: si::MassPerEnergy,
+}
+
+#[pyo3_api]
+impl FuelType {
+    #[new]
+    #[pyo3(signature = (name, lhv_joules_per_kilogram, density_kilograms_per_cubic_meter, co2_intensity_kilograms_per_joule))]
+    fn __new__(
+        name: String,
+        lhv_joules_per_kilogram: f64,
+        density_kilograms_per_cubic_meter: f64,
+        co2_intensity_kilograms_per_joule: f64,
+    ) -> anyhow::Result<Self> {
+        let fuel_type = Self {
+            name,
+            lhv: si::SpecificEnergy::new::<si::joule_per_kilogram>(lhv_joules_per_kilogram),
+            density: density_kilograms_per_cubic_meter * uc::KGPM3,
+            co2_intensity: si::MassPerEnergy::new::<si::kilogram_per_joule>(
+                co2_intensity_kilograms_per_joule,
+            ),
+        };
+        fuel_type.validate().with_context(|| format_dbg!())?;
+        Ok(fuel_type)
+    }
+
+    #[getter("lhv_joules_per_kilogram")]
+    fn get_lhv_py(&self) -> f64 {
+        self.lhv.get::<si::joule_per_kilogram>()
+    }
+
+    #[getter("density_kilograms_per_cubic_meter")]
+    fn get_density_py(&self) -> f64 {
+        self.density.get::<si::kilogram_per_cubic_meter>()
+    }
+
+    #[getter("co2_intensity_kilograms_per_joule")]
+    fn get_co2_intensity_py(&self) -> f64 {
+        self.co2_intensity.get::<si::kilogram_per_joule>()
+    }
+}
+
+impl Init for FuelType {}
+impl SerdeAPI for FuelType {}
+
+impl Default for FuelType {
+    /// Diesel
+    fn default() -> Self {
+        Self {
+            name: "Diesel".into(),
+            lhv: si::SpecificEnergy::new::<si::joule_per_kilogram>(DIESEL_LHV_JOULES_PER_KG),
+            density: DIESEL_DENSITY_KG_PER_M3 * uc::KGPM3,
+            co2_intensity: si::MassPerEnergy::new::<si::kilogram_per_joule>(
+                DIESEL_CO2_INTENSITY_KG_PER_JOULE,
+            ),
+        }
+    }
+}
+
+impl ObjState for FuelType {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gtz_fin(&mut errors, &self.lhv, "Lower heating value");
+        si_chk_num_gtz_fin(&mut errors, &self.density, "Density");
+        si_chk_num_gez_fin(&mut errors, &self.co2_intensity, "CO2 intensity");
+        errors.make_err()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -464,7 +870,7 @@ mod tests {
             .update(uc::MW * 2., || format_dbg!())
             .unwrap();
 
-        fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, true)
+        fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, None, true)
             .unwrap();
         assert!(
             fc.state.pwr_fuel.get_fresh(|| format_dbg!()).unwrap()
@@ -472,11 +878,113 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msg_pack_round_trip() {
+        // message pack is the intermediate format of python `to_pydict` and
+        // `from_pydict`
+        let fc = test_fc();
+        assert_eq!(
+            FuelConverter::from_msg_pack(&fc.to_msg_pack().unwrap(), true)
+                .unwrap()
+                .to_yaml()
+                .unwrap(),
+            fc.to_yaml().unwrap()
+        );
+    }
+
     #[test]
     fn test_default() {
         let _fc = FuelConverter::default();
     }
 
+    #[test]
+    fn test_co2_mass_proportional_to_energy_fuel() {
+        let mut fc = test_fc();
+        assert_eq!(fc.fuel_type, FuelType::default());
+        let dt = uc::S * 1.0;
+        for pwr_out_req in [uc::W * 200e3, uc::W * 500e3, uc::W * 800e3] {
+            fc.check_and_reset(|| format_dbg!()).unwrap();
+            fc.step(|| format_dbg!()).unwrap();
+            fc.set_cur_pwr_out_max(None, dt).unwrap();
+            fc.solve_energy_consumption(pwr_out_req, dt, true, None, true)
+                .unwrap();
+            fc.set_cumulative(dt, || format_dbg!()).unwrap();
+            let energy_fuel = *fc.state.energy_fuel.get_fresh(|| format_dbg!()).unwrap();
+            assert!(almost_eq(
+                fc.state
+                    .co2_mass
+                    .get_fresh(|| format_dbg!())
+                    .unwrap()
+                    .get::<si::kilogram>(),
+                energy_fuel.get::<si::joule>() * DIESEL_CO2_INTENSITY_KG_PER_JOULE,
+                None
+            ));
+        }
+    }
+
+    #[test]
+    fn test_fuel_volume_depends_on_fuel_type() {
+        let dt = uc::S * 1.0;
+        let run = |fuel_type: FuelType| -> FuelConverterState {
+            let mut fc = test_fc();
+            fc.set_fuel_type(fuel_type).unwrap();
+            for _ in 0..3 {
+                fc.check_and_reset(|| format_dbg!()).unwrap();
+                fc.step(|| format_dbg!()).unwrap();
+                fc.set_cur_pwr_out_max(None, dt).unwrap();
+                fc.solve_energy_consumption(uc::W * 500e3, dt, true, None, true)
+                    .unwrap();
+                fc.set_cumulative(dt, || format_dbg!()).unwrap();
+            }
+            fc.state
+        };
+        let energy_fuel =
+            |s: &FuelConverterState| *s.energy_fuel.get_fresh(|| format_dbg!()).unwrap();
+        let fuel_volume =
+            |s: &FuelConverterState| *s.fuel_volume.get_fresh(|| format_dbg!()).unwrap();
+        let co2_mass = |s: &FuelConverterState| *s.co2_mass.get_fresh(|| format_dbg!()).unwrap();
+
+        let state_diesel = run(FuelType::default());
+        // the default fuel is diesel
+        assert_eq!(test_fc().fuel_type, FuelType::default());
+        assert!(almost_eq(
+            fuel_volume(&state_diesel).get::<si::cubic_meter>(),
+            energy_fuel(&state_diesel).get::<si::joule>()
+                / DIESEL_LHV_JOULES_PER_KG
+                / DIESEL_DENSITY_KG_PER_M3,
+            None
+        ));
+
+        let diesel = FuelType::default();
+        let state_hi_lhv = run(FuelType {
+            name: "High LHV".into(),
+            lhv: diesel.lhv * 1.1,
+            co2_intensity: diesel.co2_intensity * 0.5,
+            ..diesel
+        });
+        // same fuel energy for the same power demand
+        assert_eq!(energy_fuel(&state_hi_lhv), energy_fuel(&state_diesel));
+        assert!(fuel_volume(&state_hi_lhv) < fuel_volume(&state_diesel));
+        assert!(almost_eq(
+            (fuel_volume(&state_hi_lhv) / fuel_volume(&state_diesel)).get::<si::ratio>(),
+            1.0 / 1.1,
+            None
+        ));
+        assert!(almost_eq(
+            (co2_mass(&state_hi_lhv) / co2_mass(&state_diesel)).get::<si::ratio>(),
+            0.5,
+            None
+        ));
+
+        assert!(test_fc()
+            .set_fuel_type(FuelType {
+                lhv: si::SpecificEnergy::ZERO,
+                ..FuelType::default()
+            })
+            .is_err());
+    }
+
     #[test]
     fn test_that_max_power_includes_rate() {
         let mut fc = test_fc();
@@ -505,7 +1013,7 @@ mod tests {
             .update(uc::MW * 2.0, || format_dbg!())
             .unwrap();
         fc.save_interval = Some(1);
-        fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, true)
+        fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, None, true)
             .unwrap();
         fc.set_cumulative(uc::S * 1.0, || format_dbg!()).unwrap();
         fc.save_state(|| format_dbg!()).unwrap();
@@ -515,7 +1023,7 @@ mod tests {
             .pwr_out_max
             .update(uc::MW * 2.0, || format_dbg!())
             .unwrap();
-        fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, true)
+        fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, None, true)
             .unwrap();
         fc.set_cumulative(uc::S * 1.0, || format_dbg!()).unwrap();
         fc.save_state(|| format_dbg!()).unwrap();
@@ -556,6 +1064,80 @@ mod tests {
         assert!(fc.history.is_empty());
     }
 
+    #[test]
+    fn test_cold_start_uses_more_fuel() {
+        let thermal = FuelConverterThermal::default();
+        let run = |temp_init: si::ThermodynamicTemperature| -> (si::Energy, FuelConverter) {
+            let mut fc = test_fc();
+            fc.set_thermal(Some(thermal)).unwrap();
+            fc.state
+                .temp
+                .update_unchecked(Some(temp_init), || format_dbg!())
+                .unwrap();
+            let mut energy_fuel = si::Energy::ZERO;
+            for _ in 0..10 {
+                fc.check_and_reset(|| format_dbg!()).unwrap();
+                fc.step(|| format_dbg!()).unwrap();
+                fc.state
+                    .pwr_out_max
+                    .update(uc::MW * 2.0, || format_dbg!())
+                    .unwrap();
+                fc.solve_energy_consumption(uc::W * 2_000e3, uc::S * 1.0, true, None, true)
+                    .unwrap();
+                fc.set_cumulative(uc::S * 1.0, || format_dbg!()).unwrap();
+                energy_fuel += *fc.state.pwr_fuel.get_fresh(|| format_dbg!()).unwrap() * uc::S;
+            }
+            (energy_fuel, fc)
+        };
+
+        let (energy_fuel_cold, fc_cold) = run(thermal.temp_cold);
+        let (energy_fuel_warm, fc_warm) = run(thermal.temp_warm);
+        // same shaft work, more fuel when cold
+        assert!(energy_fuel_cold > energy_fuel_warm);
+        // cold engine warms up, and warm engine stays at thermostat temperature
+        let temp = |fc: &FuelConverter| fc.state.temp.get_fresh(|| format_dbg!()).unwrap().unwrap();
+        assert!(temp(&fc_cold) > thermal.temp_cold);
+        assert!(temp(&fc_cold) < thermal.temp_warm);
+        assert_eq!(temp(&fc_warm), thermal.temp_warm);
+    }
+
+    #[test]
+    fn test_altitude_derate() {
+        let temp = (15.0 + uc::CELSIUS_TO_KELVIN) * uc::KELVIN;
+        let dt = uc::S * 1.0;
+        let run = |elev: si::Length| -> si::Power {
+            let mut fc = test_fc();
+            fc.set_altitude_derate(
+                vec![0.0 * uc::M, 1_000.0 * uc::M, 4_000.0 * uc::M],
+                vec![uc::R, uc::R, 0.7 * uc::R],
+            )
+            .unwrap();
+            // run at full power long enough to ramp up to the derated limit
+            for _ in 0..60 {
+                fc.check_and_reset(|| format_dbg!()).unwrap();
+                fc.step(|| format_dbg!()).unwrap();
+                fc.set_cur_pwr_out_max(Some((elev, temp)), dt).unwrap();
+                let pwr_out_max = *fc.state.pwr_out_max.get_fresh(|| format_dbg!()).unwrap();
+                fc.solve_energy_consumption(pwr_out_max, dt, true, None, true)
+                    .unwrap();
+                fc.set_cumulative(dt, || format_dbg!()).unwrap();
+            }
+            *fc.state.pwr_out_max.get_fresh(|| format_dbg!()).unwrap()
+        };
+
+        let fc = test_fc();
+        assert_eq!(fc.derate_for_altitude(2_500.0 * uc::M).unwrap(), uc::R);
+        let pwr_out_max_sea_level = run(si::Length::ZERO);
+        let pwr_out_max_high = run(2_500.0 * uc::M);
+        assert!(almost_eq_uom(&pwr_out_max_sea_level, &fc.pwr_out_max, None));
+        assert!(pwr_out_max_high < pwr_out_max_sea_level);
+        assert!(almost_eq_uom(
+            &pwr_out_max_high,
+            &(0.85 * fc.pwr_out_max),
+            None
+        ));
+    }
+
     #[test]
     fn test_get_and_set_eta() {
         let mut fc = test_fc();
@@ -359,9 +359,74 @@ impl Generator {
             || format_dbg!(),
         )?;
 
+        // split `pwr_loss` in proportion to the fitted copper and iron loss
+        // contributions at the current output power fraction
+        let (loss_frac_iron, loss_coeff_copper) = self.loss_coeffs();
+        let pwr_out_frac = ((pwr_prop_req + pwr_aux) / self.pwr_out_max).get::<si::ratio>();
+        let loss_frac_copper = loss_coeff_copper * pwr_out_frac.powi(2);
+        let copper_share = if loss_frac_copper > 0.0 {
+            loss_frac_copper / (loss_frac_iron + loss_frac_copper)
+        } else {
+            0.0
+        };
+        let pwr_loss = *self.state.pwr_loss.get_fresh(|| format_dbg!())?;
+        self.state
+            .pwr_loss_copper
+            .update(pwr_loss * copper_share, || format_dbg!())?;
+        self.state
+            .pwr_loss_iron
+            .update(pwr_loss * (1.0 - copper_share), || format_dbg!())?;
+
         Ok(())
     }
 
+    /// Returns `(iron, copper)` coefficients of a loss model,
+    /// `loss / pwr_out_max = iron + copper * (pwr_out / pwr_out_max)^2`, fit by
+    /// least squares to the losses implied by [Self::eta_interp] at nonzero
+    /// [Self::pwr_out_frac_interp].  Iron loss is thus independent of load and
+    /// copper loss scales with current squared.  Both coefficients are clamped
+    /// to be non-negative.
+    pub fn loss_coeffs(&self) -> (f64, f64) {
+        // (pwr_out_frac^2, loss / pwr_out_max) pairs
+        let points: Vec<(f64, f64)> = self
+            .pwr_out_frac_interp
+            .iter()
+            .zip(&self.eta_interp)
+            .filter(|(frac, eta)| **frac > 0.0 && **eta > 0.0)
+            .map(|(frac, eta)| (frac.powi(2), frac * (1.0 / eta - 1.0)))
+            .collect();
+        if points.is_empty() {
+            return (0.0, 0.0);
+        }
+        let n = points.len() as f64;
+        let x_mean = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let y_mean = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx = points
+            .iter()
+            .map(|(x, _)| (x - x_mean).powi(2))
+            .sum::<f64>();
+        let sxy = points
+            .iter()
+            .map(|(x, y)| (x - x_mean) * (y - y_mean))
+            .sum::<f64>();
+        if sxx == 0.0 {
+            // single output power fraction, so attribute all loss to copper
+            return (0.0, y_mean / x_mean);
+        }
+        let copper = sxy / sxx;
+        let iron = y_mean - copper * x_mean;
+        if copper < 0.0 {
+            (y_mean, 0.0)
+        } else if iron < 0.0 {
+            // least squares fit through the origin
+            let sxy0 = points.iter().map(|(x, y)| x * y).sum::<f64>();
+            let sxx0 = points.iter().map(|(x, _)| x.powi(2)).sum::<f64>();
+            (0.0, sxy0 / sxx0)
+        } else {
+            (iron, copper)
+        }
+    }
+
     impl_get_set_eta_max_min!();
     impl_get_set_eta_range!();
 }
@@ -471,8 +536,12 @@ pub struct GeneratorState {
     pub pwr_elec_prop_out: TrackedState<si::Power>,
     /// electrical power output to aux loads
     pub pwr_elec_aux: TrackedState<si::Power>,
-    /// power lost due to conversion inefficiency
+    /// power lost due to conversion inefficiency, equal to `pwr_loss_copper + pwr_loss_iron`
     pub pwr_loss: TrackedState<si::Power>,
+    /// portion of [Self::pwr_loss] attributed to winding resistance, which scales with current squared
+    pub pwr_loss_copper: TrackedState<si::Power>,
+    /// portion of [Self::pwr_loss] attributed to core losses, which are independent of load
+    pub pwr_loss_iron: TrackedState<si::Power>,
     /// cumulative mech energy in from fc
     pub energy_mech_in: TrackedState<si::Energy>,
     /// cumulative elec energy out to propulsion
@@ -481,6 +550,10 @@ pub struct GeneratorState {
     pub energy_elec_aux: TrackedState<si::Energy>,
     /// cumulative energy has lost due to imperfect efficiency
     pub energy_loss: TrackedState<si::Energy>,
+    /// cumulative energy lost to winding resistance
+    pub energy_loss_copper: TrackedState<si::Energy>,
+    /// cumulative energy lost to core losses
+    pub energy_loss_iron: TrackedState<si::Energy>,
 }
 
 #[pyo3_api]
@@ -522,6 +595,42 @@ mod tests {
         assert!(gen.history.is_empty());
     }
 
+    #[test]
+    fn test_loss_breakdown() {
+        // efficiencies implied by iron loss of 1% and copper loss of 3% of
+        // `pwr_out_max` at full load
+        let (iron, copper) = (0.01, 0.03);
+        let eta = |frac: f64| frac / (frac + iron + copper * frac.powi(2));
+        let pwr_out_frac_interp = vec![0.0, 0.1, 0.5, 1.0];
+        let eta_interp = vec![eta(0.1), eta(0.1), eta(0.5), eta(1.0)];
+        let mut gen = Generator::new(pwr_out_frac_interp, eta_interp, 5e6, None).unwrap();
+        let (iron_fit, copper_fit) = gen.loss_coeffs();
+        assert!(almost_eq(iron_fit, iron, None));
+        assert!(almost_eq(copper_fit, copper, None));
+
+        // aux load only, i.e. near zero load
+        let pwr_aux = 5e3 * uc::W;
+        gen.check_and_reset(|| format_dbg!()).unwrap();
+        gen.set_cur_pwr_max_out(gen.pwr_out_max, Some(pwr_aux))
+            .unwrap();
+        gen.set_pwr_in_req(si::Power::ZERO, pwr_aux, true, uc::S)
+            .unwrap();
+        let pwr_loss = *gen.state.pwr_loss.get_fresh(|| format_dbg!()).unwrap();
+        let pwr_loss_copper = *gen
+            .state
+            .pwr_loss_copper
+            .get_fresh(|| format_dbg!())
+            .unwrap();
+        let pwr_loss_iron = *gen.state.pwr_loss_iron.get_fresh(|| format_dbg!()).unwrap();
+        assert!(pwr_loss_iron > si::Power::ZERO);
+        assert!(pwr_loss_copper < pwr_loss_iron * 1e-3);
+        assert!(almost_eq_uom(
+            &(pwr_loss_copper + pwr_loss_iron),
+            &pwr_loss,
+            None
+        ));
+    }
+
     #[test]
     fn test_get_and_set_eta() {
         let mut res = test_gen();
@@ -43,6 +43,12 @@ pub struct ReversibleEnergyStorage {
     pub min_soc: si::Ratio,
     /// Hard limit on maximum SOC, e.g. 0.95
     pub max_soc: si::Ratio,
+    /// Open circuit voltage and internal resistance model used to compute
+    /// [ReversibleEnergyStorageState::voltage] and
+    /// [ReversibleEnergyStorageState::current].  `None` uses
+    /// [ResVoltageModel::default].
+    #[serde(default)]
+    pub voltage_model: Option<ResVoltageModel>,
     /// Time step interval at which history is saved
     pub save_interval: Option<usize>,
     #[serde(default)]
@@ -162,6 +168,40 @@ impl ReversibleEnergyStorage {
             .map(|se| se.get::<si::kilojoule_per_kilogram>())
     }
 
+    #[getter("cycles_equivalent")]
+    fn get_cycles_equivalent_py(&self) -> anyhow::Result<f64> {
+        self.cycles_equivalent()
+    }
+
+    #[getter("energy_throughput_joules")]
+    fn get_energy_throughput_py(&self) -> anyhow::Result<f64> {
+        Ok(self.energy_throughput()?.get::<si::joule>())
+    }
+
+    #[getter("energy_capacity_usable_joules")]
+    fn get_energy_capacity_usable_py(&self) -> f64 {
+        self.energy_capacity_usable().get::<si::joule>()
+    }
+
+    #[getter("usable_energy_remaining_joules")]
+    fn get_usable_energy_remaining_py(&self) -> anyhow::Result<f64> {
+        Ok(self.usable_energy_remaining()?.get::<si::joule>())
+    }
+
+    #[getter("usable_energy_headroom_joules")]
+    fn get_usable_energy_headroom_py(&self) -> anyhow::Result<f64> {
+        Ok(self.usable_energy_headroom()?.get::<si::joule>())
+    }
+
+    #[pyo3(name = "set_voltage_model")]
+    #[pyo3(signature = (voltage_model=None))]
+    fn set_voltage_model_py(
+        &mut self,
+        voltage_model: Option<ResVoltageModel>,
+    ) -> anyhow::Result<()> {
+        self.set_voltage_model(voltage_model)
+    }
+
     #[staticmethod]
     #[pyo3(name = "default")]
     fn default_py() -> Self {
@@ -250,27 +290,19 @@ impl Init for ReversibleEnergyStorage {
         self.state
             .init()
             .map_err(|err| Error::InitError(format_dbg!(err)))?;
+        if let Some(voltage_model) = &self.voltage_model {
+            voltage_model
+                .validate()
+                .map_err(|err| Error::InitError(format_dbg!(err)))?;
+        }
         Ok(())
     }
 }
 impl SerdeAPI for ReversibleEnergyStorage {
     fn from_file<P: AsRef<Path>>(filepath: P, skip_init: bool) -> Result<Self, Error> {
         let filepath = filepath.as_ref();
-        let extension = filepath
-            .extension()
-            .and_then(OsStr::to_str)
-            .ok_or_else(|| {
-                Error::SerdeError(format!("File extension could not be parsed: {filepath:?}"))
-            })?;
-        let mut file = File::open(filepath)
-            .with_context(|| {
-                if !filepath.exists() {
-                    format!("File not found: {filepath:?}")
-                } else {
-                    format!("Could not open file: {filepath:?}")
-                }
-            })
-            .map_err(|err| Error::SerdeError(format!("{err}")))?;
+        let (extension, _) = file_format(filepath)?;
+        let mut file = open_file_reader(filepath)?;
         let mut network = match Self::from_reader(&mut file, extension, skip_init) {
             Ok(network) => network,
             Err(err) => res_legacy::ReversibleEnergyStorageLegacy::from_file(filepath, false)
@@ -299,6 +331,7 @@ impl From<res_legacy::ReversibleEnergyStorageLegacy> for ReversibleEnergyStorage
             energy_capacity: value.energy_capacity,
             min_soc: value.min_soc,
             max_soc: value.max_soc,
+            voltage_model: None,
             save_interval: value.save_interval,
             history: value.history,
         }
@@ -739,16 +772,49 @@ impl ReversibleEnergyStorage {
             || format_dbg!(),
         )?;
 
-        let new_soc = *state.soc.get_stale(|| format_dbg!())?
+        let soc_prev = *state.soc.get_stale(|| format_dbg!())?;
+        let new_soc = soc_prev
             - *state.pwr_out_chemical.get_fresh(|| format_dbg!())? * dt / self.energy_capacity;
         state.soc.update(new_soc, || format_dbg!())?;
 
+        state.cycles_equivalent.increment(
+            ((new_soc - soc_prev).abs() / 2.0).get::<si::ratio>(),
+            || format_dbg!(),
+        )?;
+        state.energy_throughput.increment(
+            state.pwr_out_electrical.get_fresh(|| format_dbg!())?.abs() * dt,
+            || format_dbg!(),
+        )?;
+
+        // voltage and current are evaluated at the SOC from the start of the step,
+        // consistent with `eta`
+        let pwr_out_electrical = *state.pwr_out_electrical.get_fresh(|| format_dbg!())?;
+        let (voltage, current) = match &self.voltage_model {
+            Some(voltage_model) => voltage_model.voltage_and_current(soc_prev, pwr_out_electrical),
+            None => ResVoltageModel::default().voltage_and_current(soc_prev, pwr_out_electrical),
+        }
+        .with_context(|| format_dbg!())?;
+        state.voltage.update(voltage, || format_dbg!())?;
+        state.current.update(current, || format_dbg!())?;
+
         // TODO: change this when implementing soh
         state.soh.mark_fresh(|| format_dbg!())?;
 
         Ok(())
     }
 
+    /// Sets [Self::voltage_model], with `None` using [ResVoltageModel::default]
+    pub fn set_voltage_model(
+        &mut self,
+        voltage_model: Option<ResVoltageModel>,
+    ) -> anyhow::Result<()> {
+        if let Some(voltage_model) = &voltage_model {
+            voltage_model.validate().with_context(|| format_dbg!())?;
+        }
+        self.voltage_model = voltage_model;
+        Ok(())
+    }
+
     pub fn get_eta_max(&self) -> f64 {
         // since eta is all f64 between 0 and 1, NEG_INFINITY is safe
         self.eta_interp_values
@@ -872,11 +938,63 @@ impl ReversibleEnergyStorage {
         }
     }
 
+    /// Cumulative equivalent full cycles, i.e. cumulative |ΔSOC| / 2, as a
+    /// proxy for cycle aging
+    pub fn cycles_equivalent(&self) -> anyhow::Result<f64> {
+        Ok(*self
+            .state
+            .cycles_equivalent
+            .get_unchecked(|| format_dbg!())?)
+    }
+
+    /// Cumulative absolute electrical energy into and out of the battery
+    pub fn energy_throughput(&self) -> anyhow::Result<si::Energy> {
+        Ok(*self
+            .state
+            .energy_throughput
+            .get_unchecked(|| format_dbg!())?)
+    }
+
     /// Usable energy capacity, accounting for SOC limits
     pub fn energy_capacity_usable(&self) -> si::Energy {
         self.energy_capacity * (self.max_soc - self.min_soc)
     }
 
+    /// Energy that can still be discharged at current SOC before reaching
+    /// [Self::min_soc]
+    pub fn usable_energy_remaining(&self) -> anyhow::Result<si::Energy> {
+        Ok(
+            ((*self.state.soc.get_unchecked(|| format_dbg!())? - self.min_soc)
+                * self.energy_capacity)
+                .max(si::Energy::ZERO),
+        )
+    }
+
+    /// Energy that can still be charged at current SOC before reaching
+    /// [Self::max_soc]
+    pub fn usable_energy_headroom(&self) -> anyhow::Result<si::Energy> {
+        Ok(
+            ((self.max_soc - *self.state.soc.get_unchecked(|| format_dbg!())?)
+                * self.energy_capacity)
+                .max(si::Energy::ZERO),
+        )
+    }
+
+    /// Sets SOC at the start of a simulation, which must be within
+    /// [[Self::min_soc], [Self::max_soc]]
+    pub fn set_initial_soc(&mut self, soc: si::Ratio) -> anyhow::Result<()> {
+        ensure!(
+            self.min_soc <= soc && soc <= self.max_soc,
+            "{}\n`soc` ({}) must be between `min_soc` ({}) and `max_soc` ({})",
+            format_dbg!(),
+            soc.get::<si::ratio>(),
+            self.min_soc.get::<si::ratio>(),
+            self.max_soc.get::<si::ratio>()
+        );
+        self.state.soc.update_unchecked(soc, || format_dbg!())?;
+        Ok(())
+    }
+
     /// Mean efficiency in charge direction
     pub fn mean_chrg_eff(&self) -> anyhow::Result<si::Ratio> {
         let mut eta_sum = si::Ratio::ZERO;
@@ -916,6 +1034,127 @@ impl ReversibleEnergyStorage {
     }
 }
 
+#[serde_api]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Equivalent circuit model of [ReversibleEnergyStorage] terminal voltage,
+/// consisting of an open circuit voltage (OCV) that varies with SOC in series
+/// with a fixed internal resistance.  Terminal current, `I`, satisfies
+/// `P = (OCV - I * R) * I` for electrical output power `P`, and terminal
+/// voltage is `OCV - I * R`, so voltage sags with discharge and rises with
+/// charge.
+pub struct ResVoltageModel {
+    /// SOC grid for [Self::ocv].  A single point yields a fixed OCV.
+    pub ocv_soc_grid: Vec<si::Ratio>,
+    /// Open circuit voltage at each point in [Self::ocv_soc_grid]
+    pub ocv: Vec<si::ElectricPotential>,
+    /// Lumped internal resistance
+    pub resistance: si::ElectricalResistance,
+}
+
+#[pyo3_api]
+impl ResVoltageModel {
+    #[new]
+    #[pyo3(signature = (ocv_soc_grid, ocv_volts, resistance_ohms=None))]
+    fn __new__(
+        ocv_soc_grid: Vec<f64>,
+        ocv_volts: Vec<f64>,
+        resistance_ohms: Option<f64>,
+    ) -> anyhow::Result<Self> {
+        let vm = Self {
+            ocv_soc_grid: ocv_soc_grid.into_iter().map(|soc| soc * uc::R).collect(),
+            ocv: ocv_volts.into_iter().map(|v| v * uc::V).collect(),
+            resistance: resistance_ohms
+                .map(|r| r * uc::OHM)
+                .unwrap_or(Self::default().resistance),
+        };
+        vm.validate().with_context(|| format_dbg!())?;
+        Ok(vm)
+    }
+}
+
+impl Init for ResVoltageModel {}
+impl SerdeAPI for ResVoltageModel {}
+
+impl Default for ResVoltageModel {
+    /// Fixed 1000 V OCV with no internal resistance, so that terminal voltage
+    /// is constant and no output power is beyond what the terminals can deliver
+    fn default() -> Self {
+        Self {
+            ocv_soc_grid: vec![0.5 * uc::R],
+            ocv: vec![1_000.0 * uc::V],
+            resistance: si::ElectricalResistance::ZERO,
+        }
+    }
+}
+
+impl ResVoltageModel {
+    /// Returns open circuit voltage at `soc`, clamped to the ends of
+    /// [Self::ocv_soc_grid]
+    pub fn ocv_at(&self, soc: si::Ratio) -> anyhow::Result<si::ElectricPotential> {
+        let soc_grid: Vec<f64> = self
+            .ocv_soc_grid
+            .iter()
+            .map(|soc| soc.get::<si::ratio>())
+            .collect();
+        let ocv: Vec<f64> = self.ocv.iter().map(|v| v.get::<si::volt>()).collect();
+        Ok(interp1d(&soc.get::<si::ratio>(), &soc_grid, &ocv, false)
+            .with_context(|| format_dbg!())?
+            * uc::V)
+    }
+
+    /// Returns `(voltage, current)` at the terminals for electrical output
+    /// power `pwr_out` (positive for discharge) at `soc`
+    pub fn voltage_and_current(
+        &self,
+        soc: si::Ratio,
+        pwr_out: si::Power,
+    ) -> anyhow::Result<(si::ElectricPotential, si::ElectricCurrent)> {
+        let ocv = self
+            .ocv_at(soc)
+            .with_context(|| format_dbg!())?
+            .get::<si::volt>();
+        let resistance = self.resistance.get::<si::ohm>();
+        let pwr = pwr_out.get::<si::watt>();
+        let current = if resistance == 0.0 {
+            pwr / ocv
+        } else {
+            let discriminant = ocv.powi(2) - 4.0 * resistance * pwr;
+            ensure!(
+                discriminant >= 0.0,
+                "{}\nres output power ({:.6} MW) exceeds max power deliverable at terminals ({:.6} MW)",
+                format_dbg!(discriminant >= 0.0),
+                pwr_out.get::<si::megawatt>(),
+                ocv.powi(2) / (4.0 * resistance) / 1e6
+            );
+            // smaller root of `R * I^2 - OCV * I + P = 0`, i.e. the stable
+            // operating point
+            (ocv - discriminant.sqrt()) / (2.0 * resistance)
+        };
+        Ok(((ocv - current * resistance) * uc::V, current * uc::A))
+    }
+}
+
+impl ObjState for ResVoltageModel {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gez(&mut errors, &self.resistance, "Internal resistance");
+        if self.ocv_soc_grid.is_empty() {
+            errors.push(anyhow!("OCV SOC grid must not be empty"));
+        }
+        if self.ocv_soc_grid.len() != self.ocv.len() {
+            errors.push(anyhow!("OCV SOC grid and OCV must have the same length"));
+        }
+        if !self.ocv_soc_grid.windows(2).all(|w| w[0] < w[1]) {
+            errors.push(anyhow!("OCV SOC grid must be strictly increasing"));
+        }
+        if !self.ocv.iter().all(|v| *v > si::ElectricPotential::ZERO) {
+            errors.push(anyhow!("OCV must be positive"));
+        }
+        errors.make_err()
+    }
+}
+
 #[serde_api]
 #[derive(
     Clone, Deserialize, Serialize, Debug, PartialEq, HistoryVec, StateMethods, SetCumulative,
@@ -960,6 +1199,13 @@ pub struct ReversibleEnergyStorageState {
     /// chemical power; positive is discharging
     pub pwr_out_chemical: TrackedState<si::Power>,
 
+    /// terminal voltage
+    #[serde(default)]
+    pub voltage: TrackedState<si::ElectricPotential>,
+    /// terminal current; positive is discharging
+    #[serde(default)]
+    pub current: TrackedState<si::ElectricCurrent>,
+
     // cumulative energies
     /// cumulative total electrical energy; positive is discharging
     pub energy_out_electrical: TrackedState<si::Energy>,
@@ -971,6 +1217,10 @@ pub struct ReversibleEnergyStorageState {
     pub energy_loss: TrackedState<si::Energy>,
     /// cumulative chemical energy; positive is discharging
     pub energy_out_chemical: TrackedState<si::Energy>,
+    /// cumulative absolute electrical energy, i.e. sum of charge and discharge
+    pub energy_throughput: TrackedState<si::Energy>,
+    /// cumulative equivalent full cycles, i.e. cumulative |ΔSOC| / 2
+    pub cycles_equivalent: TrackedState<f64>,
 
     /// buffer above minimum SOC at which battery max discharge rate is linearly
     /// reduced as soc approaches `min_soc`
@@ -1006,11 +1256,15 @@ impl Default for ReversibleEnergyStorageState {
             pwr_aux: Default::default(),
             pwr_out_chemical: Default::default(),
             pwr_loss: Default::default(),
+            voltage: Default::default(),
+            current: Default::default(),
             energy_out_electrical: Default::default(),
             energy_out_propulsion: Default::default(),
             energy_aux: Default::default(),
             energy_out_chemical: Default::default(),
             energy_loss: Default::default(),
+            energy_throughput: Default::default(),
+            cycles_equivalent: Default::default(),
             soc_chrg_buffer: TrackedState::new(uc::R * 1.0),
             soc_disch_buffer: TrackedState::new(uc::R * 0.0),
             temperature_celsius: TrackedState::new(45.0),
@@ -1030,6 +1284,119 @@ mod tests {
         let _res = _mock_res();
     }
 
+    #[test]
+    fn test_cycles_equivalent() {
+        let mut res = _mock_res();
+        res.min_soc = 0.0 * uc::R;
+        res.max_soc = 1.0 * uc::R;
+        res.state
+            .soc
+            .update_unchecked(res.max_soc, || format_dbg!())
+            .unwrap();
+        let dt = 10.0 * uc::S;
+
+        let step = |res: &mut ReversibleEnergyStorage, discharge: bool| {
+            res.check_and_reset(|| format_dbg!()).unwrap();
+            res.step(|| format_dbg!()).unwrap();
+            res.set_curr_pwr_out_max(dt, si::Power::ZERO, si::Energy::ZERO, si::Energy::ZERO)
+                .unwrap();
+            let pwr_prop_req = if discharge {
+                *res.state.pwr_prop_max.get_fresh(|| format_dbg!()).unwrap()
+            } else {
+                -*res.state.pwr_regen_max.get_fresh(|| format_dbg!()).unwrap()
+            };
+            res.solve_energy_consumption(pwr_prop_req, si::Power::ZERO, dt)
+                .unwrap();
+            res.set_cumulative(dt, || format_dbg!()).unwrap();
+            *res.state.soc.get_fresh(|| format_dbg!()).unwrap()
+        };
+
+        let mut n_steps = 0;
+        while step(&mut res, true) > 1e-6 * uc::R && n_steps < 10_000 {
+            n_steps += 1;
+        }
+        while step(&mut res, false) < (1.0 - 1e-6) * uc::R && n_steps < 20_000 {
+            n_steps += 1;
+        }
+
+        assert!(
+            (res.cycles_equivalent().unwrap() - 1.0).abs() < 0.01,
+            "{}",
+            res.cycles_equivalent().unwrap()
+        );
+        assert!(res.energy_throughput().unwrap() > res.energy_capacity);
+    }
+
+    #[test]
+    fn test_voltage_sags_with_discharge_power() {
+        let dt = 1.0 * uc::S;
+        let voltage_model = ResVoltageModel {
+            resistance: 0.02 * uc::OHM,
+            ..Default::default()
+        };
+        let step = |pwr_prop_req: si::Power| -> ReversibleEnergyStorageState {
+            let mut res = _mock_res();
+            res.set_voltage_model(Some(voltage_model.clone())).unwrap();
+            res.check_and_reset(|| format_dbg!()).unwrap();
+            res.step(|| format_dbg!()).unwrap();
+            res.set_curr_pwr_out_max(dt, si::Power::ZERO, si::Energy::ZERO, si::Energy::ZERO)
+                .unwrap();
+            res.solve_energy_consumption(pwr_prop_req, si::Power::ZERO, dt)
+                .unwrap();
+            res.state
+        };
+        let state_lo = step(0.2 * uc::MW);
+        let state_hi = step(2.0 * uc::MW);
+        let voltage =
+            |s: &ReversibleEnergyStorageState| *s.voltage.get_fresh(|| format_dbg!()).unwrap();
+        let current =
+            |s: &ReversibleEnergyStorageState| *s.current.get_fresh(|| format_dbg!()).unwrap();
+
+        assert!(voltage(&state_hi) < voltage(&state_lo));
+        assert!(current(&state_hi) > current(&state_lo));
+        assert!(voltage(&state_lo) < voltage_model.ocv[0]);
+        assert!(utils::almost_eq_uom(
+            &(voltage(&state_hi) * current(&state_hi)),
+            &(2.0 * uc::MW),
+            None
+        ));
+    }
+
+    #[test]
+    fn test_default_voltage_model() {
+        let voltage_model = ResVoltageModel::default();
+        // no internal resistance, so power beyond `OCV^2 / 4R` of a resistive
+        // model is still deliverable at fixed OCV
+        let (voltage, current) = voltage_model
+            .voltage_and_current(0.5 * uc::R, 20.0 * uc::MW)
+            .unwrap();
+        assert_eq!(voltage, voltage_model.ocv[0]);
+        assert!(utils::almost_eq_uom(&current, &(20e3 * uc::A), None));
+    }
+
+    #[test]
+    fn test_usable_energy_remaining() {
+        let mut res = _mock_res();
+        res.set_initial_soc(0.5 * uc::R).unwrap();
+        assert!(utils::almost_eq_uom(
+            &res.usable_energy_remaining().unwrap(),
+            &((0.5 * uc::R - res.min_soc) * res.energy_capacity),
+            None
+        ));
+        assert!(utils::almost_eq_uom(
+            &(res.usable_energy_remaining().unwrap() + res.usable_energy_headroom().unwrap()),
+            &res.energy_capacity_usable(),
+            None
+        ));
+
+        res.set_initial_soc(res.min_soc).unwrap();
+        assert_eq!(res.usable_energy_remaining().unwrap(), si::Energy::ZERO);
+        assert_eq!(
+            res.usable_energy_headroom().unwrap(),
+            res.energy_capacity_usable()
+        );
+    }
+
     #[test]
     fn test_get_and_set_eta() {
         let mut res = _mock_res();
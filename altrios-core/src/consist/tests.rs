@@ -1,4 +1,9 @@
 use super::*;
+use crate::consist::consist_sim::ConsistSimulation;
+use crate::consist::locomotive::loco_sim::PowerTrace;
+use crate::consist::locomotive::powertrain::electric_drivetrain::DynBrakeThermal;
+use crate::consist::locomotive::powertrain::fuel_converter::FuelType;
+use crate::consist::locomotive::AxleLoadTransfer;
 use crate::si;
 
 #[test]
@@ -38,6 +43,7 @@ fn test_consist() {
             None,
             Some(5e6 * uc::LB),
             Some(10.0 * uc::MPH),
+            None,
             1.0 * uc::S,
         )
         .unwrap();
@@ -125,3 +131,685 @@ fn test_consist() {
             > si::Energy::ZERO
     );
 }
+
+#[test]
+/// Unit test for consist-level power ramp rate limit.
+fn test_consist_pwr_rate_limit() {
+    let mut consist = Consist::default();
+    let pwr_rate_out_max = 1e5 * uc::WPS;
+    consist.pwr_rate_out_max = Some(pwr_rate_out_max);
+
+    consist.check_and_reset(|| format_dbg!()).unwrap();
+    consist.set_pwr_aux(Some(true)).unwrap();
+    consist
+        .set_curr_pwr_max_out(
+            None,
+            None,
+            Some(5e6 * uc::LB),
+            Some(10.0 * uc::MPH),
+            None,
+            1.0 * uc::S,
+        )
+        .unwrap();
+    assert_eq!(
+        *consist
+            .state
+            .pwr_rate_out_max
+            .get_fresh(|| format_dbg!())
+            .unwrap(),
+        pwr_rate_out_max
+    );
+
+    // ramping from zero to 1 MW in 1 s exceeds the consist-level limit
+    assert!(consist
+        .clone()
+        .solve_energy_consumption(
+            uc::W * 1e6,
+            Some(5e6 * uc::LB),
+            Some(10.0 * uc::MPH),
+            uc::S * 1.0,
+            Some(true),
+        )
+        .is_err());
+    consist
+        .solve_energy_consumption(
+            uc::W * 1e5,
+            Some(5e6 * uc::LB),
+            Some(10.0 * uc::MPH),
+            uc::S * 1.0,
+            Some(true),
+        )
+        .unwrap();
+}
+
+/// Solves one time step of `consist` the way [ConsistSimulation::solve_step]
+/// does, with power demand from `pwr_out_req` evaluated after current power
+/// limits are set
+fn solve_step(
+    consist: &mut Consist,
+    dt: si::Time,
+    pwr_out_req: impl FnOnce(&Consist) -> si::Power,
+) {
+    let train_mass = Some(5e6 * uc::LB);
+    let train_speed = Some(10.0 * uc::MPH);
+    consist.check_and_reset(|| format_dbg!()).unwrap();
+    consist.step(|| format_dbg!()).unwrap();
+    consist
+        .state
+        .pwr_cat_lim
+        .mark_fresh(|| format_dbg!())
+        .unwrap();
+    consist.set_pwr_aux(Some(true)).unwrap();
+    consist
+        .set_curr_pwr_max_out(None, None, train_mass, train_speed, None, dt)
+        .unwrap();
+    let pwr_out_req = pwr_out_req(consist);
+    consist
+        .solve_energy_consumption(pwr_out_req, train_mass, train_speed, dt, Some(true))
+        .unwrap();
+    consist.set_cumulative(dt, || format_dbg!()).unwrap();
+}
+
+#[test]
+/// Unit test for distributed power command delay to remote locomotives.
+fn test_consist_command_delay() {
+    let mut consist = Consist::new(
+        vec![Locomotive::default(), Locomotive::default()],
+        Some(1),
+        PowerDistributionControlType::default(),
+    );
+    // 2.5 s of delay at 1 s time steps buffers 3 commands
+    consist.command_delay = 2.5 * uc::S;
+    let n_steps_delay = 3;
+    let pwr: Vec<si::Power> = [
+        0.0, 0.2, 0.3, 0.1, 0.25, 0.15, -0.2, -0.3, -0.1, 0.0, 0.0, 0.0,
+    ]
+    .iter()
+    .map(|p| p * 1e6 * uc::W)
+    .collect();
+    let n = pwr.len();
+    let power_trace = PowerTrace {
+        time: (0..n).map(|i| i as f64 * uc::S).collect(),
+        pwr,
+        engine_on: vec![Some(true); n],
+        train_speed: vec![10.0 * uc::MPH; n],
+        train_mass: Some(5e6 * uc::LB),
+        pwr_aux: None,
+    };
+    let mut consist_sim = ConsistSimulation::new(consist, power_trace, Some(1));
+    consist_sim.walk().unwrap();
+
+    let consist = &consist_sim.loco_con;
+    let hist = |vals: &Vec<TrackedState<si::Power>>| -> Vec<si::Power> {
+        vals.iter()
+            .map(|val| *val.get_unchecked(|| format_dbg!()).unwrap())
+            .collect()
+    };
+    let pwr_out_req = hist(&consist.history.pwr_out_req);
+    let pwr_out = hist(&consist.history.pwr_out);
+    let pwr_out_lead = hist(&consist.loco_vec[0].history.pwr_out);
+    let pwr_out_remote = hist(&consist.loco_vec[1].history.pwr_out);
+    let pwr_out_delay_deficit = hist(&consist.history.pwr_out_delay_deficit);
+    assert_eq!(pwr_out_remote.len(), n);
+    for i in 0..n {
+        assert!(almost_eq_uom(
+            &pwr_out[i],
+            &(pwr_out_lead[i] + pwr_out_remote[i]),
+            None
+        ));
+        // shortfall from the request is recorded rather than lost
+        assert!(almost_eq_uom(
+            &pwr_out_delay_deficit[i],
+            &(pwr_out_req[i] - pwr_out[i]),
+            None
+        ));
+        // remote applies its share of the command issued `n_steps_delay` steps
+        // earlier, which is what the lead did not apply at that time step
+        let pwr_out_remote_expected = if i < n_steps_delay {
+            si::Power::ZERO
+        } else {
+            pwr_out_req[i - n_steps_delay] - pwr_out_lead[i - n_steps_delay]
+        };
+        assert!(
+            almost_eq_uom(&pwr_out_remote[i], &pwr_out_remote_expected, None),
+            "{i}: {pwr_out_remote:?}"
+        );
+    }
+    // delayed braking commands reach the remote
+    assert!(pwr_out_remote
+        .iter()
+        .any(|pwr_out| *pwr_out < si::Power::ZERO));
+    // remote delivers nothing while the first commands propagate
+    assert!(pwr_out_delay_deficit[1] > si::Power::ZERO);
+    assert!(almost_eq_uom(
+        &pwr_out_delay_deficit[1],
+        &(pwr_out_req[1] - pwr_out_lead[1]),
+        None
+    ));
+    // and keeps delivering after the lead reduces power
+    assert!(pwr_out_delay_deficit
+        .iter()
+        .any(|pwr| *pwr < si::Power::ZERO));
+    let energy_out_delay_deficit = *consist
+        .state
+        .energy_out_delay_deficit
+        .get_unchecked(|| format_dbg!())
+        .unwrap();
+    assert!(almost_eq_uom(
+        &energy_out_delay_deficit,
+        &pwr_out_delay_deficit
+            .iter()
+            .fold(si::Energy::ZERO, |acc, pwr| acc + *pwr * uc::S),
+        None
+    ));
+}
+
+#[test]
+fn test_tractive_effort_curve() {
+    // single default conventional locomotive
+    let consist = Consist::new(
+        vec![Locomotive::default()],
+        None,
+        PowerDistributionControlType::default(),
+    );
+    let train_mass = 5e6 * uc::LB;
+    let speeds = [
+        0.0 * uc::MPS,
+        0.1 * uc::MPS,
+        4.0 * uc::MPS,
+        15.0 * uc::MPS,
+        30.0 * uc::MPS,
+    ];
+    let curve = consist.tractive_effort_curve(&speeds, train_mass).unwrap();
+    assert_eq!(curve.len(), speeds.len());
+
+    // adhesion-limited at low speed, per `LocoParams::default`
+    let force_max = 667.2e3 * uc::N;
+    for force in &curve[..3] {
+        assert_eq!(*force, force_max);
+    }
+    // power-limited at high speed, per default component parameters:
+    // 3.356 MW engine, 0.97998 generator efficiency less 8.554 kW idle aux,
+    // and 0.98912 drivetrain efficiency
+    let pwr_out_max = 3.2446e6 * uc::W;
+    for (force, speed) in curve[3..].iter().zip(&speeds[3..]) {
+        assert!(
+            almost_eq_uom(force, &(pwr_out_max / *speed), Some(1e-4)),
+            "{force:?}"
+        );
+    }
+
+    // state of `consist` is not modified
+    assert_eq!(
+        consist,
+        Consist::new(
+            vec![Locomotive::default()],
+            None,
+            PowerDistributionControlType::default(),
+        )
+    );
+    assert!(consist
+        .tractive_effort_curve(&[-1.0 * uc::MPS], train_mass)
+        .is_err());
+    assert!(consist
+        .tractive_effort_curve(&speeds, -1.0 * uc::KG)
+        .is_err());
+}
+
+#[test]
+fn test_can_hold_grade() {
+    let consist = Consist::default();
+    let speed = 10.0 * uc::MPH;
+    // light train on a modest grade
+    assert!(consist
+        .can_hold_grade(0.01 * uc::R, speed, 1_000.0 * uc::TON)
+        .unwrap());
+    // underpowered for a heavy train on a steep grade
+    assert!(!consist
+        .can_hold_grade(0.025 * uc::R, speed, 20_000.0 * uc::TON)
+        .unwrap());
+    // downgrades never require traction
+    assert!(consist
+        .can_hold_grade(-0.025 * uc::R, speed, 20_000.0 * uc::TON)
+        .unwrap());
+}
+
+#[test]
+fn test_adhesion_factor() {
+    let mut consist = Consist::default();
+    let speeds = [0.1 * uc::MPS];
+    let train_mass = 5e6 * uc::LB;
+    let force_base = consist.tractive_effort_curve(&speeds, train_mass).unwrap()[0];
+    let loco_force_max_base: Vec<si::Force> = consist
+        .loco_vec
+        .iter()
+        .map(|loco| loco.force_max().unwrap())
+        .collect();
+
+    consist.set_adhesion_factor(0.7 * uc::R).unwrap();
+    let force_wet = consist.tractive_effort_curve(&speeds, train_mass).unwrap()[0];
+    assert!(almost_eq_uom(&force_wet, &(0.7 * force_base), None));
+
+    // base `mu * mass * g` relationship is unaffected by the runtime factor
+    for (loco, force_max_base) in consist.loco_vec.iter().zip(loco_force_max_base) {
+        loco.check_force_max().unwrap();
+        assert_eq!(loco.force_max().unwrap(), force_max_base);
+        assert!(almost_eq_uom(
+            &loco.force_max_effective().unwrap(),
+            &(0.7 * force_max_base),
+            None
+        ));
+    }
+
+    assert!(consist.set_adhesion_factor(-0.1 * uc::R).is_err());
+}
+
+#[test]
+fn test_assert_limits_context() {
+    let mut consist = Consist::default();
+    let train_mass = Some(5e6 * uc::LB);
+    let train_speed = Some(10.0 * uc::MPH);
+    consist.check_and_reset(|| format_dbg!()).unwrap();
+    consist.set_pwr_aux(Some(true)).unwrap();
+    consist
+        .set_curr_pwr_max_out(None, None, train_mass, train_speed, None, uc::S)
+        .unwrap();
+    let pwr_out_max = *consist
+        .state
+        .pwr_out_max
+        .get_fresh(|| format_dbg!())
+        .unwrap();
+    let err = consist
+        .solve_energy_consumption(
+            2.0 * pwr_out_max,
+            train_mass,
+            train_speed,
+            uc::S,
+            Some(true),
+        )
+        .unwrap_err();
+    let msg = format!("{err:?}");
+    assert!(msg.contains("exceeds max power"));
+    for (i, loco) in consist.loco_vec.iter().enumerate() {
+        let loco_type = loco.loco_type.to_string();
+        assert!(msg.contains(&format!(
+            "loco #: {i}, loco type: {loco_type}, pwr_out_max: "
+        )));
+    }
+}
+
+#[test]
+fn test_axle_load_transfer() {
+    /// Returns `force_max_curr` after previous time step with wheel power at
+    /// `pwr_frac` of current max power at `speed`
+    fn force_max_curr(loco: &mut Locomotive, pwr_frac: f64, speed: si::Velocity) -> si::Force {
+        // first step sets `pwr_out`, and second step uses it to find `force_max_curr`
+        for _ in 0..2 {
+            loco.check_and_reset(|| format_dbg!()).unwrap();
+            loco.step(|| format_dbg!()).unwrap();
+            loco.set_pwr_aux(Some(true)).unwrap();
+            loco.set_curr_pwr_max_out(None, None, None, Some(speed), None, uc::S)
+                .unwrap();
+            let pwr_out = pwr_frac * *loco.state.pwr_out_max.get_fresh(|| format_dbg!()).unwrap();
+            loco.solve_energy_consumption(pwr_out, uc::S, Some(true), None, Some(speed))
+                .unwrap();
+            loco.set_cumulative(uc::S, || format_dbg!()).unwrap();
+        }
+        *loco
+            .state
+            .force_max_curr
+            .get_fresh(|| format_dbg!())
+            .unwrap()
+    }
+    let speed = 5.0 * uc::MPH;
+
+    let mut loco = Locomotive::default();
+    let force_max = loco.force_max_effective().unwrap();
+    assert_eq!(force_max_curr(&mut loco, 1.0, speed), force_max);
+
+    loco.set_axle_load_transfer(AxleLoadTransfer {
+        transfer_coeff: uc::R,
+        ..Default::default()
+    })
+    .unwrap();
+    let force_high = force_max_curr(&mut loco, 1.0, speed);
+    let force_low = force_max_curr(&mut loco, 0.2, speed);
+    assert!(force_high < force_low);
+    assert!(force_low < force_max);
+    // full effort is assumed when starting from rest
+    let force_rest = force_max_curr(&mut loco, 0.0, si::Velocity::ZERO);
+    assert_eq!(force_rest, loco.force_max_dynamic(force_max).unwrap());
+    assert!(force_rest < force_high);
+
+    assert!(loco
+        .set_axle_load_transfer(AxleLoadTransfer {
+            transfer_coeff: -uc::R,
+            ..Default::default()
+        })
+        .is_err());
+}
+
+#[test]
+fn test_pwr_aux_idle_override() {
+    /// Returns `pwr_aux` after previous time step with wheel power `pwr_out`
+    fn pwr_aux(loco: &mut Locomotive, pwr_out: si::Power) -> si::Power {
+        loco.state.pwr_out = TrackedState::new(pwr_out);
+        loco.state.pwr_out.mark_stale();
+        loco.state.pwr_aux.mark_stale();
+        loco.set_pwr_aux(Some(true)).unwrap();
+        *loco.state.pwr_aux.get_fresh(|| format_dbg!()).unwrap()
+    }
+
+    let mut loco = Locomotive::default();
+    assert_eq!(loco.pwr_aux_at_idle(), loco.pwr_aux_offset);
+    assert_eq!(pwr_aux(&mut loco, si::Power::ZERO), loco.pwr_aux_offset);
+
+    let pwr_aux_idle = 20e3 * uc::W;
+    loco.pwr_aux_idle_override = Some(pwr_aux_idle);
+    loco.init().unwrap();
+    assert_eq!(loco.pwr_aux_at_idle(), pwr_aux_idle);
+    assert_eq!(pwr_aux(&mut loco, si::Power::ZERO), pwr_aux_idle);
+    // override does not apply when producing tractive power
+    let pwr_out = 1e6 * uc::W;
+    assert_eq!(
+        pwr_aux(&mut loco, pwr_out),
+        loco.pwr_aux_offset + loco.pwr_aux_traction_coeff * pwr_out
+    );
+
+    loco.pwr_aux_idle_override = Some(-uc::W);
+    assert!(loco.init().is_err());
+}
+
+#[test]
+fn test_loco_summary() {
+    let loco = Locomotive::default();
+    let summary = loco.summary().unwrap();
+    assert!(summary.contains("ConventionalLoco"));
+    assert!(summary.contains(&format!(
+        "{:.1} kW",
+        loco.get_pwr_rated().get::<si::kilowatt>()
+    )));
+    assert!(summary.contains("Fuel converter: yes"));
+    assert!(!summary.contains("RES energy capacity"));
+
+    let bel = Locomotive::default_battery_electric_loco();
+    let summary = bel.summary().unwrap();
+    assert!(summary.contains("BatteryElectricLoco"));
+    assert!(summary.contains("Fuel converter: no"));
+    assert!(summary.contains("RES energy capacity"));
+}
+
+#[test]
+fn test_describe_topology() {
+    let consist = Consist::default();
+    let topology = consist.describe_topology();
+    assert!(topology.starts_with("flowchart LR"));
+    assert!(topology.contains("loco0[\"#0 lead: ConventionalLoco"));
+    assert!(topology.contains("loco1[\"#1 remote: BatteryElectricLoco"));
+    // each locomotive type is listed in order, one node per line
+    let nodes: Vec<&str> = topology
+        .lines()
+        .skip(1)
+        .take(consist.loco_vec.len())
+        .collect();
+    assert_eq!(nodes.len(), consist.loco_vec.len());
+    for (i, (node, loco)) in nodes.iter().zip(&consist.loco_vec).enumerate() {
+        assert!(node.contains(&format!("loco{i}[")), "{node}");
+        assert!(node.contains(&loco.loco_type.to_string()), "{node}");
+    }
+    assert_eq!(
+        topology.matches(" --- ").count(),
+        consist.loco_vec.len() - 1
+    );
+}
+
+#[test]
+fn test_loco_and_consist_display() {
+    let loco = Locomotive::default();
+    let repr = format!("{loco}");
+    assert!(repr.starts_with("Locomotive("));
+    assert!(repr.contains("loco_type=ConventionalLoco"));
+    assert!(repr.contains(&format!(
+        "pwr_rated_kilowatts={:.1}",
+        loco.get_pwr_rated().get::<si::kilowatt>()
+    )));
+
+    let consist = Consist::default();
+    let repr = format!("{consist}");
+    assert!(repr.starts_with("Consist("));
+    assert!(repr.contains(&format!("n_locos={}", consist.loco_vec.len())));
+    assert!(repr.contains(&format!(
+        "pwr_rated_kilowatts={:.1}",
+        consist.pwr_rated().get::<si::kilowatt>()
+    )));
+}
+
+#[test]
+fn test_set_initial_soc() {
+    let mut consist = Consist::default();
+    let soc = 0.6 * uc::R;
+    consist.set_initial_soc(soc).unwrap();
+    let mut n_res = 0;
+    for loco in consist.loco_vec.iter() {
+        if let Some(res) = loco.reversible_energy_storage() {
+            assert_eq!(*res.state.soc.get_unchecked(|| format_dbg!()).unwrap(), soc);
+            n_res += 1;
+        }
+    }
+    assert_eq!(n_res, consist.n_res_equipped());
+
+    // out of range SOC errors and leaves all locomotives unmodified
+    let consist_prev = consist.clone();
+    assert!(consist.set_initial_soc(1.1 * uc::R).is_err());
+    assert!(consist.set_initial_soc(-0.1 * uc::R).is_err());
+    assert_eq!(consist, consist_prev);
+}
+
+#[test]
+fn test_energy_by_loco_type() {
+    let mut consist = Consist::default();
+    consist.check_and_reset(|| format_dbg!()).unwrap();
+    consist.set_pwr_aux(Some(true)).unwrap();
+    consist
+        .set_curr_pwr_max_out(
+            None,
+            None,
+            Some(5e6 * uc::LB),
+            Some(10.0 * uc::MPH),
+            None,
+            1.0 * uc::S,
+        )
+        .unwrap();
+    consist
+        .solve_energy_consumption(
+            uc::W * 1e6,
+            Some(5e6 * uc::LB),
+            Some(10.0 * uc::MPH),
+            uc::S * 1.0,
+            Some(true),
+        )
+        .unwrap();
+    consist.set_cumulative(uc::S, || format_dbg!()).unwrap();
+
+    let energy_by_loco_type = consist.energy_by_loco_type().unwrap();
+    let (fuel_conv, res_conv) = energy_by_loco_type["ConventionalLoco"];
+    assert!(fuel_conv > si::Energy::ZERO);
+    assert_eq!(res_conv, si::Energy::ZERO);
+    let (fuel_bel, res_bel) = energy_by_loco_type["BatteryElectricLoco"];
+    assert_eq!(fuel_bel, si::Energy::ZERO);
+    assert!(res_bel > si::Energy::ZERO);
+
+    let fuel_total = energy_by_loco_type
+        .values()
+        .fold(si::Energy::ZERO, |acc, (fuel, _)| acc + *fuel);
+    assert!(almost_eq_uom(
+        &fuel_total,
+        &consist.get_energy_fuel().unwrap(),
+        None
+    ));
+    // all fuel converters default to diesel
+    assert!(almost_eq_uom(
+        &consist.get_co2_mass().unwrap(),
+        &(fuel_total * FuelType::default().co2_intensity),
+        None
+    ));
+}
+
+#[test]
+fn test_check_save_interval_consistency() {
+    let mut consist = Consist::default();
+    consist.set_save_interval(Some(1));
+    consist.check_save_interval_consistency().unwrap();
+
+    // loco-level mismatch
+    let mut consist_bad = consist.clone();
+    consist_bad.loco_vec[1].set_save_interval(Some(2));
+    let err = consist_bad.check_save_interval_consistency().unwrap_err();
+    assert!(err.to_string().contains("loco_vec[1]"), "{err}");
+
+    // component-level mismatch
+    let mut consist_bad = consist.clone();
+    match &mut consist_bad.loco_vec[0].loco_type {
+        PowertrainType::ConventionalLoco(loco) => loco.fc.save_interval = None,
+        PowertrainType::HybridLoco(loco) => loco.fc.save_interval = None,
+        PowertrainType::BatteryElectricLoco(loco) => loco.res.save_interval = None,
+        PowertrainType::DummyLoco(_) => unreachable!(),
+    }
+    let err = consist_bad.check_save_interval_consistency().unwrap_err();
+    assert!(err.to_string().contains("loco_vec[0]."), "{err}");
+
+    // re-propagating restores consistency
+    consist_bad.set_save_interval(Some(1));
+    consist_bad.check_save_interval_consistency().unwrap();
+}
+
+#[test]
+fn test_empty_consist_init() {
+    let mut consist = Consist::new(vec![], None, Default::default());
+    let err = consist.init().unwrap_err();
+    assert!(err.to_string().contains("`loco_vec`"), "{err}");
+}
+
+#[test]
+fn test_insert_remove_locomotive() {
+    let mut consist = Consist::new(
+        vec![Locomotive::default(), Locomotive::default()],
+        Some(1),
+        PowerDistributionControlType::default(),
+    );
+    assert_eq!(consist.n_res_equipped(), 0);
+
+    consist
+        .insert_locomotive(1, Locomotive::default_battery_electric_loco())
+        .unwrap();
+    assert_eq!(consist.loco_vec.len(), 3);
+    assert_eq!(consist.n_res_equipped(), 1);
+    assert!(consist.loco_vec[1].reversible_energy_storage().is_some());
+    consist.check_save_interval_consistency().unwrap();
+    assert!(consist.insert_locomotive(4, Locomotive::default()).is_err());
+
+    let bel = consist.remove_locomotive(1).unwrap();
+    assert!(bel.reversible_energy_storage().is_some());
+    assert_eq!(consist.loco_vec.len(), 2);
+    assert_eq!(consist.n_res_equipped(), 0);
+    assert!(consist.remove_locomotive(2).is_err());
+
+    consist.remove_locomotive(0).unwrap();
+    assert_eq!(consist.loco_vec.len(), 1);
+    assert!(consist
+        .remove_locomotive(0)
+        .unwrap_err()
+        .to_string()
+        .contains("Cannot remove the last locomotive"));
+    assert_eq!(consist.loco_vec.len(), 1);
+}
+
+#[test]
+fn test_dummy_loco_energy() {
+    let mut consist = Consist::new(
+        vec![Locomotive::build_dummy_loco()],
+        Some(1),
+        PowerDistributionControlType::default(),
+    );
+    consist.init().unwrap();
+    let dt = 1.0 * uc::S;
+    for pwr_out_req in [1e6 * uc::W, -0.5e6 * uc::W] {
+        solve_step(&mut consist, dt, |_| pwr_out_req);
+    }
+
+    let energy_fuel = consist.get_energy_fuel().unwrap();
+    assert!(energy_fuel.is_finite());
+    assert_eq!(energy_fuel, si::Energy::ZERO);
+    assert_eq!(consist.get_net_energy_res().unwrap(), si::Energy::ZERO);
+    for energy in [&consist.state.energy_fuel, &consist.state.energy_reves] {
+        assert_eq!(
+            *energy.get_fresh(|| format_dbg!()).unwrap(),
+            si::Energy::ZERO
+        );
+    }
+}
+
+#[test]
+/// Dynamic braking capability fades on a sustained descent as the resistor
+/// grids heat up, and recovers once braking stops.
+fn test_dyn_brake_fade() {
+    let mut consist = Consist::new(
+        vec![Locomotive::default(), Locomotive::default()],
+        Some(1),
+        PowerDistributionControlType::default(),
+    );
+    consist.init().unwrap();
+    consist
+        .set_dyn_brake_thermal(Some(DynBrakeThermal::default()))
+        .unwrap();
+    let dt = 1.0 * uc::S;
+    let pwr_dyn_brake_max_init = *consist
+        .state
+        .pwr_dyn_brake_max
+        .get_fresh(|| format_dbg!())
+        .unwrap();
+    // steps consist with braking demand `pwr_brake_demand`, limited to
+    // current capability, and returns braking capability
+    let mut step = |pwr_brake_demand: si::Power| -> si::Power {
+        let mut pwr_dyn_brake_max = si::Power::ZERO;
+        solve_step(&mut consist, dt, |consist| {
+            pwr_dyn_brake_max = *consist
+                .state
+                .pwr_dyn_brake_max
+                .get_fresh(|| format_dbg!())
+                .unwrap();
+            -pwr_brake_demand.min(pwr_dyn_brake_max)
+        });
+        pwr_dyn_brake_max
+    };
+
+    // 20 minutes of heavy braking
+    let pwr_brake_demand = 0.9 * pwr_dyn_brake_max_init;
+    let pwr_dyn_brake_max_descent: Vec<si::Power> =
+        (0..1_200).map(|_| step(pwr_brake_demand)).collect();
+    // full capability while grids are cool, then fades without recovering
+    assert_eq!(pwr_dyn_brake_max_descent[0], pwr_dyn_brake_max_init);
+    assert!(pwr_dyn_brake_max_descent
+        .windows(2)
+        .all(|w| w[1] <= w[0] * (1.0 + 1e-9)));
+    let pwr_dyn_brake_max_faded = *pwr_dyn_brake_max_descent.last().unwrap();
+    assert!(pwr_dyn_brake_max_faded < pwr_brake_demand);
+
+    // grids cool with no braking
+    let mut pwr_dyn_brake_max_cooled = si::Power::ZERO;
+    for _ in 0..600 {
+        pwr_dyn_brake_max_cooled = step(si::Power::ZERO);
+    }
+    assert!(pwr_dyn_brake_max_cooled > pwr_dyn_brake_max_faded);
+
+    // disabling fade restores full capability
+    consist.set_dyn_brake_thermal(None).unwrap();
+    assert_eq!(
+        *consist
+            .state
+            .pwr_dyn_brake_max
+            .get_fresh(|| format_dbg!())
+            .unwrap(),
+        pwr_dyn_brake_max_init
+    );
+}
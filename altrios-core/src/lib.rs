@@ -33,6 +33,7 @@ pub mod track;
 pub mod train;
 pub mod traits;
 pub mod uc;
+pub mod units;
 pub mod utils;
 pub mod validate;
 
@@ -2,23 +2,83 @@ use crate::combo_error::ComboErrors;
 use crate::train::LinkIdxTime;
 
 use super::disp_imports::*;
+#[cfg(feature = "pyo3")]
+use super::dispatch_result::DispatchResult;
 use super::train_disp::{FreePathStatus, TrainDisp};
 use crate::train::TimedLinkPath;
 
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[repr(u8)]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+/// Objective used to decide which train gets to move first when several trains
+/// are ready to be dispatched at the same time (e.g. at a meet/pass)
+pub enum DispatchObjective {
+    /// Trains ready at the same time are dispatched in train order
+    #[default]
+    MinTime = 0,
+    /// Trains are dispatched in order of time less a time-equivalent restart
+    /// penalty, i.e. estimated restart energy at the hold point (see
+    /// [est_restart_energy]) divided by the rated power of the train's
+    /// consist, so that trains that are costly to restart are kept moving and
+    /// others are held
+    MinEnergy = 1,
+}
+
+/// Estimates the energy wasted by holding a train of `mass`, i.e. the energy
+/// required to accelerate it at `acc_startup` from a stop back up to `speed`
+/// while climbing `grade`.  Downgrades are treated as level track.
+pub fn est_restart_energy(
+    mass: si::Mass,
+    grade: si::Ratio,
+    speed: si::Velocity,
+    acc_startup: si::Acceleration,
+) -> si::Energy {
+    let dist_startup = speed * speed / (2.0 * acc_startup);
+    mass * (0.5 * speed * speed + uc::ACC_GRAV * grade.max(si::Ratio::ZERO) * dist_startup)
+}
+
+/// Grade at the entry of `link`, where a train held at the link entry would
+/// restart, or zero if `link` has no elevation profile
+fn grade_at_entry(link: &Link) -> si::Ratio {
+    match link.elevs.as_slice() {
+        [first, second, ..] if second.offset > first.offset => {
+            (second.elev - first.elev) / (second.offset - first.offset)
+        }
+        _ => si::Ratio::ZERO,
+    }
+}
+
 #[readonly::make]
 #[derive(Debug, PartialEq, Clone, Copy)]
 struct TrainDispNext {
     pub time: si::Time,
     pub train_idx: TrainIdx,
+    /// Time-equivalent penalty for restarting the train if held, which
+    /// advances its priority ahead of [Self::time]; zero unless dispatching
+    /// with [DispatchObjective::MinEnergy]
+    pub time_restart: si::Time,
 }
 impl TrainDispNext {
-    pub fn new(time: si::Time, train_idx: TrainIdx) -> Self {
+    pub fn new(time: si::Time, train_idx: TrainIdx, time_restart: si::Time) -> Self {
         assert!(!time.is_nan());
         assert!(train_idx.is_some());
-        TrainDispNext { time, train_idx }
+        assert!(!time_restart.is_nan());
+        TrainDispNext {
+            time,
+            train_idx,
+            time_restart,
+        }
+    }
+    pub fn from_train_disp(train_disp: &TrainDisp, time_restart: si::Time) -> Self {
+        Self::new(
+            train_disp.time_update(),
+            train_disp.train_idx(),
+            time_restart,
+        )
     }
-    pub fn from_train_disp(train_disp: &TrainDisp) -> Self {
-        Self::new(train_disp.time_update(), train_disp.train_idx())
+    /// Time used for ordering, i.e. [Self::time] less [Self::time_restart]
+    pub fn time_priority(&self) -> si::Time {
+        self.time - self.time_restart
     }
 }
 
@@ -33,9 +93,10 @@ impl Eq for TrainDispNext {}
 impl Ord for TrainDispNext {
     fn cmp(&self, other: &Self) -> Ordering {
         other
-            .time
-            .partial_cmp(&self.time)
+            .time_priority()
+            .partial_cmp(&self.time_priority())
             .unwrap()
+            .then_with(|| other.time.partial_cmp(&self.time).unwrap())
             .then_with(|| other.train_idx.cmp(&self.train_idx))
     }
 }
@@ -96,6 +157,7 @@ pub fn run_dispatch<N: AsRef<[Link]>>(
     est_time_nets: Vec<EstTimeNet>,
     print_train_move: bool,
     print_train_exit: bool,
+    objective: DispatchObjective,
 ) -> anyhow::Result<Vec<Vec<LinkIdxTime>>> {
     let network = network.as_ref();
     let train_count = speed_limit_train_sims.len();
@@ -106,13 +168,33 @@ pub fn run_dispatch<N: AsRef<[Link]>>(
     }
 
     let train_idx_width = (train_count as f64).log(10.0).floor() as usize + 1;
+    let acc_startup = 0.5 * uc::MPH / uc::S;
     let mut train_disps = Vec::with_capacity(train_count + 1);
     train_disps.push(TrainDisp::default());
+    let mut masses = Vec::with_capacity(train_count + 1);
+    masses.push(si::Mass::ZERO);
+    let mut pwrs_rated = Vec::with_capacity(train_count + 1);
+    pwrs_rated.push(si::Power::ZERO);
     for (idx, (slts, est_time_net)) in speed_limit_train_sims
         .iter()
         .zip(est_time_nets.into_iter())
         .enumerate()
     {
+        masses.push(
+            slts.state
+                .mass()
+                .with_context(|| format_dbg!())?
+                .with_context(|| format!("{}\nExpected `Some`", format_dbg!()))?,
+        );
+        let pwr_rated = slts.loco_con.pwr_rated();
+        ensure!(
+            objective == DispatchObjective::MinTime || pwr_rated > si::Power::ZERO,
+            "{}\nTrain `{}` must have positive rated power for `{:?}`",
+            format_dbg!(),
+            slts.train_id,
+            objective
+        );
+        pwrs_rated.push(pwr_rated);
         train_disps.push(TrainDisp::new(
             slts.train_id.clone(),
             (idx + 1).try_from_idx()?,
@@ -120,11 +202,33 @@ pub fn run_dispatch<N: AsRef<[Link]>>(
             8.0 * uc::MIN,
             30.0 * uc::MI,
             10.0 * uc::MI,
-            0.5 * uc::MPH / uc::S,
+            acc_startup,
             est_time_net,
         )?);
     }
 
+    // time-equivalent restart penalty at the point where the train would be
+    // held, i.e. the time the train's consist would need at rated power to
+    // supply the restart energy, evaluated each time the train is queued
+    let time_restart = |train_disp: &TrainDisp| -> si::Time {
+        match objective {
+            DispatchObjective::MinTime => si::Time::ZERO,
+            DispatchObjective::MinEnergy => {
+                let train_idx = train_disp.train_idx().idx();
+                let (link_idx, speed) = train_disp.hold_point();
+                est_restart_energy(
+                    masses[train_idx],
+                    network
+                        .get(link_idx.idx())
+                        .map(grade_at_entry)
+                        .unwrap_or_default(),
+                    speed,
+                    acc_startup,
+                ) / pwrs_rated[train_idx]
+            }
+        }
+    };
+
     let mut link_disp_auths = vec![
         vec![DispAuth {
             arrive_entry: f64::NEG_INFINITY * uc::S,
@@ -144,7 +248,10 @@ pub fn run_dispatch<N: AsRef<[Link]>>(
     let mut train_disp_queue = BinaryHeap::with_capacity(train_disps.len());
     for (idx, train_disp) in train_disps.iter().enumerate().skip(1) {
         assert!(train_disp.train_idx().idx() == idx);
-        train_disp_queue.push(TrainDispNext::from_train_disp(train_disp));
+        train_disp_queue.push(TrainDispNext::from_train_disp(
+            train_disp,
+            time_restart(train_disp),
+        ));
     }
 
     let mut train_idxs_blocked = Vec::with_capacity(train_disps.len() / 2);
@@ -233,16 +340,17 @@ pub fn run_dispatch<N: AsRef<[Link]>>(
         // Otherwise, add it and all currently blocked trains back to the queue
         else {
             if !train_curr.is_finished() {
-                train_disp_queue.push(TrainDispNext {
-                    time: train_curr.time_update(),
-                    train_idx: train_idx_curr,
-                });
+                train_disp_queue.push(TrainDispNext::from_train_disp(
+                    train_curr,
+                    time_restart(train_curr),
+                ));
             }
             train_idxs_blocked.drain(..).for_each(|train_idx| {
-                train_disp_queue.push(TrainDispNext {
-                    time: train_disps[train_idx.idx()].time_update(),
-                    train_idx,
-                });
+                let train_disp = &train_disps[train_idx.idx()];
+                train_disp_queue.push(TrainDispNext::from_train_disp(
+                    train_disp,
+                    time_restart(train_disp),
+                ));
                 debug_assert!(train_idx != train_idx_curr);
             });
         }
@@ -259,13 +367,29 @@ pub fn run_dispatch<N: AsRef<[Link]>>(
 
 #[cfg(feature = "pyo3")]
 #[cfg_attr(feature = "pyo3", pyfunction(name = "run_dispatch"))]
+#[allow(clippy::too_many_arguments)]
+#[pyo3(signature = (
+    network,
+    speed_limit_train_sims,
+    est_time_vec,
+    print_train_move,
+    print_train_exit,
+    return_dispatch_result=false,
+    objective=None,
+))]
+/// Runs dispatch and returns a list of [TimedLinkPath], or a [DispatchResult]
+/// if `return_dispatch_result` is true.  `objective` defaults to
+/// [DispatchObjective::MinTime].
 pub fn run_dispatch_py(
+    py: Python<'_>,
     network: &Bound<PyAny>,
     speed_limit_train_sims: crate::train::SpeedLimitTrainSimVec,
     est_time_vec: Vec<EstTimeNet>,
     print_train_move: bool,
     print_train_exit: bool,
-) -> anyhow::Result<Vec<TimedLinkPath>> {
+    return_dispatch_result: bool,
+    objective: Option<DispatchObjective>,
+) -> anyhow::Result<PyObject> {
     let network = match network.extract::<Network>() {
         Ok(n) => n,
         Err(_) => {
@@ -276,16 +400,34 @@ pub fn run_dispatch_py(
         }
     };
 
-    Ok(run_dispatch(
-        network,
+    let est_time_nets = if return_dispatch_result {
+        Some(est_time_vec.clone())
+    } else {
+        None
+    };
+    let timed_paths = run_dispatch(
+        &network,
         &speed_limit_train_sims.0,
         est_time_vec,
         print_train_move,
         print_train_exit,
-    )?
-    .iter()
-    .map(TimedLinkPath::from)
-    .collect::<Vec<TimedLinkPath>>())
+        objective.unwrap_or_default(),
+    )?;
+
+    match est_time_nets {
+        Some(est_time_nets) => Ok(DispatchResult::new(
+            &network,
+            &speed_limit_train_sims.0,
+            &est_time_nets,
+            &timed_paths,
+        )?
+        .into_py(py)),
+        None => Ok(timed_paths
+            .iter()
+            .map(TimedLinkPath::from)
+            .collect::<Vec<TimedLinkPath>>()
+            .into_py(py)),
+    }
 }
 
 #[cfg(test)]
@@ -295,7 +437,7 @@ mod test_dispatch {
 
     #[test]
     fn test_empty_dispatch() {
-        let output = run_dispatch(&[], &[], vec![], false, false).unwrap();
+        let output = run_dispatch(&[], &[], vec![], false, false, Default::default()).unwrap();
         assert!(output.is_empty());
     }
 
@@ -332,6 +474,98 @@ mod test_dispatch {
             .iter()
             .map(|slts| make_est_times(slts.clone(), &network, None).unwrap().0)
             .collect::<Vec<EstTimeNet>>();
-        let _output = run_dispatch(&network, &train_sims, est_time_vec, true, true).unwrap();
+        let _output = run_dispatch(
+            &network,
+            &train_sims,
+            est_time_vec,
+            true,
+            true,
+            DispatchObjective::MinTime,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_est_restart_energy() {
+        let acc_startup = 0.5 * uc::MPH / uc::S;
+        let speed = 40.0 * uc::MPH;
+        let grade = 0.01 * uc::R;
+        let energy_light = est_restart_energy(5e3 * uc::TON, grade, speed, acc_startup);
+        let energy_heavy = est_restart_energy(15e3 * uc::TON, grade, speed, acc_startup);
+        assert!(energy_heavy > energy_light);
+        // downgrades are treated as level track
+        assert_eq!(
+            est_restart_energy(5e3 * uc::TON, -grade, speed, acc_startup),
+            est_restart_energy(5e3 * uc::TON, si::Ratio::ZERO, speed, acc_startup)
+        );
+    }
+
+    #[test]
+    fn test_grade_at_entry() {
+        use crate::track::Elev;
+
+        let mut link = Link::valid();
+        link.elevs = vec![
+            Elev::new(si::Length::ZERO, 100.0 * uc::M),
+            Elev::new(1000.0 * uc::M, 110.0 * uc::M),
+            Elev::new(link.length, 110.0 * uc::M),
+        ];
+        assert!(almost_eq(
+            grade_at_entry(&link).get::<si::ratio>(),
+            0.01,
+            None
+        ));
+        link.elevs.truncate(1);
+        assert_eq!(grade_at_entry(&link), si::Ratio::ZERO);
+    }
+
+    #[test]
+    fn test_min_energy_dispatch() {
+        let network_file_path = project_root::get_project_root()
+            .unwrap()
+            .join("python/altrios/resources/networks/Taconite.yaml");
+        let network = Network::from_file(network_file_path, false).unwrap();
+
+        let mut train_sim_heavy = crate::train::speed_limit_train_sim_fwd();
+        let mass_heavy = 2.0 * train_sim_heavy.state.mass().unwrap().unwrap();
+        train_sim_heavy
+            .state
+            .mass_static
+            .update_unchecked(mass_heavy, || format_dbg!())
+            .unwrap();
+        // light train departs shortly before the heavy train reaches the
+        // siding where it waits under `MinTime`
+        let mut train_sim_light = crate::train::speed_limit_train_sim_rev();
+        train_sim_light
+            .state
+            .time
+            .update_unchecked(1420.0 * uc::S, || format_dbg!())
+            .unwrap();
+        let train_sims = vec![train_sim_heavy, train_sim_light];
+        let est_time_vec = train_sims
+            .iter()
+            .map(|slts| make_est_times(slts.clone(), &network, None).unwrap().0)
+            .collect::<Vec<EstTimeNet>>();
+
+        let timed_paths = |objective: DispatchObjective| {
+            run_dispatch(
+                &network,
+                &train_sims,
+                est_time_vec.clone(),
+                false,
+                false,
+                objective,
+            )
+            .unwrap()
+        };
+        let timed_paths_energy = timed_paths(DispatchObjective::MinEnergy);
+        let timed_paths_time = timed_paths(DispatchObjective::MinTime);
+        let time_arrive = |timed_path: &Vec<LinkIdxTime>| timed_path.last().unwrap().time;
+        // the heavy train meets the light train, which departs while the heavy
+        // train is moving, so under `MinTime` the heavy train is held for the
+        // light train, whereas under `MinEnergy` it keeps the main and the
+        // light train is held instead
+        assert!(time_arrive(&timed_paths_energy[0]) < time_arrive(&timed_paths_time[0]));
+        assert!(time_arrive(&timed_paths_energy[1]) > time_arrive(&timed_paths_time[1]));
     }
 }
@@ -0,0 +1,367 @@
+use super::disp_imports::*;
+use crate::train::{LinkIdxTime, TimedLinkPath};
+
+#[serde_api]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Dispatch outcome for a single train
+pub struct TrainDispatchSummary {
+    pub train_id: String,
+    /// Time at which the train enters the first link of its path
+    pub time_depart: si::Time,
+    /// Time at which the train enters the last link of its path
+    pub time_arrive: si::Time,
+    /// Running time between the first and last link of the path assuming no
+    /// delays, per [EstTimeNet], if both links are in the [EstTimeNet]
+    pub time_running_est: Option<si::Time>,
+    /// Dispatched running time in excess of [Self::time_running_est]
+    pub time_delay: Option<si::Time>,
+    /// Dispatched path with link entry times
+    pub timed_path: TimedLinkPath,
+    /// Fuel energy consumed by the consist, populated by [DispatchResult::set_energy]
+    #[serde(default)]
+    pub energy_fuel: Option<si::Energy>,
+    /// Net chemical energy drawn from reversible energy storage by the consist,
+    /// populated by [DispatchResult::set_energy]
+    #[serde(default)]
+    pub energy_res: Option<si::Energy>,
+    /// CO2 emitted by the consist, populated by [DispatchResult::set_energy]
+    #[serde(default)]
+    pub co2_mass: Option<si::Mass>,
+}
+
+#[pyo3_api]
+impl TrainDispatchSummary {}
+
+impl Init for TrainDispatchSummary {}
+impl SerdeAPI for TrainDispatchSummary {}
+
+impl TrainDispatchSummary {
+    /// Dispatched running time from the first to the last link of the path
+    pub fn time_running(&self) -> si::Time {
+        self.time_arrive - self.time_depart
+    }
+}
+
+#[serde_api]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Meet between two trains travelling in opposite directions
+pub struct Meet {
+    pub train_id_a: String,
+    pub train_id_b: String,
+    /// First link on the path of train A that train B had already traversed in
+    /// the opposite direction when train A entered it
+    pub link_idx: LinkIdx,
+    /// Time at which train A enters [Self::link_idx]
+    pub time: si::Time,
+}
+
+#[pyo3_api]
+impl Meet {}
+
+impl Init for Meet {}
+impl SerdeAPI for Meet {}
+
+#[serde_api]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Scenario-level outcome of [super::dispatch::run_dispatch]
+pub struct DispatchResult {
+    /// Per-train summaries in the same order as the dispatched trains
+    pub train_summaries: Vec<TrainDispatchSummary>,
+    /// Meets between trains travelling in opposite directions
+    pub meets: Vec<Meet>,
+    /// Sum of dispatched running times across all trains
+    pub time_running_total: si::Time,
+    /// Sum of delays across all trains with a known delay
+    pub time_delay_total: si::Time,
+    /// Total fuel energy across all trains, populated by [Self::set_energy]
+    #[serde(default)]
+    pub energy_fuel_total: Option<si::Energy>,
+    /// Total net reversible energy storage energy across all trains, populated
+    /// by [Self::set_energy]
+    #[serde(default)]
+    pub energy_res_total: Option<si::Energy>,
+    /// Total CO2 emitted across all trains, populated by [Self::set_energy]
+    #[serde(default)]
+    pub co2_mass_total: Option<si::Mass>,
+}
+
+#[pyo3_api]
+impl DispatchResult {
+    #[pyo3(name = "set_energy")]
+    fn set_energy_py(
+        &mut self,
+        speed_limit_train_sims: crate::train::SpeedLimitTrainSimVec,
+    ) -> anyhow::Result<()> {
+        self.set_energy(&speed_limit_train_sims.0)
+    }
+
+    #[getter("meet_count")]
+    fn get_meet_count_py(&self) -> usize {
+        self.meets.len()
+    }
+}
+
+impl Init for DispatchResult {}
+impl SerdeAPI for DispatchResult {}
+
+impl DispatchResult {
+    /// Assembles the result of a dispatch
+    /// # Arguments
+    /// - `network`: network on which trains were dispatched
+    /// - `speed_limit_train_sims`: dispatched trains
+    /// - `est_time_nets`: estimated time networks passed to [super::dispatch::run_dispatch]
+    /// - `timed_paths`: output of [super::dispatch::run_dispatch]
+    pub fn new<N: AsRef<[Link]>>(
+        network: N,
+        speed_limit_train_sims: &[SpeedLimitTrainSim],
+        est_time_nets: &[EstTimeNet],
+        timed_paths: &[Vec<LinkIdxTime>],
+    ) -> anyhow::Result<Self> {
+        let network = network.as_ref();
+        ensure!(
+            speed_limit_train_sims.len() == est_time_nets.len()
+                && speed_limit_train_sims.len() == timed_paths.len(),
+            "{}\n`speed_limit_train_sims`, `est_time_nets`, and `timed_paths` must have equal lengths",
+            format_dbg!()
+        );
+
+        let train_summaries = speed_limit_train_sims
+            .iter()
+            .zip(est_time_nets)
+            .zip(timed_paths)
+            .map(|((slts, est_time_net), timed_path)| {
+                let (first, last) =
+                    timed_path.first().zip(timed_path.last()).with_context(|| {
+                        format!(
+                            "{}\nTimed path for train {} is empty",
+                            format_dbg!(),
+                            slts.train_id
+                        )
+                    })?;
+                let time_running_est = est_time_net
+                    .est_time_at_link(last.link_idx)
+                    .zip(est_time_net.est_time_at_link(first.link_idx))
+                    .map(|(time_last, time_first)| time_last - time_first);
+                Ok(TrainDispatchSummary {
+                    train_id: slts.train_id.clone(),
+                    time_depart: first.time,
+                    time_arrive: last.time,
+                    time_running_est,
+                    time_delay: time_running_est
+                        .map(|time_est| (last.time - first.time - time_est).max(si::Time::ZERO)),
+                    timed_path: TimedLinkPath::new(timed_path.clone()),
+                    energy_fuel: None,
+                    energy_res: None,
+                    co2_mass: None,
+                })
+            })
+            .collect::<anyhow::Result<Vec<TrainDispatchSummary>>>()?;
+
+        let mut meets = vec![];
+        for (idx_a, train_a) in train_summaries.iter().enumerate() {
+            for train_b in train_summaries.iter().skip(idx_a + 1) {
+                if let Some(meet) = find_meet(network, train_a, train_b)? {
+                    meets.push(meet);
+                }
+            }
+        }
+        meets.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap_or(Ordering::Equal));
+
+        Ok(Self {
+            time_running_total: train_summaries
+                .iter()
+                .fold(si::Time::ZERO, |acc, ts| acc + ts.time_running()),
+            time_delay_total: train_summaries
+                .iter()
+                .filter_map(|ts| ts.time_delay)
+                .fold(si::Time::ZERO, |acc, delay| acc + delay),
+            train_summaries,
+            meets,
+            energy_fuel_total: None,
+            energy_res_total: None,
+            co2_mass_total: None,
+        })
+    }
+
+    /// Populates per-train and total energy and CO2 from `speed_limit_train_sims`, which
+    /// must have been simulated along the dispatched paths, e.g. via
+    /// `run_speed_limit_train_sims`.  Trains are matched by `train_id`.
+    pub fn set_energy(
+        &mut self,
+        speed_limit_train_sims: &[SpeedLimitTrainSim],
+    ) -> anyhow::Result<()> {
+        for ts in self.train_summaries.iter_mut() {
+            let slts = speed_limit_train_sims
+                .iter()
+                .find(|slts| slts.train_id == ts.train_id)
+                .with_context(|| {
+                    format!(
+                        "{}\nNo train sim provided for train {}",
+                        format_dbg!(),
+                        ts.train_id
+                    )
+                })?;
+            ts.energy_fuel = Some(
+                *slts
+                    .loco_con
+                    .state
+                    .energy_fuel
+                    .get_unchecked(|| format_dbg!())?,
+            );
+            ts.energy_res = Some(
+                *slts
+                    .loco_con
+                    .state
+                    .energy_reves
+                    .get_unchecked(|| format_dbg!())?,
+            );
+            ts.co2_mass = Some(
+                slts.loco_con
+                    .get_co2_mass()
+                    .with_context(|| format_dbg!())?,
+            );
+        }
+        self.energy_fuel_total = Some(
+            self.train_summaries
+                .iter()
+                .filter_map(|ts| ts.energy_fuel)
+                .fold(si::Energy::ZERO, |acc, energy| acc + energy),
+        );
+        self.energy_res_total = Some(
+            self.train_summaries
+                .iter()
+                .filter_map(|ts| ts.energy_res)
+                .fold(si::Energy::ZERO, |acc, energy| acc + energy),
+        );
+        self.co2_mass_total = Some(
+            self.train_summaries
+                .iter()
+                .filter_map(|ts| ts.co2_mass)
+                .fold(si::Mass::ZERO, |acc, co2_mass| acc + co2_mass),
+        );
+        Ok(())
+    }
+}
+
+/// Returns the meet between `train_a` and `train_b`, if they travel over
+/// common track in opposite directions and pass each other while doing so
+fn find_meet(
+    network: &[Link],
+    train_a: &TrainDispatchSummary,
+    train_b: &TrainDispatchSummary,
+) -> anyhow::Result<Option<Meet>> {
+    let times_b: HashMap<LinkIdx, si::Time> = train_b
+        .timed_path
+        .0
+        .iter()
+        .map(|lit| (lit.link_idx, lit.time))
+        .collect();
+    let mut b_ahead = false;
+    for lit_a in &train_a.timed_path.0 {
+        let link = network.get(lit_a.link_idx.idx()).with_context(|| {
+            format!(
+                "{}\n`network` does not contain link {:?}",
+                format_dbg!(),
+                lit_a.link_idx
+            )
+        })?;
+        if let Some(time_b) = times_b.get(&link.idx_flip) {
+            if *time_b > lit_a.time {
+                // train B has yet to reach this link
+                b_ahead = true;
+            } else if b_ahead {
+                return Ok(Some(Meet {
+                    train_id_a: train_a.train_id.clone(),
+                    train_id_b: train_b.train_id.clone(),
+                    link_idx: lit_a.link_idx,
+                    time: lit_a.time,
+                }));
+            } else {
+                // train B traversed the common track before train A arrived
+                return Ok(None);
+            }
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::meet_pass::dispatch::run_dispatch;
+
+    #[test]
+    fn test_dispatch_result() {
+        let network_file_path = project_root::get_project_root()
+            .unwrap()
+            .join("python/altrios/resources/networks/Taconite.yaml");
+        let network = Network::from_file(network_file_path, false).unwrap();
+
+        let mut train_sims = vec![
+            crate::train::speed_limit_train_sim_fwd(),
+            crate::train::speed_limit_train_sim_rev(),
+        ];
+        // `set_energy` matches trains by id
+        for (idx, slts) in train_sims.iter_mut().enumerate() {
+            slts.train_id = idx.to_string();
+        }
+        let est_time_nets = train_sims
+            .iter()
+            .map(|slts| make_est_times(slts.clone(), &network, None).unwrap().0)
+            .collect::<Vec<EstTimeNet>>();
+        let timed_paths = run_dispatch(
+            &network,
+            &train_sims,
+            est_time_nets.clone(),
+            false,
+            false,
+            Default::default(),
+        )
+        .unwrap();
+
+        let dispatch_result =
+            DispatchResult::new(&network, &train_sims, &est_time_nets, &timed_paths).unwrap();
+        assert_eq!(dispatch_result.train_summaries.len(), train_sims.len());
+        for ts in &dispatch_result.train_summaries {
+            assert!(ts.time_running() > si::Time::ZERO);
+        }
+        assert!(dispatch_result.time_running_total > si::Time::ZERO);
+        assert!(dispatch_result.energy_fuel_total.is_none());
+        assert!(dispatch_result.co2_mass_total.is_none());
+
+        let dispatch_result_rt =
+            DispatchResult::from_yaml(dispatch_result.to_yaml().unwrap(), false).unwrap();
+        assert_eq!(dispatch_result_rt, dispatch_result);
+
+        let mut dispatch_result = dispatch_result;
+        let train_sims_walked = train_sims
+            .into_iter()
+            .zip(&timed_paths)
+            .map(|(mut slts, timed_path)| {
+                slts.walk_timed_path(&network, timed_path).unwrap();
+                slts
+            })
+            .collect::<Vec<SpeedLimitTrainSim>>();
+        dispatch_result.set_energy(&train_sims_walked).unwrap();
+        for (ts, slts) in dispatch_result
+            .train_summaries
+            .iter()
+            .zip(&train_sims_walked)
+        {
+            let co2_mass = ts.co2_mass.unwrap();
+            assert!(co2_mass > si::Mass::ZERO);
+            assert_eq!(co2_mass, slts.loco_con.get_co2_mass().unwrap());
+        }
+        assert!(almost_eq_uom(
+            &dispatch_result.co2_mass_total.unwrap(),
+            &train_sims_walked
+                .iter()
+                .map(|slts| slts.loco_con.get_co2_mass().unwrap())
+                .fold(si::Mass::ZERO, |acc, co2_mass| acc + co2_mass),
+            None
+        ));
+    }
+}
@@ -61,7 +61,8 @@ impl SavedSim {
                 *train_sim.state.offset.get_fresh(|| format_dbg!())?,
                 *train_sim.state.speed.get_fresh(|| format_dbg!())?,
                 train_sim.fric_brake.ramp_up_time * train_sim.fric_brake.ramp_up_coeff,
-            );
+                train_sim.speed_violation_policy,
+            )?;
             Ok(speed_target > si::Velocity::ZERO
                 || (
                     train_sim.is_finished()
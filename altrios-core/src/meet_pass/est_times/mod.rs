@@ -3,6 +3,7 @@
 use super::disp_imports::*;
 use crate::consist::Consist;
 use crate::track::Network;
+use polars::prelude::{Column, DataFrame};
 use uc::SPEED_DIFF_JOIN;
 use uc::TIME_NAN;
 
@@ -74,6 +75,24 @@ impl EstTimeNet {
         (self.val.last().unwrap().time_sched - self.val.first().unwrap().time_sched)
             .get::<si::hour>()
     }
+
+    #[pyo3(name = "est_time_at_link_seconds")]
+    /// Estimated arrival time at `link_idx`, or `None` if the network never reaches it
+    pub fn est_time_at_link_py(&self, link_idx: LinkIdx) -> Option<f64> {
+        self.est_time_at_link(link_idx)
+            .map(|time| time.get::<si::second>())
+    }
+
+    #[getter("total_time_seconds")]
+    pub fn get_total_time_py(&self) -> f64 {
+        self.total_time().get::<si::second>()
+    }
+
+    #[pyo3(name = "to_dataframe")]
+    /// Returns DataFrame with one row per estimated time node
+    pub fn to_dataframe_py(&self) -> anyhow::Result<pyo3_polars::PyDataFrame> {
+        Ok(pyo3_polars::PyDataFrame(self.to_dataframe()?))
+    }
 }
 
 impl Init for EstTimeNet {}
@@ -83,6 +102,70 @@ impl EstTimeNet {
     pub fn new(val: Vec<EstTime>) -> Self {
         Self { val }
     }
+
+    /// Returns the earliest estimated time of arrival at `link_idx` across all paths, or `None`
+    /// if no path in the network reaches `link_idx`
+    pub fn est_time_at_link(&self, link_idx: LinkIdx) -> Option<si::Time> {
+        self.val
+            .iter()
+            .filter(|est_time| {
+                est_time.link_event.link_idx == link_idx
+                    && est_time.link_event.est_type == EstType::Arrive
+                    && !est_time.time_sched.is_nan()
+            })
+            .map(|est_time| est_time.time_sched)
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+    }
+
+    /// Returns the span between the earliest and latest scheduled times in the network
+    pub fn total_time(&self) -> si::Time {
+        let times = self
+            .val
+            .iter()
+            .map(|est_time| est_time.time_sched)
+            .filter(|time_sched| !time_sched.is_nan());
+        match (
+            times.clone().min_by(|a, b| a.partial_cmp(b).unwrap()),
+            times.max_by(|a, b| a.partial_cmp(b).unwrap()),
+        ) {
+            (Some(time_min), Some(time_max)) => time_max - time_min,
+            _ => si::Time::ZERO,
+        }
+    }
+
+    /// Returns DataFrame with one row per estimated time node and columns for
+    /// node index, link index, scheduled time, and speed, for inspecting the
+    /// output of [make_est_times]
+    pub fn to_dataframe(&self) -> anyhow::Result<DataFrame> {
+        DataFrame::new(vec![
+            Column::new(
+                "node_idx".into(),
+                (0..self.val.len() as u32).collect::<Vec<u32>>(),
+            ),
+            Column::new(
+                "link_idx".into(),
+                self.val
+                    .iter()
+                    .map(|est_time| est_time.link_event.link_idx.idx() as u32)
+                    .collect::<Vec<u32>>(),
+            ),
+            Column::new(
+                "time_sched_seconds".into(),
+                self.val
+                    .iter()
+                    .map(|est_time| est_time.time_sched.get::<si::second>())
+                    .collect::<Vec<f64>>(),
+            ),
+            Column::new(
+                "speed_meters_per_second".into(),
+                self.val
+                    .iter()
+                    .map(|est_time| est_time.speed.get::<si::meter_per_second>())
+                    .collect::<Vec<f64>>(),
+            ),
+        ])
+        .with_context(|| format_dbg!())
+    }
 }
 
 #[cfg(feature = "pyo3")]
@@ -534,9 +617,49 @@ fn add_new_join_paths(
 /// * The path is unexpectedly truncated.
 /// * The simulation fails internally while updating movements or extending paths.
 pub fn make_est_times<N: AsRef<[Link]>>(
+    speed_limit_train_sim: SpeedLimitTrainSim,
+    network: N,
+    path_for_failed_sim: Option<PathBuf>,
+) -> anyhow::Result<(EstTimeNet, Consist)> {
+    make_est_times_with_progress(speed_limit_train_sim, network, path_for_failed_sim, None)
+}
+
+/// Reports progress of [make_est_times_with_progress] as the fraction of
+/// links that can reach a destination that have been simulated
+struct EstTimesProgress<'a> {
+    progress: &'a dyn Fn(f64),
+    n_links: usize,
+    link_idxs_visited: IntSet<LinkIdx>,
+}
+
+impl<'a> EstTimesProgress<'a> {
+    fn new(progress: &'a dyn Fn(f64), n_links: usize) -> Self {
+        Self {
+            progress,
+            n_links: n_links.max(1),
+            link_idxs_visited: IntSet::with_capacity_and_hasher(n_links, Default::default()),
+        }
+    }
+
+    /// Records that `link_idx` has been added to a train's path, invoking
+    /// callback only if `link_idx` has not been visited before
+    fn visit(&mut self, link_idx: LinkIdx) {
+        if self.link_idxs_visited.insert(link_idx) {
+            (self.progress)((self.link_idxs_visited.len() as f64 / self.n_links as f64).min(1.0));
+        }
+    }
+}
+
+/// Same as [make_est_times], but if `progress` is provided, it is invoked with
+/// the fraction complete, between 0 and 1, each time a new link is
+/// simulated and with 1.0 once the network is complete.  Fraction complete is
+/// approximated as the fraction of links that can reach a destination that
+/// have been simulated.
+pub fn make_est_times_with_progress<N: AsRef<[Link]>>(
     mut speed_limit_train_sim: SpeedLimitTrainSim,
     network: N,
     path_for_failed_sim: Option<PathBuf>,
+    progress: Option<&dyn Fn(f64)>,
 ) -> anyhow::Result<(EstTimeNet, Consist)> {
     speed_limit_train_sim.set_save_interval(None);
     let network = network.as_ref();
@@ -545,6 +668,8 @@ pub fn make_est_times<N: AsRef<[Link]>>(
     let (link_idx_options, origs) =
         get_link_idx_options(&speed_limit_train_sim.origs, dests, network)
             .with_context(|| format_dbg!())?;
+    let mut progress =
+        progress.map(|progress| EstTimesProgress::new(progress, link_idx_options.len()));
     // We'll store our estimated times and a map of link events here.
     let mut est_times = Vec::with_capacity(network.len() * 10);
     let mut consist_out = None;
@@ -632,6 +757,9 @@ pub fn make_est_times<N: AsRef<[Link]>>(
             },
         );
 
+        if let Some(progress) = &mut progress {
+            progress.visit(orig.link_idx);
+        }
         // Save this train simulator state to be processed.
         // NOTE, there may be a way to just clone the state(s) and not the whole thing
         saved_sims.push(SavedSim {
@@ -781,6 +909,9 @@ pub fn make_est_times<N: AsRef<[Link]>>(
                             bail!(err)
                         }
                         new_sim.check_dests(dests);
+                        if let Some(progress) = &mut progress {
+                            progress.visit(link_idx_next_alt);
+                        }
                         saved_sims.push(new_sim);
                         link_idx_next
                     }
@@ -797,6 +928,9 @@ pub fn make_est_times<N: AsRef<[Link]>>(
                     .extend_path(network, &[link_idx_next])
                     .with_context(|| format_dbg!())?;
                 sim.check_dests(dests);
+                if let Some(progress) = &mut progress {
+                    progress.visit(link_idx_next);
+                }
             }
         }
     }
@@ -880,17 +1014,148 @@ pub fn make_est_times<N: AsRef<[Link]>>(
         "All times are 0.0 so something went wrong.\n{}",
         format_dbg!()
     );
+    if let Some(progress) = &progress {
+        (progress.progress)(1.0);
+    }
     // Return the finished network and the locomotive consist.
     Ok((est_time_net, consist_out.unwrap()))
 }
 
+/// Output of [make_est_times] saved to disk by [make_est_times_cached], along
+/// with the hash of the inputs it was computed from
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct EstTimesCache {
+    hash: u64,
+    est_time_net: EstTimeNet,
+    consist: Consist,
+}
+
+impl Init for EstTimesCache {}
+impl SerdeAPI for EstTimesCache {}
+
+/// Hash of `links` and the origin/destination set, used as the key for
+/// [make_est_times_cached].  Inputs are hashed via [serde_json::Value], whose
+/// maps are sorted, so that the hash does not depend on `HashMap` iteration
+/// order.
+fn est_times_hash(links: &[Link], origs: &[Location], dests: &[Location]) -> anyhow::Result<u64> {
+    let value = serde_json::to_value((links, origs, dests)).with_context(|| format_dbg!())?;
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    value.to_string().hash(&mut hasher);
+    Ok(hasher.finish())
+}
+
+/// Wrapper around [make_est_times] that loads the result from `cache_path` if
+/// the file exists and was computed from the same network and
+/// origin/destination set, and otherwise runs [make_est_times] and saves the
+/// result to `cache_path`.  Note that the cache key does not account for any
+/// other changes to `speed_limit_train_sim` (e.g. consist or train mass).
+///
+/// # Arguments
+///
+/// * `cache_path` - cache file, in any format supported by [SerdeAPI::to_file]
+///
+/// See [make_est_times] for the other arguments.
+pub fn make_est_times_cached<N: AsRef<[Link]>, P: AsRef<Path>>(
+    speed_limit_train_sim: SpeedLimitTrainSim,
+    network: N,
+    path_for_failed_sim: Option<PathBuf>,
+    cache_path: P,
+) -> anyhow::Result<(EstTimeNet, Consist)> {
+    let cache_path = cache_path.as_ref();
+    let hash = est_times_hash(
+        network.as_ref(),
+        &speed_limit_train_sim.origs,
+        &speed_limit_train_sim.dests,
+    )?;
+    if cache_path.exists() {
+        let cache = EstTimesCache::from_file(cache_path, true).with_context(|| {
+            format!(
+                "{}\nFailed to load cache from {:?}",
+                format_dbg!(),
+                cache_path
+            )
+        })?;
+        if cache.hash == hash {
+            return Ok((cache.est_time_net, cache.consist));
+        }
+    }
+    let (est_time_net, consist) =
+        make_est_times(speed_limit_train_sim, network, path_for_failed_sim)?;
+    let cache = EstTimesCache {
+        hash,
+        est_time_net,
+        consist,
+    };
+    cache.to_file(cache_path).with_context(|| {
+        format!(
+            "{}\nFailed to save cache to {:?}",
+            format_dbg!(),
+            cache_path
+        )
+    })?;
+    Ok((cache.est_time_net, cache.consist))
+}
+
 #[cfg(feature = "pyo3")]
 #[pyfunction(name = "make_est_times")]
-#[pyo3(signature=(speed_limit_train_sim, network, path_for_failed_sim=None))]
+#[pyo3(signature=(speed_limit_train_sim, network, path_for_failed_sim=None, progress=None))]
+/// If provided, `progress` is called with fraction complete as
+/// [make_est_times_with_progress] runs, and any exception it raises is
+/// re-raised once the network is complete
 pub fn make_est_times_py(
     speed_limit_train_sim: SpeedLimitTrainSim,
     network: &Bound<PyAny>,
     path_for_failed_sim: Option<&Bound<PyAny>>,
+    progress: Option<&Bound<PyAny>>,
+) -> anyhow::Result<(EstTimeNet, Consist)> {
+    let network = match network.extract::<Network>() {
+        Ok(n) => n,
+        Err(_) => {
+            let n = network
+                .extract::<Vec<Link>>()
+                .map_err(|_| anyhow!("{}", format_dbg!()))?;
+            Network(Default::default(), n)
+        }
+    };
+
+    let path_for_failed_sim = match path_for_failed_sim {
+        Some(pffs) => Some(PathBuf::extract_bound(pffs)?),
+        None => None,
+    };
+
+    // first exception raised by `progress`, which cannot be propagated from
+    // within the callback
+    let progress_err = std::cell::RefCell::new(None);
+    let progress_fn = |frac: f64| {
+        if let Some(progress) = progress {
+            if progress_err.borrow().is_none() {
+                if let Err(err) = progress.call1((frac,)) {
+                    *progress_err.borrow_mut() = Some(err);
+                }
+            }
+        }
+    };
+    let est_times = make_est_times_with_progress(
+        speed_limit_train_sim,
+        network,
+        path_for_failed_sim,
+        progress.map(|_| &progress_fn as &dyn Fn(f64)),
+    );
+    if let Some(err) = progress_err.into_inner() {
+        return Err(err.into());
+    }
+    est_times
+}
+
+#[cfg(feature = "pyo3")]
+#[pyfunction(name = "make_est_times_cached")]
+#[pyo3(signature=(speed_limit_train_sim, network, cache_path, path_for_failed_sim=None))]
+pub fn make_est_times_cached_py(
+    speed_limit_train_sim: SpeedLimitTrainSim,
+    network: &Bound<PyAny>,
+    cache_path: PathBuf,
+    path_for_failed_sim: Option<&Bound<PyAny>>,
 ) -> anyhow::Result<(EstTimeNet, Consist)> {
     let network = match network.extract::<Network>() {
         Ok(n) => n,
@@ -907,5 +1172,146 @@ pub fn make_est_times_py(
         None => None,
     };
 
-    make_est_times(speed_limit_train_sim, network, path_for_failed_sim)
+    make_est_times_cached(
+        speed_limit_train_sim,
+        network,
+        path_for_failed_sim,
+        cache_path,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_est_time_net_queries() {
+        let network_file_path = project_root::get_project_root()
+            .unwrap()
+            .join("python/altrios/resources/networks/Taconite.yaml");
+        let network = Network::from_file(network_file_path, false).unwrap();
+
+        let speed_limit_train_sim = crate::train::speed_limit_train_sim_fwd();
+        let est_time_net = make_est_times(speed_limit_train_sim.clone(), network, None)
+            .unwrap()
+            .0;
+
+        assert!(est_time_net.total_time() > si::Time::ZERO);
+        // origins and destinations may be listed in both directions, only
+        // some of which are reached by the network
+        let time_orig = speed_limit_train_sim
+            .origs
+            .iter()
+            .filter_map(|orig| est_time_net.est_time_at_link(orig.link_idx))
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap();
+        let time_dest = speed_limit_train_sim
+            .dests
+            .iter()
+            .filter_map(|dest| est_time_net.est_time_at_link(dest.link_idx))
+            .min_by(|a, b| a.partial_cmp(b).unwrap())
+            .unwrap();
+        assert!(time_dest > time_orig);
+        assert!(time_dest - time_orig <= est_time_net.total_time());
+        assert!(est_time_net
+            .est_time_at_link(LinkIdx::new(u32::MAX))
+            .is_none());
+    }
+
+    #[test]
+    fn test_make_est_times_progress() {
+        let network_file_path = project_root::get_project_root()
+            .unwrap()
+            .join("python/altrios/resources/networks/Taconite.yaml");
+        let network = Network::from_file(network_file_path, false).unwrap();
+        let speed_limit_train_sim = crate::train::speed_limit_train_sim_fwd();
+
+        let fracs = std::cell::RefCell::new(Vec::<f64>::new());
+        let progress = |frac: f64| fracs.borrow_mut().push(frac);
+        let (est_time_net, _) = make_est_times_with_progress(
+            speed_limit_train_sim.clone(),
+            &network,
+            None,
+            Some(&progress),
+        )
+        .unwrap();
+        let fracs = fracs.into_inner();
+        assert!(fracs.len() > 1);
+        assert!(fracs.iter().all(|frac| (0.0..=1.0).contains(frac)));
+        assert!(fracs.windows(2).all(|w| w[1] >= w[0]));
+        assert_eq!(*fracs.last().unwrap(), 1.0);
+
+        // progress reporting does not affect the result
+        let est_time_net_no_progress = make_est_times(speed_limit_train_sim, &network, None)
+            .unwrap()
+            .0;
+        // fake nodes have NaN times, so compare serialized forms
+        assert_eq!(
+            est_time_net.to_yaml().unwrap(),
+            est_time_net_no_progress.to_yaml().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_make_est_times_cached() {
+        let network_file_path = project_root::get_project_root()
+            .unwrap()
+            .join("python/altrios/resources/networks/Taconite.yaml");
+        let network = Network::from_file(network_file_path, false).unwrap();
+        let tempdir = tempfile::tempdir().unwrap();
+        let cache_path = tempdir.path().join("est_times.yaml");
+
+        let speed_limit_train_sim = crate::train::speed_limit_train_sim_fwd();
+        let (est_time_net, consist) =
+            make_est_times_cached(speed_limit_train_sim.clone(), &network, None, &cache_path)
+                .unwrap();
+        assert!(cache_path.exists());
+
+        // a sim that cannot be run still returns the cached result, so the
+        // cache hit did not recompute
+        let mut sim_stale = speed_limit_train_sim.clone();
+        sim_stale.state.time.mark_stale();
+        assert!(make_est_times(sim_stale.clone(), &network, None).is_err());
+        let (est_time_net_cached, consist_cached) =
+            make_est_times_cached(sim_stale.clone(), &network, None, &cache_path).unwrap();
+        // fake nodes have NaN times, so compare serialized forms
+        assert_eq!(
+            est_time_net_cached.to_yaml().unwrap(),
+            est_time_net.to_yaml().unwrap()
+        );
+        assert_eq!(
+            consist_cached.to_yaml().unwrap(),
+            consist.to_yaml().unwrap()
+        );
+
+        // a different origin/destination set misses the cache
+        sim_stale.dests.clear();
+        assert!(make_est_times_cached(sim_stale, &network, None, &cache_path).is_err());
+    }
+
+    #[test]
+    fn test_est_time_net_to_dataframe() {
+        let network_file_path = project_root::get_project_root()
+            .unwrap()
+            .join("python/altrios/resources/networks/Taconite.yaml");
+        let network = Network::from_file(network_file_path, false).unwrap();
+
+        let est_time_net = make_est_times(crate::train::speed_limit_train_sim_fwd(), network, None)
+            .unwrap()
+            .0;
+        let df = est_time_net.to_dataframe().unwrap();
+        assert_eq!(df.height(), est_time_net.val.len());
+        assert_eq!(
+            df.get_column_names()
+                .iter()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "node_idx",
+                "link_idx",
+                "time_sched_seconds",
+                "speed_meters_per_second"
+            ]
+        );
+    }
 }
@@ -7,5 +7,6 @@
 mod disp_imports;
 pub mod disp_structs;
 pub mod dispatch;
+pub mod dispatch_result;
 pub mod est_times;
 pub mod train_disp;
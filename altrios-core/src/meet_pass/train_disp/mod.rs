@@ -78,6 +78,24 @@ impl TrainDisp {
     pub fn is_blocked(&self) -> bool {
         self.is_blocked
     }
+    /// Returns link index and estimated pass speed at the end of the fixed
+    /// part of the path, i.e. where the train is held if it is not advanced
+    pub fn hold_point(&self) -> (LinkIdx, si::Velocity) {
+        match self.disp_path.get(
+            self.disp_node_idx_fixed
+                .idx()
+                .min(self.disp_path.len().saturating_sub(1)),
+        ) {
+            Some(disp_node) => (
+                disp_node.link_event.link_idx,
+                self.est_times
+                    .get(disp_node.est_idx.idx())
+                    .map(|est_time| est_time.speed)
+                    .unwrap_or_default(),
+            ),
+            None => (track::LINK_IDX_NA, si::Velocity::ZERO),
+        }
+    }
     pub fn fix_advance(&mut self) {
         assert!(self.time_update <= self.time_update_next);
         assert!(self.offset_fixed <= self.offset_free);
@@ -1,42 +1,57 @@
-pub use crate::consist::consist_sim::ConsistSimulation;
+pub use crate::consist::consist_sim::{ConsistSimulation, ConsistSimulationVec};
 pub use crate::consist::locomotive::loco_sim::{LocomotiveSimulation, PowerTrace};
 pub use crate::consist::locomotive::powertrain::electric_drivetrain::{
-    ElectricDrivetrain, ElectricDrivetrainState, ElectricDrivetrainStateHistoryVec,
+    DynBrakeThermal, ElectricDrivetrain, ElectricDrivetrainState, ElectricDrivetrainStateHistoryVec,
 };
 pub use crate::consist::locomotive::powertrain::fuel_converter::{
-    FuelConverter, FuelConverterState, FuelConverterStateHistoryVec,
+    FuelConverter, FuelConverterState, FuelConverterStateHistoryVec, FuelConverterThermal, FuelType,
 };
 pub use crate::consist::locomotive::powertrain::generator::{
     Generator, GeneratorState, GeneratorStateHistoryVec,
 };
 pub use crate::consist::locomotive::powertrain::powertrain_traits::*;
 pub use crate::consist::locomotive::powertrain::reversible_energy_storage::{
-    ReversibleEnergyStorage, ReversibleEnergyStorageState, ReversibleEnergyStorageStateHistoryVec,
+    ResVoltageModel, ReversibleEnergyStorage, ReversibleEnergyStorageState,
+    ReversibleEnergyStorageStateHistoryVec,
 };
 pub use crate::consist::locomotive::{
-    BatteryElectricLoco, ConventionalLoco, DummyLoco, HybridLoco, LocoParams, Locomotive,
-    LocomotiveState, LocomotiveStateHistoryVec, RESGreedyWithDynamicBuffers,
+    AxleLoadTransfer, BatteryElectricLoco, ConventionalLoco, DummyLoco, HybridLoco, LocoParams,
+    Locomotive, LocomotiveState, LocomotiveStateHistoryVec, RESGreedyWithDynamicBuffers,
     RESGreedyWithDynamicBuffersBEL,
 };
 pub use crate::consist::{Consist, ConsistState, ConsistStateHistoryVec};
+pub use crate::meet_pass::dispatch::DispatchObjective;
+pub use crate::meet_pass::dispatch_result::{DispatchResult, Meet, TrainDispatchSummary};
 pub use crate::meet_pass::est_times::est_time_structs::SavedSim;
-pub use crate::meet_pass::est_times::{make_est_times, EstTimeNet};
+pub use crate::meet_pass::est_times::{make_est_times, make_est_times_cached, EstTimeNet};
 #[cfg(feature = "pyo3")]
 pub use crate::meet_pass::{
-    dispatch::run_dispatch_py, est_times::check_od_pair_valid, est_times::make_est_times_py,
+    dispatch::run_dispatch_py, est_times::check_od_pair_valid, est_times::make_est_times_cached_py,
+    est_times::make_est_times_py,
 };
 #[cfg(feature = "pyo3")]
 pub use crate::track::import_locations_py;
 pub use crate::track::{
-    Elev, Heading, Link, LinkIdx, LinkPath, LinkPoint, Location, Network, PathTpc, SpeedSet,
-    TrainParams, TrainType,
+    CurveSpeedModel, Elev, Heading, Link, LinkIdx, LinkPath, LinkPoint, Location, Network, PathTpc,
+    SpeedSet, TrainParams, TrainType,
 };
 #[cfg(feature = "pyo3")]
+pub use crate::train::compare_sims_py;
+#[cfg(feature = "pyo3")]
 pub use crate::train::run_speed_limit_train_sims;
 #[cfg(feature = "pyo3")]
+pub use crate::train::summarize_speed_limit_train_sims_py;
+#[cfg(feature = "pyo3")]
 pub use crate::train::TrainResWrapper;
 pub use crate::train::{
-    InitTrainState, LinkIdxTime, RailVehicle, SetSpeedTrainSim, SpeedLimitTrainSim,
-    SpeedLimitTrainSimVec, SpeedTrace, TemperatureTrace, TemperatureTraceBuilder, TimedLinkPath,
-    TrainConfig, TrainRes, TrainSimBuilder, TrainState, TrainStateHistoryVec,
+    compare_sims, summarize_speed_limit_train_sims, CurveComfort, EcoDriveConfig, InitTrainState,
+    LinkIdxTime, ObjectiveWeights, RailVehicle, SetSpeedTrainSim, SimCheckpoint,
+    SpeedLimitTrainSim, SpeedLimitTrainSimVec, SpeedTrace, SpeedViolationPolicy, TemperatureTrace,
+    TemperatureTraceBuilder, TimedLinkPath, TractionLimitCause, TrainConfig, TrainRes,
+    TrainSimBuilder, TrainState, TrainStateHistoryVec,
+};
+pub use crate::traits::IntegrationMethod;
+pub use crate::units::{
+    hp_to_watt, kg_to_ton, lbf_to_newton, mph_to_mps, mps_to_mph, newton_to_lbf, ton_to_kg,
+    watt_to_hp,
 };
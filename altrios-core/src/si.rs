@@ -8,20 +8,31 @@ pub use si::angle::degree;
 pub use si::area::square_meter;
 pub use si::available_energy::{joule_per_kilogram, kilojoule_per_kilogram};
 pub use si::curvature::{degree_per_meter, radian_per_meter};
-pub use si::energy::{joule, watt_hour};
+pub use si::electric_current::ampere;
+pub use si::electric_potential::volt;
+pub use si::electrical_resistance::ohm;
+pub use si::energy::{joule, kilowatt_hour, watt_hour};
 pub use si::f64::{
-    Acceleration, Angle, Area, AvailableEnergy as SpecificEnergy, Curvature, Energy, Force,
-    Frequency, InverseVelocity, Length, Mass, MassDensity, Power, PowerRate, Pressure, Ratio,
-    SpecificHeatCapacity, SpecificPower, TemperatureInterval, ThermodynamicTemperature, Time,
-    Velocity, Volume,
+    Acceleration, Angle, Area, AvailableEnergy as SpecificEnergy, Curvature, ElectricCurrent,
+    ElectricPotential, ElectricalResistance, Energy, Force, Frequency, HeatCapacity,
+    InverseVelocity, Jerk, Length, Mass, MassDensity, MassPerEnergy, MassRate, Power, PowerRate,
+    Pressure, Ratio, SpecificHeatCapacity, SpecificPower, TemperatureInterval,
+    ThermodynamicTemperature, Time, Velocity, Volume, VolumeRate,
 };
 pub use si::force::{newton, pound_force};
+pub use si::heat_capacity::joule_per_kelvin;
+pub use si::jerk::meter_per_second_cubed;
 pub use si::length::{foot, kilometer, meter};
 pub use si::mass::{kilogram, megagram};
-pub use si::power::{kilowatt, megawatt, watt};
+pub use si::mass_density::kilogram_per_cubic_meter;
+pub use si::mass_per_energy::kilogram_per_joule;
+pub use si::mass_rate::kilogram_per_second;
+pub use si::power::{horsepower, kilowatt, megawatt, watt};
 pub use si::power_rate::watt_per_second;
 pub use si::ratio::{percent, ratio};
 pub use si::specific_power::kilowatt_per_kilogram;
 pub use si::thermodynamic_temperature::{degree_celsius, kelvin};
 pub use si::time::{hour, second};
+pub use si::velocity::{meter_per_second, mile_per_hour};
 pub use si::volume::cubic_meter;
+pub use si::volume_rate::cubic_meter_per_second;
@@ -3,6 +3,7 @@ use super::elev::*;
 use super::heading::*;
 use super::link_idx::*;
 use super::link_old::LinkOld;
+use super::location::*;
 use super::speed::*;
 use crate::imports::*;
 
@@ -64,7 +65,32 @@ pub struct Link {
 }
 
 #[pyo3_api]
-impl Link {}
+impl Link {
+    #[pyo3(name = "set_elev_from_grade")]
+    /// Sets elevations by integrating `grades` starting from `elev_start_meters`
+    fn set_elev_from_grade_py(
+        &mut self,
+        offsets_meters: Vec<f64>,
+        grades: Vec<f64>,
+        elev_start_meters: f64,
+    ) -> anyhow::Result<()> {
+        self.set_elev_from_grade(
+            offsets_meters
+                .iter()
+                .map(|offset| *offset * uc::M)
+                .collect(),
+            grades.iter().map(|grade| *grade * uc::R).collect(),
+            elev_start_meters * uc::M,
+        )
+    }
+
+    #[pyo3(name = "speed_limit_at_offset")]
+    fn speed_limit_at_offset_py(&self, offset_meters: f64) -> anyhow::Result<f64> {
+        Ok(self
+            .speed_limit_at_offset(offset_meters * uc::M)?
+            .get::<si::meter_per_second>())
+    }
+}
 
 impl Link {
     fn is_linked_prev(&self, idx: LinkIdx) -> bool {
@@ -90,6 +116,99 @@ impl Link {
         self.speed_sets = HashMap::new();
         Ok(())
     }
+
+    /// Returns the posted speed limit at `offset` along [Self], i.e. the most
+    /// restrictive [SpeedLimit] spanning `offset`.  Limits are read from
+    /// [Self::speed_set] if provided, or otherwise from the only entry in
+    /// [Self::speed_sets].  If [Self::speed_sets] has multiple train types,
+    /// call [Self::set_speed_set_for_train_type] first.
+    pub fn speed_limit_at_offset(&self, offset: si::Length) -> anyhow::Result<si::Velocity> {
+        ensure!(
+            offset >= si::Length::ZERO && offset <= self.length,
+            "{}
+`offset` ({:?}) must be within link length ({:?})",
+            format_dbg!(),
+            offset,
+            self.length
+        );
+        let speed_set = match &self.speed_set {
+            Some(speed_set) => speed_set,
+            None => {
+                ensure!(
+                    self.speed_sets.len() == 1,
+                    "{}
+`speed_set` is `None` and `speed_sets` has {} train types.  {}",
+                    format_dbg!(),
+                    self.speed_sets.len(),
+                    "Call `set_speed_set_for_train_type` to select one."
+                );
+                self.speed_sets.values().next().unwrap()
+            }
+        };
+        speed_set
+            .speed_limits
+            .iter()
+            .filter(|sl| sl.offset_start <= offset && offset <= sl.offset_end)
+            .map(|sl| sl.speed)
+            .reduce(si::Velocity::min)
+            .with_context(|| {
+                format!(
+                    "{}
+No speed limit found at {:?}",
+                    format_dbg!(),
+                    offset
+                )
+            })
+    }
+
+    /// Sets [Self::elevs] by integrating piecewise-constant grades, for data
+    /// sources that provide grade rather than absolute elevation
+    /// # Arguments
+    /// - `offsets`: sorted offsets at which each grade starts, beginning at zero
+    /// - `grades`: grade (rise over run) starting at the corresponding offset
+    ///   and extending to the next offset, or to [Self::length] for the last grade
+    /// - `elev_start`: elevation at the start of the link
+    pub fn set_elev_from_grade(
+        &mut self,
+        offsets: Vec<si::Length>,
+        grades: Vec<si::Ratio>,
+        elev_start: si::Length,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            !offsets.is_empty() && offsets.len() == grades.len(),
+            "{}\nExpected `offsets` and `grades` to be non-empty and of equal length, got {} and {}",
+            format_dbg!(),
+            offsets.len(),
+            grades.len()
+        );
+        ensure!(
+            offsets[0] == si::Length::ZERO,
+            "{}\nExpected first offset to be zero, got {:?}",
+            format_dbg!(),
+            offsets[0]
+        );
+        ensure!(
+            offsets.windows(2).all(|w| w[0] < w[1]) && *offsets.last().unwrap() <= self.length,
+            "{}\nExpected offsets to be sorted, unique, and no greater than link length",
+            format_dbg!()
+        );
+        ensure!(
+            grades.iter().all(|grade| grade.is_finite()) && elev_start.is_finite(),
+            "{}\nExpected finite grades and starting elevation",
+            format_dbg!()
+        );
+
+        let mut elevs = vec![Elev::new(si::Length::ZERO, elev_start)];
+        let offsets_end = offsets.iter().skip(1).chain(std::iter::once(&self.length));
+        for ((offset_start, offset_end), grade) in offsets.iter().zip(offsets_end).zip(&grades) {
+            if offset_end > offset_start {
+                let elev = elevs.last().unwrap().elev + *grade * (*offset_end - *offset_start);
+                elevs.push(Elev::new(*offset_end, elev));
+            }
+        }
+        self.elevs = elevs;
+        Ok(())
+    }
 }
 
 impl Init for Link {}
@@ -365,6 +484,32 @@ impl ObjState for Link {
     }
 }
 
+/// Entry in the priority queue of [Network::path_between_locations], ordered
+/// such that [BinaryHeap] pops the smallest `dist` first
+#[derive(PartialEq)]
+struct PathCandidate {
+    /// Cumulative path length in meters, including link `idx`
+    dist: f64,
+    idx: LinkIdx,
+}
+
+impl Eq for PathCandidate {}
+
+impl PartialOrd for PathCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for PathCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .dist
+            .total_cmp(&self.dist)
+            .then_with(|| other.idx.cmp(&self.idx))
+    }
+}
+
 #[serde_api]
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
@@ -382,6 +527,60 @@ impl Network {
     fn set_speed_set_for_train_type_py(&mut self, train_type: TrainType) -> anyhow::Result<()> {
         self.set_speed_set_for_train_type(train_type)
     }
+
+    #[pyo3(name = "validate_speed_sets")]
+    fn validate_speed_sets_py(&self) -> anyhow::Result<()> {
+        self.validate_speed_sets()
+    }
+
+    #[pyo3(name = "validate_connectivity")]
+    fn validate_connectivity_py(&self) -> anyhow::Result<()> {
+        self.validate_connectivity()
+    }
+
+    #[pyo3(name = "link_length")]
+    fn link_length_py(&self, idx: u32) -> anyhow::Result<f64> {
+        Ok(self.link_length(LinkIdx::new(idx))?.get::<si::meter>())
+    }
+
+    #[getter("total_track_length_meters")]
+    fn get_total_track_length_py(&self) -> f64 {
+        self.total_track_length().get::<si::meter>()
+    }
+
+    #[pyo3(name = "adjacency")]
+    fn adjacency_py(&self) -> Vec<(usize, Vec<usize>)> {
+        self.adjacency()
+            .into_iter()
+            .map(|(idx, idxs_next)| (idx.idx(), idxs_next.iter().map(|i| i.idx()).collect()))
+            .collect()
+    }
+
+    #[pyo3(name = "find_location")]
+    fn find_location_py(&self, location_map: LocationMap, name: &str) -> Option<Location> {
+        self.find_location(&location_map, name)
+    }
+
+    #[pyo3(name = "path_between_locations")]
+    fn path_between_locations_py(
+        &self,
+        location_map: LocationMap,
+        origin: &str,
+        dest: &str,
+    ) -> anyhow::Result<LinkPath> {
+        self.path_between_locations(&location_map, origin, dest)
+    }
+
+    #[pyo3(name = "merge")]
+    fn merge_py(&mut self, other: Network, link_joins: Vec<(u32, u32)>) -> anyhow::Result<()> {
+        self.merge(
+            other,
+            link_joins
+                .into_iter()
+                .map(|(idx_self, idx_other)| (LinkIdx::new(idx_self), LinkIdx::new(idx_other)))
+                .collect(),
+        )
+    }
 }
 
 #[serde_api]
@@ -438,6 +637,381 @@ impl Network {
         }
         Ok(())
     }
+
+    /// Returns length of link at `idx`, which must be a real link in [Self]
+    pub fn link_length(&self, idx: LinkIdx) -> anyhow::Result<si::Length> {
+        ensure!(
+            idx.is_real(),
+            "{}\nExpected a real link index, got {}",
+            format_dbg!(),
+            idx
+        );
+        self.1
+            .get(idx.idx())
+            .map(|link| link.length)
+            .with_context(|| {
+                format!(
+                    "{}\nLink index {} does not exist in network with {} links",
+                    format_dbg!(),
+                    idx,
+                    self.1.len()
+                )
+            })
+    }
+
+    /// Returns sum of lengths of all real links.  Note that a link and its
+    /// flip (i.e. the same track in the opposite direction) are each counted.
+    pub fn total_track_length(&self) -> si::Length {
+        self.1
+            .iter()
+            .skip(1)
+            .fold(si::Length::ZERO, |acc, link| acc + link.length)
+    }
+
+    /// Returns each link paired with its downstream successor links, i.e.
+    /// `idx_next` and, if it exists, `idx_next_alt`.  The fake link at index 0
+    /// is excluded.
+    pub fn adjacency(&self) -> Vec<(LinkIdx, Vec<LinkIdx>)> {
+        self.1
+            .iter()
+            .skip(1)
+            .map(|link| {
+                (
+                    link.idx_curr,
+                    [link.idx_next, link.idx_next_alt]
+                        .into_iter()
+                        .filter(|idx| !idx.is_fake())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+
+    /// Returns the first location in `location_map` named `name` that lies on
+    /// a real link in [Self], or `None` if there is no such location
+    pub fn find_location(&self, location_map: &LocationMap, name: &str) -> Option<Location> {
+        self.locations_named(location_map, name).into_iter().next()
+    }
+
+    /// All locations in `location_map` named `name` that lie on real links in
+    /// [Self].  A terminal typically has multiple locations, e.g. one per
+    /// direction of travel.
+    fn locations_named(&self, location_map: &LocationMap, name: &str) -> Vec<Location> {
+        location_map
+            .get(name)
+            .map(|locations| {
+                locations
+                    .iter()
+                    .filter(|loc| loc.link_idx.is_real() && loc.link_idx.idx() < self.1.len())
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Returns the shortest path by track length, following `idx_next` and
+    /// `idx_next_alt`, from any link containing a location named `origin` to
+    /// any link containing a location named `dest`, with locations looked up
+    /// in `location_map`.  The returned path includes both end links.
+    pub fn path_between_locations(
+        &self,
+        location_map: &LocationMap,
+        origin: &str,
+        dest: &str,
+    ) -> anyhow::Result<LinkPath> {
+        let find = |name: &str| -> anyhow::Result<Vec<Location>> {
+            let locations = self.locations_named(location_map, name);
+            ensure!(
+                !locations.is_empty(),
+                "{}\nNo location named `{}` found in network",
+                format_dbg!(),
+                name
+            );
+            Ok(locations)
+        };
+        let origs = find(origin)?;
+        let dests: HashSet<LinkIdx> = find(dest)?.iter().map(|loc| loc.link_idx).collect();
+
+        // Dijkstra's algorithm, where the cost of entering a link is its length
+        let mut dists = vec![f64::INFINITY; self.1.len()];
+        let mut idxs_prev = vec![LINK_IDX_NA; self.1.len()];
+        let mut heap = BinaryHeap::new();
+        for orig in origs.iter() {
+            let dist = self.link_length(orig.link_idx)?.get::<si::meter>();
+            if dist < dists[orig.link_idx.idx()] {
+                dists[orig.link_idx.idx()] = dist;
+                heap.push(PathCandidate {
+                    dist,
+                    idx: orig.link_idx,
+                });
+            }
+        }
+        while let Some(PathCandidate { dist, idx }) = heap.pop() {
+            if dist > dists[idx.idx()] {
+                continue;
+            }
+            if dests.contains(&idx) {
+                let mut path = vec![idx];
+                while !idxs_prev[path.last().unwrap().idx()].is_fake() {
+                    path.push(idxs_prev[path.last().unwrap().idx()]);
+                }
+                path.reverse();
+                return Ok(LinkPath(path));
+            }
+            let link = &self.1[idx.idx()];
+            for idx_next in [link.idx_next, link.idx_next_alt] {
+                if idx_next.is_fake() {
+                    continue;
+                }
+                let dist_next = dist + self.link_length(idx_next)?.get::<si::meter>();
+                if dist_next < dists[idx_next.idx()] {
+                    dists[idx_next.idx()] = dist_next;
+                    idxs_prev[idx_next.idx()] = idx;
+                    heap.push(PathCandidate {
+                        dist: dist_next,
+                        idx: idx_next,
+                    });
+                }
+            }
+        }
+        bail!(
+            "{}\nNo path found from `{}` to `{}`",
+            format_dbg!(),
+            origin,
+            dest
+        )
+    }
+
+    /// Appends the links of `other` to `self`, offsetting all of `other`'s
+    /// [LinkIdx] values by the number of real links in `self`, and then joins
+    /// the two networks at `link_joins`.  Each join `(idx_self, idx_other)`
+    /// connects the end of link `idx_self` in `self` to the start of link
+    /// `idx_other` in `other` (before offsetting), using `idx_next_alt` or
+    /// `idx_prev_alt` if `idx_next` or `idx_prev` is already taken.  If both
+    /// links have flips, the flips are joined in the opposite direction.
+    ///
+    /// Fails without modifying `self` if either network has links whose
+    /// `idx_curr` does not match their position, if a join references a
+    /// nonexistent link, or if a join has no free successor or predecessor.
+    pub fn merge(
+        &mut self,
+        other: Network,
+        link_joins: Vec<(LinkIdx, LinkIdx)>,
+    ) -> anyhow::Result<()> {
+        let check_idxs = |links: &[Link], name: &str| -> anyhow::Result<()> {
+            for (idx, link) in links.iter().enumerate().skip(1) {
+                ensure!(
+                    link.idx_curr.idx() == idx,
+                    "{}\nLink at position {} in `{}` has `idx_curr` {}",
+                    format_dbg!(),
+                    idx,
+                    name,
+                    link.idx_curr
+                );
+            }
+            Ok(())
+        };
+        check_idxs(&self.1, "self")?;
+        check_idxs(&other.1, "other")?;
+
+        let mut links = self.1.clone();
+        if links.is_empty() {
+            links.push(Link::default());
+        }
+        let idx_offset = u32::try_from(links.len() - 1).with_context(|| format_dbg!())?;
+        let remap = |idx: LinkIdx| -> anyhow::Result<LinkIdx> {
+            if idx.is_fake() {
+                return Ok(idx);
+            }
+            Ok(LinkIdx::new(
+                u32::try_from(idx.idx())
+                    .ok()
+                    .and_then(|idx| idx.checked_add(idx_offset))
+                    .with_context(|| format!("{}\nLink index overflow", format_dbg!()))?,
+            ))
+        };
+        for mut link in other.1.into_iter().skip(1) {
+            for idx in [
+                &mut link.idx_curr,
+                &mut link.idx_flip,
+                &mut link.idx_next,
+                &mut link.idx_next_alt,
+                &mut link.idx_prev,
+                &mut link.idx_prev_alt,
+            ]
+            .into_iter()
+            .chain(link.link_idxs_lockout.iter_mut())
+            {
+                *idx = remap(*idx)?;
+            }
+            links.push(link);
+        }
+        check_idxs(&links, "merged")?;
+
+        let connect =
+            |links: &mut [Link], idx_from: LinkIdx, idx_to: LinkIdx| -> anyhow::Result<()> {
+                for idx in [idx_from, idx_to] {
+                    ensure!(
+                        idx.is_real() && idx.idx() < links.len(),
+                        "{}\nJoin link index {} does not exist in merged network",
+                        format_dbg!(),
+                        idx
+                    );
+                }
+                let link_from = &mut links[idx_from.idx()];
+                if link_from.idx_next.is_fake() {
+                    link_from.idx_next = idx_to;
+                } else if link_from.idx_next_alt.is_fake() {
+                    link_from.idx_next_alt = idx_to;
+                } else {
+                    bail!(
+                        "{}\nLink {} has no free successor for join to link {}",
+                        format_dbg!(),
+                        idx_from,
+                        idx_to
+                    );
+                }
+                let link_to = &mut links[idx_to.idx()];
+                if link_to.idx_prev.is_fake() {
+                    link_to.idx_prev = idx_from;
+                } else if link_to.idx_prev_alt.is_fake() {
+                    link_to.idx_prev_alt = idx_from;
+                } else {
+                    bail!(
+                        "{}\nLink {} has no free predecessor for join from link {}",
+                        format_dbg!(),
+                        idx_to,
+                        idx_from
+                    );
+                }
+                Ok(())
+            };
+        for (idx_self, idx_other) in link_joins {
+            ensure!(
+                idx_self.idx() < self.1.len(),
+                "{}\nJoin link index {} does not exist in `self`",
+                format_dbg!(),
+                idx_self
+            );
+            let idx_other = remap(idx_other)?;
+            connect(&mut links, idx_self, idx_other)?;
+            let (idx_self_flip, idx_other_flip) = (
+                links[idx_self.idx()].idx_flip,
+                links
+                    .get(idx_other.idx())
+                    .map(|link| link.idx_flip)
+                    .unwrap_or_default(),
+            );
+            if idx_self_flip.is_real() && idx_other_flip.is_real() {
+                connect(&mut links, idx_other_flip, idx_self_flip)?;
+            }
+        }
+
+        self.1 = links;
+        Ok(())
+    }
+
+    /// Checks for network authoring errors that would prevent trains from being
+    /// routed, returning an error enumerating
+    /// - links referencing a [LinkIdx] (i.e. flip, next, prev, or lockout) that
+    ///   is out of range
+    /// - orphaned links, i.e. links with neither valid predecessors nor valid
+    ///   successors
+    ///
+    /// Links with only one dangling end are treated as terminals (e.g. end of
+    /// track) and are not flagged, nor are links in a network comprising only a
+    /// single link and its flip.
+    pub fn validate_connectivity(&self) -> anyhow::Result<()> {
+        let n_links = self.1.len();
+        let in_range = |idx: &LinkIdx| idx.idx() < n_links;
+        let is_valid = |idx: &LinkIdx| idx.is_real() && in_range(idx);
+        let mut idxs_out_of_range: Vec<LinkIdx> = vec![];
+        let mut idxs_orphaned: Vec<LinkIdx> = vec![];
+        for link in self.1.iter().skip(1) {
+            if [
+                link.idx_flip,
+                link.idx_next,
+                link.idx_next_alt,
+                link.idx_prev,
+                link.idx_prev_alt,
+            ]
+            .iter()
+            .chain(link.link_idxs_lockout.iter())
+            .any(|idx| !in_range(idx))
+            {
+                idxs_out_of_range.push(link.idx_curr);
+            }
+            let has_prev = [link.idx_prev, link.idx_prev_alt].iter().any(is_valid);
+            let has_next = [link.idx_next, link.idx_next_alt].iter().any(is_valid);
+            let n_links_self = if is_valid(&link.idx_flip) { 2 } else { 1 };
+            if !has_prev && !has_next && n_links - 1 > n_links_self {
+                idxs_orphaned.push(link.idx_curr);
+            }
+        }
+
+        let fmt_idxs = |idxs: &[LinkIdx]| {
+            idxs.iter()
+                .map(|idx| idx.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let mut errors = ValidationErrors::new();
+        if !idxs_out_of_range.is_empty() {
+            errors.push(anyhow!(
+                "Links [{}] reference link indices that are out of range (network has {} links)!",
+                fmt_idxs(&idxs_out_of_range),
+                n_links
+            ));
+        }
+        if !idxs_orphaned.is_empty() {
+            errors.push(anyhow!(
+                "Links [{}] have no valid predecessors or successors!",
+                fmt_idxs(&idxs_orphaned)
+            ));
+        }
+        errors
+            .make_err()
+            .map_err(|err| anyhow!("{}\n{}", format_dbg!(), err))
+    }
+
+    /// Checks the speed sets of all links for invalid train types, speed limits that are not
+    /// larger than zero, and speed params that are invalid or contradict each other, any of
+    /// which would cause incorrect speed limit selection.  Returns an error listing every
+    /// offending link.
+    pub fn validate_speed_sets(&self) -> anyhow::Result<()> {
+        let mut errors = ValidationErrors::new();
+        for link in self.1.iter().skip(1) {
+            let mut link_errors = ValidationErrors::new();
+            let mut train_types: Vec<&TrainType> = link.speed_sets.keys().collect();
+            train_types.sort_by_key(|train_type| **train_type as u8);
+            for train_type in train_types {
+                if train_type.is_fake() {
+                    link_errors.push(anyhow!(
+                        "Train type {:?} is not a valid train type!",
+                        train_type
+                    ));
+                }
+                if let Err(mut errors_add) = link.speed_sets[train_type].validate_limits() {
+                    errors_add
+                        .add_context(anyhow!("Speed set for {:?} must be valid!", train_type));
+                    link_errors.append(&mut errors_add);
+                }
+            }
+            if let Some(speed_set) = &link.speed_set {
+                if let Err(mut errors_add) = speed_set.validate_limits() {
+                    errors_add.add_context(anyhow!("Speed set must be valid!"));
+                    link_errors.append(&mut errors_add);
+                }
+            }
+            if !link_errors.is_empty() {
+                link_errors.add_context(anyhow!("Link {} has invalid speed sets!", link.idx_curr));
+                errors.append(&mut link_errors);
+            }
+        }
+        errors
+            .make_err()
+            .map_err(|err| anyhow!("{}\n{}", format_dbg!(), err))
+    }
 }
 
 impl ObjState for Network {
@@ -460,22 +1034,8 @@ impl ObjState for Network {
 impl SerdeAPI for Network {
     fn from_file<P: AsRef<Path>>(filepath: P, skip_init: bool) -> Result<Self, Error> {
         let filepath = filepath.as_ref();
-        let extension = filepath
-            .extension()
-            .and_then(OsStr::to_str)
-            .ok_or_else(|| {
-                Error::SerdeError(format!("File extension could not be parsed: {filepath:?}"))
-            })?;
-        let mut file = File::open(filepath).map_err(|err| {
-            Error::SerdeError(format!(
-                "{err}\n{}",
-                if !filepath.exists() {
-                    format!("File not found: {filepath:?}")
-                } else {
-                    format!("Could not open file: {filepath:?}")
-                }
-            ))
-        })?;
+        let (extension, _) = file_format(filepath)?;
+        let mut file = open_file_reader(filepath)?;
         match Self::from_reader(&mut file, extension, skip_init) {
             Ok(network) => {
                 // init already happened in `from_reader`
@@ -929,6 +1489,58 @@ mod tests {
         tempdir.close().unwrap();
     }
 
+    #[test]
+    fn test_to_and_from_gz_file() {
+        let network = Network(Default::default(), Vec::<Link>::valid());
+        let tempdir = tempfile::tempdir().unwrap();
+        let temp_file_path = tempdir.path().join("network.yaml.gz");
+        network.to_file(&temp_file_path).unwrap();
+        // file is actually compressed rather than plain yaml
+        assert!(std::fs::read_to_string(&temp_file_path).is_err());
+        assert_eq!(Network::from_file(&temp_file_path, false).unwrap(), network);
+        tempdir.close().unwrap();
+    }
+
+    #[test]
+    fn test_set_elev_from_grade() {
+        let mut link = Link {
+            length: 1000.0 * uc::M,
+            ..Link::valid()
+        };
+        link.set_elev_from_grade(
+            vec![si::Length::ZERO, 500.0 * uc::M],
+            vec![0.01 * uc::R, 0.01 * uc::R],
+            100.0 * uc::M,
+        )
+        .unwrap();
+        assert_eq!(link.elevs.len(), 3);
+        assert_eq!(link.elevs.first().unwrap().offset, si::Length::ZERO);
+        assert_eq!(link.elevs.last().unwrap().offset, link.length);
+        assert!(almost_eq(
+            (link.elevs.last().unwrap().elev - link.elevs[0].elev).get::<si::meter>(),
+            10.0,
+            None
+        ));
+        link.elevs.validate().unwrap();
+
+        // unsorted offsets
+        assert!(link
+            .set_elev_from_grade(
+                vec![si::Length::ZERO, 600.0 * uc::M, 500.0 * uc::M],
+                vec![0.01 * uc::R; 3],
+                si::Length::ZERO,
+            )
+            .is_err());
+        // non-finite grade
+        assert!(link
+            .set_elev_from_grade(
+                vec![si::Length::ZERO],
+                vec![f64::NAN * uc::R],
+                si::Length::ZERO,
+            )
+            .is_err());
+    }
+
     #[test]
     fn test_set_speed_set_from_train_type() {
         let network_file_path = project_root::get_project_root()
@@ -953,4 +1565,279 @@ mod tests {
         assert!(network_speed_set.1[0].speed_sets.is_empty());
         assert!(network_speed_sets.1[0].speed_set.is_none());
     }
+
+    #[test]
+    fn test_speed_limit_at_offset() {
+        let transition = 4000.0 * uc::M;
+        let mut link = Link {
+            length: 10000.0 * uc::M,
+            speed_set: Some(SpeedSet {
+                speed_limits: vec![
+                    SpeedLimit {
+                        offset_start: si::Length::ZERO,
+                        offset_end: transition,
+                        speed: 30.0 * uc::MPS,
+                    },
+                    SpeedLimit {
+                        offset_start: transition,
+                        offset_end: 10000.0 * uc::M,
+                        speed: 15.0 * uc::MPS,
+                    },
+                ],
+                speed_params: vec![],
+                is_head_end: false,
+            }),
+            ..Link::valid()
+        };
+        assert_eq!(
+            link.speed_limit_at_offset(transition - 1.0 * uc::M)
+                .unwrap(),
+            30.0 * uc::MPS
+        );
+        assert_eq!(
+            link.speed_limit_at_offset(transition + 1.0 * uc::M)
+                .unwrap(),
+            15.0 * uc::MPS
+        );
+        assert!(link.speed_limit_at_offset(link.length * 2.0).is_err());
+
+        // ambiguous without a train type
+        link.speed_set = None;
+        link.speed_sets = HashMap::from([
+            (TrainType::Freight, SpeedSet::valid()),
+            (TrainType::Passenger, SpeedSet::valid()),
+        ]);
+        assert!(link.speed_limit_at_offset(transition).is_err());
+        link.set_speed_set_for_train_type(TrainType::Freight)
+            .unwrap();
+        assert!(link.speed_limit_at_offset(transition).is_ok());
+    }
+
+    #[test]
+    fn test_link_length() {
+        let mut links = Vec::<Link>::valid();
+        links.push(Link {
+            idx_curr: LinkIdx::new(2),
+            length: 2.0 * Link::valid().length,
+            ..Link::valid()
+        });
+        let network = Network(Default::default(), links);
+        let lengths: Vec<si::Length> = (1..network.1.len() as u32)
+            .map(|idx| network.link_length(LinkIdx::new(idx)).unwrap())
+            .collect();
+        assert!(lengths.iter().all(|length| *length > si::Length::ZERO));
+        assert_eq!(
+            network.total_track_length(),
+            lengths.iter().fold(si::Length::ZERO, |acc, l| acc + *l)
+        );
+        assert!(network.link_length(LinkIdx::new(0)).is_err());
+        assert!(network.link_length(LinkIdx::new(3)).is_err());
+    }
+
+    #[test]
+    fn test_adjacency() {
+        let link = |idx_curr: u32, idx_next: u32, idx_next_alt: u32| Link {
+            idx_curr: LinkIdx::new(idx_curr),
+            idx_next: LinkIdx::new(idx_next),
+            idx_next_alt: LinkIdx::new(idx_next_alt),
+            ..Link::valid()
+        };
+        let network = Network(
+            Default::default(),
+            vec![Link::default(), link(1, 2, 3), link(2, 0, 0), link(3, 2, 0)],
+        );
+        let adjacency = network.adjacency();
+        assert_eq!(adjacency.len(), 3);
+        assert_eq!(
+            adjacency[0],
+            (LinkIdx::new(1), vec![LinkIdx::new(2), LinkIdx::new(3)])
+        );
+        assert_eq!(adjacency[1], (LinkIdx::new(2), vec![]));
+        assert_eq!(adjacency[2], (LinkIdx::new(3), vec![LinkIdx::new(2)]));
+    }
+
+    #[test]
+    fn test_merge() {
+        let link = |idx_curr: u32, idx_next: u32, idx_prev: u32| Link {
+            idx_curr: LinkIdx::new(idx_curr),
+            idx_next: LinkIdx::new(idx_next),
+            idx_prev: LinkIdx::new(idx_prev),
+            ..Link::valid()
+        };
+        let sub_network = Network(
+            Default::default(),
+            vec![Link::default(), link(1, 2, 0), link(2, 0, 1)],
+        );
+        let mut network = sub_network.clone();
+        network
+            .merge(
+                sub_network.clone(),
+                vec![(LinkIdx::new(2), LinkIdx::new(1))],
+            )
+            .unwrap();
+        assert_eq!(network.1.len(), 5);
+        for (idx, link) in network.1.iter().enumerate().skip(1) {
+            assert_eq!(link.idx_curr.idx(), idx);
+        }
+        assert_eq!(network.1[2].idx_next, LinkIdx::new(3));
+        assert_eq!(network.1[3].idx_prev, LinkIdx::new(2));
+        assert_eq!(network.1[3].idx_next, LinkIdx::new(4));
+        assert_eq!(network.1[4].idx_prev, LinkIdx::new(3));
+        network.validate_connectivity().unwrap();
+
+        // a path can be routed across the join
+        let mut path_tpc = crate::track::PathTpc::default();
+        path_tpc
+            .extend(&network.1, [1, 2, 3, 4].map(LinkIdx::new))
+            .unwrap();
+
+        // joins must reference existing links with a free successor
+        let mut network_bad = sub_network.clone();
+        assert!(network_bad
+            .merge(
+                sub_network.clone(),
+                vec![(LinkIdx::new(1), LinkIdx::new(1))],
+            )
+            .is_ok());
+        assert!(network_bad
+            .merge(
+                sub_network.clone(),
+                vec![(LinkIdx::new(1), LinkIdx::new(1))],
+            )
+            .is_err());
+        assert_eq!(network_bad.1.len(), 5);
+        let mut network_bad = sub_network.clone();
+        assert!(network_bad
+            .merge(
+                sub_network.clone(),
+                vec![(LinkIdx::new(2), LinkIdx::new(5))],
+            )
+            .is_err());
+        assert_eq!(network_bad, sub_network);
+    }
+
+    #[test]
+    fn test_validate_connectivity() {
+        let link = |idx_curr: u32, idx_next: u32, idx_prev: u32| Link {
+            idx_curr: LinkIdx::new(idx_curr),
+            idx_next: LinkIdx::new(idx_next),
+            idx_prev: LinkIdx::new(idx_prev),
+            ..Link::valid()
+        };
+        let mut network = Network(
+            Default::default(),
+            vec![Link::default(), link(1, 2, 0), link(2, 3, 1), link(3, 0, 2)],
+        );
+        network.validate_connectivity().unwrap();
+
+        // single link networks have no connections but are fine
+        Network(Default::default(), Vec::<Link>::valid())
+            .validate_connectivity()
+            .unwrap();
+
+        // dangling link
+        network.1.push(link(4, 0, 0));
+        let err = network.validate_connectivity().unwrap_err().to_string();
+        assert!(err.contains("Links [4] have no valid predecessors or successors"));
+        assert!(!err.contains("out of range"));
+
+        // out-of-range successor
+        network.1[3].idx_next = LinkIdx::new(10);
+        let err = network.validate_connectivity().unwrap_err().to_string();
+        assert!(err.contains("Links [3] reference link indices that are out of range"));
+        assert!(err.contains("Links [4] have"));
+    }
+
+    #[test]
+    fn test_path_between_locations() {
+        let link = |idx_curr: u32, idx_next: u32, idx_next_alt: u32, length: f64| Link {
+            idx_curr: LinkIdx::new(idx_curr),
+            idx_next: LinkIdx::new(idx_next),
+            idx_next_alt: LinkIdx::new(idx_next_alt),
+            length: length * uc::M,
+            ..Link::valid()
+        };
+        // link 1 branches to a short route via link 2 and a long route via
+        // link 4, both of which lead to link 3
+        let network = Network(
+            Default::default(),
+            vec![
+                Link::default(),
+                link(1, 4, 2, 1000.0),
+                link(2, 3, 0, 1000.0),
+                link(3, 0, 0, 1000.0),
+                link(4, 3, 0, 5000.0),
+            ],
+        );
+        let location = |name: &str, idx: u32| Location {
+            location_id: name.into(),
+            link_idx: LinkIdx::new(idx),
+            ..Default::default()
+        };
+        let location_map = LocationMap::from([
+            ("A".to_string(), vec![location("A", 1)]),
+            ("B".to_string(), vec![location("B", 3)]),
+            ("C".to_string(), vec![location("C", 10)]),
+        ]);
+
+        assert_eq!(
+            network.find_location(&location_map, "B"),
+            Some(location("B", 3))
+        );
+        // locations on links outside the network are not found
+        assert!(network.find_location(&location_map, "C").is_none());
+        assert!(network.find_location(&location_map, "D").is_none());
+
+        assert_eq!(
+            network
+                .path_between_locations(&location_map, "A", "B")
+                .unwrap(),
+            LinkPath(vec![LinkIdx::new(1), LinkIdx::new(2), LinkIdx::new(3)])
+        );
+        assert_eq!(
+            network
+                .path_between_locations(&location_map, "A", "A")
+                .unwrap(),
+            LinkPath(vec![LinkIdx::new(1)])
+        );
+        // no path against the direction of travel
+        assert!(network
+            .path_between_locations(&location_map, "B", "A")
+            .is_err());
+        assert!(network
+            .path_between_locations(&location_map, "A", "C")
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_speed_sets() {
+        let mut network = Network(Default::default(), Vec::<Link>::valid());
+        network.validate_speed_sets().unwrap();
+
+        let mut speed_set = SpeedSet::valid();
+        speed_set.speed_params = vec![
+            SpeedParam {
+                limit_val: 100.0,
+                limit_type: LimitType::MassTotal,
+                compare_type: CompareType::TpGreaterThanRp,
+            },
+            SpeedParam {
+                limit_val: 50.0,
+                limit_type: LimitType::MassTotal,
+                compare_type: CompareType::TpLessThanRp,
+            },
+        ];
+        network.1[1]
+            .speed_sets
+            .insert(TrainType::Freight, speed_set);
+        let err = network.validate_speed_sets().unwrap_err().to_string();
+        assert!(err.contains(&format!("Link {}", network.1[1].idx_curr)));
+        assert!(err.contains("MassTotal"));
+
+        let mut network = Network(Default::default(), Vec::<Link>::valid());
+        network.1[1]
+            .speed_sets
+            .insert(TrainType::None, SpeedSet::valid());
+        network.validate_speed_sets().unwrap_err();
+    }
 }
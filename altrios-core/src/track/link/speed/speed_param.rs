@@ -62,6 +62,82 @@ impl Valid for SpeedParam {
     }
 }
 
+impl LimitType {
+    /// Array of all [LimitType] variants
+    pub const ALL: [Self; 3] = [Self::MassTotal, Self::MassPerBrake, Self::AxleCount];
+}
+
+/// Returns the [LimitType]s for which the [CompareType]s in `speed_params` cannot all be
+/// satisfied at the same time, in which case the corresponding speed set can never apply
+pub fn contradictory_limit_types(speed_params: &[SpeedParam]) -> Vec<LimitType> {
+    LimitType::ALL
+        .into_iter()
+        .filter(|limit_type| {
+            // feasible train parameter range as (value, is inclusive) bounds
+            let mut lower = (f64::NEG_INFINITY, true);
+            let mut upper = (f64::INFINITY, true);
+            for speed_param in speed_params
+                .iter()
+                .filter(|sp| sp.limit_type == *limit_type)
+            {
+                let val = speed_param.limit_val;
+                let (lower_new, upper_new) = match (speed_param.compare_type, limit_type) {
+                    (CompareType::TpEqualRp, _) => (Some((val, true)), Some((val, true))),
+                    (CompareType::TpGreaterThanRp, LimitType::AxleCount) => {
+                        (Some((val.floor() + 1.0, true)), None)
+                    }
+                    (CompareType::TpGreaterThanRp, _) => (Some((val, false)), None),
+                    (CompareType::TpLessThanRp, LimitType::AxleCount) => {
+                        (None, Some((val.ceil() - 1.0, true)))
+                    }
+                    (CompareType::TpLessThanRp, _) => (None, Some((val, false))),
+                    (CompareType::TpGreaterThanEqualRp, _) => (Some((val, true)), None),
+                    (CompareType::TpLessThanEqualRp, _) => (None, Some((val, true))),
+                };
+                if let Some(lower_new) = lower_new {
+                    if lower_new.0 > lower.0 || (lower_new.0 == lower.0 && !lower_new.1) {
+                        lower = lower_new;
+                    }
+                }
+                if let Some(upper_new) = upper_new {
+                    if upper_new.0 < upper.0 || (upper_new.0 == upper.0 && !upper_new.1) {
+                        upper = upper_new;
+                    }
+                }
+            }
+            lower.0 > upper.0 || (lower.0 == upper.0 && !(lower.1 && upper.1))
+        })
+        .collect()
+}
+
+/// Returns the [LimitType]s that have more than one lower bound, more than one upper
+/// bound, or an equality alongside any other [CompareType] in `speed_params`, in which
+/// case some of those speed params are implied by the others.  [LimitType]s returned
+/// by [contradictory_limit_types] are excluded.
+pub fn redundant_limit_types(speed_params: &[SpeedParam]) -> Vec<LimitType> {
+    let contradictory = contradictory_limit_types(speed_params);
+    LimitType::ALL
+        .into_iter()
+        .filter(|limit_type| !contradictory.contains(limit_type))
+        .filter(|limit_type| {
+            let (mut n_eq, mut n_lower, mut n_upper) = (0, 0, 0);
+            for speed_param in speed_params
+                .iter()
+                .filter(|sp| sp.limit_type == *limit_type)
+            {
+                match speed_param.compare_type {
+                    CompareType::TpEqualRp => n_eq += 1,
+                    CompareType::TpGreaterThanRp | CompareType::TpGreaterThanEqualRp => {
+                        n_lower += 1
+                    }
+                    CompareType::TpLessThanRp | CompareType::TpLessThanEqualRp => n_upper += 1,
+                }
+            }
+            n_lower > 1 || n_upper > 1 || (n_eq > 0 && n_eq + n_lower + n_upper > 1)
+        })
+        .collect()
+}
+
 impl ObjState for SpeedParam {
     fn validate(&self) -> ValidationResults {
         let mut errors = ValidationErrors::new();
@@ -128,4 +204,72 @@ mod test_speed_params {
     check_cases!(Vec<SpeedParam>);
     check_vec_elems!(SpeedParam);
     check_vec_duplicates!(SpeedParam);
+
+    #[test]
+    fn test_contradictory_limit_types() {
+        let speed_param = |limit_val, limit_type, compare_type| SpeedParam {
+            limit_val,
+            limit_type,
+            compare_type,
+        };
+        assert!(contradictory_limit_types(&Vec::<SpeedParam>::valid()).is_empty());
+        assert!(contradictory_limit_types(&[
+            speed_param(100.0, LimitType::MassTotal, CompareType::TpGreaterThanRp),
+            speed_param(200.0, LimitType::MassTotal, CompareType::TpLessThanEqualRp),
+            speed_param(50.0, LimitType::MassPerBrake, CompareType::TpLessThanRp),
+        ])
+        .is_empty());
+        assert_eq!(
+            contradictory_limit_types(&[
+                speed_param(
+                    100.0,
+                    LimitType::MassTotal,
+                    CompareType::TpGreaterThanEqualRp
+                ),
+                speed_param(100.0, LimitType::MassTotal, CompareType::TpLessThanRp),
+                speed_param(50.0, LimitType::MassPerBrake, CompareType::TpLessThanRp),
+            ]),
+            vec![LimitType::MassTotal]
+        );
+        assert_eq!(
+            contradictory_limit_types(&[
+                speed_param(4.0, LimitType::AxleCount, CompareType::TpGreaterThanRp),
+                speed_param(5.0, LimitType::AxleCount, CompareType::TpLessThanRp),
+            ]),
+            vec![LimitType::AxleCount]
+        );
+    }
+
+    #[test]
+    fn test_redundant_limit_types() {
+        let speed_param = |limit_val, limit_type, compare_type| SpeedParam {
+            limit_val,
+            limit_type,
+            compare_type,
+        };
+        assert!(redundant_limit_types(&[
+            speed_param(100.0, LimitType::MassTotal, CompareType::TpGreaterThanRp),
+            speed_param(200.0, LimitType::MassTotal, CompareType::TpLessThanEqualRp),
+        ])
+        .is_empty());
+        assert_eq!(
+            redundant_limit_types(&[
+                speed_param(100.0, LimitType::MassTotal, CompareType::TpGreaterThanRp),
+                speed_param(
+                    50.0,
+                    LimitType::MassTotal,
+                    CompareType::TpGreaterThanEqualRp
+                ),
+                speed_param(4.0, LimitType::AxleCount, CompareType::TpEqualRp),
+                speed_param(6.0, LimitType::AxleCount, CompareType::TpLessThanRp),
+            ]),
+            vec![LimitType::MassTotal, LimitType::AxleCount]
+        );
+        // contradictory limit types are not also reported as redundant
+        assert!(redundant_limit_types(&[
+            speed_param(4.0, LimitType::AxleCount, CompareType::TpEqualRp),
+            speed_param(5.0, LimitType::AxleCount, CompareType::TpEqualRp),
+        ])
+        .is_empty());
+    }
 }
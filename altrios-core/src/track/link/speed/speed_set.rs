@@ -30,6 +30,22 @@ impl ObjState for TrainType {
     }
 }
 
+impl TryFrom<&str> for TrainType {
+    type Error = anyhow::Error;
+    fn try_from(value: &str) -> anyhow::Result<Self> {
+        Ok(match value {
+            "None" => Self::None,
+            "Freight" => Self::Freight,
+            "Passenger" => Self::Passenger,
+            "Intermodal" => Self::Intermodal,
+            "HighSpeedPassenger" => Self::HighSpeedPassenger,
+            "TiltTrain" => Self::TiltTrain,
+            "Commuter" => Self::Commuter,
+            _ => bail!("{}\nUnrecognized `TrainType`: `{}`", format_dbg!(), value),
+        })
+    }
+}
+
 #[serde_api]
 #[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
@@ -44,6 +60,32 @@ pub struct SpeedSet {
 #[pyo3_api]
 impl SpeedSet {}
 
+impl SpeedSet {
+    /// Validates [Self], additionally checking that all speed limits and speed param limit
+    /// values are larger than zero
+    pub fn validate_limits(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        if let Err(mut errors_add) = self.validate() {
+            errors.append(&mut errors_add);
+        }
+        for speed_limit in &self.speed_limits {
+            si_chk_num_gtz(&mut errors, &speed_limit.speed, "Speed limit");
+        }
+        for speed_param in &self.speed_params {
+            if let None | Some(Ordering::Less) | Some(Ordering::Equal) =
+                speed_param.limit_val.partial_cmp(&0.0)
+            {
+                errors.push(anyhow!(
+                    "Limit val for {:?} = {:?} must be a number larger than zero!",
+                    speed_param.limit_type,
+                    speed_param.limit_val,
+                ));
+            }
+        }
+        errors.make_err()
+    }
+}
+
 impl Init for SpeedSet {}
 impl SerdeAPI for SpeedSet {}
 
@@ -78,25 +120,10 @@ impl Valid for SpeedSet {
 
 impl ObjState for &SpeedSet {
     fn is_fake(&self) -> bool {
-        self.speed_limits.is_empty()
+        (**self).is_fake()
     }
     fn validate(&self) -> ValidationResults {
-        let mut errors = ValidationErrors::new();
-        if self.is_fake() {
-            validate_field_fake(&mut errors, &self.speed_limits, "Speed limits");
-
-            if !self.speed_params.is_empty() {
-                errors.push(anyhow!("Speed params must be empty!"));
-            }
-            if self.is_head_end {
-                errors.push(anyhow!("Is head end must be false!"));
-            }
-        } else {
-            validate_field_real(&mut errors, &self.speed_limits, "Speed limits");
-            validate_field_real(&mut errors, &self.speed_params, "Speed params");
-        }
-
-        errors.make_err()
+        (**self).validate()
     }
 }
 
@@ -118,6 +145,16 @@ impl ObjState for SpeedSet {
         } else {
             validate_field_real(&mut errors, &self.speed_limits, "Speed limits");
             validate_field_real(&mut errors, &self.speed_params, "Speed params");
+            // empty speed params are allowed and mean the speed set applies to all trains
+            for limit_type in contradictory_limit_types(&self.speed_params) {
+                errors.push(anyhow!(
+                    "Speed params for {:?} contradict each other!",
+                    limit_type
+                ));
+            }
+            for limit_type in redundant_limit_types(&self.speed_params) {
+                errors.push(anyhow!("Speed params for {:?} are redundant!", limit_type));
+            }
         }
 
         errors.make_err()
@@ -226,6 +263,33 @@ mod test_speed_set {
         }
     }
     check_cases!(SpeedParam);
+
+    #[test]
+    fn test_speed_set_contradictory_params() {
+        let speed_param = |limit_val, compare_type| SpeedParam {
+            limit_val,
+            limit_type: LimitType::MassTotal,
+            compare_type,
+        };
+        let mut speed_set = SpeedSet {
+            speed_params: vec![
+                speed_param(200.0, CompareType::TpGreaterThanEqualRp),
+                speed_param(100.0, CompareType::TpLessThanEqualRp),
+            ],
+            ..SpeedSet::valid()
+        };
+        assert!(speed_set.validate().is_err());
+        let speed_set_ref: &SpeedSet = &speed_set;
+        assert!(ObjState::validate(&speed_set_ref).is_err());
+
+        speed_set.speed_params[1].limit_val = 300.0;
+        speed_set.validate().unwrap();
+
+        speed_set
+            .speed_params
+            .push(speed_param(400.0, CompareType::TpLessThanRp));
+        assert!(speed_set.validate().is_err());
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,65 @@
+use crate::imports::*;
+
+/// Model for deriving a speed ceiling through curves from superelevation and
+/// allowable unbalance (i.e. cant deficiency), used by [super::PathTpc] when
+/// `curve_speed_limit` is provided
+#[serde_api]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+pub struct CurveSpeedModel {
+    /// Height of outer rail above inner rail, assumed uniform for all curves
+    pub superelevation: si::Length,
+    /// Additional superelevation that would be needed to fully balance lateral
+    /// acceleration at the speed ceiling
+    pub cant_deficiency: si::Length,
+}
+
+#[pyo3_api]
+impl CurveSpeedModel {
+    #[new]
+    fn __new__(superelevation_meters: f64, cant_deficiency_meters: f64) -> Self {
+        Self {
+            superelevation: superelevation_meters * uc::M,
+            cant_deficiency: cant_deficiency_meters * uc::M,
+        }
+    }
+}
+
+impl Init for CurveSpeedModel {}
+impl SerdeAPI for CurveSpeedModel {}
+
+impl Default for CurveSpeedModel {
+    /// No superelevation and 3 inches of cant deficiency
+    fn default() -> Self {
+        Self {
+            superelevation: si::Length::ZERO,
+            cant_deficiency: 3.0 * 0.0254 * uc::M,
+        }
+    }
+}
+
+impl CurveSpeedModel {
+    /// Returns max speed through a curve with given `curvature` (i.e. inverse
+    /// of radius) such that unbalanced lateral acceleration does not exceed that
+    /// corresponding to `self.cant_deficiency`
+    pub fn speed_max(&self, curvature: si::Curvature) -> si::Velocity {
+        let curvature = curvature.get::<si::radian_per_meter>().abs();
+        if curvature == 0.0 {
+            return f64::INFINITY * uc::MPS;
+        }
+        let radius = uc::M / curvature;
+        (uc::ACC_GRAV * (self.superelevation + self.cant_deficiency) / uc::GAUGE_EFF * radius)
+            .sqrt()
+    }
+}
+
+impl Valid for CurveSpeedModel {}
+
+impl ObjState for CurveSpeedModel {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gez(&mut errors, &self.superelevation, "Superelevation");
+        si_chk_num_gtz(&mut errors, &self.cant_deficiency, "Cant deficiency");
+        errors.make_err()
+    }
+}
@@ -1,9 +1,11 @@
+mod curve_speed_model;
 mod link_point;
 mod path_res_coeff;
 mod path_tpc;
 mod speed_point;
 mod train_params;
 
+pub use curve_speed_model::*;
 pub use link_point::*;
 pub use path_res_coeff::*;
 pub use path_tpc::*;
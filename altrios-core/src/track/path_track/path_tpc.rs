@@ -1,4 +1,5 @@
 use super::super::link::*;
+use super::curve_speed_model::*;
 use super::link_point::*;
 use super::path_res_coeff::*;
 use super::speed_point::*;
@@ -19,10 +20,63 @@ pub struct PathTpc {
     cat_power_limits: Vec<CatPowerLimit>,
     train_params: TrainParams,
     is_finished: bool,
+    /// If provided, curvature-derived speed ceilings are combined with the
+    /// civil speed limits in `speed_points`
+    #[serde(default)]
+    curve_speed_limit: Option<CurveSpeedModel>,
 }
 
 #[pyo3_api]
-impl PathTpc {}
+impl PathTpc {
+    #[pyo3(name = "elevation_profile")]
+    /// Returns tuple of cumulative offsets (m) and corresponding elevations (m)
+    fn elevation_profile_py(&self) -> (Vec<f64>, Vec<f64>) {
+        let (offsets, elevs) = self.elevation_profile();
+        (
+            offsets.iter().map(|x| x.get::<si::meter>()).collect(),
+            elevs.iter().map(|x| x.get::<si::meter>()).collect(),
+        )
+    }
+
+    #[getter("speed_points")]
+    /// Tuple of offsets (m) and speed limits (m/s) of speed points
+    fn get_speed_points_py(&self) -> (Vec<f64>, Vec<f64>) {
+        let (offsets, speed_limits) = self.speed_point_arrays();
+        (
+            offsets.iter().map(|x| x.get::<si::meter>()).collect(),
+            speed_limits
+                .iter()
+                .map(|x| x.get::<si::meter_per_second>())
+                .collect(),
+        )
+    }
+
+    #[getter("grade_points")]
+    /// Tuple of offsets (m) and grades of grade points
+    fn get_grade_points_py(&self) -> (Vec<f64>, Vec<f64>) {
+        let (offsets, grades) = Self::res_coeff_arrays(&self.grades);
+        (
+            offsets.iter().map(|x| x.get::<si::meter>()).collect(),
+            grades.iter().map(|x| x.get::<si::ratio>()).collect(),
+        )
+    }
+
+    #[getter("curve_points")]
+    /// Tuple of offsets (m) and curve resistance coefficients of curve points
+    fn get_curve_points_py(&self) -> (Vec<f64>, Vec<f64>) {
+        let (offsets, curves) = Self::res_coeff_arrays(&self.curves);
+        (
+            offsets.iter().map(|x| x.get::<si::meter>()).collect(),
+            curves.iter().map(|x| x.get::<si::ratio>()).collect(),
+        )
+    }
+
+    #[pyo3(name = "set_curve_speed_limit")]
+    #[pyo3(signature = (curve_speed_limit=None))]
+    fn set_curve_speed_limit_py(&mut self, curve_speed_limit: Option<CurveSpeedModel>) {
+        self.set_curve_speed_limit(curve_speed_limit);
+    }
+}
 
 impl Init for PathTpc {}
 impl SerdeAPI for PathTpc {}
@@ -59,6 +113,259 @@ impl PathTpc {
     pub fn is_finished(&self) -> bool {
         self.is_finished
     }
+    pub fn curve_speed_limit(&self) -> Option<&CurveSpeedModel> {
+        self.curve_speed_limit.as_ref()
+    }
+    /// Sets curvature-based speed limit model, which applies to links added by
+    /// subsequent calls to [Self::extend] and to all links after
+    /// [Self::recalc_speeds]
+    pub fn set_curve_speed_limit(&mut self, curve_speed_limit: Option<CurveSpeedModel>) {
+        self.curve_speed_limit = curve_speed_limit;
+    }
+
+    /// Returns cumulative offsets and corresponding elevations along the path
+    /// assembled from the [Elev] arrays of the concatenated links.  Each link
+    /// boundary point appears only once.
+    pub fn elevation_profile(&self) -> (Vec<si::Length>, Vec<si::Length>) {
+        // `res_net` for grades is the elevation at `offset`, and points added by
+        // `finish` are at infinite offset
+        self.grades
+            .iter()
+            .filter(|grade| grade.offset.is_finite())
+            .map(|grade| (grade.offset, grade.res_net))
+            .unzip()
+    }
+
+    /// Returns parallel vectors of offsets and speed limits of [Self::speed_points]
+    pub fn speed_point_arrays(&self) -> (Vec<si::Length>, Vec<si::Velocity>) {
+        self.speed_points
+            .iter()
+            .map(|sp| (sp.offset, sp.speed_limit))
+            .unzip()
+    }
+
+    /// Returns parallel vectors of offsets and resistance coefficients of
+    /// `res_coeffs`, e.g. [Self::grades] or [Self::curves]
+    pub fn res_coeff_arrays(res_coeffs: &[PathResCoeff]) -> (Vec<si::Length>, Vec<si::Ratio>) {
+        res_coeffs
+            .iter()
+            .map(|rc| (rc.offset, rc.res_coeff))
+            .unzip()
+    }
+
+    /// Returns elevation at `offset`, interpolated from grades
+    pub fn elev_at(&self, offset: si::Length) -> si::Length {
+        let idx = self
+            .grades
+            .partition_point(|grade| grade.offset <= offset)
+            .max(1)
+            - 1;
+        self.grades[idx].calc_res_val(offset)
+    }
+
+    /// Returns average grade over `lookahead` distance in front of `offset`,
+    /// truncated at the end of the path
+    pub fn grade_ahead(&self, offset: si::Length, lookahead: si::Length) -> si::Ratio {
+        let offset_ahead = (offset + lookahead).min(self.offset_end());
+        if offset_ahead > offset {
+            (self.elev_at(offset_ahead) - self.elev_at(offset)) / (offset_ahead - offset)
+        } else {
+            si::Ratio::ZERO
+        }
+    }
+
+    /// Returns steepest grade, positive uphill, between [Self::offset_begin]
+    /// and [Self::offset_end]
+    pub fn max_grade(&self) -> anyhow::Result<si::Ratio> {
+        ensure!(
+            !self.link_points.is_empty() && self.grades.len() >= 2,
+            "{}\nExpected path with at least one grade segment",
+            format_dbg!()
+        );
+        let (offset_begin, offset_end) = (self.offset_begin(), self.offset_end());
+        Ok(self
+            .grades
+            .windows(2)
+            .filter(|w| w[0].offset < offset_end && w[1].offset > offset_begin)
+            .fold(f64::NEG_INFINITY * uc::R, |acc, w| acc.max(w[0].res_coeff)))
+    }
+
+    /// Returns ruling grade, i.e. the steepest average grade over any stretch
+    /// of at least `length_min` between [Self::offset_begin] and
+    /// [Self::offset_end], which excludes short spikes that a train can
+    /// climb on momentum.  If the path is shorter than `length_min`, the
+    /// average grade of the whole path is returned.
+    pub fn ruling_grade(&self, length_min: si::Length) -> anyhow::Result<si::Ratio> {
+        ensure!(
+            !self.link_points.is_empty() && self.grades.len() >= 2,
+            "{}\nExpected path with at least one grade segment",
+            format_dbg!()
+        );
+        ensure!(
+            length_min > si::Length::ZERO && length_min.is_finite(),
+            "{}\nExpected positive, finite `length_min`, got {:?}",
+            format_dbg!(),
+            length_min
+        );
+        let (offset_begin, offset_end) = (self.offset_begin(), self.offset_end());
+        if offset_end - offset_begin <= length_min {
+            return Ok(self.grade_ahead(offset_begin, offset_end - offset_begin));
+        }
+        // Elevation is piecewise linear, so the average grade over a window of
+        // fixed length is maximized with one of its ends at a grade change.
+        let offset_start_max = offset_end - length_min;
+        Ok(self
+            .grades
+            .iter()
+            .filter(|grade| grade.offset.is_finite())
+            .flat_map(|grade| [grade.offset, grade.offset - length_min])
+            .chain([offset_begin, offset_start_max])
+            .filter(|offset| *offset >= offset_begin && *offset <= offset_start_max)
+            .fold(f64::NEG_INFINITY * uc::R, |acc, offset| {
+                acc.max((self.elev_at(offset + length_min) - self.elev_at(offset)) / length_min)
+            }))
+    }
+
+    /// Splits `self` at `offset` into the portions before and after `offset`, e.g. to
+    /// simulate only part of a route.  Grades, curves, speed limits, and catenary power
+    /// limits are interpolated or clipped at `offset`, and a link that straddles `offset`
+    /// appears at the end of the first portion and at the start of the second.  If `self`
+    /// is finished, both portions are finished.
+    pub fn split_at_offset(&self, offset: si::Length) -> anyhow::Result<(PathTpc, PathTpc)> {
+        ensure!(
+            self.link_points.len() >= 2,
+            "{}\nExpected path with at least one link",
+            format_dbg!()
+        );
+        ensure!(
+            offset > self.offset_begin() && offset < self.offset_end(),
+            "{}\nExpected `offset` = {:?} to be within ({:?}, {:?})",
+            format_dbg!(),
+            offset,
+            self.offset_begin(),
+            self.offset_end()
+        );
+
+        // `res_coeffs` before `offset`, ending with a point at `offset`, and
+        // `res_coeffs` after `offset`, starting with a point at `offset`
+        let split_res_coeffs = |res_coeffs: &[PathResCoeff]| {
+            let idx = res_coeffs.partition_point(|rc| rc.offset <= offset) - 1;
+            let res_net = res_coeffs[idx].calc_res_val(offset);
+            let mut before = res_coeffs[..idx + 1]
+                .iter()
+                .filter(|rc| rc.offset < offset)
+                .copied()
+                .collect::<Vec<_>>();
+            before.push(PathResCoeff {
+                offset,
+                res_net,
+                ..Default::default()
+            });
+            let mut after = vec![PathResCoeff {
+                offset,
+                res_coeff: res_coeffs[idx].res_coeff,
+                res_net,
+            }];
+            after.extend_from_slice(&res_coeffs[idx + 1..]);
+            (before, after)
+        };
+        let (grades_before, grades_after) = split_res_coeffs(&self.grades);
+        let (curves_before, curves_after) = split_res_coeffs(&self.curves);
+
+        let idx_speed = self.speed_points.partition_point(|sp| sp.offset <= offset) - 1;
+        let speed_points_before = self.speed_points[..idx_speed + 1]
+            .iter()
+            .filter(|sp| sp.offset < offset)
+            .copied()
+            .collect::<Vec<_>>();
+        let mut speed_points_after = vec![SpeedLimitPoint {
+            offset,
+            speed_limit: self.speed_points[idx_speed].speed_limit,
+        }];
+        speed_points_after.extend_from_slice(&self.speed_points[idx_speed + 1..]);
+
+        let cat_power_limits_before = self
+            .cat_power_limits
+            .iter()
+            .filter(|cpl| cpl.offset_start < offset)
+            .map(|cpl| CatPowerLimit {
+                offset_end: cpl.offset_end.min(offset),
+                ..cpl.clone()
+            })
+            .collect();
+        let cat_power_limits_after = self
+            .cat_power_limits
+            .iter()
+            .filter(|cpl| cpl.offset_end > offset)
+            .map(|cpl| CatPowerLimit {
+                offset_start: cpl.offset_start.max(offset),
+                ..cpl.clone()
+            })
+            .collect();
+
+        // link in which `offset` falls, noting that the last link point is a dummy
+        let idx_link = self.link_points.partition_point(|lp| lp.offset <= offset) - 1;
+        let mut link_points_before = self.link_points[..idx_link + 1]
+            .iter()
+            .filter(|lp| lp.offset < offset)
+            .copied()
+            .collect::<Vec<_>>();
+        link_points_before.push(LinkPoint {
+            offset,
+            ..Default::default()
+        });
+        let mut link_points_after = self.link_points[idx_link..].to_vec();
+        link_points_after[0].offset = offset;
+
+        let mut path_tpc_before = Self {
+            link_points: link_points_before,
+            grades: grades_before,
+            curves: curves_before,
+            speed_points: speed_points_before,
+            cat_power_limits: cat_power_limits_before,
+            train_params: self.train_params,
+            is_finished: false,
+            curve_speed_limit: self.curve_speed_limit,
+        };
+        path_tpc_before.recalc_link_point_counts();
+        let mut path_tpc_after = Self {
+            link_points: link_points_after,
+            grades: grades_after,
+            curves: curves_after,
+            speed_points: speed_points_after,
+            cat_power_limits: cat_power_limits_after,
+            train_params: self.train_params,
+            is_finished: self.is_finished,
+            curve_speed_limit: self.curve_speed_limit,
+        };
+        path_tpc_after.recalc_link_point_counts();
+        if self.is_finished {
+            path_tpc_before.finish();
+        }
+        Ok((path_tpc_before, path_tpc_after))
+    }
+
+    /// Recalculates grade, curve, and catenary power limit counts of each link point
+    /// from the offsets of the grades, curves, and catenary power limits starting
+    /// within the corresponding link
+    fn recalc_link_point_counts(&mut self) {
+        let n_links = self.link_points.len() - 1;
+        for idx in 0..n_links {
+            let (offset_start, offset_end) = (
+                self.link_points[idx].offset,
+                self.link_points[idx + 1].offset,
+            );
+            let in_link = |offset: si::Length| offset >= offset_start && offset < offset_end;
+            let link_point = &mut self.link_points[idx];
+            link_point.grade_count = self.grades.iter().filter(|g| in_link(g.offset)).count();
+            link_point.curve_count = self.curves.iter().filter(|c| in_link(c.offset)).count();
+            link_point.cat_power_count = self
+                .cat_power_limits
+                .iter()
+                .filter(|cpl| in_link(cpl.offset_start))
+                .count();
+        }
+    }
 
     pub fn new(train_params: TrainParams) -> Self {
         Self {
@@ -72,6 +379,7 @@ impl PathTpc {
             cat_power_limits: vec![],
             train_params,
             is_finished: false,
+            curve_speed_limit: None,
         }
     }
 
@@ -152,6 +460,15 @@ impl PathTpc {
                 extract_speed_set(&link.speed_sets, &link.speed_set, &self.train_params)?,
                 offset_base,
             )?;
+            if let Some(curve_speed_limit) = &self.curve_speed_limit {
+                Self::add_curve_speeds(
+                    &mut self.speed_points,
+                    &self.train_params,
+                    curve_speed_limit,
+                    &link.headings,
+                    offset_base,
+                );
+            }
 
             // Update link point
             let link_point_add = self.link_points.last_mut().unwrap();
@@ -327,6 +644,15 @@ impl PathTpc {
                 )?,
                 link_point.offset,
             )?;
+            if let Some(curve_speed_limit) = &self.curve_speed_limit {
+                Self::add_curve_speeds(
+                    &mut self.speed_points,
+                    &self.train_params,
+                    curve_speed_limit,
+                    &links[link_point.link_idx.idx()].headings,
+                    link_point.offset,
+                );
+            }
         }
         Ok(())
     }
@@ -367,6 +693,31 @@ impl PathTpc {
         }
         Ok(())
     }
+
+    /// Adds speed limits derived from the curvature between consecutive
+    /// `headings` via `curve_speed_limit`.  Each limit applies until the rear of
+    /// the train has cleared the curve.
+    fn add_curve_speeds(
+        speed_points: &mut Vec<SpeedLimitPoint>,
+        train_params: &TrainParams,
+        curve_speed_limit: &CurveSpeedModel,
+        headings: &[Heading],
+        offset_base: si::Length,
+    ) {
+        for (prev, curr) in headings.windows(2).map(|x| (&x[0], &x[1])) {
+            let dh: si::Angle =
+                (curr.heading - prev.heading + 3.0 * uc::REV / 2.0) % uc::REV - uc::REV / 2.0;
+            let curvature: si::Curvature = (dh.abs() / (curr.offset - prev.offset)).into();
+            let speed = curve_speed_limit.speed_max(curvature);
+            if speed < train_params.speed_max {
+                speed_points.insert_speed(&SpeedLimit {
+                    offset_start: prev.offset + offset_base,
+                    offset_end: curr.offset + offset_base + train_params.length,
+                    speed,
+                })
+            }
+        }
+    }
 }
 
 /// If provided, returns `speed_set`.  Otherwise, finds speed_set appropriate for
@@ -431,6 +782,9 @@ impl ObjState for PathTpc {
             validate_field_real(&mut errors, &self.curves, "Curves");
             validate_field_real(&mut errors, &self.speed_points, "Speed points");
             validate_field_real(&mut errors, &self.train_params, "Train params");
+            if let Some(curve_speed_limit) = &self.curve_speed_limit {
+                validate_field_real(&mut errors, curve_speed_limit, "Curve speed limit");
+            }
             early_err!(errors, "Path TPC");
 
             let mut link_point_sum = LinkPoint::default();
@@ -515,4 +869,175 @@ mod test_path_tpc {
         }
     }
     check_cases!(PathTpc);
+
+    #[test]
+    fn test_elevation_profile() {
+        let path_tpc = PathTpc::valid();
+        let (offsets, elevs) = path_tpc.elevation_profile();
+        let elevs_link = Vec::<Elev>::valid();
+        assert_eq!(offsets.len(), elevs_link.len());
+        assert_eq!(offsets.len(), elevs.len());
+        for ((offset, elev), elev_link) in offsets.iter().zip(&elevs).zip(&elevs_link) {
+            assert_eq!(*offset, elev_link.offset);
+            assert_eq!(*elev, elev_link.elev);
+        }
+    }
+
+    #[test]
+    fn test_point_arrays() {
+        let path_tpc = PathTpc::valid();
+        let (offsets, speed_limits) = path_tpc.speed_point_arrays();
+        assert!(!offsets.is_empty());
+        assert_eq!(offsets.len(), speed_limits.len());
+        assert!(offsets.windows(2).all(|w| w[0] <= w[1]));
+        for res_coeffs in [path_tpc.grades(), path_tpc.curves()] {
+            let (offsets, vals) = PathTpc::res_coeff_arrays(res_coeffs);
+            assert!(!offsets.is_empty());
+            assert_eq!(offsets.len(), vals.len());
+            assert!(offsets.windows(2).all(|w| w[0] <= w[1]));
+        }
+    }
+
+    #[test]
+    fn test_split_at_offset() {
+        let path_tpc = PathTpc::valid();
+        assert!(path_tpc.split_at_offset(path_tpc.offset_begin()).is_err());
+        assert!(path_tpc.split_at_offset(path_tpc.offset_end()).is_err());
+
+        for offset in [4e3 * uc::M, 5e3 * uc::M] {
+            let (before, after) = path_tpc.split_at_offset(offset).unwrap();
+            before.validate().unwrap();
+            after.validate().unwrap();
+            assert_eq!(before.offset_end(), offset);
+            assert_eq!(after.offset_begin(), offset);
+
+            // link point counts account for every grade point but the last
+            for half in [&before, &after] {
+                let mut link_point_sum = LinkPoint::default();
+                for link_point in half.link_points() {
+                    link_point_sum.add_counts(link_point);
+                }
+                let n_grades = half
+                    .grades()
+                    .iter()
+                    .filter(|g| g.offset.is_finite())
+                    .count();
+                assert_eq!(link_point_sum.grade_count + 1, n_grades);
+            }
+
+            // concatenating the halves reproduces the original profile
+            let (offsets_before, elevs_before) = before.elevation_profile();
+            let (offsets_after, elevs_after) = after.elevation_profile();
+            let offsets: Vec<si::Length> = offsets_before
+                .into_iter()
+                .chain(offsets_after.into_iter().skip(1))
+                .collect();
+            let elevs: Vec<si::Length> = elevs_before
+                .into_iter()
+                .chain(elevs_after.into_iter().skip(1))
+                .collect();
+            let (offsets_orig, elevs_orig) = path_tpc.elevation_profile();
+            for (offset_orig, elev_orig) in offsets_orig.iter().zip(&elevs_orig) {
+                let idx = offsets.iter().position(|o| o == offset_orig).unwrap();
+                assert!(almost_eq_uom(&elevs[idx], elev_orig, None));
+            }
+            for (offset, elev) in offsets.iter().zip(&elevs) {
+                assert!(almost_eq_uom(elev, &path_tpc.elev_at(*offset), None));
+            }
+            assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(after.speed_points()[0].offset, offset);
+        }
+    }
+
+    #[test]
+    fn test_grade_ahead() {
+        let path_tpc = PathTpc::valid();
+        let lookahead = 5e3 * uc::M;
+        assert_eq!(path_tpc.elev_at(2.5e3 * uc::M), 10.0 * uc::M);
+        assert!(almost_eq_uom(
+            &path_tpc.grade_ahead(si::Length::ZERO, lookahead),
+            &(0.004 * uc::R),
+            None
+        ));
+        assert!(almost_eq_uom(
+            &path_tpc.grade_ahead(2.5e3 * uc::M, lookahead),
+            &si::Ratio::ZERO,
+            None
+        ));
+        assert!(almost_eq_uom(
+            &path_tpc.grade_ahead(5e3 * uc::M, lookahead),
+            &(-0.004 * uc::R),
+            None
+        ));
+        // truncated at end of path
+        assert!(almost_eq_uom(
+            &path_tpc.grade_ahead(7.5e3 * uc::M, lookahead),
+            &(-0.004 * uc::R),
+            None
+        ));
+        assert_eq!(
+            path_tpc.grade_ahead(path_tpc.offset_end(), lookahead),
+            si::Ratio::ZERO
+        );
+    }
+
+    #[test]
+    fn test_curve_speed_limit() {
+        let speed_limit_at = |path_tpc: &PathTpc, offset: si::Length| {
+            let speed_points = path_tpc.speed_points();
+            speed_points[speed_points.partition_point(|sp| sp.offset <= offset) - 1].speed_limit
+        };
+        let path_tpc_posted = PathTpc::valid();
+        assert!(path_tpc_posted.curve_speed_limit().is_none());
+
+        let mut path_tpc = PathTpc::default();
+        // small cant deficiency so that the gentle curve in `Link::valid` is restrictive
+        let curve_speed_limit = CurveSpeedModel {
+            superelevation: si::Length::ZERO,
+            cant_deficiency: 0.002 * uc::M,
+        };
+        path_tpc.set_curve_speed_limit(Some(curve_speed_limit));
+        path_tpc
+            .extend(Vec::<Link>::valid(), [LinkIdx::valid()])
+            .unwrap();
+        path_tpc.finish();
+
+        // `Link::valid` is straight for the first half and curved for the second half
+        let offset_straight = 2.5e3 * uc::M;
+        let offset_curve = 7.5e3 * uc::M;
+        assert_eq!(
+            speed_limit_at(&path_tpc, offset_straight),
+            speed_limit_at(&path_tpc_posted, offset_straight)
+        );
+        let speed_curve = curve_speed_limit.speed_max(uc::RADPM / 5e3);
+        assert!(speed_curve < speed_limit_at(&path_tpc_posted, offset_curve));
+        assert!(almost_eq_uom(
+            &speed_limit_at(&path_tpc, offset_curve),
+            &speed_curve,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_elevation_profile_link_join() {
+        let mut network = Vec::<Link>::valid();
+        let mut link_next = Link::valid();
+        link_next.idx_curr = LinkIdx::new(2);
+        link_next.idx_prev = LinkIdx::valid();
+        network[1].idx_next = link_next.idx_curr;
+        network.push(link_next);
+
+        let mut path_tpc = PathTpc::default();
+        path_tpc
+            .extend(&network, [LinkIdx::valid(), LinkIdx::new(2)])
+            .unwrap();
+        path_tpc.finish();
+        let (offsets, elevs) = path_tpc.elevation_profile();
+        let elevs_link = Vec::<Elev>::valid();
+        // shared boundary point is not duplicated
+        assert_eq!(offsets.len(), 2 * elevs_link.len() - 1);
+        assert_eq!(offsets.len(), elevs.len());
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(*offsets.last().unwrap(), 2.0 * network[1].length);
+    }
 }
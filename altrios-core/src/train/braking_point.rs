@@ -1,4 +1,4 @@
-use super::{friction_brakes::FricBrake, train_imports::*};
+use super::{friction_brakes::FricBrake, train_imports::*, SpeedViolationPolicy};
 
 #[serde_api]
 #[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq)]
@@ -46,12 +46,16 @@ impl BrakingPoints {
     /// - speed: current train speed
     /// - adj_ramp_up_time: corrected ramp up time to account for approximately
     ///     linear brake build up
+    /// - speed_violation_policy: what to do if `speed` exceeds the speed limit
+    ///
+    /// Returns speed limit and speed target
     pub fn calc_speeds(
         &mut self,
         offset: si::Length,
         speed: si::Velocity,
         adj_ramp_up_time: si::Time,
-    ) -> (si::Velocity, si::Velocity) {
+        speed_violation_policy: SpeedViolationPolicy,
+    ) -> anyhow::Result<(si::Velocity, si::Velocity)> {
         if self.points.first().unwrap().offset <= offset {
             self.idx_curr = 0;
         } else {
@@ -59,14 +63,26 @@ impl BrakingPoints {
                 self.idx_curr -= 1;
             }
         }
-        assert!(
-            speed <= self.points[self.idx_curr].speed_limit,
-            "Speed limit violated! idx_curr={:?}, offset={:?}, speed={speed:?}, speed_limit={:?}, speed_target={:?}",
-            self.idx_curr,
-            self.points[self.idx_curr].offset,
-            self.points[self.idx_curr].speed_limit,
-            self.points[self.idx_curr].speed_target
-        );
+        let speed_limit = self.points[self.idx_curr].speed_limit;
+        let speed = if speed > speed_limit {
+            let msg = format!(
+                "Speed limit violated! idx_curr={:?}, offset={:?}, speed={speed:?}, speed_limit={:?}, speed_target={:?}",
+                self.idx_curr,
+                self.points[self.idx_curr].offset,
+                speed_limit,
+                self.points[self.idx_curr].speed_target
+            );
+            match speed_violation_policy {
+                SpeedViolationPolicy::Error => bail!("{}\n{}", format_dbg!(), msg),
+                SpeedViolationPolicy::Clamp => speed_limit,
+                SpeedViolationPolicy::Log => {
+                    log::warn!("{msg}");
+                    speed
+                }
+            }
+        } else {
+            speed
+        };
 
         // need to make a way for this to never decrease until a stop happens or maybe never at all
         // need to maybe save `offset_far`
@@ -78,16 +94,37 @@ impl BrakingPoints {
             idx -= 1;
         }
 
-        (self.points[self.idx_curr].speed_limit, speed_target)
+        Ok((speed_limit, speed_target))
+    }
+
+    /// Returns lowest speed target between `offset` and `offset + lookahead`.
+    /// Must be called after [Self::calc_speeds] for the current `offset`.
+    pub fn speed_target_min_ahead(
+        &self,
+        offset: si::Length,
+        lookahead: si::Length,
+    ) -> si::Velocity {
+        let offset_far = offset + lookahead;
+        let mut speed_target = self.points[self.idx_curr].speed_target;
+        let mut idx = self.idx_curr;
+        while idx >= 1 && self.points[idx - 1].offset <= offset_far {
+            speed_target = speed_target.min(self.points[idx - 1].speed_target);
+            idx -= 1;
+        }
+        speed_target
     }
 
     /// Any time [PathTpc] is updated, everything is recalculated
+    ///
+    /// Arguments:
+    /// - speed_limit_margin: amount by which path speed limits are reduced
     pub fn recalc(
         &mut self,
         train_state: &TrainState,
         fric_brake: &FricBrake,
         train_res: &TrainRes,
         path_tpc: &PathTpc,
+        speed_limit_margin: si::Velocity,
     ) -> anyhow::Result<()> {
         self.points.clear();
         self.points.push(BrakingPoint {
@@ -106,12 +143,15 @@ impl BrakingPoints {
             .update_unchecked(si::Velocity::ZERO, || format_dbg!())?;
         train_res.update_res(&mut train_state, path_tpc, &Dir::Unk)?;
         let speed_points = path_tpc.speed_points();
+        let speed_limit_at = |idx: usize| {
+            (speed_points[idx].speed_limit.abs() - speed_limit_margin).max(si::Velocity::ZERO)
+        };
         let mut idx = path_tpc.speed_points().len();
 
         // Iterate backwards through all the speed points
         while 0 < idx {
             idx -= 1;
-            if speed_points[idx].speed_limit.abs() > self.points.last().unwrap().speed_limit {
+            if speed_limit_at(idx) > self.points.last().unwrap().speed_limit {
                 // Iterate until breaking through the speed limit curve
                 loop {
                     let bp_curr = *self.points.last().unwrap();
@@ -120,7 +160,7 @@ impl BrakingPoints {
                     while bp_curr.offset <= speed_points[idx].offset {
                         idx -= 1;
                     }
-                    let speed_limit = speed_points[idx].speed_limit.abs();
+                    let speed_limit = speed_limit_at(idx);
 
                     train_state
                         .offset
@@ -169,7 +209,7 @@ impl BrakingPoints {
                             speed_limit,
                             speed_target: bp_curr.speed_target,
                         });
-                        if bp_curr.speed_limit == speed_points[idx].speed_limit.abs() {
+                        if bp_curr.speed_limit == speed_limit_at(idx) {
                             break;
                         }
                     } else {
@@ -191,8 +231,8 @@ impl BrakingPoints {
             }
             self.points.push(BrakingPoint {
                 offset: speed_points[idx].offset,
-                speed_limit: speed_points[idx].speed_limit.abs(),
-                speed_target: speed_points[idx].speed_limit.abs(),
+                speed_limit: speed_limit_at(idx),
+                speed_target: speed_limit_at(idx),
             });
         }
 
@@ -200,3 +240,48 @@ impl BrakingPoints {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calc_speeds_violation() {
+        let speed_limit = 20.0 * uc::MPS;
+        let braking_points = BrakingPoints {
+            points: vec![
+                BrakingPoint {
+                    offset: 1000.0 * uc::M,
+                    ..Default::default()
+                },
+                BrakingPoint {
+                    offset: si::Length::ZERO,
+                    speed_limit,
+                    speed_target: speed_limit,
+                },
+            ],
+            idx_curr: 1,
+        };
+        let calc_speeds = |speed: si::Velocity, policy: SpeedViolationPolicy| {
+            braking_points
+                .clone()
+                .calc_speeds(100.0 * uc::M, speed, uc::S, policy)
+        };
+
+        let speed_ok = 19.0 * uc::MPS;
+        let speed_over = speed_limit + 0.01 * uc::MPS;
+        assert_eq!(
+            calc_speeds(speed_ok, SpeedViolationPolicy::Error).unwrap(),
+            (speed_limit, speed_limit)
+        );
+        assert!(calc_speeds(speed_over, SpeedViolationPolicy::Error).is_err());
+        assert_eq!(
+            calc_speeds(speed_over, SpeedViolationPolicy::Clamp).unwrap(),
+            (speed_limit, speed_limit)
+        );
+        assert_eq!(
+            calc_speeds(speed_over, SpeedViolationPolicy::Log).unwrap(),
+            (speed_limit, speed_limit)
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use super::train_imports::*;
+use crate::track::CurveSpeedModel;
+
+#[serde_api]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Passenger comfort results for a single curve along a [PathTpc]
+pub struct CurveComfort {
+    /// Offset of start of curve along [PathTpc]
+    pub offset_start: si::Length,
+    /// Offset of end of curve along [PathTpc]
+    pub offset_end: si::Length,
+    /// Curvature (i.e. inverse of radius) of curve
+    pub curvature: si::Curvature,
+    /// Max speed through curve such that unbalanced lateral acceleration does
+    /// not exceed that corresponding to the specified cant deficiency
+    pub speed_comfort_max: si::Velocity,
+    /// Max posted speed limit through curve
+    pub speed_limit_max: si::Velocity,
+    /// Max speed at which head end of train operated through curve, if
+    /// history is available
+    pub speed_max: Option<si::Velocity>,
+}
+
+#[pyo3_api]
+impl CurveComfort {
+    #[pyo3(name = "is_uncomfortable")]
+    fn is_uncomfortable_py(&self) -> bool {
+        self.is_uncomfortable()
+    }
+}
+
+impl Init for CurveComfort {}
+impl SerdeAPI for CurveComfort {}
+
+impl CurveComfort {
+    /// Returns max comfortable speed through a curve with given `curvature`
+    /// # Arguments
+    /// - `curvature`: curvature (i.e. inverse of radius) of curve
+    /// - `superelevation`: height of outer rail above inner rail
+    /// - `cant_deficiency`: additional superelevation that would be needed to
+    ///   fully balance lateral acceleration at max comfortable speed
+    pub fn calc_speed_comfort_max(
+        curvature: si::Curvature,
+        superelevation: si::Length,
+        cant_deficiency: si::Length,
+    ) -> si::Velocity {
+        CurveSpeedModel {
+            superelevation,
+            cant_deficiency,
+        }
+        .speed_max(curvature)
+    }
+
+    /// Returns true if either posted or operated speed exceeds max comfortable speed
+    pub fn is_uncomfortable(&self) -> bool {
+        self.speed_limit_max > self.speed_comfort_max
+            || self
+                .speed_max
+                .map(|speed_max| speed_max > self.speed_comfort_max)
+                .unwrap_or_default()
+    }
+}
@@ -0,0 +1,124 @@
+use super::train_imports::*;
+use crate::track::PathResCoeff;
+
+#[serde_api]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Settings for optional eco-driving in [super::SpeedLimitTrainSim], in which
+/// propulsion is cut (i.e. the train coasts) when a slowdown, stop, or
+/// downgrade is coming up within [Self::lookahead] so that energy spent
+/// accelerating is not subsequently wasted by braking
+pub struct EcoDriveConfig {
+    /// Distance ahead of the front of the train to check for upcoming speed
+    /// target reductions and downgrades
+    pub lookahead: si::Length,
+    /// Downgrade magnitude beyond which an upcoming grade triggers coasting
+    pub grade_coast: si::Ratio,
+    /// Speed below which propulsion is always allowed, to avoid stalling
+    pub speed_coast_min: si::Velocity,
+}
+
+#[pyo3_api]
+impl EcoDriveConfig {
+    #[new]
+    #[pyo3(signature = (lookahead_meters, grade_coast=None, speed_coast_min_meters_per_second=None))]
+    fn __new__(
+        lookahead_meters: f64,
+        grade_coast: Option<f64>,
+        speed_coast_min_meters_per_second: Option<f64>,
+    ) -> Self {
+        let def = Self::default();
+        Self {
+            lookahead: lookahead_meters * uc::M,
+            grade_coast: grade_coast.map(|g| g * uc::R).unwrap_or(def.grade_coast),
+            speed_coast_min: speed_coast_min_meters_per_second
+                .map(|s| s * uc::MPS)
+                .unwrap_or(def.speed_coast_min),
+        }
+    }
+}
+
+impl Init for EcoDriveConfig {}
+impl SerdeAPI for EcoDriveConfig {}
+
+impl Default for EcoDriveConfig {
+    /// 2 mile lookahead, 0.5% downgrade threshold, and 10 mph minimum coasting speed
+    fn default() -> Self {
+        Self {
+            lookahead: 2.0 * uc::MI,
+            grade_coast: 0.005 * uc::R,
+            speed_coast_min: 10.0 * uc::MPH,
+        }
+    }
+}
+
+impl EcoDriveConfig {
+    /// Returns true if propulsion should be cut at `offset` and `speed`
+    ///
+    /// Arguments:
+    /// - `speed_target_ahead`: lowest speed target within [Self::lookahead]
+    /// - `grades`: path grades, as in [PathTpc::grades]
+    pub fn should_coast(
+        &self,
+        offset: si::Length,
+        speed: si::Velocity,
+        speed_target_ahead: si::Velocity,
+        grades: &[PathResCoeff],
+    ) -> bool {
+        if speed <= self.speed_coast_min {
+            return false;
+        }
+        if speed_target_ahead < speed {
+            return true;
+        }
+        let offset_far = offset + self.lookahead;
+        grades.windows(2).any(|w| {
+            w[0].offset < offset_far && w[1].offset > offset && w[0].res_coeff < -self.grade_coast
+        })
+    }
+}
+
+impl Valid for EcoDriveConfig {}
+
+impl ObjState for EcoDriveConfig {
+    fn validate(&self) -> ValidationResults {
+        let mut errors = ValidationErrors::new();
+        si_chk_num_gtz(&mut errors, &self.lookahead, "Lookahead");
+        si_chk_num_gez(&mut errors, &self.grade_coast, "Grade coast");
+        si_chk_num_gez(&mut errors, &self.speed_coast_min, "Speed coast min");
+        errors.make_err()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_coast() {
+        let eco = EcoDriveConfig {
+            lookahead: 1000.0 * uc::M,
+            ..Default::default()
+        };
+        let speed = 20.0 * uc::MPS;
+        let grade = |offset: f64, res_coeff: f64| PathResCoeff {
+            offset: offset * uc::M,
+            res_coeff: res_coeff * uc::R,
+            ..Default::default()
+        };
+        let grades = vec![grade(0.0, 0.0), grade(2000.0, -0.01), grade(3000.0, 0.0)];
+
+        assert!(!eco.should_coast(si::Length::ZERO, speed, speed, &grades));
+        // downgrade within lookahead
+        assert!(eco.should_coast(1500.0 * uc::M, speed, speed, &grades));
+        // stop within lookahead
+        assert!(eco.should_coast(si::Length::ZERO, speed, si::Velocity::ZERO, &grades));
+        // never coast at low speed
+        assert!(!eco.should_coast(
+            1500.0 * uc::M,
+            eco.speed_coast_min,
+            si::Velocity::ZERO,
+            &grades
+        ));
+    }
+}
@@ -108,7 +108,18 @@ pub struct TemperatureTraceElement {
 pub struct TemperatureTrace(pub(crate) Interp1DOwned<f64, strategy::Linear>);
 
 #[pyo3_api]
-impl TemperatureTrace {}
+impl TemperatureTrace {
+    #[staticmethod]
+    #[pyo3(name = "from_csv_file")]
+    fn from_csv_file_py(filepath: &Bound<PyAny>) -> anyhow::Result<Self> {
+        Self::from_csv_file(PathBuf::extract_bound(filepath)?)
+    }
+
+    #[pyo3(name = "to_csv_file")]
+    fn to_csv_file_py(&self, filepath: &Bound<PyAny>) -> anyhow::Result<()> {
+        self.to_csv_file(PathBuf::extract_bound(filepath)?)
+    }
+}
 
 impl Init for TemperatureTrace {}
 impl SerdeAPI for TemperatureTrace {}
@@ -148,6 +159,79 @@ impl TemperatureTrace {
     }
 }
 
+/// Row of [TemperatureTrace] csv file
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TemperatureTraceCsvRow {
+    /// simulation time
+    time_seconds: f64,
+    /// ambient temperature at sea level
+    temp_celsius: f64,
+}
+
+impl TemperatureTrace {
+    /// Load trace from csv file with `time_seconds` and `temp_celsius`
+    /// (i.e. sea level temperature) columns.  Time must be strictly increasing
+    /// and temperatures must be finite.
+    pub fn from_csv_file<P: AsRef<Path>>(filepath: P) -> anyhow::Result<Self> {
+        let filepath = filepath.as_ref();
+        let file = File::open(filepath)
+            .with_context(|| format!("{}\nFailed to open {:?}", format_dbg!(), filepath))?;
+        let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(file);
+
+        let mut tt = TemperatureTraceBuilder::empty();
+        for (i, result) in rdr.deserialize().enumerate() {
+            let row: TemperatureTraceCsvRow = result.with_context(|| format_dbg!())?;
+            ensure!(
+                row.time_seconds.is_finite() && row.temp_celsius.is_finite(),
+                "{}\nNon-finite value in row {} of {:?}",
+                format_dbg!(),
+                i,
+                filepath
+            );
+            tt.push(TemperatureTraceElement {
+                time: row.time_seconds * uc::S,
+                temp_at_sea_level: (row.temp_celsius + uc::CELSIUS_TO_KELVIN) * uc::KELVIN,
+            });
+        }
+        ensure!(
+            !tt.is_empty(),
+            "{}\nInvalid TemperatureTrace file {:?}; TemperatureTrace is empty",
+            format_dbg!(),
+            filepath
+        );
+        ensure!(
+            tt.time.windows(2).all(|w| w[1] > w[0]),
+            "{}\n`time_seconds` in {:?} must be strictly increasing",
+            format_dbg!(),
+            filepath
+        );
+        Self::try_from(tt).with_context(|| format_dbg!())
+    }
+
+    /// Save sea level temperature trace to csv file with `time_seconds` and
+    /// `temp_celsius` columns
+    pub fn to_csv_file<P: AsRef<Path>>(&self, filepath: P) -> anyhow::Result<()> {
+        let file = std::fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(filepath)?;
+        let mut wrtr = csv::WriterBuilder::new()
+            .has_headers(true)
+            .from_writer(file);
+        for (time_seconds, temp_celsius) in self.0.data.grid[0].iter().zip(&self.0.data.values) {
+            wrtr.serialize(TemperatureTraceCsvRow {
+                time_seconds: *time_seconds,
+                temp_celsius: *temp_celsius,
+            })?;
+        }
+        wrtr.flush()?;
+        Ok(())
+    }
+}
+
 impl Serialize for TemperatureTrace {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -210,3 +294,50 @@ impl Default for TemperatureTrace {
 }
 
 impl TemperatureTrace {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_temperature_trace_csv_round_trip() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("temp_trace.csv");
+        let tt = TemperatureTrace::try_from(TemperatureTraceBuilder {
+            time: vec![0.0 * uc::S, 3_600.0 * uc::S, 7_200.0 * uc::S],
+            temp_at_sea_level: [22.0, 30.0, 45.0]
+                .iter()
+                .map(|c| (c + uc::CELSIUS_TO_KELVIN) * uc::KELVIN)
+                .collect(),
+        })
+        .unwrap();
+        tt.to_csv_file(&path).unwrap();
+
+        let mut rdr = csv::Reader::from_path(&path).unwrap();
+        assert_eq!(
+            rdr.headers().unwrap().iter().collect::<Vec<_>>(),
+            vec!["time_seconds", "temp_celsius"]
+        );
+
+        let tt_read = TemperatureTrace::from_csv_file(&path).unwrap();
+        assert_eq!(tt_read.0.data.grid, tt.0.data.grid);
+        for (read, orig) in tt_read.0.data.values.iter().zip(&tt.0.data.values) {
+            assert!((read - orig).abs() < 1e-9, "{read} != {orig}");
+        }
+    }
+
+    #[test]
+    fn test_temperature_trace_csv_invalid() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("temp_trace.csv");
+
+        std::fs::write(&path, "time_seconds,temp_celsius\n0,22\n0,25\n").unwrap();
+        assert!(TemperatureTrace::from_csv_file(&path).is_err());
+
+        std::fs::write(&path, "time_seconds,temp_celsius\n0,22\n1,NaN\n").unwrap();
+        assert!(TemperatureTrace::from_csv_file(&path).is_err());
+
+        std::fs::write(&path, "time_seconds,temp_celsius\n").unwrap();
+        assert!(TemperatureTrace::from_csv_file(&path).is_err());
+    }
+}
@@ -12,10 +12,11 @@ pub struct FricBrake {
     pub ramp_up_time: si::Time,
     /// ramp-up correction factor
     pub ramp_up_coeff: si::Ratio,
+    /// time to go from max braking force to zero braking force
+    #[serde(default)]
+    pub ramp_down_time: si::Time,
     // commented out.  This stuff needs refinement but
     // added complexity is probably worthwhile
-    // /// time to go from max braking force to zero braking force
-    // pub ramp_down_time: si::Time,
     // /// rate at which brakes can be recovered after full release
     // pub recharge_rate_pa_per_sec: f64,
     // TODO: add in whatever is needed to estimate aux load impact
@@ -34,6 +35,7 @@ impl FricBrake {
         force_max_newtons,
         ramp_up_time_seconds=None,
         ramp_up_coeff=None,
+        ramp_down_time_seconds=None,
         state=None,
         save_interval=None,
     ))]
@@ -41,6 +43,7 @@ impl FricBrake {
         force_max_newtons: f64,
         ramp_up_time_seconds: Option<f64>,
         ramp_up_coeff: Option<f64>,
+        ramp_down_time_seconds: Option<f64>,
         state: Option<FricBrakeState>,
         save_interval: Option<usize>,
     ) -> Self {
@@ -48,6 +51,7 @@ impl FricBrake {
             force_max_newtons * uc::N,
             ramp_up_time_seconds.map(|ruts| ruts * uc::S),
             ramp_up_coeff.map(|ruc| ruc * uc::R),
+            ramp_down_time_seconds.map(|rdts| rdts * uc::S),
             state,
             save_interval,
         )
@@ -63,6 +67,7 @@ impl Default for FricBrake {
             force_max: 600_000.0 * uc::LBF,
             ramp_up_time: 0.0 * uc::S,
             ramp_up_coeff: 0.6 * uc::R,
+            ramp_down_time: 0.0 * uc::S,
             state: Default::default(),
             history: Default::default(),
             save_interval: Default::default(),
@@ -75,6 +80,7 @@ impl FricBrake {
         force_max: si::Force,
         ramp_up_time: Option<si::Time>,
         ramp_up_coeff: Option<si::Ratio>,
+        ramp_down_time: Option<si::Time>,
         // recharge_rate_pa_per_sec: f64,
         state: Option<FricBrakeState>,
         save_interval: Option<usize>,
@@ -87,10 +93,12 @@ impl FricBrake {
         let fric_brake_def: Self = Default::default();
         let ramp_up_time = ramp_up_time.unwrap_or(fric_brake_def.ramp_up_time);
         let ramp_up_coeff = ramp_up_coeff.unwrap_or(fric_brake_def.ramp_up_coeff);
+        let ramp_down_time = ramp_down_time.unwrap_or(fric_brake_def.ramp_down_time);
         Self {
             force_max,
             ramp_up_time,
             ramp_up_coeff,
+            ramp_down_time,
             // recharge_rate_pa_per_sec,
             state,
             history: Default::default(),
@@ -98,12 +106,24 @@ impl FricBrake {
         }
     }
 
+    /// Sets the range of braking force achievable in the current time step
+    /// based on the force applied in the previous time step.  The upper bound,
+    /// `state.force_max_curr`, is limited by `ramp_up_time`, and the lower
+    /// bound, `state.force_min_curr`, is limited by `ramp_down_time` such that
+    /// brakes cannot be released instantaneously.
     pub fn set_cur_force_max_out(&mut self, dt: si::Time) -> anyhow::Result<()> {
         // maybe check parameter values here and propagate any errors
+        let force_prev = *self.state.force.get_stale(|| format_dbg!())?;
         self.state.force_max_curr.update(
-            (*self.state.force.get_stale(|| format_dbg!())?
-                + self.force_max / self.ramp_up_time * dt)
-                .min(self.force_max),
+            (force_prev + self.force_max / self.ramp_up_time * dt).min(self.force_max),
+            || format_dbg!(),
+        )?;
+        self.state.force_min_curr.update(
+            if self.ramp_down_time > si::Time::ZERO {
+                (force_prev - self.force_max / self.ramp_down_time * dt).max(si::Force::ZERO)
+            } else {
+                si::Force::ZERO
+            },
             || format_dbg!(),
         )
     }
@@ -131,6 +151,9 @@ pub struct FricBrakeState {
     pub force: TrackedState<si::Force>,
     // time-varying max force of brakes in current time step
     pub force_max_curr: TrackedState<si::Force>,
+    // time-varying min force of brakes in current time step, limited by how
+    // quickly brakes can be released
+    pub force_min_curr: TrackedState<si::Force>,
     // pressure: si::Pressure,
 }
 
@@ -151,3 +174,67 @@ impl FricBrakeState {
         Self::default()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ramp_down() {
+        let force_max = 600_000.0 * uc::LBF;
+        let dt = 1.0 * uc::S;
+        let mut fric_brake = FricBrake::new(force_max, None, None, Some(10.0 * uc::S), None, None);
+        fric_brake
+            .state
+            .force
+            .update_unchecked(force_max, || format_dbg!())
+            .unwrap();
+
+        let mut force_prev = force_max;
+        for _ in 0..10 {
+            fric_brake.check_and_reset(|| format_dbg!()).unwrap();
+            fric_brake.step(|| format_dbg!()).unwrap();
+            fric_brake.set_cur_force_max_out(dt).unwrap();
+            // command full release
+            let force_min_curr = *fric_brake
+                .state
+                .force_min_curr
+                .get_fresh(|| format_dbg!())
+                .unwrap();
+            fric_brake
+                .state
+                .force
+                .update(si::Force::ZERO.max(force_min_curr), || format_dbg!())
+                .unwrap();
+            let force = *fric_brake.state.force.get_fresh(|| format_dbg!()).unwrap();
+            assert!(almost_eq_uom(
+                &(force_prev - force),
+                &(force_max * 0.1),
+                None
+            ));
+            force_prev = force;
+        }
+        assert!(almost_eq_uom(&force_prev, &si::Force::ZERO, None));
+    }
+
+    #[test]
+    fn test_ramp_down_default_is_instantaneous() {
+        let mut fric_brake = FricBrake::default();
+        fric_brake
+            .state
+            .force
+            .update_unchecked(fric_brake.force_max, || format_dbg!())
+            .unwrap();
+        fric_brake.check_and_reset(|| format_dbg!()).unwrap();
+        fric_brake.step(|| format_dbg!()).unwrap();
+        fric_brake.set_cur_force_max_out(1.0 * uc::S).unwrap();
+        assert_eq!(
+            *fric_brake
+                .state
+                .force_min_curr
+                .get_fresh(|| format_dbg!())
+                .unwrap(),
+            si::Force::ZERO
+        );
+    }
+}
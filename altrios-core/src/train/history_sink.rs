@@ -0,0 +1,241 @@
+use super::train_imports::*;
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom};
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
+
+/// Number of rows written between flushes to disk
+const FLUSH_INTERVAL: usize = 100;
+
+/// CSV sink for [TrainState] rows, used in place of in-memory history so that
+/// long simulations do not accumulate a large [TrainStateHistoryVec].  Rows
+/// are flushed every [FLUSH_INTERVAL] rows, at the end of a walk, and when
+/// the sink is dropped.  Each clone writes to its own file, named by
+/// appending `_clone<n>` to the file stem of the original, which is created
+/// on the clone's first write.  To continue writing to the same file from an
+/// earlier point, e.g. when restoring a checkpoint, use [Self::pos] and
+/// [Self::resume] instead of cloning.
+pub struct HistorySink {
+    path: PathBuf,
+    writer: Mutex<Option<HistorySinkWriter>>,
+    /// Number of clones made from this sink and its clones, shared to keep
+    /// clone file names unique
+    n_clones: Arc<AtomicUsize>,
+}
+
+struct HistorySinkWriter {
+    writer: csv::Writer<File>,
+    n_unflushed: usize,
+}
+
+impl Drop for HistorySinkWriter {
+    fn drop(&mut self) {
+        let _ = self.writer.flush();
+    }
+}
+
+impl HistorySinkWriter {
+    /// Creates or truncates file at `path` for writing
+    fn create(path: &Path) -> anyhow::Result<Self> {
+        let file = File::create(path)
+            .with_context(|| format!("{}\nFailed to create {:?}", format_dbg!(), path))?;
+        Ok(Self {
+            writer: csv::WriterBuilder::new()
+                .has_headers(true)
+                .from_writer(file),
+            n_unflushed: 0,
+        })
+    }
+
+    /// Opens file at `path` truncated to `len` bytes for appending, writing
+    /// headers only if `len` is zero
+    fn resume(path: &Path, len: u64) -> anyhow::Result<Self> {
+        if len == 0 {
+            return Self::create(path);
+        }
+        let mut file = OpenOptions::new()
+            .write(true)
+            .open(path)
+            .with_context(|| format!("{}\nFailed to open {:?}", format_dbg!(), path))?;
+        file.set_len(len).with_context(|| format_dbg!())?;
+        file.seek(SeekFrom::End(0)).with_context(|| format_dbg!())?;
+        Ok(Self {
+            writer: csv::WriterBuilder::new()
+                .has_headers(false)
+                .from_writer(file),
+            n_unflushed: 0,
+        })
+    }
+}
+
+/// Path and length of a [HistorySink] file at some point in a simulation,
+/// from which writing can be resumed with [HistorySink::resume]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistorySinkPos {
+    path: PathBuf,
+    len: u64,
+}
+
+impl Clone for HistorySink {
+    fn clone(&self) -> Self {
+        let n = self.n_clones.fetch_add(1, AtomicOrdering::Relaxed) + 1;
+        let mut file_name = self.path.file_stem().unwrap_or_default().to_os_string();
+        file_name.push(format!("_clone{n}"));
+        if let Some(ext) = self.path.extension() {
+            file_name.push(".");
+            file_name.push(ext);
+        }
+        Self {
+            path: self.path.with_file_name(file_name),
+            writer: Mutex::new(None),
+            n_clones: self.n_clones.clone(),
+        }
+    }
+}
+
+impl HistorySink {
+    /// Creates sink writing to `path`, which is created or truncated
+    pub fn new<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        Ok(Self {
+            writer: Mutex::new(Some(HistorySinkWriter::create(&path)?)),
+            path,
+            n_clones: Default::default(),
+        })
+    }
+
+    /// Creates sink writing to the file at `pos`, discarding any rows written
+    /// to it after `pos`
+    pub fn resume(pos: &HistorySinkPos) -> anyhow::Result<Self> {
+        Ok(Self {
+            writer: Mutex::new(Some(HistorySinkWriter::resume(&pos.path, pos.len)?)),
+            path: pos.path.clone(),
+            n_clones: Default::default(),
+        })
+    }
+
+    /// Path of file being written
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Flushes any buffered rows to disk and returns current position in file
+    pub fn pos(&self) -> anyhow::Result<HistorySinkPos> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|err| anyhow!("{}\n{}", format_dbg!(), err))?;
+        let len = match writer.as_mut() {
+            Some(writer) => {
+                writer.writer.flush().with_context(|| format_dbg!())?;
+                writer.n_unflushed = 0;
+                writer
+                    .writer
+                    .get_ref()
+                    .metadata()
+                    .with_context(|| format_dbg!())?
+                    .len()
+            }
+            None => 0,
+        };
+        Ok(HistorySinkPos {
+            path: self.path.clone(),
+            len,
+        })
+    }
+
+    /// Appends `state` as a row
+    pub fn write(&self, state: &TrainState) -> anyhow::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|err| anyhow!("{}\n{}", format_dbg!(), err))?;
+        if writer.is_none() {
+            *writer = Some(HistorySinkWriter::create(&self.path)?);
+        }
+        let writer = writer.as_mut().unwrap();
+        writer
+            .writer
+            .serialize(state)
+            .with_context(|| format_dbg!())?;
+        writer.n_unflushed += 1;
+        if writer.n_unflushed >= FLUSH_INTERVAL {
+            writer.writer.flush().with_context(|| format_dbg!())?;
+            writer.n_unflushed = 0;
+        }
+        Ok(())
+    }
+
+    /// Flushes any buffered rows to disk
+    pub fn flush(&self) -> anyhow::Result<()> {
+        let mut writer = self
+            .writer
+            .lock()
+            .map_err(|err| anyhow!("{}\n{}", format_dbg!(), err))?;
+        if let Some(writer) = writer.as_mut() {
+            writer.writer.flush().with_context(|| format_dbg!())?;
+            writer.n_unflushed = 0;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HistorySink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HistorySink")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl PartialEq for HistorySink {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::train::SpeedLimitTrainSim;
+
+    #[test]
+    fn test_history_sink() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("history.csv");
+
+        let mut ts_ref = SpeedLimitTrainSim::valid();
+        ts_ref.set_save_interval(Some(1));
+        let mut ts = ts_ref.clone();
+        ts_ref.walk().unwrap();
+
+        ts.set_history_sink(Some(HistorySink::new(&path).unwrap()));
+        let mut ts_clone = ts.clone();
+        ts.walk().unwrap();
+        assert!(ts.history.is_empty());
+        // nested histories are not kept in memory either
+        assert!(ts.loco_con.history.is_empty());
+        assert!(ts.fric_brake.history.is_empty());
+
+        let mut rdr = csv::Reader::from_path(&path).unwrap();
+        let headers = rdr.headers().unwrap().clone();
+        assert!(headers.iter().any(|h| h == "speed_meters_per_second"));
+        assert!(headers.iter().any(|h| h == "offset_meters"));
+        assert_eq!(rdr.records().count(), ts_ref.history.len());
+
+        // clone streams to its own file rather than interleaving rows
+        ts_clone.walk().unwrap();
+        let path_clone = tempdir.path().join("history_clone1.csv");
+        assert_eq!(
+            csv::Reader::from_path(&path_clone)
+                .unwrap()
+                .records()
+                .count(),
+            ts_ref.history.len()
+        );
+        assert_eq!(
+            csv::Reader::from_path(&path).unwrap().records().count(),
+            ts_ref.history.len()
+        );
+    }
+}
@@ -1,18 +1,30 @@
 mod braking_point;
+mod curve_comfort;
+mod eco_drive;
 mod environment;
 mod friction_brakes;
+mod history_sink;
+mod objective;
 mod rail_vehicle;
 mod resistance;
 mod set_speed_train_sim;
+mod sim_checkpoint;
+mod sim_compare;
 mod speed_limit_train_sim;
 mod train_config;
 mod train_imports;
 mod train_state;
 
+pub use curve_comfort::*;
+pub use eco_drive::*;
 pub use environment::*;
+pub use history_sink::*;
+pub use objective::*;
 pub use rail_vehicle::*;
 pub use resistance::*;
 pub use set_speed_train_sim::*;
+pub use sim_checkpoint::*;
+pub use sim_compare::*;
 pub use speed_limit_train_sim::*;
 pub use train_config::*;
 pub use train_state::*;
@@ -0,0 +1,69 @@
+use super::train_imports::*;
+
+/// Weights for [super::SpeedLimitTrainSim::objective], which sums each weight
+/// times the corresponding trip metric divided by its reference value
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveWeights {
+    /// Weight on trip time
+    pub time: f64,
+    /// Weight on fuel energy
+    pub energy_fuel: f64,
+    /// Weight on net RES energy, positive for discharge
+    pub energy_res: f64,
+    /// Trip time that normalizes to 1
+    pub time_ref: si::Time,
+    /// Energy that normalizes to 1, for both fuel and net RES energy
+    pub energy_ref: si::Energy,
+}
+
+impl Default for ObjectiveWeights {
+    /// Equal weights on fuel and net RES energy, no weight on trip time, and
+    /// reference values of 1 hour and 1 GJ
+    fn default() -> Self {
+        Self {
+            time: 0.0,
+            energy_fuel: 1.0,
+            energy_res: 1.0,
+            time_ref: 3600.0 * uc::S,
+            energy_ref: 1e9 * uc::J,
+        }
+    }
+}
+
+impl ObjectiveWeights {
+    /// Builds [Self] from a dict with optional keys `"time"`, `"energy_fuel"`,
+    /// `"energy_res"`, `"time_ref_seconds"`, and `"energy_ref_joules"`, with
+    /// missing keys taking their default values
+    pub fn from_dict(dict: &HashMap<String, f64>) -> anyhow::Result<Self> {
+        let mut weights = Self::default();
+        for (key, val) in dict {
+            match key.as_str() {
+                "time" => weights.time = *val,
+                "energy_fuel" => weights.energy_fuel = *val,
+                "energy_res" => weights.energy_res = *val,
+                "time_ref_seconds" => weights.time_ref = *val * uc::S,
+                "energy_ref_joules" => weights.energy_ref = *val * uc::J,
+                _ => bail!("{}\nUnrecognized objective weight `{}`", format_dbg!(), key),
+            }
+        }
+        weights.check()?;
+        Ok(weights)
+    }
+
+    /// Checks that weights are finite and reference values are positive
+    pub fn check(&self) -> anyhow::Result<()> {
+        ensure!(
+            [self.time, self.energy_fuel, self.energy_res]
+                .iter()
+                .all(|w| w.is_finite()),
+            "{}\nObjective weights must be finite",
+            format_dbg!()
+        );
+        ensure!(
+            self.time_ref > si::Time::ZERO && self.energy_ref > si::Energy::ZERO,
+            "{}\n`time_ref` and `energy_ref` must be positive",
+            format_dbg!()
+        );
+        Ok(())
+    }
+}
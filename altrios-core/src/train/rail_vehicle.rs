@@ -1,5 +1,8 @@
 use crate::imports::*;
 
+/// AAR car type codes with presets available via [RailVehicle::from_aar_type]
+pub const AAR_TYPE_CODES: [&str; 3] = ["C113", "T106", "V"];
+
 #[serde_api]
 #[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
@@ -29,6 +32,10 @@ pub struct RailVehicle {
     /// Freight component of total static railcar mass
     #[serde(alias = "Mass Freight (kg)")]
     pub mass_freight: si::Mass,
+    /// Whether railcar is empty, in which case [Self::mass_freight] is not
+    /// included in its mass
+    #[serde(default, alias = "Empty")]
+    pub empty: bool,
     /// Railcar speed limit
     #[serde(alias = "Speed Max (m/s)")]
     pub speed_max: si::Velocity,
@@ -52,6 +59,11 @@ pub struct RailVehicle {
     /// Drag area (Cd*A), where Cd is drag coefficient and A is front cross-sectional area
     #[serde(alias = "Cd*A (m^2)")]
     pub cd_area: si::Area,
+    /// Factor on [Self::cd_area] for cars of this type other than the lead
+    /// car of the train, accounting for reduced drag from drafting behind the
+    /// car ahead.  `None` is equivalent to 1.0, i.e. no drafting.
+    #[serde(default, alias = "Drafting Factor")]
+    pub drafting_factor: Option<si::Ratio>,
     /// Curve coefficient 0
     #[serde(alias = "Curve Coefficient 0")]
     pub curve_coeff_0: si::Ratio,
@@ -63,12 +75,229 @@ pub struct RailVehicle {
     pub curve_coeff_2: si::Ratio,
 }
 
-#[pyo3_api]
-impl RailVehicle {}
+#[pyo3_api(repr)]
+impl RailVehicle {
+    #[cfg(feature = "yaml")]
+    #[staticmethod]
+    #[pyo3(name = "map_from_file")]
+    fn map_from_file_py(filepath: &Bound<PyAny>) -> anyhow::Result<HashMap<String, Self>> {
+        Self::map_from_file(PathBuf::extract_bound(filepath)?)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_aar_type")]
+    /// Preset loaded rail vehicle for AAR car type `code`
+    fn from_aar_type_py(code: &str) -> anyhow::Result<Self> {
+        Self::from_aar_type(code)
+    }
+
+    #[pyo3(name = "total_mass_kilograms")]
+    /// Static mass of railcar, including freight if `loaded`
+    fn total_mass_kilograms_py(&self, loaded: bool) -> f64 {
+        self.total_mass(loaded).get::<si::kilogram>()
+    }
+
+    #[getter("freight_mass_kilograms")]
+    fn get_freight_mass_kilograms_py(&self) -> f64 {
+        self.freight_mass().get::<si::kilogram>()
+    }
+}
 
 impl Init for RailVehicle {}
 impl SerdeAPI for RailVehicle {}
 
+impl RailVehicle {
+    /// Returns a loaded rail vehicle with typical mass, dimensions, and
+    /// resistance coefficients for AAR car type `code`, one of
+    /// [AAR_TYPE_CODES]:
+    /// - `C113`: 286k lb covered hopper
+    /// - `T106`: 286k lb general service tank car
+    /// - `V`: tri-level autorack
+    pub fn from_aar_type(code: &str) -> anyhow::Result<Self> {
+        // resistance and curve coefficients shared by all presets
+        let base = Self {
+            axle_count: 4,
+            brake_count: 1,
+            braking_ratio: 0.11 * uc::R,
+            mass_rot_per_axle: 750.0 * uc::KG,
+            bearing_res_per_axle: 40.26 * uc::N,
+            rolling_ratio: 0.001546 * uc::R,
+            davis_b: si::InverseVelocity::ZERO,
+            curve_coeff_0: 0.056 * uc::R,
+            curve_coeff_1: 0.4387579 * uc::R,
+            curve_coeff_2: 0.01025485 * uc::R,
+            ..Default::default()
+        };
+        let rail_vehicle = match code {
+            "C113" => Self {
+                car_type: "C113_Loaded".into(),
+                freight_type: "Covered_Hopper".into(),
+                length: 18.0 * uc::M,
+                mass_static_base: 29_000.0 * uc::KG,
+                mass_freight: 100_700.0 * uc::KG,
+                speed_max: 22.4 * uc::MPS,
+                cd_area: 4.1 * uc::M2,
+                ..base
+            },
+            "T106" => Self {
+                car_type: "T106_Loaded".into(),
+                freight_type: "Tank".into(),
+                length: 18.3 * uc::M,
+                mass_static_base: 33_000.0 * uc::KG,
+                mass_freight: 96_700.0 * uc::KG,
+                speed_max: 22.4 * uc::MPS,
+                cd_area: 3.7 * uc::M2,
+                ..base
+            },
+            "V" => Self {
+                car_type: "V_Loaded".into(),
+                freight_type: "Autorack".into(),
+                length: 27.4 * uc::M,
+                mass_static_base: 50_000.0 * uc::KG,
+                mass_freight: 16_000.0 * uc::KG,
+                speed_max: 31.3 * uc::MPS,
+                cd_area: 8.0 * uc::M2,
+                ..base
+            },
+            _ => bail!(
+                "{}\nUnsupported AAR car type code `{}`.  Supported codes: {:?}",
+                format_dbg!(),
+                code,
+                AAR_TYPE_CODES
+            ),
+        };
+        Ok(rail_vehicle)
+    }
+
+    /// Static mass of railcar, not including effective rotational mass, with
+    /// [Self::freight_mass] included only if `loaded`
+    pub fn total_mass(&self, loaded: bool) -> si::Mass {
+        if loaded {
+            self.mass_static_base + self.freight_mass()
+        } else {
+            self.mass_static_base
+        }
+    }
+
+    /// Freight mass carried by railcar, which is zero if [Self::empty]
+    pub fn freight_mass(&self) -> si::Mass {
+        if self.empty {
+            si::Mass::ZERO
+        } else {
+            self.mass_freight
+        }
+    }
+
+    /// Loads a library of [RailVehicle]s keyed by name from a YAML file
+    /// containing a top-level mapping.  Top-level entries of the form
+    /// `<label>: !include <path>` are replaced by the entries of the YAML file
+    /// at `<path>`, relative to the including file, which may itself contain
+    /// includes.  Keys must be unique across all included files.
+    #[cfg(feature = "yaml")]
+    pub fn map_from_file<P: AsRef<Path>>(filepath: P) -> anyhow::Result<HashMap<String, Self>> {
+        let mut rail_vehicles = HashMap::new();
+        Self::extend_map_from_file(filepath.as_ref(), &mut rail_vehicles, &mut vec![])
+            .with_context(|| format_dbg!())?;
+        Ok(rail_vehicles)
+    }
+
+    /// Adds entries from `filepath` and its includes to `rail_vehicles`
+    ///
+    /// # Arguments
+    /// - `include_stack`: files currently being loaded, used to detect circular includes
+    #[cfg(feature = "yaml")]
+    fn extend_map_from_file(
+        filepath: &Path,
+        rail_vehicles: &mut HashMap<String, Self>,
+        include_stack: &mut Vec<PathBuf>,
+    ) -> anyhow::Result<()> {
+        let filepath_canon = filepath
+            .canonicalize()
+            .with_context(|| format!("{}\nCould not find {:?}", format_dbg!(), filepath))?;
+        ensure!(
+            !include_stack.contains(&filepath_canon),
+            "{}\nCircular include of {:?}",
+            format_dbg!(),
+            filepath
+        );
+        let contents = std::fs::read_to_string(filepath)
+            .with_context(|| format!("{}\nCould not read {:?}", format_dbg!(), filepath))?;
+
+        let dir = filepath.parent().unwrap_or_else(|| Path::new(""));
+        let mut includes: Vec<PathBuf> = vec![];
+        let mut yaml = String::new();
+        for line in contents.lines() {
+            match parse_include(line) {
+                Some(include) => includes.push(dir.join(include)),
+                None => {
+                    yaml.push_str(line);
+                    yaml.push('\n');
+                }
+            }
+        }
+
+        let has_entries = yaml
+            .lines()
+            .any(|line| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+        if has_entries {
+            let rail_vehicles_file: HashMap<String, Self> = serde_yaml::from_str(&yaml)
+                .with_context(|| format!("{}\nCould not parse {:?}", format_dbg!(), filepath))?;
+            for (key, mut rail_vehicle) in rail_vehicles_file {
+                ensure!(
+                    !rail_vehicles.contains_key(&key),
+                    "{}\nDuplicate rail vehicle key `{}` in {:?}",
+                    format_dbg!(),
+                    key,
+                    filepath
+                );
+                rail_vehicle.init()?;
+                rail_vehicles.insert(key, rail_vehicle);
+            }
+        }
+
+        include_stack.push(filepath_canon);
+        for include in includes {
+            Self::extend_map_from_file(&include, rail_vehicles, include_stack)
+                .with_context(|| format!("{}\nIncluded from {:?}", format_dbg!(), filepath))?;
+        }
+        include_stack.pop();
+        Ok(())
+    }
+}
+
+/// Returns included path if `line` is a top-level YAML entry of the form
+/// `<label>: !include <path>`
+#[cfg(feature = "yaml")]
+fn parse_include(line: &str) -> Option<&str> {
+    if line.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let (label, include) = line.split_once("!include")?;
+    if !label.trim_end().ends_with(':') {
+        return None;
+    }
+    let include = include
+        .split('#')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'');
+    (!include.is_empty()).then_some(include)
+}
+
+impl fmt::Display for RailVehicle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "RailVehicle(car_type={}, axle_count={}, mass_kilograms={}, length_meters={:.2})",
+            self.car_type,
+            self.axle_count,
+            utils::mass_repr(self.mass()),
+            self.length.get::<si::meter>(),
+        )
+    }
+}
+
 impl Mass for RailVehicle {
     /// Static mass of rail vehicle, not including effective rotational mass
     fn mass(&self) -> anyhow::Result<Option<si::Mass>> {
@@ -84,8 +313,64 @@ impl Mass for RailVehicle {
     }
 
     fn derived_mass(&self) -> anyhow::Result<Option<si::Mass>> {
-        Ok(Some(self.mass_static_base + self.mass_freight))
+        Ok(Some(self.total_mass(true)))
     }
 
     fn expunge_mass_fields(&mut self) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_aar_type() {
+        for code in AAR_TYPE_CODES {
+            let rv = RailVehicle::from_aar_type(code).unwrap();
+            assert!(rv.mass().unwrap().unwrap() > si::Mass::ZERO, "{code}");
+        }
+        let err = RailVehicle::from_aar_type("X999").unwrap_err();
+        assert!(format!("{err:?}").contains("C113"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_map_from_file_with_include() {
+        let rv = |car_type: &str| RailVehicle {
+            car_type: car_type.into(),
+            ..Default::default()
+        };
+        let tempdir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(tempdir.path().join("lib")).unwrap();
+        std::fs::write(
+            tempdir.path().join("lib").join("intermodal.yaml"),
+            serde_yaml::to_string(&HashMap::from([("Intermodal", rv("Intermodal"))])).unwrap(),
+        )
+        .unwrap();
+        let main = format!(
+            "{}\nintermodal: !include lib/intermodal.yaml\n",
+            serde_yaml::to_string(&HashMap::from([("Bulk", rv("Bulk"))])).unwrap()
+        );
+        let main_path = tempdir.path().join("main.yaml");
+        std::fs::write(&main_path, &main).unwrap();
+
+        let rail_vehicles = RailVehicle::map_from_file(&main_path).unwrap();
+        assert_eq!(rail_vehicles.len(), 2);
+        assert_eq!(rail_vehicles["Bulk"], rv("Bulk"));
+        assert_eq!(rail_vehicles["Intermodal"], rv("Intermodal"));
+
+        // duplicate keys across includes
+        std::fs::write(
+            &main_path,
+            format!("{main}intermodal_again: !include lib/intermodal.yaml\n"),
+        )
+        .unwrap();
+        let err = RailVehicle::map_from_file(&main_path).unwrap_err();
+        assert!(format!("{err:?}").contains("Duplicate rail vehicle key `Intermodal`"));
+
+        // circular include
+        std::fs::write(&main_path, format!("{main}main: !include main.yaml\n")).unwrap();
+        let err = RailVehicle::map_from_file(&main_path).unwrap_err();
+        assert!(format!("{err:?}").contains("Circular include"));
+    }
+}
@@ -7,6 +7,12 @@ use crate::train::TrainState;
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
 pub struct Basic {
     cd_area: si::Area,
+    /// Along-track wind speed, positive for tailwind and negative for headwind.
+    /// Drag is based on air speed, i.e. train speed minus `wind_speed`, so a
+    /// tailwind faster than the train yields a negative (i.e. propulsive)
+    /// resistance.
+    #[serde(default)]
+    wind_speed: si::Velocity,
 }
 
 #[pyo3_api]
@@ -17,17 +23,26 @@ impl SerdeAPI for Basic {}
 
 impl Basic {
     pub fn new(cd_area: si::Area) -> Self {
-        Self { cd_area }
+        Self {
+            cd_area,
+            wind_speed: si::Velocity::ZERO,
+        }
+    }
+
+    pub fn wind_speed(&self) -> si::Velocity {
+        self.wind_speed
+    }
+
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) {
+        self.wind_speed = wind_speed;
     }
 
     /// Note that the factor of 0.5 typically used in
     /// [the drag equation](https://en.wikipedia.org/wiki/Drag_(physics)#The_drag_equation)
     /// is traditionally lumped into a coefficient in the Davis equation and is treated
-    /// the same here.
+    /// the same here.  See [Self::wind_speed] for how wind enters air speed.
     pub fn calc_res(&mut self, state: &TrainState) -> anyhow::Result<si::Force> {
-        Ok(self.cd_area
-            * uc::rho_air()
-            * *state.speed.get_unchecked(|| format_dbg!())?
-            * *state.speed.get_unchecked(|| format_dbg!())?)
+        let speed_air = *state.speed.get_unchecked(|| format_dbg!())? - self.wind_speed;
+        Ok(self.cd_area * uc::rho_air() * speed_air * speed_air.abs())
     }
 }
@@ -0,0 +1,297 @@
+use super::super::kind::*;
+use super::super::ResMethod;
+use crate::imports::*;
+use crate::track::{LinkPoint, PathResCoeff, PathTpc};
+use crate::train::{RailVehicle, TrainState};
+
+/// Davis coefficients for a single car of a given car type
+#[serde_api]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+pub struct DavisCoeffs {
+    /// Bearing resistance force per car (Davis A)
+    pub bearing: si::Force,
+    /// Rolling resistance force per car weight
+    pub rolling_ratio: si::Ratio,
+    /// Davis B coefficient
+    pub davis_b: si::InverseVelocity,
+    /// Drag area (Cd*A) per car (Davis C)
+    pub cd_area: si::Area,
+    /// Static mass per car, including freight
+    pub mass_static: si::Mass,
+}
+
+#[pyo3_api]
+impl DavisCoeffs {}
+
+impl Init for DavisCoeffs {}
+impl SerdeAPI for DavisCoeffs {}
+
+impl TryFrom<&RailVehicle> for DavisCoeffs {
+    type Error = anyhow::Error;
+    fn try_from(rv: &RailVehicle) -> anyhow::Result<Self> {
+        Ok(Self {
+            bearing: rv.bearing_res_per_axle * rv.axle_count as f64,
+            rolling_ratio: rv.rolling_ratio,
+            davis_b: rv.davis_b,
+            cd_area: rv.cd_area,
+            mass_static: rv
+                .mass()
+                .with_context(|| format_dbg!())?
+                .with_context(|| format!("{}\nExpected `Some`", format_dbg!()))?,
+        })
+    }
+}
+
+/// Resistance method that sums bearing, rolling, Davis B, and aerodynamic
+/// resistance over car types using a lookup table of per-car [DavisCoeffs],
+/// rather than lumping them into train-level averages as in [super::Strap]
+#[serde_api]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+pub struct Interp {
+    /// Number of cars of each car type
+    n_cars_by_type: HashMap<String, u32>,
+    /// Per-car Davis coefficients for each car type
+    davis_by_car_type: HashMap<String, DavisCoeffs>,
+    grade: path_res::Strap,
+    curve: path_res::Strap,
+    /// Along-track wind speed, see [aerodynamic::Basic::wind_speed]
+    #[serde(default)]
+    wind_speed: si::Velocity,
+}
+
+#[pyo3_api]
+impl Interp {}
+
+impl Init for Interp {}
+impl SerdeAPI for Interp {}
+
+impl Interp {
+    pub fn new(
+        rail_vehicles: &[RailVehicle],
+        n_cars_by_type: HashMap<String, u32>,
+        grade: path_res::Strap,
+        curve: path_res::Strap,
+    ) -> anyhow::Result<Self> {
+        let davis_by_car_type = rail_vehicles
+            .iter()
+            .map(|rv| -> anyhow::Result<(String, DavisCoeffs)> {
+                Ok((rv.car_type.clone(), DavisCoeffs::try_from(rv)?))
+            })
+            .collect::<anyhow::Result<HashMap<String, DavisCoeffs>>>()?;
+        if let Some(car_type) = n_cars_by_type
+            .keys()
+            .find(|car_type| !davis_by_car_type.contains_key(*car_type))
+        {
+            bail!(
+                "{}\nNo Davis coefficients found for car type `{}`",
+                format_dbg!(),
+                car_type
+            );
+        }
+        Ok(Self {
+            n_cars_by_type,
+            davis_by_car_type,
+            grade,
+            curve,
+            wind_speed: si::Velocity::ZERO,
+        })
+    }
+
+    /// Sets along-track wind speed used in aerodynamic resistance, see
+    /// [aerodynamic::Basic::wind_speed]
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) {
+        self.wind_speed = wind_speed;
+    }
+
+    /// Iterator over `(n_cars, coeffs)` for each car type in the train
+    fn car_groups(&self) -> impl Iterator<Item = (f64, &DavisCoeffs)> {
+        self.n_cars_by_type.iter().filter_map(|(car_type, n)| {
+            self.davis_by_car_type
+                .get(car_type)
+                .map(|coeffs| (*n as f64, coeffs))
+        })
+    }
+}
+
+impl ResMethod for Interp {
+    fn update_res(
+        &mut self,
+        state: &mut TrainState,
+        path_tpc: &PathTpc,
+        dir: &Dir,
+    ) -> anyhow::Result<()> {
+        state.offset_back.update_unchecked(
+            *state.offset.get_unchecked(|| format_dbg!())?
+                - *state.length.get_unchecked(|| format_dbg!())?,
+            || format_dbg!(),
+        )?;
+        state.weight_static.update_unchecked(
+            state
+                .mass()
+                .with_context(|| format_dbg!())? // extract result
+                .with_context(|| "{}\nExpected `Some`.")? // extract option
+                * uc::ACC_GRAV,
+            || format_dbg!(),
+        )?;
+        let speed = *state.speed.get_unchecked(|| format_dbg!())?;
+        let speed_air = speed - self.wind_speed;
+        let (res_bearing, res_rolling, res_davis_b, res_aero) = self.car_groups().fold(
+            (
+                si::Force::ZERO,
+                si::Force::ZERO,
+                si::Force::ZERO,
+                si::Force::ZERO,
+            ),
+            |(bearing, rolling, davis_b, aero), (n, coeffs)| {
+                let weight = coeffs.mass_static * uc::ACC_GRAV;
+                (
+                    bearing + n * coeffs.bearing,
+                    rolling + n * coeffs.rolling_ratio * weight,
+                    davis_b + n * coeffs.davis_b * speed * weight,
+                    aero + n * coeffs.cd_area * uc::rho_air() * speed_air * speed_air.abs(),
+                )
+            },
+        );
+        state
+            .res_bearing
+            .update_unchecked(res_bearing, || format_dbg!())?;
+        state
+            .res_rolling
+            .update_unchecked(res_rolling, || format_dbg!())?;
+        state
+            .res_davis_b
+            .update_unchecked(res_davis_b, || format_dbg!())?;
+        state
+            .res_aero
+            .update_unchecked(res_aero, || format_dbg!())?;
+        state.res_grade.update_unchecked(
+            self.grade.calc_res(path_tpc.grades(), state, dir)?,
+            || format_dbg!(),
+        )?;
+        state.res_curve.update_unchecked(
+            self.curve.calc_res(path_tpc.curves(), state, dir)?,
+            || format_dbg!(),
+        )?;
+        state.grade_front.update_unchecked(
+            self.grade.res_coeff_front(path_tpc.grades()),
+            || format_dbg!(),
+        )?;
+        state.grade_back.update_unchecked(
+            self.grade.res_coeff_back(path_tpc.grades()),
+            || format_dbg!(),
+        )?;
+        state.elev_front.update_unchecked(
+            self.grade.res_net_front(path_tpc.grades(), state)?,
+            || format_dbg!(),
+        )?;
+        state.elev_back.update_unchecked(
+            self.grade.res_net_back(path_tpc.grades(), state)?,
+            || format_dbg!(),
+        )?;
+        Ok(())
+    }
+
+    fn fix_cache(&mut self, link_point_del: &LinkPoint) {
+        self.grade.fix_cache(link_point_del.grade_count);
+        self.curve.fix_cache(link_point_del.curve_count);
+    }
+}
+
+impl Valid for Interp {
+    fn valid() -> Self {
+        let n_cars = 100;
+        Self {
+            n_cars_by_type: HashMap::from([("Bulk".into(), n_cars)]),
+            davis_by_car_type: HashMap::from([(
+                "Bulk".into(),
+                DavisCoeffs {
+                    bearing: 40.0 * uc::LBF,
+                    rolling_ratio: 1.5 * uc::LB / uc::TON,
+                    davis_b: 0.03 / uc::MPH * uc::LB / uc::TON,
+                    cd_area: 5.0 * uc::FT2,
+                    mass_static: 6000.0 * uc::TON / n_cars as f64,
+                },
+            )]),
+            grade: path_res::Strap::new(&Vec::<PathResCoeff>::valid(), &TrainState::valid())
+                .unwrap(),
+            curve: path_res::Strap::new(&Vec::<PathResCoeff>::valid(), &TrainState::valid())
+                .unwrap(),
+            wind_speed: si::Velocity::ZERO,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::Strap;
+    use super::*;
+
+    #[test]
+    fn test_interp_matches_strap_for_single_car_type() {
+        let n_cars = 80_u32;
+        let rv = RailVehicle {
+            car_type: "Bulk".into(),
+            axle_count: 4,
+            mass_static_base: 25.0 * uc::TON,
+            mass_freight: 100.0 * uc::TON,
+            bearing_res_per_axle: 60.0 * uc::LBF,
+            rolling_ratio: 1.5 * uc::LB / uc::TON,
+            davis_b: 0.03 / uc::MPH * uc::LB / uc::TON,
+            cd_area: 2.0 * uc::M2,
+            ..Default::default()
+        };
+        let mut state = TrainState::valid();
+        state
+            .mass_static
+            .update_unchecked(125.0 * uc::TON * n_cars as f64, || format_dbg!())
+            .unwrap();
+        state
+            .speed
+            .update_unchecked(20.0 * uc::MPS, || format_dbg!())
+            .unwrap();
+        let path_tpc = PathTpc::valid();
+        let grade = path_res::Strap::new(path_tpc.grades(), &state).unwrap();
+        let curve = path_res::Strap::new(path_tpc.curves(), &state).unwrap();
+
+        let mut interp = Interp::new(
+            std::slice::from_ref(&rv),
+            HashMap::from([(rv.car_type.clone(), n_cars)]),
+            grade,
+            curve,
+        )
+        .unwrap();
+        let mut strap = Strap::new(
+            bearing::Basic::new(rv.bearing_res_per_axle * rv.axle_count as f64 * n_cars as f64),
+            rolling::Basic::new(rv.rolling_ratio),
+            davis_b::Basic::new(rv.davis_b),
+            aerodynamic::Basic::new(rv.cd_area * n_cars as f64),
+            grade,
+            curve,
+        );
+
+        let mut state_interp = state.clone();
+        interp
+            .update_res(&mut state_interp, &path_tpc, &Dir::Fwd)
+            .unwrap();
+        strap.update_res(&mut state, &path_tpc, &Dir::Fwd).unwrap();
+
+        let res_net = |s: &TrainState| -> si::Force { s.res_net().unwrap() };
+        let err = (res_net(&state_interp) - res_net(&state)).abs() / res_net(&state).abs();
+        assert!(err < 1e-6 * uc::R, "relative error: {err:?}");
+        assert_eq!(state_interp.res_grade, state.res_grade);
+        assert_eq!(state_interp.res_curve, state.res_curve);
+    }
+
+    #[test]
+    fn test_interp_missing_car_type() {
+        assert!(Interp::new(
+            &[],
+            HashMap::from([("Bulk".into(), 1)]),
+            Default::default(),
+            Default::default(),
+        )
+        .is_err());
+    }
+}
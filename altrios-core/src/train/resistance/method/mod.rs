@@ -1,6 +1,8 @@
+pub mod interp;
 pub mod point;
 pub mod strap;
 
+pub use interp::{DavisCoeffs, Interp};
 pub use point::Point;
 pub use strap::Strap;
 
@@ -22,6 +22,14 @@ impl Point {}
 impl Init for Point {}
 impl SerdeAPI for Point {}
 
+impl Point {
+    /// Sets along-track wind speed used in aerodynamic resistance, see
+    /// [aerodynamic::Basic::wind_speed]
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) {
+        self.aerodynamic.set_wind_speed(wind_speed);
+    }
+}
+
 impl ResMethod for Point {
     fn update_res(
         &mut self,
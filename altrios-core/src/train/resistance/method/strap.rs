@@ -41,6 +41,14 @@ impl Strap {
         }
     }
 }
+impl Strap {
+    /// Sets along-track wind speed used in aerodynamic resistance, see
+    /// [aerodynamic::Basic::wind_speed]
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) {
+        self.aerodynamic.set_wind_speed(wind_speed);
+    }
+}
+
 impl ResMethod for Strap {
     fn update_res(
         &mut self,
@@ -16,6 +16,51 @@ pub trait ResMethod {
     fn fix_cache(&mut self, link_point_del: &LinkPoint);
 }
 
+/// User-defined [ResMethod] that can be injected via [TrainRes::custom]
+pub trait CustomResMethod: ResMethod + fmt::Debug + Send + Sync {
+    fn clone_box(&self) -> Box<dyn CustomResMethod>;
+    fn as_any(&self) -> &dyn std::any::Any;
+    /// Returns true if `other` is the same concrete type as `self` and equal
+    /// to it
+    fn eq_dyn(&self, other: &dyn CustomResMethod) -> bool;
+}
+
+impl<T> CustomResMethod for T
+where
+    T: ResMethod + fmt::Debug + Clone + PartialEq + Send + Sync + 'static,
+{
+    fn clone_box(&self) -> Box<dyn CustomResMethod> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn eq_dyn(&self, other: &dyn CustomResMethod) -> bool {
+        other
+            .as_any()
+            .downcast_ref::<T>()
+            .is_some_and(|other| self == other)
+    }
+}
+
+/// Boxed [CustomResMethod] contained in [TrainRes::Custom]
+#[derive(Debug)]
+pub struct CustomRes(pub Box<dyn CustomResMethod>);
+
+impl Clone for CustomRes {
+    fn clone(&self) -> Self {
+        Self(self.0.clone_box())
+    }
+}
+
+impl PartialEq for CustomRes {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_dyn(other.0.as_ref())
+    }
+}
+
 #[serde_api]
 #[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
@@ -33,6 +78,11 @@ impl TrainResWrapper {
     fn get_strap_py(&self) -> Option<method::Strap> {
         self.get_strap()
     }
+
+    #[getter("interp")]
+    fn get_interp_py(&self) -> Option<method::Interp> {
+        self.get_interp()
+    }
 }
 
 impl Init for TrainResWrapper {}
@@ -53,6 +103,13 @@ impl TrainResWrapper {
             _ => None,
         }
     }
+
+    fn get_interp(&self) -> Option<method::Interp> {
+        match &self.0 {
+            TrainRes::Interp(i) => Some(i.clone()),
+            _ => None,
+        }
+    }
 }
 
 /// Train resistance calculator that calculates resistive powers due to rolling, curvature, flange,
@@ -62,11 +119,38 @@ impl TrainResWrapper {
 pub enum TrainRes {
     Point(method::Point),
     Strap(method::Strap),
+    Interp(method::Interp),
+    /// User-defined resistance method, which cannot be serialized and must be
+    /// set again via [TrainRes::custom] after deserialization
+    #[serde(skip)]
+    Custom(CustomRes),
 }
 
 impl Init for TrainRes {}
 impl SerdeAPI for TrainRes {}
 
+impl TrainRes {
+    /// Wraps user-defined `res_method` in [TrainRes::Custom]
+    pub fn custom<R: CustomResMethod + 'static>(res_method: R) -> Self {
+        Self::Custom(CustomRes(Box::new(res_method)))
+    }
+
+    /// Sets along-track wind speed used in aerodynamic resistance, see
+    /// [kind::aerodynamic::Basic::wind_speed]
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) -> anyhow::Result<()> {
+        match self {
+            TrainRes::Point(p) => p.set_wind_speed(wind_speed),
+            TrainRes::Strap(s) => s.set_wind_speed(wind_speed),
+            TrainRes::Interp(i) => i.set_wind_speed(wind_speed),
+            TrainRes::Custom(_) => bail!(
+                "{}\nWind speed is not supported for `TrainRes::Custom`",
+                format_dbg!()
+            ),
+        }
+        Ok(())
+    }
+}
+
 impl ResMethod for TrainRes {
     fn update_res(
         &mut self,
@@ -77,12 +161,16 @@ impl ResMethod for TrainRes {
         match self {
             TrainRes::Point(p) => p.update_res(state, path_tpc, dir),
             TrainRes::Strap(s) => s.update_res(state, path_tpc, dir),
+            TrainRes::Interp(i) => i.update_res(state, path_tpc, dir),
+            TrainRes::Custom(c) => c.0.update_res(state, path_tpc, dir),
         }
     }
     fn fix_cache(&mut self, link_point_del: &LinkPoint) {
         match self {
             TrainRes::Point(p) => p.fix_cache(link_point_del),
             TrainRes::Strap(s) => s.fix_cache(link_point_del),
+            TrainRes::Interp(i) => i.fix_cache(link_point_del),
+            TrainRes::Custom(c) => c.0.fix_cache(link_point_del),
         }
     }
 }
@@ -109,3 +197,106 @@ impl Valid for TrainRes {
 //         serde_yaml::to_writer(file, &TrainRes::valid()).unwrap();
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::train::SetSpeedTrainSim;
+
+    /// Constant rolling resistance with no grade or curve effects
+    #[derive(Debug, Clone, PartialEq)]
+    struct ConstRes {
+        force: si::Force,
+    }
+
+    impl ResMethod for ConstRes {
+        fn update_res(
+            &mut self,
+            state: &mut TrainState,
+            _path_tpc: &PathTpc,
+            _dir: &Dir,
+        ) -> anyhow::Result<()> {
+            state.offset_back.update_unchecked(
+                *state.offset.get_unchecked(|| format_dbg!())?
+                    - *state.length.get_unchecked(|| format_dbg!())?,
+                || format_dbg!(),
+            )?;
+            state.weight_static.update_unchecked(
+                *state.mass_static.get_unchecked(|| format_dbg!())? * uc::ACC_GRAV,
+                || format_dbg!(),
+            )?;
+            state
+                .res_rolling
+                .update_unchecked(self.force, || format_dbg!())?;
+            for res in [
+                &mut state.res_bearing,
+                &mut state.res_davis_b,
+                &mut state.res_aero,
+                &mut state.res_grade,
+                &mut state.res_curve,
+            ] {
+                res.update_unchecked(si::Force::ZERO, || format_dbg!())?;
+            }
+            for grade in [&mut state.grade_front, &mut state.grade_back] {
+                grade.update_unchecked(si::Ratio::ZERO, || format_dbg!())?;
+            }
+            for elev in [&mut state.elev_front, &mut state.elev_back] {
+                elev.update_unchecked(si::Length::ZERO, || format_dbg!())?;
+            }
+            Ok(())
+        }
+
+        fn fix_cache(&mut self, _link_point_del: &LinkPoint) {}
+    }
+
+    #[test]
+    fn test_custom_res() {
+        let force = 10e3 * uc::N;
+        let mut train_res = TrainRes::custom(ConstRes { force });
+        let mut state = TrainState::valid();
+        train_res
+            .update_res(&mut state, &PathTpc::valid(), &Dir::Fwd)
+            .unwrap();
+        assert_eq!(state.res_net().unwrap(), force);
+
+        // custom methods are cloneable but not serializable
+        assert!(matches!(train_res.clone(), TrainRes::Custom(_)));
+        assert_eq!(train_res.clone(), train_res);
+        assert_ne!(train_res, TrainRes::custom(ConstRes { force: 2.0 * force }));
+        assert!(train_res.to_json().is_err());
+
+        let mut train_sim = SetSpeedTrainSim::default();
+        train_sim.train_res = train_res;
+        train_sim.walk().unwrap();
+    }
+
+    #[test]
+    fn test_wind_speed() {
+        let mut state = TrainState::valid();
+        state
+            .speed
+            .update_unchecked(20.0 * uc::MPS, || format_dbg!())
+            .unwrap();
+        let res_aero = |wind_speed: si::Velocity| -> si::Force {
+            let mut train_res = TrainRes::valid();
+            train_res.set_wind_speed(wind_speed).unwrap();
+            let mut state = state.clone();
+            train_res
+                .update_res(&mut state, &PathTpc::valid(), &Dir::Fwd)
+                .unwrap();
+            *state.res_aero.get_unchecked(|| format_dbg!()).unwrap()
+        };
+        let res_aero_calm = res_aero(si::Velocity::ZERO);
+        assert!(res_aero_calm > si::Force::ZERO);
+        // headwind
+        assert!(res_aero(-5.0 * uc::MPS) > res_aero_calm);
+        // tailwind
+        assert!(res_aero(5.0 * uc::MPS) < res_aero_calm);
+
+        assert!(TrainRes::custom(ConstRes {
+            force: si::Force::ZERO
+        })
+        .set_wind_speed(5.0 * uc::MPS)
+        .is_err());
+    }
+}
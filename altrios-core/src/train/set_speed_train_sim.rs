@@ -1,5 +1,6 @@
 use super::environment::TemperatureTrace;
 use super::train_imports::*;
+use crate::consist::locomotive::loco_sim::PowerTrace;
 
 #[serde_api]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -29,6 +30,18 @@ impl SpeedTrace {
         Ok(Self::new(time_seconds, speed_meters_per_second, engine_on))
     }
 
+    #[staticmethod]
+    #[pyo3(name = "from_gps")]
+    #[pyo3(signature = (time_seconds, lats_degrees, lons_degrees, smoothing_window=None))]
+    fn from_gps_py(
+        time_seconds: Vec<f64>,
+        lats_degrees: Vec<f64>,
+        lons_degrees: Vec<f64>,
+        smoothing_window: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        Self::from_gps(time_seconds, lats_degrees, lons_degrees, smoothing_window)
+    }
+
     #[staticmethod]
     #[pyo3(name = "from_csv_file")]
     fn from_csv_file_py(filepath: &Bound<PyAny>) -> anyhow::Result<Self> {
@@ -44,6 +57,37 @@ impl SpeedTrace {
         self.to_csv_file(PathBuf::extract_bound(filepath)?)
     }
 
+    #[pyo3(name = "append")]
+    /// Appends `other` after `self` with `other`'s times offset by `self`'s last time
+    fn append_py(&mut self, other: &Self) -> anyhow::Result<()> {
+        self.append(other)
+    }
+
+    #[pyo3(name = "detect_stops")]
+    /// Returns inclusive `(start, end)` index ranges over which speed stays
+    /// below `speed_thresh_meters_per_second` for at least `min_duration_seconds`
+    fn detect_stops_py(
+        &self,
+        speed_thresh_meters_per_second: f64,
+        min_duration_seconds: f64,
+    ) -> Vec<(usize, usize)> {
+        self.detect_stops(
+            speed_thresh_meters_per_second * uc::MPS,
+            min_duration_seconds * uc::S,
+        )
+    }
+
+    #[pyo3(name = "clip_speed")]
+    /// Caps speeds above `speed_max_meters_per_second` at that value
+    fn clip_speed_py(&mut self, speed_max_meters_per_second: f64) -> anyhow::Result<()> {
+        self.clip_speed(speed_max_meters_per_second * uc::MPS)
+    }
+
+    #[pyo3(name = "to_power_trace")]
+    fn to_power_trace_py(&self, sim: &SetSpeedTrainSim) -> anyhow::Result<PowerTrace> {
+        self.to_power_trace(sim)
+    }
+
     #[staticmethod]
     #[pyo3(name = "default")]
     fn default_py() -> Self {
@@ -60,6 +104,121 @@ impl SpeedTrace {
         }
     }
 
+    /// Builds speed trace from GPS points, with speed at each point computed
+    /// from great-circle distance from the previous point over the time step.
+    /// The first point takes the speed of the first segment.
+    ///
+    /// # Arguments
+    /// - `times_s`: strictly increasing time of each point in seconds
+    /// - `lats`: latitude of each point in degrees
+    /// - `lons`: longitude of each point in degrees
+    /// - `smoothing_window`: optional number of points in centered moving
+    ///   average applied to speed, truncated at the ends of the trace
+    pub fn from_gps(
+        times_s: Vec<f64>,
+        lats: Vec<f64>,
+        lons: Vec<f64>,
+        smoothing_window: Option<usize>,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            times_s.len() == lats.len() && times_s.len() == lons.len(),
+            "{}\n`times_s` ({}), `lats` ({}), and `lons` ({}) must have equal lengths",
+            format_dbg!(),
+            times_s.len(),
+            lats.len(),
+            lons.len()
+        );
+        ensure!(
+            times_s.len() >= 2,
+            "{}\nAt least two points are required",
+            format_dbg!()
+        );
+        ensure!(
+            times_s.windows(2).all(|w| w[1] > w[0]),
+            "{}\n`times_s` must be strictly increasing",
+            format_dbg!()
+        );
+        ensure!(
+            smoothing_window != Some(0),
+            "{}\n`smoothing_window` must be positive",
+            format_dbg!()
+        );
+
+        let speed_segs: Vec<f64> = (1..times_s.len())
+            .map(|i| {
+                great_circle_dist(lats[i - 1], lons[i - 1], lats[i], lons[i]).get::<si::meter>()
+                    / (times_s[i] - times_s[i - 1])
+            })
+            .collect();
+        let mut speed_mps = Vec::with_capacity(times_s.len());
+        speed_mps.push(speed_segs[0]);
+        speed_mps.extend_from_slice(&speed_segs);
+
+        if let Some(window) = smoothing_window {
+            let half = window / 2;
+            speed_mps = (0..speed_mps.len())
+                .map(|i| {
+                    let start = i.saturating_sub(half);
+                    let end = (i + window - half).min(speed_mps.len());
+                    speed_mps[start..end].iter().sum::<f64>() / (end - start) as f64
+                })
+                .collect();
+        }
+
+        Ok(Self::new(times_s, speed_mps, None))
+    }
+
+    /// Returns [PowerTrace] of consist power output requested at each time
+    /// step when a clone of `sim`, which must not have been walked yet, follows
+    /// [Self] in place of its own speed trace.  The initial time step has no
+    /// power request, so the result has one fewer element than [Self].
+    pub fn to_power_trace(&self, sim: &SetSpeedTrainSim) -> anyhow::Result<PowerTrace> {
+        ensure!(
+            self.len() >= 2,
+            "{}
+At least two points are required",
+            format_dbg!()
+        );
+        ensure!(
+            *sim.state.i.get_unchecked(|| format_dbg!())? == 0,
+            "{}
+`sim` must not have been walked yet",
+            format_dbg!()
+        );
+        let mut sim = sim.clone();
+        sim.speed_trace = self.clone();
+        sim.set_history_sink(None);
+        sim.set_save_interval(Some(1));
+        sim.walk().with_context(|| format_dbg!())?;
+
+        let unpack = |hist: &[TrackedState<si::Power>]| -> anyhow::Result<Vec<si::Power>> {
+            hist.iter()
+                .skip(1)
+                .map(|x| Ok(*x.get_unchecked(|| format_dbg!())?))
+                .collect()
+        };
+        let pwr = unpack(&sim.loco_con.history.pwr_out_req)?;
+        ensure!(
+            pwr.len() == self.len() - 1,
+            "{}
+Expected {} power values, got {}",
+            format_dbg!(),
+            self.len() - 1,
+            pwr.len()
+        );
+        Ok(PowerTrace {
+            time: self.time[1..].to_vec(),
+            pwr,
+            engine_on: match &self.engine_on {
+                Some(engine_on) => engine_on[1..].iter().map(|eo| Some(*eo)).collect(),
+                None => vec![None; self.len() - 1],
+            },
+            train_speed: self.speed[1..].to_vec(),
+            train_mass: sim.state.mass().with_context(|| format_dbg!())?,
+            pwr_aux: None,
+        })
+    }
+
     pub fn trim(&mut self, start_idx: Option<usize>, end_idx: Option<usize>) -> anyhow::Result<()> {
         let start_idx = start_idx.unwrap_or(0);
         let end_idx = end_idx.unwrap_or_else(|| self.len());
@@ -74,6 +233,19 @@ impl SpeedTrace {
         Ok(())
     }
 
+    /// Caps speeds above `v_max` at `v_max`, leaving time unchanged
+    pub fn clip_speed(&mut self, v_max: si::Velocity) -> anyhow::Result<()> {
+        ensure!(
+            v_max > si::Velocity::ZERO,
+            "{}\nExpected `v_max` to be positive",
+            format_dbg!()
+        );
+        for speed in self.speed.iter_mut() {
+            *speed = speed.min(v_max);
+        }
+        Ok(())
+    }
+
     pub fn dt(&self, i: usize) -> si::Time {
         self.time[i] - self.time[i - 1]
     }
@@ -86,6 +258,31 @@ impl SpeedTrace {
         (self.speed[i] - self.speed[i - 1]) / self.dt(i)
     }
 
+    /// Returns inclusive `(start, end)` index ranges of consecutive points
+    /// with speed below `speed_thresh` that span at least `min_duration`
+    pub fn detect_stops(
+        &self,
+        speed_thresh: si::Velocity,
+        min_duration: si::Time,
+    ) -> Vec<(usize, usize)> {
+        let mut stops = Vec::new();
+        let mut start: Option<usize> = None;
+        for i in 0..=self.len() {
+            let stopped = i < self.len() && self.speed[i] < speed_thresh;
+            match (start, stopped) {
+                (None, true) => start = Some(i),
+                (Some(i_start), false) => {
+                    if self.time[i - 1] - self.time[i_start] >= min_duration {
+                        stops.push((i_start, i - 1));
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        stops
+    }
+
     pub fn len(&self) -> usize {
         self.time.len()
     }
@@ -111,6 +308,56 @@ impl SpeedTrace {
         Ok(())
     }
 
+    /// Appends `other` after `self` with continuous time, offsetting `other`'s
+    /// times by `self`'s last time.  If `other` starts at time zero, its first
+    /// point coincides with `self`'s last point and is dropped, which requires
+    /// that the two points match.
+    pub fn append(&mut self, other: &SpeedTrace) -> anyhow::Result<()> {
+        ensure!(
+            self.engine_on.is_some() == other.engine_on.is_some(),
+            "{}\n`engine_on` in both `SpeedTrace`s must have same option variant.",
+            format_dbg!()
+        );
+        let (time_last, speed_last) = match (self.time.last(), self.speed.last()) {
+            (Some(time_last), Some(speed_last)) => (*time_last, *speed_last),
+            _ => {
+                *self = other.clone();
+                return Ok(());
+            }
+        };
+        let idx_start = match other.time.first() {
+            Some(time_first) if *time_first == si::Time::ZERO => {
+                let engine_on_match = match (&self.engine_on, &other.engine_on) {
+                    (Some(eo), Some(eo_other)) => eo.last() == eo_other.first(),
+                    _ => true,
+                };
+                ensure!(
+                    other.speed[0] == speed_last && engine_on_match,
+                    "{}\n`other` starts at time zero, so its first point must match last point of `self`",
+                    format_dbg!()
+                );
+                1
+            }
+            Some(time_first) => {
+                ensure!(
+                    *time_first > si::Time::ZERO,
+                    "{}\n`other` must not start at negative time",
+                    format_dbg!()
+                );
+                0
+            }
+            None => return Ok(()),
+        };
+
+        self.time
+            .extend(other.time[idx_start..].iter().map(|t| *t + time_last));
+        self.speed.extend_from_slice(&other.speed[idx_start..]);
+        if let (Some(eo), Some(eo_other)) = (self.engine_on.as_mut(), &other.engine_on) {
+            eo.extend_from_slice(&eo_other[idx_start..]);
+        }
+        Ok(())
+    }
+
     pub fn empty() -> Self {
         Self {
             time: Vec::new(),
@@ -174,6 +421,16 @@ impl SpeedTrace {
 impl Init for SpeedTrace {}
 impl SerdeAPI for SpeedTrace {}
 
+/// Returns haversine great-circle distance between two points given as
+/// latitude and longitude in degrees
+fn great_circle_dist(lat0: f64, lon0: f64, lat1: f64, lon1: f64) -> si::Length {
+    let (lat0, lat1) = (lat0.to_radians(), lat1.to_radians());
+    let dlat = lat1 - lat0;
+    let dlon = (lon1 - lon0).to_radians();
+    let a = (dlat / 2.0).sin().powi(2) + lat0.cos() * lat1.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * a.sqrt().min(1.0).asin() * uc::EARTH_RADIUS
+}
+
 impl Default for SpeedTrace {
     fn default() -> Self {
         let mut speed_mps: Vec<f64> = Vec::linspace(0.0, 20.0, 800);
@@ -216,9 +473,17 @@ pub struct SetSpeedTrainSim {
     pub train_res: TrainRes,
 
     path_tpc: PathTpc,
+    /// Numerical integration method for cumulative energies
+    #[serde(default)]
+    pub integration_method: IntegrationMethod,
     /// Custom vector of [Self::state]
     #[serde(default)]
     pub history: TrainStateHistoryVec,
+    /// If provided, [Self::state] is streamed to this CSV sink rather than
+    /// pushed to [Self::history], and histories of [Self::loco_con]
+    /// are not saved.  Clones of the sim stream to separate files.
+    #[serde(skip)]
+    history_sink: Option<HistorySink>,
 
     save_interval: Option<usize>,
     /// Time-dependent temperature at sea level that can be corrected for
@@ -244,6 +509,14 @@ impl SetSpeedTrainSim {
         }
     }
 
+    #[getter]
+    pub fn get_res_interp(&self) -> anyhow::Result<Option<method::Interp>> {
+        match &self.train_res {
+            TrainRes::Interp(interp) => Ok(Some(interp.clone())),
+            _ => Ok(None),
+        }
+    }
+
     #[pyo3(name = "walk")]
     /// Exposes `walk` to Python.
     fn walk_py(&mut self) -> anyhow::Result<()> {
@@ -255,6 +528,12 @@ impl SetSpeedTrainSim {
         self.step(|| format_dbg!())
     }
 
+    #[pyo3(name = "strip_history")]
+    /// Clear history and cascade to nested components.
+    fn strip_history_py(&mut self) {
+        self.strip_history();
+    }
+
     #[pyo3(name = "set_save_interval")]
     #[pyo3(signature = (save_interval=None))]
     /// Set save interval and cascade to nested components.
@@ -272,6 +551,27 @@ impl SetSpeedTrainSim {
         self.trim_failed_steps()?;
         Ok(())
     }
+
+    #[pyo3(name = "set_history_sink")]
+    #[pyo3(signature = (path=None))]
+    /// Streams saved states to a CSV file at `path` instead of `history`,
+    /// with `None` restoring in-memory history
+    fn set_history_sink_py(&mut self, path: Option<PathBuf>) -> anyhow::Result<()> {
+        self.set_history_sink(path.map(HistorySink::new).transpose()?);
+        Ok(())
+    }
+
+    #[pyo3(name = "set_wind_speed")]
+    /// Sets along-track wind speed in m/s, see [Self::set_wind_speed]
+    fn set_wind_speed_py(&mut self, wind_speed_meters_per_second: f64) -> anyhow::Result<()> {
+        self.set_wind_speed(wind_speed_meters_per_second * uc::MPS)
+    }
+
+    #[pyo3(name = "set_integration_method")]
+    /// Sets numerical integration method for cumulative energies
+    fn set_integration_method_py(&mut self, integration_method: IntegrationMethod) {
+        self.integration_method = integration_method;
+    }
 }
 
 pub struct SetSpeedTrainSimBuilder {
@@ -296,7 +596,9 @@ impl From<SetSpeedTrainSimBuilder> for SetSpeedTrainSim {
             speed_trace: value.speed_trace,
             train_res: value.train_res,
             path_tpc: value.path_tpc,
+            integration_method: Default::default(),
             history: Default::default(),
+            history_sink: None,
             save_interval: value.save_interval,
             temp_trace: value.temp_trace,
         }
@@ -304,6 +606,10 @@ impl From<SetSpeedTrainSimBuilder> for SetSpeedTrainSim {
 }
 
 impl SetSpeedTrainSim {
+    pub fn path_tpc(&self) -> &PathTpc {
+        &self.path_tpc
+    }
+
     /// Trims off any portion of the trip that failed to run
     pub fn trim_failed_steps(&mut self) -> anyhow::Result<()> {
         if *self.state.i.get_fresh(|| format_dbg!())? <= 1 {
@@ -321,6 +627,27 @@ impl SetSpeedTrainSim {
         self.loco_con.set_save_interval(save_interval);
     }
 
+    /// Sets sink to which saved states are streamed instead of being pushed
+    /// to [Self::history], with `None` restoring in-memory history
+    pub fn set_history_sink(&mut self, history_sink: Option<HistorySink>) {
+        self.history_sink = history_sink;
+    }
+
+    /// Sets along-track wind speed in [Self::train_res], see
+    /// [TrainRes::set_wind_speed]
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) -> anyhow::Result<()> {
+        self.train_res
+            .set_wind_speed(wind_speed)
+            .with_context(|| format_dbg!())
+    }
+
+    /// Clears `self.history` and history of nested `loco_con` so that `self`
+    /// can be serialized compactly, e.g. for reuse as an input
+    pub fn strip_history(&mut self) {
+        self.history.clear();
+        self.loco_con.strip_history();
+    }
+
     /// Returns `self.save_interval` and asserts that this is equal
     /// to `self.loco_con.get_save_interval()`.
     pub fn get_save_interval(&self) -> Option<usize> {
@@ -335,6 +662,7 @@ impl SetSpeedTrainSim {
         let dt = self.speed_trace.time[*self.state.i.get_fresh(|| format_dbg!())?]
             - *self.state.time.get_stale(|| format_dbg!())?;
         self.state.dt.update(dt, || format_dbg!())?;
+        self.set_cumulative_prev(dt, self.integration_method, || format_dbg!())?;
 
         ensure!(
             self.speed_trace.speed[*self.state.i.get_fresh(|| format_dbg!())?]
@@ -389,6 +717,10 @@ impl SetSpeedTrainSim {
             elev_and_temp,
             train_mass,
             Some(*self.state.speed.get_stale(|| format_dbg!())?),
+            Some(self.path_tpc.grade_ahead(
+                *self.state.offset.get_stale(|| format_dbg!())?,
+                GRADE_LOOKAHEAD_MILES * uc::MI,
+            )),
             self.speed_trace
                 .dt(*self.state.i.get_fresh(|| format_dbg!())?),
         )?;
@@ -400,13 +732,15 @@ impl SetSpeedTrainSim {
             self.speed_trace
                 .dt(*self.state.i.get_fresh(|| format_dbg!())?),
         )?;
-        self.loco_con.solve_energy_consumption(
-            *self.state.pwr_whl_out.get_fresh(|| format_dbg!())?,
-            train_mass,
-            Some(self.speed_trace.speed[*self.state.i.get_fresh(|| format_dbg!())?]),
-            self.speed_trace
-                .dt(*self.state.i.get_fresh(|| format_dbg!())?),
-            Some(true),
+        self.state.with_loc_context(
+            self.loco_con.solve_energy_consumption(
+                *self.state.pwr_whl_out.get_fresh(|| format_dbg!())?,
+                train_mass,
+                Some(self.speed_trace.speed[*self.state.i.get_fresh(|| format_dbg!())?]),
+                self.speed_trace
+                    .dt(*self.state.i.get_fresh(|| format_dbg!())?),
+                Some(true),
+            ),
         )?;
         // advance time
         self.state.time.increment(dt, || format_dbg!())?;
@@ -432,8 +766,9 @@ impl SetSpeedTrainSim {
             .abs(),
             || format_dbg!(),
         )?;
-        self.set_cumulative(
+        self.set_cumulative_with(
             *self.state.dt.get_fresh(|| format_dbg!())?,
+            self.integration_method,
             || format_dbg!(),
         )?;
         Ok(())
@@ -448,6 +783,9 @@ impl SetSpeedTrainSim {
             }
             self.step(|| format_dbg!()).with_context(|| format_dbg!())?;
         }
+        if let Some(sink) = &self.history_sink {
+            sink.flush().with_context(|| format_dbg!())?;
+        }
         Ok(())
     }
 
@@ -520,6 +858,38 @@ impl SetSpeedTrainSim {
             pwr_whl_out_unclipped.max(-pwr_neg_max).min(pwr_pos_max),
             || format_dbg!(),
         )?;
+        // adhesion is not enforced when following a speed trace
+        self.state.traction_limit_cause.update(
+            if pwr_whl_out_unclipped > pwr_pos_max {
+                TractionLimitCause::Power
+            } else {
+                TractionLimitCause::Unlimited
+            }
+            .into(),
+            || format_dbg!(),
+        )?;
+        // speed trace is followed regardless of limits, so applied force is
+        // whatever is needed to overcome resistance and achieve the trace
+        let i = *self.state.i.get_fresh(|| format_dbg!())?;
+        self.state.force_applied.update(
+            self.state.res_net().with_context(|| format_dbg!())?
+                + self.state.mass_compound().with_context(|| format_dbg!())?
+                    * (self.speed_trace.speed[i] - self.speed_trace.speed[i - 1])
+                    / self.speed_trace.dt(i),
+            || format_dbg!(),
+        )?;
+
+        // braking accounting -- the consist's negative traction is dynamic
+        // braking, and friction braking only makes up the deficit beyond it
+        self.state.pwr_dyn_brake.update(
+            (-*self.state.pwr_whl_out.get_fresh(|| format_dbg!())?).max(si::Power::ZERO),
+            || format_dbg!(),
+        )?;
+        self.state.pwr_fric_brake.update(
+            (*self.state.pwr_whl_out.get_fresh(|| format_dbg!())? - pwr_whl_out_unclipped)
+                .max(si::Power::ZERO),
+            || format_dbg!(),
+        )?;
 
         // add to positive or negative wheel energy tracking.
         if *self.state.pwr_whl_out.get_fresh(|| format_dbg!())? >= 0. * uc::W {
@@ -555,11 +925,35 @@ impl CheckAndResetState for SetSpeedTrainSim {
     }
 }
 impl SetCumulative for SetSpeedTrainSim {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
-        self.state
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        self.state.set_cumulative_with(dt, integration_method, || {
+            format!("{}\n{}", loc(), format_dbg!())
+        })?;
+        self.loco_con
+            .set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
+        Ok(())
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        self.state.set_cumulative_prev(dt, integration_method, || {
+            format!("{}\n{}", loc(), format_dbg!())
+        })?;
         self.loco_con
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
+            .set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
         Ok(())
     }
 }
@@ -585,8 +979,15 @@ impl SaveState for SetSpeedTrainSim {
     fn save_state<F: Fn() -> String>(&mut self, _loc: F) -> anyhow::Result<()> {
         if let Some(interval) = self.save_interval {
             if self.state.i.get_fresh(|| format_dbg!())? % interval == 0 {
-                self.history.push(self.state.clone());
-                self.loco_con.save_state(|| format_dbg!())?;
+                // with a sink, nested histories are not saved so that memory
+                // use stays bounded
+                match &self.history_sink {
+                    Some(sink) => sink.write(&self.state).with_context(|| format_dbg!())?,
+                    None => {
+                        self.history.push(self.state.clone());
+                        self.loco_con.save_state(|| format_dbg!())?;
+                    }
+                }
             }
         }
         Ok(())
@@ -614,7 +1015,9 @@ impl Default for SetSpeedTrainSim {
             train_res: TrainRes::valid(),
             path_tpc: PathTpc::valid(),
             speed_trace: SpeedTrace::default(),
+            integration_method: Default::default(),
             history: TrainStateHistoryVec::default(),
+            history_sink: None,
             save_interval: None,
             temp_trace: Default::default(),
         }
@@ -623,7 +1026,106 @@ impl Default for SetSpeedTrainSim {
 
 #[cfg(test)]
 mod tests {
-    use super::SetSpeedTrainSim;
+    use super::{SetSpeedTrainSim, SpeedTrace};
+    use crate::imports::*;
+
+    #[test]
+    fn test_speed_trace_append() {
+        let mut speed_trace = SpeedTrace::default();
+        let other = SpeedTrace::default();
+        speed_trace.append(&other).unwrap();
+        // shared boundary point is not duplicated
+        assert_eq!(speed_trace.len(), 2 * other.len() - 1);
+        assert_eq!(speed_trace.speed.len(), speed_trace.len());
+        assert!(speed_trace.time.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(
+            *speed_trace.time.last().unwrap(),
+            2.0 * *other.time.last().unwrap()
+        );
+
+        let mut other_eo = other.clone();
+        other_eo.engine_on = Some(vec![true; other.len()]);
+        assert!(speed_trace.append(&other_eo).is_err());
+    }
+
+    #[test]
+    fn test_detect_stops() {
+        let speed_trace = SpeedTrace::default();
+        let stops = speed_trace.detect_stops(0.5 * uc::MPS, 3.0 * uc::S);
+        assert_eq!(stops.len(), 2);
+        assert_eq!(stops[0].0, 0);
+        assert_eq!(stops.last().unwrap().1, speed_trace.len() - 1);
+        assert!(stops
+            .iter()
+            .all(|(start, end)| speed_trace.speed[*start..=*end]
+                .iter()
+                .all(|s| *s < 0.5 * uc::MPS)));
+
+        // stops shorter than minimum duration are excluded
+        assert!(speed_trace
+            .detect_stops(0.5 * uc::MPS, 1000.0 * uc::S)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_clip_speed() {
+        let mut speed_trace = SpeedTrace::default();
+        let time = speed_trace.time.clone();
+        let speed_max = speed_trace
+            .speed
+            .iter()
+            .fold(si::Velocity::ZERO, |acc, s| acc.max(*s));
+        let v_max = 0.5 * speed_max;
+        speed_trace.clip_speed(v_max).unwrap();
+        assert!(speed_trace.speed.iter().all(|s| *s <= v_max));
+        assert!(speed_trace.speed.contains(&v_max));
+        assert_eq!(speed_trace.time, time);
+
+        assert!(speed_trace.clip_speed(si::Velocity::ZERO).is_err());
+    }
+
+    #[test]
+    fn test_from_gps() {
+        // 1 arc-minute of latitude every 60 s along a meridian
+        let speed = (1.0_f64 / 60.0).to_radians() * uc::EARTH_RADIUS / (60.0 * uc::S);
+        let times_s: Vec<f64> = (0..10).map(|i| i as f64 * 60.0).collect();
+        let lats: Vec<f64> = (0..10).map(|i| 40.0 + i as f64 / 60.0).collect();
+        let lons = vec![-105.0; 10];
+
+        let speed_trace =
+            SpeedTrace::from_gps(times_s.clone(), lats.clone(), lons.clone(), None).unwrap();
+        assert_eq!(speed_trace.len(), times_s.len());
+        for s in &speed_trace.speed {
+            assert!(almost_eq_uom(s, &speed, None), "{s:?} != {speed:?}");
+        }
+        let speed_trace_smooth =
+            SpeedTrace::from_gps(times_s.clone(), lats.clone(), lons.clone(), Some(3)).unwrap();
+        for s in &speed_trace_smooth.speed {
+            assert!(almost_eq_uom(s, &speed, None));
+        }
+
+        assert!(
+            SpeedTrace::from_gps(times_s.clone(), lats[1..].to_vec(), lons.clone(), None).is_err()
+        );
+        let mut times_s_bad = times_s;
+        times_s_bad.swap(2, 3);
+        assert!(SpeedTrace::from_gps(times_s_bad, lats, lons, None).is_err());
+    }
+
+    #[test]
+    fn test_to_power_trace() {
+        let speed_trace = SpeedTrace::default();
+        let sim = SetSpeedTrainSim::default();
+        let pt = speed_trace.to_power_trace(&sim).unwrap();
+        assert_eq!(pt.len(), speed_trace.len() - 1);
+        assert_eq!(pt.pwr.len(), pt.len());
+        assert_eq!(pt.train_speed.len(), pt.len());
+        assert!(pt.pwr.iter().any(|pwr| *pwr > si::Power::ZERO));
+
+        let mut sim_walked = sim.clone();
+        sim_walked.walk().unwrap();
+        assert!(speed_trace.to_power_trace(&sim_walked).is_err());
+    }
 
     #[test]
     fn test_set_speed_train_sim() {
@@ -639,4 +1141,18 @@ mod tests {
                 > 1
         );
     }
+
+    #[test]
+    fn test_assert_limits_context() {
+        let mut train_sim = SetSpeedTrainSim::default();
+        // power demand is clipped to consist limits, so a negative consist
+        // ramp rate is used to make even zero power at the first step exceed
+        // the limit
+        train_sim.loco_con.pwr_rate_out_max = Some(-1.0 * uc::WPS);
+        let msg = format!("{:?}", train_sim.walk().unwrap_err());
+        assert!(msg.contains("exceeds consist ramp rate limit"));
+        assert!(msg.contains("link_idx_front: "));
+        assert!(msg.contains("offset: "));
+        assert!(msg.contains("loco #: 0, loco type: "));
+    }
 }
@@ -0,0 +1,34 @@
+use super::train_imports::*;
+use super::{HistorySinkPos, SpeedLimitTrainSim};
+
+#[serde_api]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
+/// Snapshot of a partially run [SpeedLimitTrainSim], including state and
+/// history, created by [SpeedLimitTrainSim::checkpoint] and applied by
+/// [SpeedLimitTrainSim::restore]
+pub struct SimCheckpoint {
+    pub train_sim: Box<SpeedLimitTrainSim>,
+    /// Position in history sink of checkpointed sim, if any, from which
+    /// [SpeedLimitTrainSim::restore] resumes writing to the same file
+    #[serde(default)]
+    pub history_sink_pos: Option<HistorySinkPos>,
+}
+
+#[pyo3_api]
+impl SimCheckpoint {
+    #[getter("i")]
+    fn get_i_py(&self) -> anyhow::Result<usize> {
+        self.i()
+    }
+}
+
+impl Init for SimCheckpoint {}
+impl SerdeAPI for SimCheckpoint {}
+
+impl SimCheckpoint {
+    /// Returns time step index at which the checkpoint was created
+    pub fn i(&self) -> anyhow::Result<usize> {
+        Ok(*self.train_sim.state.i.get_unchecked(|| format_dbg!())?)
+    }
+}
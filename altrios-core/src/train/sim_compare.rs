@@ -0,0 +1,152 @@
+use super::train_imports::*;
+use super::{SetSpeedTrainSim, SpeedLimitTrainSim};
+
+/// Returns relative differences, `(sts - sls) / |sls|`, of `sts` with respect
+/// to `sls` for cumulative fuel energy (`"energy_fuel"`), net RES energy
+/// (`"energy_res"`), and trip time (`"time_trip"`).  Both sims must already
+/// have been run with history saved.  When `sts` follows the speed trace of
+/// `sls`, energy differences shrink in proportion to time step because `sls`
+/// evaluates wheel power at the speed at the end of each step, whereas `sts`
+/// evaluates it from kinetic energy change and resistance over the step.
+pub fn compare_sims(
+    sls: &SpeedLimitTrainSim,
+    sts: &SetSpeedTrainSim,
+) -> anyhow::Result<HashMap<String, f64>> {
+    let time_trip = |history: &TrainStateHistoryVec| -> anyhow::Result<si::Time> {
+        ensure!(
+            !history.time.is_empty(),
+            "{}\nExpected non-empty history",
+            format_dbg!()
+        );
+        Ok(*history
+            .time
+            .last()
+            .unwrap()
+            .get_unchecked(|| format_dbg!())?
+            - *history.time[0].get_unchecked(|| format_dbg!())?)
+    };
+
+    Ok(HashMap::from([
+        (
+            "energy_fuel".into(),
+            rel_diff(
+                sls.loco_con
+                    .get_energy_fuel()
+                    .with_context(|| format_dbg!())?
+                    .get::<si::joule>(),
+                sts.loco_con
+                    .get_energy_fuel()
+                    .with_context(|| format_dbg!())?
+                    .get::<si::joule>(),
+            ),
+        ),
+        (
+            "energy_res".into(),
+            rel_diff(
+                sls.loco_con
+                    .get_net_energy_res()
+                    .with_context(|| format_dbg!())?
+                    .get::<si::joule>(),
+                sts.loco_con
+                    .get_net_energy_res()
+                    .with_context(|| format_dbg!())?
+                    .get::<si::joule>(),
+            ),
+        ),
+        (
+            "time_trip".into(),
+            rel_diff(
+                time_trip(&sls.history)?.get::<si::second>(),
+                time_trip(&sts.history)?.get::<si::second>(),
+            ),
+        ),
+    ]))
+}
+
+/// Relative difference of `val` with respect to `reference`, which is zero
+/// if both are equal (e.g. both zero for a consist without RES)
+fn rel_diff(reference: f64, val: f64) -> f64 {
+    if val == reference {
+        0.0
+    } else {
+        (val - reference) / reference.abs()
+    }
+}
+
+#[cfg(feature = "pyo3")]
+#[cfg_attr(feature = "pyo3", pyfunction(name = "compare_sims"))]
+/// Python wrapper for [compare_sims]
+pub fn compare_sims_py(
+    sls: SpeedLimitTrainSim,
+    sts: SetSpeedTrainSim,
+) -> anyhow::Result<HashMap<String, f64>> {
+    compare_sims(&sls, &sts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{SetSpeedTrainSimBuilder, SpeedTrace};
+    use super::*;
+
+    /// Runs [SpeedLimitTrainSim::valid] with time step `dt` and a
+    /// [SetSpeedTrainSim] following its speed trace from the same initial
+    /// consist, train, and path, and returns [compare_sims] of the two
+    fn compare_valid_sims(dt: si::Time) -> HashMap<String, f64> {
+        let mut sls = SpeedLimitTrainSim::valid();
+        sls.set_save_interval(Some(1));
+        sls.state.dt = TrackedState::new(dt);
+        let sls_init = sls.clone();
+        sls.walk().unwrap();
+
+        let mut sts: SetSpeedTrainSim = SetSpeedTrainSimBuilder {
+            loco_con: sls_init.loco_con,
+            n_cars_by_type: sls_init.n_cars_by_type,
+            state: sls_init.state,
+            speed_trace: SpeedTrace {
+                time: sls
+                    .history
+                    .time
+                    .iter()
+                    .map(|t| *t.get_unchecked(|| format_dbg!()).unwrap())
+                    .collect(),
+                speed: sls
+                    .history
+                    .speed
+                    .iter()
+                    .map(|s| *s.get_unchecked(|| format_dbg!()).unwrap())
+                    .collect(),
+                engine_on: None,
+            },
+            train_res: sls_init.train_res,
+            path_tpc: sls_init.path_tpc,
+            save_interval: Some(1),
+            temp_trace: None,
+        }
+        .into();
+        sts.walk().unwrap();
+
+        // sims that have not been run cannot be compared
+        assert!(compare_sims(&SpeedLimitTrainSim::valid(), &sts).is_err());
+
+        compare_sims(&sls, &sts).unwrap()
+    }
+
+    #[test]
+    fn test_compare_sims() {
+        let diffs_coarse = compare_valid_sims(uc::S);
+        let diffs_fine = compare_valid_sims(0.1 * uc::S);
+        assert_eq!(diffs_coarse.len(), 3);
+        assert_eq!(diffs_coarse["time_trip"], 0.0);
+        // the speed-limit sim evaluates wheel power at end-of-step speed, so
+        // energy differences are first order in time step
+        for key in ["energy_fuel", "energy_res"] {
+            assert!(diffs_coarse[key] != 0.0, "{key}");
+            assert!(
+                diffs_fine[key].abs() < diffs_coarse[key].abs() / 5.0,
+                "{key}: {} at 1 s, {} at 0.1 s",
+                diffs_coarse[key],
+                diffs_fine[key]
+            );
+        }
+    }
+}
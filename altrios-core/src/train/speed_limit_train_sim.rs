@@ -1,8 +1,13 @@
 use super::environment::TemperatureTrace;
-use super::{braking_point::BrakingPoints, friction_brakes::*, train_imports::*};
+use super::{
+    braking_point::BrakingPoints, curve_comfort::CurveComfort, eco_drive::EcoDriveConfig,
+    friction_brakes::*, objective::ObjectiveWeights, sim_checkpoint::SimCheckpoint,
+    train_imports::*,
+};
 use crate::imports::*;
 use crate::track::link::network::Network;
 use crate::track::{LinkPoint, Location};
+use polars::prelude::{Column, DataFrame};
 
 #[serde_api]
 #[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
@@ -61,6 +66,47 @@ impl From<&Vec<LinkIdxTime>> for TimedLinkPath {
     }
 }
 
+/// Total time a locomotive spent in each operating mode, as returned by
+/// [SpeedLimitTrainSim::time_in_state_histogram]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct TimeInState {
+    /// time with propulsive power output above threshold
+    pub motoring: si::Time,
+    /// time with regenerative power below negative threshold
+    pub braking: si::Time,
+    /// time with power output within threshold of zero
+    pub idling: si::Time,
+}
+
+/// Components of [SpeedLimitTrainSim] as they were before [SpeedLimitTrainSim::walk]
+/// was first called, which [SpeedLimitTrainSim::reset] restores
+#[derive(Clone, Debug, PartialEq)]
+struct SimStateInit {
+    state: TrainState,
+    loco_con: Consist,
+    train_res: TrainRes,
+    braking_points: BrakingPoints,
+    fric_brake: FricBrake,
+}
+
+/// What [SpeedLimitTrainSim] does when train speed exceeds the current speed
+/// limit, e.g. due to numerical overshoot
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+pub enum SpeedViolationPolicy {
+    /// Abort the simulation with an error
+    #[default]
+    Error,
+    /// Clamp speed used for braking lookahead to the speed limit and continue
+    Clamp,
+    /// Log a warning via the `log` crate and continue
+    Log,
+}
+
+fn default_n_substeps() -> u32 {
+    1
+}
+
 #[serde_api]
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "pyo3", pyclass(module = "altrios", subclass, eq))]
@@ -84,11 +130,53 @@ pub struct SpeedLimitTrainSim {
     pub path_tpc: PathTpc,
 
     pub braking_points: BrakingPoints,
+    /// Margin subtracted from path speed limits when computing speed targets,
+    /// such that a positive margin yields more conservative driving
+    #[serde(default)]
+    pub speed_limit_margin: si::Velocity,
+    /// Optional eco-driving settings.  If provided, propulsion is cut ahead of
+    /// upcoming slowdowns, stops, and downgrades.
+    #[serde(default)]
+    pub eco_drive: Option<EcoDriveConfig>,
+    /// Numerical integration method for cumulative energies
+    #[serde(default)]
+    pub integration_method: IntegrationMethod,
+    /// Optional limit on rate of change of acceleration (i.e. jerk) commanded
+    /// by the driver model, applied to the change in applied tractive or
+    /// braking force between steps for ride-quality modeling.  Tight limits
+    /// may cause the train to lag its speed target.  The limit is not applied
+    /// when the train must slow down, and it never pushes the train past the
+    /// speed limit or backwards.
+    #[serde(default)]
+    pub max_jerk: Option<si::Jerk>,
+    /// Number of internal sub-steps into which each step is divided when the
+    /// magnitude of acceleration at the start or predicted end of the step
+    /// exceeds [Self::substep_accel_threshold], with resistance re-evaluated
+    /// at each sub-step.  Sub-steps are accumulated into the single reported
+    /// state, and a value of 1 disables substepping.
+    #[serde(default = "default_n_substeps")]
+    pub n_substeps: u32,
+    /// Acceleration magnitude above which steps are subdivided per
+    /// [Self::n_substeps]
+    #[serde(default)]
+    pub substep_accel_threshold: si::Acceleration,
+    /// What to do when train speed exceeds the current speed limit
+    #[serde(default)]
+    pub speed_violation_policy: SpeedViolationPolicy,
     // #[has_state]
     pub fric_brake: FricBrake,
     /// Custom vector of [Self::state]
     #[serde(default)]
     pub history: TrainStateHistoryVec,
+    /// If provided, [Self::state] is streamed to this CSV sink rather than
+    /// pushed to [Self::history], and histories of [Self::loco_con] and [Self::fric_brake]
+    /// are not saved.  Clones of the sim stream to separate files, but
+    /// [Self::restore] resumes streaming to the file of the checkpointed sim.
+    #[serde(skip)]
+    history_sink: Option<HistorySink>,
+    /// Initial state saved by [Self::walk] for [Self::reset]
+    #[serde(skip)]
+    state_init: Option<Box<SimStateInit>>,
 
     save_interval: Option<usize>,
     simulation_days: Option<i32>,
@@ -98,8 +186,14 @@ pub struct SpeedLimitTrainSim {
     temp_trace: Option<TemperatureTrace>,
 }
 
-#[pyo3_api]
+#[pyo3_api(repr)]
 impl SpeedLimitTrainSim {
+    #[pyo3(name = "strip_history")]
+    /// Clear history and cascade to nested components.
+    fn strip_history_py(&mut self) {
+        self.strip_history();
+    }
+
     #[pyo3(name = "set_save_interval")]
     #[pyo3(signature = (save_interval=None))]
     /// Set save interval and cascade to nested components.
@@ -160,11 +254,70 @@ impl SpeedLimitTrainSim {
             .get::<si::joule>())
     }
 
+    #[pyo3(name = "regen_recovery_fraction")]
+    fn regen_recovery_fraction_py(&self) -> anyhow::Result<f64> {
+        self.regen_recovery_fraction()
+    }
+
+    #[pyo3(name = "objective")]
+    /// Returns weighted objective, with `weights` keyed by `"time"`,
+    /// `"energy_fuel"`, `"energy_res"`, `"time_ref_seconds"`, and
+    /// `"energy_ref_joules"`, all optional
+    fn objective_py(&self, weights: HashMap<String, f64>) -> anyhow::Result<f64> {
+        self.objective(ObjectiveWeights::from_dict(&weights)?)
+    }
+
+    #[pyo3(name = "max_grade_on_route")]
+    fn max_grade_on_route_py(&self) -> anyhow::Result<f64> {
+        Ok(self.max_grade_on_route()?.get::<si::ratio>())
+    }
+
+    #[pyo3(name = "ruling_grade")]
+    fn ruling_grade_py(&self, length_min_meters: f64) -> anyhow::Result<f64> {
+        Ok(self
+            .ruling_grade(length_min_meters * uc::M)?
+            .get::<si::ratio>())
+    }
+
     #[pyo3(name = "walk")]
     fn walk_py(&mut self) -> anyhow::Result<()> {
         self.walk()
     }
 
+    #[pyo3(name = "checkpoint")]
+    fn checkpoint_py(&self) -> anyhow::Result<SimCheckpoint> {
+        self.checkpoint()
+    }
+
+    #[pyo3(name = "restore")]
+    fn restore_py(&mut self, checkpoint: &SimCheckpoint) -> anyhow::Result<()> {
+        self.restore(checkpoint)
+    }
+
+    #[pyo3(name = "time_in_state_histogram")]
+    /// Returns dict keyed by locomotive index of dicts with time in seconds
+    /// spent motoring, braking, and idling
+    fn time_in_state_histogram_py(
+        &self,
+        pwr_thresh_watts: f64,
+    ) -> anyhow::Result<HashMap<usize, HashMap<String, f64>>> {
+        Ok(self
+            .time_in_state_histogram(pwr_thresh_watts * uc::W)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, tis)| {
+                (
+                    i,
+                    HashMap::from([
+                        ("motoring_seconds".into(), tis.motoring.get::<si::second>()),
+                        ("braking_seconds".into(), tis.braking.get::<si::second>()),
+                        ("idling_seconds".into(), tis.idling.get::<si::second>()),
+                    ]),
+                )
+            })
+            .collect())
+    }
+
     #[staticmethod]
     #[pyo3(name = "valid")]
     fn valid_py() -> Self {
@@ -211,6 +364,126 @@ impl SpeedLimitTrainSim {
         self.walk_timed_path(&network, timed_path)
     }
 
+    #[pyo3(name = "get_curve_comfort")]
+    pub fn get_curve_comfort_py(
+        &self,
+        network: &Bound<PyAny>,
+        superelevation_meters: f64,
+        cant_deficiency_meters: f64,
+    ) -> anyhow::Result<Vec<CurveComfort>> {
+        let network = match network.extract::<Network>() {
+            Ok(n) => n,
+            Err(_) => {
+                let n = network
+                    .extract::<Vec<Link>>()
+                    .map_err(|_| anyhow!("{}", format_dbg!()))?;
+                Network(Default::default(), n)
+            }
+        };
+        self.get_curve_comfort(
+            &network,
+            superelevation_meters * uc::M,
+            cant_deficiency_meters * uc::M,
+        )
+    }
+
+    #[pyo3(name = "compare")]
+    /// Returns time-aligned DataFrame of key time series for `self` (`_a`
+    /// columns) and `other` (`_b` columns), with `_delta` columns as b - a
+    pub fn compare_py(&self, other: &Self) -> anyhow::Result<pyo3_polars::PyDataFrame> {
+        Ok(pyo3_polars::PyDataFrame(self.compare(other)?))
+    }
+
+    #[pyo3(name = "compare_summary")]
+    /// Returns DataFrame of trip totals for `self` and `other` and their deltas
+    pub fn compare_summary_py(&self, other: &Self) -> anyhow::Result<pyo3_polars::PyDataFrame> {
+        Ok(pyo3_polars::PyDataFrame(self.compare_summary(other)?))
+    }
+
+    #[pyo3(name = "speed_target_trace")]
+    /// Returns DataFrame of saved time, achieved speed, speed limit, and
+    /// target speed
+    pub fn speed_target_trace_py(&self) -> anyhow::Result<pyo3_polars::PyDataFrame> {
+        Ok(pyo3_polars::PyDataFrame(self.speed_target_trace()?))
+    }
+
+    #[pyo3(name = "set_speed_limit_offset")]
+    /// - `speed_limit_margin_meters_per_second` - margin subtracted from path
+    ///   speed limits, with positive values yielding conservative driving
+    pub fn set_speed_limit_offset_py(
+        &mut self,
+        speed_limit_margin_meters_per_second: f64,
+    ) -> anyhow::Result<()> {
+        self.set_speed_limit_margin(speed_limit_margin_meters_per_second * uc::MPS)
+    }
+
+    #[pyo3(name = "set_eco_drive")]
+    #[pyo3(signature = (eco_drive=None))]
+    /// Enables eco-driving with `eco_drive` settings, or disables it if `None`
+    fn set_eco_drive_py(&mut self, eco_drive: Option<EcoDriveConfig>) {
+        self.set_eco_drive(eco_drive);
+    }
+
+    #[pyo3(name = "set_integration_method")]
+    /// Sets numerical integration method for cumulative energies
+    fn set_integration_method_py(&mut self, integration_method: IntegrationMethod) {
+        self.integration_method = integration_method;
+    }
+
+    #[pyo3(name = "set_max_jerk")]
+    #[pyo3(signature = (max_jerk_meters_per_second_cubed=None))]
+    /// Sets jerk limit, with `None` disabling jerk limiting
+    fn set_max_jerk_py(
+        &mut self,
+        max_jerk_meters_per_second_cubed: Option<f64>,
+    ) -> anyhow::Result<()> {
+        self.set_max_jerk(max_jerk_meters_per_second_cubed.map(|j| j * uc::MPS3))
+    }
+
+    #[pyo3(name = "set_substepping")]
+    #[pyo3(signature = (n_substeps, accel_threshold_meters_per_second_squared=0.0))]
+    /// Divides steps into `n_substeps` internal sub-steps when acceleration
+    /// magnitude exceeds `accel_threshold_meters_per_second_squared`, with
+    /// `n_substeps=1` disabling substepping
+    fn set_substepping_py(
+        &mut self,
+        n_substeps: u32,
+        accel_threshold_meters_per_second_squared: f64,
+    ) -> anyhow::Result<()> {
+        self.set_substepping(
+            n_substeps,
+            accel_threshold_meters_per_second_squared * uc::MPS2,
+        )
+    }
+
+    #[pyo3(name = "set_speed_violation_policy")]
+    /// Sets what to do when train speed exceeds the current speed limit
+    fn set_speed_violation_policy_py(&mut self, speed_violation_policy: SpeedViolationPolicy) {
+        self.speed_violation_policy = speed_violation_policy;
+    }
+
+    #[pyo3(name = "set_history_sink")]
+    #[pyo3(signature = (path=None))]
+    /// Streams saved states to a CSV file at `path` instead of `history`,
+    /// with `None` restoring in-memory history
+    fn set_history_sink_py(&mut self, path: Option<PathBuf>) -> anyhow::Result<()> {
+        self.set_history_sink(path.map(HistorySink::new).transpose()?);
+        Ok(())
+    }
+
+    #[pyo3(name = "reset")]
+    /// Restores initial state and clears history so that `walk` can be
+    /// called again
+    fn reset_py(&mut self) -> anyhow::Result<()> {
+        self.reset()
+    }
+
+    #[pyo3(name = "set_wind_speed")]
+    /// Sets along-track wind speed in m/s, see [Self::set_wind_speed]
+    fn set_wind_speed_py(&mut self, wind_speed_meters_per_second: f64) -> anyhow::Result<()> {
+        self.set_wind_speed(wind_speed_meters_per_second * uc::MPS)
+    }
+
     #[staticmethod]
     #[pyo3(name = "default")]
     fn default_py() -> Self {
@@ -248,8 +521,17 @@ impl From<SpeedLimitTrainSimBuilder> for SpeedLimitTrainSim {
             train_res: value.train_res,
             path_tpc: value.path_tpc,
             braking_points: Default::default(),
+            speed_limit_margin: si::Velocity::ZERO,
+            eco_drive: None,
+            integration_method: Default::default(),
+            max_jerk: None,
+            n_substeps: default_n_substeps(),
+            substep_accel_threshold: si::Acceleration::ZERO,
+            speed_violation_policy: Default::default(),
             fric_brake: value.fric_brake,
             history: Default::default(),
+            history_sink: None,
+            state_init: None,
             save_interval: value.save_interval,
             simulation_days: value.simulation_days,
             scenario_year: value.scenario_year,
@@ -337,6 +619,19 @@ impl SpeedLimitTrainSim {
         Ok(self.loco_con.get_energy_fuel()? * self.get_scaling_factor(annualize))
     }
 
+    /// Returns steepest grade, positive uphill, along `self.path_tpc`
+    pub fn max_grade_on_route(&self) -> anyhow::Result<si::Ratio> {
+        self.path_tpc.max_grade().with_context(|| format_dbg!())
+    }
+
+    /// Returns ruling grade along `self.path_tpc`, i.e. the steepest average
+    /// grade sustained over at least `length_min`.  See [PathTpc::ruling_grade].
+    pub fn ruling_grade(&self, length_min: si::Length) -> anyhow::Result<si::Ratio> {
+        self.path_tpc
+            .ruling_grade(length_min)
+            .with_context(|| format_dbg!())
+    }
+
     /// Returns total fuel and fuel-equivalent battery energy used for consist
     pub fn get_energy_fuel_soc_corrected(&self) -> anyhow::Result<si::Energy> {
         if self.save_interval != Some(1) && self.history.is_empty() {
@@ -438,10 +733,143 @@ impl SpeedLimitTrainSim {
                 .with_context(|| format_dbg!())?)
     }
 
+    /// Returns passenger comfort results for every curve along `self.path_tpc`,
+    /// flagging curves where posted or operated speed exceeds the max
+    /// comfortable speed for the given superelevation and cant deficiency.
+    /// Operated speed is available only if `self.history` is populated.
+    /// # Arguments
+    /// - `network`: network containing all links in `self.path_tpc`
+    /// - `superelevation`: height of outer rail above inner rail, applied to all curves
+    /// - `cant_deficiency`: max allowable cant deficiency
+    pub fn get_curve_comfort<Q: AsRef<[Link]>>(
+        &self,
+        network: Q,
+        superelevation: si::Length,
+        cant_deficiency: si::Length,
+    ) -> anyhow::Result<Vec<CurveComfort>> {
+        let network = network.as_ref();
+        let speed_points = self.path_tpc.speed_points();
+        let mut curve_comforts: Vec<CurveComfort> = vec![];
+        for link_point in self
+            .path_tpc
+            .link_points()
+            .iter()
+            .filter(|lp| lp.link_idx.is_real())
+        {
+            let link = network.get(link_point.link_idx.idx()).with_context(|| {
+                format!(
+                    "{}\n`network` does not contain link {:?}",
+                    format_dbg!(),
+                    link_point.link_idx
+                )
+            })?;
+            for (prev, curr) in link.headings.windows(2).map(|x| (&x[0], &x[1])) {
+                let dh: si::Angle =
+                    (curr.heading - prev.heading + 3.0 * uc::REV / 2.0) % uc::REV - uc::REV / 2.0;
+                let curvature: si::Curvature = (dh.abs() / (curr.offset - prev.offset)).into();
+                if curvature == si::Curvature::ZERO {
+                    continue;
+                }
+                let offset_start = link_point.offset + prev.offset;
+                let offset_end = link_point.offset + curr.offset;
+
+                // speed limit in effect at `offset_start` and any that start within the curve
+                let idx_start = speed_points
+                    .iter()
+                    .rposition(|sp| sp.offset <= offset_start)
+                    .unwrap_or_default();
+                let speed_limit_max = speed_points[idx_start..]
+                    .iter()
+                    .take_while(|sp| sp.offset < offset_end || sp.offset <= offset_start)
+                    .fold(si::Velocity::ZERO, |acc, sp| acc.max(sp.speed_limit));
+
+                let speed_max = self
+                    .history
+                    .offset
+                    .iter()
+                    .zip(&self.history.speed)
+                    .filter_map(|(offset, speed)| {
+                        let offset = *offset.get_unchecked(|| format_dbg!()).ok()?;
+                        if offset >= offset_start && offset <= offset_end {
+                            Some(*speed.get_unchecked(|| format_dbg!()).ok()?)
+                        } else {
+                            None
+                        }
+                    })
+                    .reduce(si::Velocity::max);
+
+                curve_comforts.push(CurveComfort {
+                    offset_start,
+                    offset_end,
+                    curvature,
+                    speed_comfort_max: CurveComfort::calc_speed_comfort_max(
+                        curvature,
+                        superelevation,
+                        cant_deficiency,
+                    ),
+                    speed_limit_max,
+                    speed_max,
+                });
+            }
+        }
+        Ok(curve_comforts)
+    }
+
     pub fn get_net_energy_res(&self, annualize: bool) -> anyhow::Result<si::Energy> {
         Ok(self.loco_con.get_net_energy_res()? * self.get_scaling_factor(annualize))
     }
 
+    /// Returns fraction of braking energy at the wheels, i.e.
+    /// `state.energy_whl_out_neg`, that was recovered as electrical charge
+    /// energy into reversible energy storage over the trip, with the remainder
+    /// dissipated in friction brakes or dynamic brake resistors.  Charge energy
+    /// is recovered from cumulative throughput and net output, so for hybrids
+    /// it also includes any charging from the engine.  Returns zero if the
+    /// train has not braked.
+    pub fn regen_recovery_fraction(&self) -> anyhow::Result<f64> {
+        let energy_whl_out_neg = *self
+            .state
+            .energy_whl_out_neg
+            .get_unchecked(|| format_dbg!())?;
+        if energy_whl_out_neg <= si::Energy::ZERO {
+            return Ok(0.0);
+        }
+        let energy_charge = self.loco_con.loco_vec.iter().try_fold(
+            si::Energy::ZERO,
+            |acc, loco| -> anyhow::Result<si::Energy> {
+                Ok(match loco.reversible_energy_storage() {
+                    // throughput is charge plus discharge, and net output is
+                    // discharge minus charge
+                    Some(res) => {
+                        acc + (res.energy_throughput()?
+                            - *res
+                                .state
+                                .energy_out_electrical
+                                .get_unchecked(|| format_dbg!())?)
+                            / 2.0
+                    }
+                    None => acc,
+                })
+            },
+        )?;
+        Ok((energy_charge / energy_whl_out_neg).get::<si::ratio>())
+    }
+
+    /// Returns scalar objective for calibration and optimization loops, i.e.
+    /// the weighted sum of trip time, fuel energy, and net RES energy, each
+    /// normalized by its reference value in `weights`
+    pub fn objective(&self, weights: ObjectiveWeights) -> anyhow::Result<f64> {
+        weights.check().with_context(|| format_dbg!())?;
+        let time_trip = *self.state.time.get_unchecked(|| format_dbg!())?;
+        Ok(
+            weights.time * (time_trip / weights.time_ref).get::<si::ratio>()
+                + weights.energy_fuel
+                    * (self.get_energy_fuel(false)? / weights.energy_ref).get::<si::ratio>()
+                + weights.energy_res
+                    * (self.get_net_energy_res(false)? / weights.energy_ref).get::<si::ratio>(),
+        )
+    }
+
     pub fn set_save_interval(&mut self, save_interval: Option<usize>) {
         self.save_interval = save_interval;
         self.loco_con.set_save_interval(save_interval);
@@ -451,6 +879,271 @@ impl SpeedLimitTrainSim {
         self.save_interval
     }
 
+    /// Clears `self.history` and history of nested `loco_con` and `fric_brake`
+    /// so that `self` can be serialized compactly, e.g. for reuse as an input
+    pub fn strip_history(&mut self) {
+        self.history.clear();
+        self.loco_con.strip_history();
+        self.fric_brake.history.clear();
+    }
+
+    /// Restores [Self::state], [Self::loco_con], [Self::train_res],
+    /// [Self::braking_points], and [Self::fric_brake] to what they were before
+    /// [Self::walk] was called, clears history, truncates [Self::history_sink]
+    /// if provided, and re-initializes so that `walk` can be called again
+    /// without rebuilding [Self::path_tpc].  [Self::loco_con] may be replaced
+    /// after resetting, e.g. for parameter sweeps over consists on the same
+    /// route.
+    pub fn reset(&mut self) -> anyhow::Result<()> {
+        let state_init = self.state_init.take().with_context(|| {
+            format!(
+                "{}
+Nothing to reset because `walk` has not been called",
+                format_dbg!()
+            )
+        })?;
+        let SimStateInit {
+            state,
+            loco_con,
+            train_res,
+            braking_points,
+            fric_brake,
+        } = *state_init;
+        self.state = state;
+        self.loco_con = loco_con;
+        self.train_res = train_res;
+        self.braking_points = braking_points;
+        self.fric_brake = fric_brake;
+        self.strip_history();
+        self.history_sink = self
+            .history_sink
+            .take()
+            .map(|sink| HistorySink::new(sink.path()))
+            .transpose()
+            .with_context(|| format_dbg!())?;
+        self.init().with_context(|| format_dbg!())?;
+        Ok(())
+    }
+
+    /// Returns key time series, in SI units, as (column name, values) pairs with
+    /// time first.  Requires history from a completed [Self::walk] with
+    /// `save_interval` of `Some(1)`.
+    fn get_comparison_series(&self) -> anyhow::Result<Vec<(&'static str, Vec<f64>)>> {
+        ensure!(
+            self.save_interval == Some(1) && !self.history.is_empty(),
+            "{}\nExpected `save_interval = Some(1)` and non-empty history",
+            format_dbg!()
+        );
+        let hist_con = &self.loco_con.history;
+        ensure!(
+            hist_con.energy_fuel.len() == self.history.time.len(),
+            "{}\nConsist and train histories have different lengths",
+            format_dbg!()
+        );
+
+        // mean SOC across RES-equipped locomotives, NaN if there are none
+        let soc_hists: Vec<_> = self
+            .loco_con
+            .loco_vec
+            .iter()
+            .filter_map(|loco| loco.reversible_energy_storage())
+            .map(|res| &res.history.soc)
+            .collect();
+        let soc_mean = (0..self.history.time.len())
+            .map(|i| -> anyhow::Result<f64> {
+                let soc_sum = soc_hists.iter().try_fold(0.0, |acc, soc| {
+                    let soc = soc.get(i).with_context(|| format_dbg!())?;
+                    anyhow::Ok(acc + soc.get_unchecked(|| format_dbg!())?.get::<si::ratio>())
+                })?;
+                Ok(soc_sum / soc_hists.len() as f64)
+            })
+            .collect::<anyhow::Result<Vec<f64>>>()?;
+
+        Ok(vec![
+            (
+                "time_seconds",
+                tracked_to_vec(&self.history.time, |x| x.get::<si::second>())?,
+            ),
+            (
+                "offset_meters",
+                tracked_to_vec(&self.history.offset, |x| x.get::<si::meter>())?,
+            ),
+            (
+                "speed_meters_per_second",
+                tracked_to_vec(&self.history.speed, |x| x.get::<si::meter_per_second>())?,
+            ),
+            (
+                "pwr_whl_out_watts",
+                tracked_to_vec(&self.history.pwr_whl_out, |x| x.get::<si::watt>())?,
+            ),
+            (
+                "energy_whl_out_joules",
+                tracked_to_vec(&self.history.energy_whl_out, |x| x.get::<si::joule>())?,
+            ),
+            (
+                "energy_fuel_joules",
+                tracked_to_vec(&hist_con.energy_fuel, |x| x.get::<si::joule>())?,
+            ),
+            (
+                "energy_reves_joules",
+                tracked_to_vec(&hist_con.energy_reves, |x| x.get::<si::joule>())?,
+            ),
+            ("soc_mean", soc_mean),
+        ])
+    }
+
+    /// Returns DataFrame of key time series (speed, power, SOC, energy) for
+    /// `self` and `other` aligned on the union of their time steps via linear
+    /// interpolation, with values held constant beyond the end of either sim.
+    /// Each series has paired `_a` (`self`) and `_b` (`other`) columns and a
+    /// `_delta` column (b - a).
+    /// # Arguments
+    /// - `other`: sim to compare against, e.g. with a modified parameter
+    pub fn compare(&self, other: &Self) -> anyhow::Result<DataFrame> {
+        let series_a = self
+            .get_comparison_series()
+            .with_context(|| format_dbg!())?;
+        let series_b = other
+            .get_comparison_series()
+            .with_context(|| format_dbg!())?;
+        let (time_a, time_b) = (&series_a[0].1, &series_b[0].1);
+        let mut time: Vec<f64> = time_a.iter().chain(time_b).cloned().collect();
+        time.sort_by(|a, b| a.total_cmp(b));
+        time.dedup();
+
+        let mut columns = vec![Column::new("time_seconds".into(), &time)];
+        for ((name, vals_a), (_, vals_b)) in series_a.iter().zip(&series_b).skip(1) {
+            let vals_a = interp_clamped(&time, time_a, vals_a);
+            let vals_b = interp_clamped(&time, time_b, vals_b);
+            let delta: Vec<f64> = vals_a.iter().zip(&vals_b).map(|(a, b)| b - a).collect();
+            columns.push(Column::new(format!("{name}_a").into(), vals_a));
+            columns.push(Column::new(format!("{name}_b").into(), vals_b));
+            columns.push(Column::new(format!("{name}_delta").into(), delta));
+        }
+        DataFrame::new(columns).with_context(|| format_dbg!())
+    }
+
+    /// Returns DataFrame with one row per trip total (e.g. elapsed time, fuel
+    /// energy) and columns for `self` (`a`), `other` (`b`), and `delta` (b - a)
+    pub fn compare_summary(&self, other: &Self) -> anyhow::Result<DataFrame> {
+        let series_a = self
+            .get_comparison_series()
+            .with_context(|| format_dbg!())?;
+        let series_b = other
+            .get_comparison_series()
+            .with_context(|| format_dbg!())?;
+        let mut metrics: Vec<String> = vec![];
+        let (mut vals_a, mut vals_b, mut delta) = (vec![], vec![], vec![]);
+        for ((name, series_a), (_, series_b)) in series_a.iter().zip(&series_b) {
+            let (a, b) = (*series_a.last().unwrap(), *series_b.last().unwrap());
+            metrics.push(format!("{name}_final"));
+            vals_a.push(a);
+            vals_b.push(b);
+            delta.push(b - a);
+        }
+        DataFrame::new(vec![
+            Column::new("metric".into(), metrics),
+            Column::new("a".into(), vals_a),
+            Column::new("b".into(), vals_b),
+            Column::new("delta".into(), delta),
+        ])
+        .with_context(|| format_dbg!())
+    }
+
+    /// Returns DataFrame with parallel columns of time, achieved speed, speed
+    /// limit, and target speed from [Self::history], e.g. for plotting what
+    /// the driver model aimed for versus what was achieved
+    pub fn speed_target_trace(&self) -> anyhow::Result<DataFrame> {
+        let mps = |x: si::Velocity| x.get::<si::meter_per_second>();
+        DataFrame::new(vec![
+            Column::new(
+                "time_seconds".into(),
+                tracked_to_vec(&self.history.time, |x| x.get::<si::second>())?,
+            ),
+            Column::new(
+                "speed_meters_per_second".into(),
+                tracked_to_vec(&self.history.speed, mps)?,
+            ),
+            Column::new(
+                "speed_limit_meters_per_second".into(),
+                tracked_to_vec(&self.history.speed_limit, mps)?,
+            ),
+            Column::new(
+                "speed_target_meters_per_second".into(),
+                tracked_to_vec(&self.history.speed_target, mps)?,
+            ),
+        ])
+        .with_context(|| format_dbg!())
+    }
+
+    /// Sets [Self::eco_drive], with `None` disabling eco-driving
+    pub fn set_eco_drive(&mut self, eco_drive: Option<EcoDriveConfig>) {
+        self.eco_drive = eco_drive;
+    }
+
+    /// Sets [Self::max_jerk], with `None` disabling jerk limiting
+    pub fn set_max_jerk(&mut self, max_jerk: Option<si::Jerk>) -> anyhow::Result<()> {
+        if let Some(max_jerk) = max_jerk {
+            ensure!(
+                max_jerk > si::Jerk::ZERO,
+                "{}
+Expected `max_jerk` to be positive",
+                format_dbg!()
+            );
+        }
+        self.max_jerk = max_jerk;
+        Ok(())
+    }
+
+    /// Sets [Self::n_substeps] and [Self::substep_accel_threshold]
+    pub fn set_substepping(
+        &mut self,
+        n_substeps: u32,
+        accel_threshold: si::Acceleration,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            n_substeps >= 1,
+            "{}\nExpected `n_substeps` to be at least 1",
+            format_dbg!()
+        );
+        ensure!(
+            accel_threshold >= si::Acceleration::ZERO,
+            "{}\nExpected `accel_threshold` to be non-negative",
+            format_dbg!()
+        );
+        self.n_substeps = n_substeps;
+        self.substep_accel_threshold = accel_threshold;
+        Ok(())
+    }
+
+    /// Sets sink to which saved states are streamed instead of being pushed
+    /// to [Self::history], with `None` restoring in-memory history
+    pub fn set_history_sink(&mut self, history_sink: Option<HistorySink>) {
+        self.history_sink = history_sink;
+    }
+
+    /// Sets along-track wind speed in [Self::train_res], see
+    /// [TrainRes::set_wind_speed]
+    pub fn set_wind_speed(&mut self, wind_speed: si::Velocity) -> anyhow::Result<()> {
+        self.train_res
+            .set_wind_speed(wind_speed)
+            .with_context(|| format_dbg!())
+    }
+
+    /// Sets [Self::speed_limit_margin] and recalculates braking points if the
+    /// path has already been populated
+    pub fn set_speed_limit_margin(
+        &mut self,
+        speed_limit_margin: si::Velocity,
+    ) -> anyhow::Result<()> {
+        self.speed_limit_margin = speed_limit_margin;
+        if !self.path_tpc.is_fake() {
+            self.recalc_braking_points()
+                .with_context(|| format_dbg!())?;
+        }
+        Ok(())
+    }
+
     pub fn extend_path(&mut self, network: &[Link], link_path: &[LinkIdx]) -> anyhow::Result<()> {
         self.path_tpc
             .extend(network, link_path)
@@ -484,6 +1177,12 @@ impl SpeedLimitTrainSim {
     }
 
     pub fn solve_step(&mut self) -> anyhow::Result<()> {
+        // `dt` is constant, so its stale value is the current time step size
+        self.set_cumulative_prev(
+            *self.state.dt.get_stale(|| format_dbg!())?,
+            self.integration_method,
+            || format_dbg!(),
+        )?;
         self.loco_con
             .state
             .pwr_cat_lim
@@ -523,6 +1222,10 @@ impl SpeedLimitTrainSim {
                 elev_and_temp,
                 Some(self.state.mass_compound().with_context(|| format_dbg!())?),
                 Some(*self.state.speed.get_stale(|| format_dbg!())?),
+                Some(self.path_tpc.grade_ahead(
+                    *self.state.offset.get_stale(|| format_dbg!())?,
+                    GRADE_LOOKAHEAD_MILES * uc::MI,
+                )),
                 *self.state.dt.get_fresh(|| format_dbg!())?,
             )
             .with_context(|| format_dbg!())?);
@@ -536,18 +1239,18 @@ impl SpeedLimitTrainSim {
         timer!(self.solve_required_pwr().with_context(|| format_dbg!())?);
 
         timer!(self
-            .loco_con
-            .solve_energy_consumption(
+            .state
+            .with_loc_context(self.loco_con.solve_energy_consumption(
                 *self.state.pwr_whl_out.get_fresh(|| format_dbg!())?,
                 Some(self.state.mass_compound().with_context(|| format_dbg!())?),
                 Some(*self.state.speed.get_fresh(|| format_dbg!())?),
                 *self.state.dt.get_fresh(|| format_dbg!())?,
                 Some(true),
-            )
-            .with_context(|| format_dbg!())?);
+            ))?);
 
-        timer!(self.set_cumulative(
+        timer!(self.set_cumulative_with(
             *self.state.dt.get_fresh(|| format_dbg!())?,
+            self.integration_method,
             || format_dbg!(),
         )?);
 
@@ -563,12 +1266,119 @@ impl SpeedLimitTrainSim {
         {
             self.step(|| format_dbg!())?;
         }
+        if let Some(sink) = &self.history_sink {
+            sink.flush().with_context(|| format_dbg!())?;
+        }
+        Ok(())
+    }
+
+    /// Returns a deep copy of `self`, including state and history, that can be
+    /// passed to [Self::restore] to resume simulation from the current step.
+    /// If [Self::history_sink] is provided, its buffered rows are flushed and
+    /// its position is saved rather than the sink itself.
+    pub fn checkpoint(&self) -> anyhow::Result<SimCheckpoint> {
+        let history_sink_pos = self
+            .history_sink
+            .as_ref()
+            .map(HistorySink::pos)
+            .transpose()
+            .with_context(|| format_dbg!())?;
+        let mut train_sim = self.clone();
+        train_sim.history_sink = None;
+        Ok(SimCheckpoint {
+            train_sim: Box::new(train_sim),
+            history_sink_pos,
+        })
+    }
+
+    /// Replaces `self`, including state and history, with that saved in
+    /// `checkpoint`.  If the checkpointed sim had a history sink, streaming
+    /// resumes in the same file, and any rows written after the checkpoint are
+    /// discarded.
+    pub fn restore(&mut self, checkpoint: &SimCheckpoint) -> anyhow::Result<()> {
+        // drop current sink, flushing its rows, before truncating the file
+        self.history_sink = None;
+        *self = (*checkpoint.train_sim).clone();
+        self.history_sink = checkpoint
+            .history_sink_pos
+            .as_ref()
+            .map(HistorySink::resume)
+            .transpose()
+            .with_context(|| format_dbg!())?;
         Ok(())
     }
 
+    /// Returns, for each locomotive in [Self::loco_con], the total time spent
+    /// with `pwr_out > pwr_thresh` (motoring), `pwr_out < -pwr_thresh`
+    /// (braking), and otherwise (idling), based on saved history.  Each saved
+    /// step is credited with the time elapsed since the previous saved step,
+    /// so results are exact only when the save interval is 1.
+    pub fn time_in_state_histogram(
+        &self,
+        pwr_thresh: si::Power,
+    ) -> anyhow::Result<Vec<TimeInState>> {
+        ensure!(
+            pwr_thresh >= si::Power::ZERO,
+            "{}\n`pwr_thresh` must be non-negative",
+            format_dbg!()
+        );
+        let time = self
+            .history
+            .time
+            .iter()
+            .map(|t| t.get_unchecked(|| format_dbg!()).copied())
+            .collect::<anyhow::Result<Vec<si::Time>>>()?;
+        ensure!(
+            !time.is_empty(),
+            "{}\nHistory is empty. Set save interval to `Some(1)` before running the simulation.",
+            format_dbg!()
+        );
+        self.loco_con
+            .loco_vec
+            .iter()
+            .enumerate()
+            .map(|(i, loco)| -> anyhow::Result<TimeInState> {
+                ensure!(
+                    loco.history.pwr_out.len() == time.len(),
+                    "{}\nLocomotive {i} history length ({}) does not match train history length ({})",
+                    format_dbg!(),
+                    loco.history.pwr_out.len(),
+                    time.len()
+                );
+                let mut tis = TimeInState::default();
+                for (pwr_out, dt) in loco
+                    .history
+                    .pwr_out
+                    .iter()
+                    .skip(1)
+                    .zip(time.windows(2).map(|w| w[1] - w[0]))
+                {
+                    let pwr_out = *pwr_out.get_unchecked(|| format_dbg!())?;
+                    if pwr_out > pwr_thresh {
+                        tis.motoring += dt;
+                    } else if pwr_out < -pwr_thresh {
+                        tis.braking += dt;
+                    } else {
+                        tis.idling += dt;
+                    }
+                }
+                Ok(tis)
+            })
+            .collect()
+    }
+
     /// Iterates `save_state` and `step` until offset >= final offset --
     /// i.e. moves train forward until it reaches destination.
     pub fn walk(&mut self) -> anyhow::Result<()> {
+        if self.state_init.is_none() {
+            self.state_init = Some(Box::new(SimStateInit {
+                state: self.state.clone(),
+                loco_con: self.loco_con.clone(),
+                train_res: self.train_res.clone(),
+                braking_points: self.braking_points.clone(),
+                fric_brake: self.fric_brake.clone(),
+            }));
+        }
         self.save_state(|| format_dbg!())?;
         self.walk_internal()?;
         Ok(())
@@ -620,6 +1430,8 @@ impl SpeedLimitTrainSim {
     /// - target acceleration
     pub fn solve_required_pwr(&mut self) -> anyhow::Result<()> {
         let res_net = self.state.res_net().with_context(|| format_dbg!())?;
+        // applied force from previous step, captured before it is updated below
+        let f_applied_prev = *self.state.force_applied.get_stale(|| format_dbg!())?;
 
         // Verify that train can slow down -- if `self.state.res_net()`, which
         // includes grade, is negative (negative `res_net` means the downgrade
@@ -658,11 +1470,14 @@ impl SpeedLimitTrainSim {
         // this figures out when to start braking in advance of a speed limit
         // drop.  Takes into account air brake dynamics. I have not reviewed
         // this code, but that is my understanding.
-        let (speed_limit, speed_target) = self.braking_points.calc_speeds(
-            *self.state.offset.get_stale(|| format_dbg!())?,
-            *self.state.speed.get_stale(|| format_dbg!())?,
-            self.fric_brake.ramp_up_time * self.fric_brake.ramp_up_coeff,
-        );
+        let (speed_limit, speed_target) =
+            self.state
+                .with_loc_context(self.braking_points.calc_speeds(
+                    *self.state.offset.get_stale(|| format_dbg!())?,
+                    *self.state.speed.get_stale(|| format_dbg!())?,
+                    self.fric_brake.ramp_up_time * self.fric_brake.ramp_up_coeff,
+                    self.speed_violation_policy,
+                ))?;
         self.state
             .speed_limit
             .update(speed_limit, || format_dbg!())?;
@@ -730,10 +1545,9 @@ impl SpeedLimitTrainSim {
 
         // Final v_max value should also be bounded by speed_target
         // maximum achievable positive tractive force
-        let f_pos_max = self
-            .loco_con
-            .force_max()?
-            .min(pwr_pos_max / speed_target.min(v_max));
+        let f_pwr_max = pwr_pos_max / speed_target.min(v_max);
+        let f_adhesion_max = self.loco_con.force_max_curr()?;
+        let f_pos_max = f_adhesion_max.min(f_pwr_max);
         // Verify that train has sufficient power to move
         if *self.state.speed.get_stale(|| format_dbg!())? < uc::MPH * 0.1 && f_pos_max <= res_net {
             let mut soc_vec: Vec<String> = vec![];
@@ -883,27 +1697,101 @@ impl SpeedLimitTrainSim {
                 self.loco_con.force_max()?
             };
 
+        // friction braking force that cannot yet be released
+        let f_fric_min = *self
+            .fric_brake
+            .state
+            .force_min_curr
+            .get_fresh(|| format_dbg!())?;
+
+        // when eco-driving, coast rather than propel if the train will have to
+        // shed speed anyway within the lookahead
+        let coast = match &self.eco_drive {
+            Some(eco_drive) => {
+                let offset = *self.state.offset.get_stale(|| format_dbg!())?;
+                eco_drive.should_coast(
+                    offset,
+                    *self.state.speed.get_stale(|| format_dbg!())?,
+                    self.braking_points
+                        .speed_target_min_ahead(offset, eco_drive.lookahead),
+                    self.path_tpc.grades(),
+                )
+            }
+            None => false,
+        };
+        let f_pos_max = if coast {
+            f_fric_min.min(f_pos_max)
+        } else {
+            f_pos_max
+        };
+
+        // most negative applied force achievable with friction and dynamic braking
+        let f_neg_max = -*self
+            .fric_brake
+            .state
+            .force_max_curr
+            .get_fresh(|| format_dbg!())?
+            - f_max_consist_regen_dyn;
+        // limit change in applied force from previous step per jerk limit,
+        // which is bypassed when the train must slow down so that
+        // braking curves and stops are always honored
+        let speed = *self.state.speed.get_stale(|| format_dbg!())?;
+        let f_applied_target_jerk_lim = match self.max_jerk {
+            Some(max_jerk) if speed_target >= speed => {
+                let mass = self.state.mass_compound().with_context(|| format_dbg!())?;
+                let dt = *self.state.dt.get_fresh(|| format_dbg!())?;
+                let f_change_max = mass * max_jerk * dt;
+                // applied forces that bring the train to rest and to the speed
+                // limit by the end of the step, which the jerk limit must not
+                // go beyond so that releasing brakes never reverses the train
+                // and lagging the target never violates the speed limit
+                let f_stop = res_net - mass * speed / dt;
+                let f_speed_limit = res_net + mass * (speed_limit - speed) / dt;
+                f_applied_target
+                    .max(f_applied_prev - f_change_max)
+                    .min(f_applied_prev + f_change_max)
+                    .max(f_stop.min(f_applied_target))
+                    .min(f_speed_limit.max(f_applied_target))
+            }
+            _ => f_applied_target,
+        };
+
         // total impetus force applied to control train speed
         // calculating the applied drawbar force based on targets and enforcing limits.
-        let f_applied = f_pos_max.min(
-            f_applied_target.max(
-                -*self
-                    .fric_brake
-                    .state
-                    .force_max_curr
-                    .get_fresh(|| format_dbg!())?
-                    - f_max_consist_regen_dyn,
-            ),
-        );
+        let f_applied = (f_pos_max - f_fric_min).min(f_applied_target_jerk_lim.max(f_neg_max));
+
+        let traction_limit_cause = if coast || f_pos_max - f_fric_min >= f_applied_target {
+            TractionLimitCause::Unlimited
+        } else if f_applied <= res_net {
+            TractionLimitCause::Resistance
+        } else if f_adhesion_max <= f_pwr_max {
+            TractionLimitCause::Adhesion
+        } else {
+            TractionLimitCause::Power
+        };
+        self.state
+            .traction_limit_cause
+            .update(traction_limit_cause.into(), || format_dbg!())?;
 
         // physics......
-        let vel_change = time_per_mass * (f_applied - res_net);
-        let vel_avg = *self.state.speed.get_stale(|| format_dbg!())? + 0.5 * vel_change;
+        let (vel_change, dist, res_avg, f_applied_avg) = self
+            .integrate_motion(f_applied, f_neg_max, res_net, speed_target)
+            .with_context(|| format_dbg!())?;
+        let vel_avg = dist / *self.state.dt.get_fresh(|| format_dbg!())?;
+        // end speed assumed by the power limits above, which substepping may
+        // exceed as resistance changes within the step
+        let speed_unsubstepped =
+            *self.state.speed.get_stale(|| format_dbg!())? + time_per_mass * (f_applied - res_net);
+        // substepping may reduce applied force to hold the speed target
+        let f_applied = f_applied_avg;
+        self.state
+            .force_applied
+            .update(f_applied, || format_dbg!())?;
 
         // updating states of the train.
         self.state
             .pwr_res
-            .update(res_net * vel_avg, || format_dbg!())?;
+            .update(res_avg * vel_avg, || format_dbg!())?;
         self.state.pwr_accel.update(
             self.state.mass_compound().with_context(|| format_dbg!())?
                 / (2.0 * *self.state.dt.get_fresh(|| format_dbg!())?)
@@ -918,14 +1806,10 @@ impl SpeedLimitTrainSim {
             *self.state.dt.get_fresh(|| format_dbg!())?,
             || format_dbg!(),
         )?;
-        self.state.offset.increment(
-            *self.state.dt.get_fresh(|| format_dbg!())? * vel_avg,
-            || format_dbg!(),
-        )?;
-        self.state.total_dist.increment(
-            *self.state.dt.get_fresh(|| format_dbg!())? * vel_avg.abs(),
-            || format_dbg!(),
-        )?;
+        self.state.offset.increment(dist, || format_dbg!())?;
+        self.state
+            .total_dist
+            .increment(dist.abs(), || format_dbg!())?;
 
         let new_speed = *self.state.speed.get_stale(|| format_dbg!())? + vel_change;
         self.state.speed.update(
@@ -939,8 +1823,9 @@ impl SpeedLimitTrainSim {
 
         let (f_consist, fric_brake_force): (si::Force, si::Force) = if f_applied >= si::Force::ZERO
         {
-            // net positive traction is being exerted on train
-            (f_applied, si::Force::ZERO)
+            // net positive traction is being exerted on train, and consist
+            // must overcome any friction braking that is still being released
+            (f_applied + f_fric_min, f_fric_min)
         } else {
             // net negative traction is being exerted on train
             let f_consist = f_applied + *self.fric_brake.state.force.get_stale(|| format_dbg!())?;
@@ -990,17 +1875,33 @@ impl SpeedLimitTrainSim {
         );
 
         let pwr_whl_out_unclipped = f_consist * *self.state.speed.get_fresh(|| format_dbg!())?;
+        let pwr_lim_scale = if speed_unsubstepped > si::Velocity::ZERO {
+            (*self.state.speed.get_fresh(|| format_dbg!())? / speed_unsubstepped)
+                .get::<si::ratio>()
+                .max(1.0)
+        } else {
+            1.0
+        };
 
-        // this allows for float rounding error overshoot
+        // this allows for float rounding error overshoot and for speed gained
+        // via substepping
         ensure!(
-            utils::almost_le_uom(&pwr_whl_out_unclipped, &pwr_pos_max, Some(1.0e-7)),
+            utils::almost_le_uom(
+                &pwr_whl_out_unclipped,
+                &(pwr_pos_max * pwr_lim_scale),
+                Some(1.0e-7)
+            ),
             format!("{}\nPower wheel out is larger than max positive power! pwr_whl_out={:?}, pwr_pos_max={:?}",
             format_dbg!(utils::almost_le_uom(self.state.pwr_whl_out.get_fresh(|| format_dbg!())?, &pwr_pos_max, Some(1.0e-7))),
             self.state.pwr_whl_out.get_fresh(|| format_dbg!())?,
             pwr_pos_max)
         );
         ensure!(
-            utils::almost_le_uom(&-pwr_whl_out_unclipped, &pwr_neg_max, Some(1.0e-7)),
+            utils::almost_le_uom(
+                &-pwr_whl_out_unclipped,
+                &(pwr_neg_max * pwr_lim_scale),
+                Some(1.0e-7)
+            ),
             format!("{}\nPower wheel out is larger than max negative power! pwr_whl_out={:?}, pwr_neg_max={:?}
             {:?}\n{:?}\n{:?}\n{:?}",
             format_dbg!(utils::almost_le_uom(&-*self.state.pwr_whl_out.get_fresh(|| format_dbg!())?, &pwr_neg_max, Some(1.0e-7))),
@@ -1016,6 +1917,18 @@ impl SpeedLimitTrainSim {
             || format_dbg!(),
         )?;
 
+        // braking accounting -- the consist's negative traction is dynamic
+        // braking, and friction braking only makes up the deficit beyond it
+        self.state.pwr_dyn_brake.update(
+            (-*self.state.pwr_whl_out.get_fresh(|| format_dbg!())?).max(si::Power::ZERO),
+            || format_dbg!(),
+        )?;
+        self.state.pwr_fric_brake.update(
+            *self.fric_brake.state.force.get_fresh(|| format_dbg!())?
+                * *self.state.speed.get_fresh(|| format_dbg!())?,
+            || format_dbg!(),
+        )?;
+
         if *self.state.pwr_whl_out.get_fresh(|| format_dbg!())? >= 0. * uc::W {
             self.state.energy_whl_out_pos.increment(
                 *self.state.pwr_whl_out.get_fresh(|| format_dbg!())?
@@ -1039,12 +1952,99 @@ impl SpeedLimitTrainSim {
         Ok(())
     }
 
+    /// Integrates train motion over the current step under constant
+    /// `f_applied`, returning speed change, distance traveled, and
+    /// distance-averaged net resistance and applied force.  If the
+    /// acceleration magnitude at the start or predicted end of the step
+    /// exceeds [Self::substep_accel_threshold], the step is divided into
+    /// [Self::n_substeps] sub-steps with resistance re-evaluated at each one.
+    /// Sub-steps reduce applied force, down to `f_applied_min`, where needed to
+    /// keep the train from ending the step faster than `speed_target` or than
+    /// the full step would, so that substepping never violates a speed limit
+    /// or braking curve that the full step honors.
+    fn integrate_motion(
+        &self,
+        f_applied: si::Force,
+        f_applied_min: si::Force,
+        res_net: si::Force,
+        speed_target: si::Velocity,
+    ) -> anyhow::Result<(si::Velocity, si::Length, si::Force, si::Force)> {
+        let dt = *self.state.dt.get_fresh(|| format_dbg!())?;
+        let mass = self.state.mass_compound().with_context(|| format_dbg!())?;
+        let speed = *self.state.speed.get_stale(|| format_dbg!())?;
+        let offset = *self.state.offset.get_stale(|| format_dbg!())?;
+        let vel_change = dt / mass * (f_applied - res_net);
+        let dist = dt * (speed + 0.5 * vel_change);
+        if self.n_substeps <= 1 {
+            return Ok((vel_change, dist, res_net, f_applied));
+        }
+
+        // evaluates net resistance with the train at `offset` and `speed`
+        // without disturbing the state or resistance caches of `self`
+        let mut state = self.state.clone();
+        let mut train_res = self.train_res.clone();
+        let mut calc_res_net = |offset: si::Length, speed: si::Velocity| {
+            state.offset.update_unchecked(offset, || format_dbg!())?;
+            state.speed.update_unchecked(speed, || format_dbg!())?;
+            train_res
+                .update_res(&mut state, &self.path_tpc, &Dir::Fwd)
+                .with_context(|| format_dbg!())?;
+            state.res_net().with_context(|| format_dbg!())
+        };
+
+        let res_net_end = calc_res_net(offset + dist, speed + vel_change)?;
+        let accel_max = (f_applied - res_net)
+            .abs()
+            .max((f_applied - res_net_end).abs())
+            / mass;
+        if accel_max <= self.substep_accel_threshold {
+            return Ok((vel_change, dist, res_net, f_applied));
+        }
+
+        // speeds that no intermediate sub-step and the final sub-step,
+        // respectively, may exceed
+        let speed_end_max = speed_target.max(speed + vel_change);
+        let speed_sub_max = speed_end_max.max(speed);
+        let dt_sub = dt / self.n_substeps as f64;
+        let mut speed_sub = speed;
+        let mut dist = si::Length::ZERO;
+        let mut work_res = si::Energy::ZERO;
+        let mut work_applied = si::Energy::ZERO;
+        let mut res_net_sub = res_net;
+        for i in 0..self.n_substeps {
+            if i > 0 {
+                res_net_sub = calc_res_net(offset + dist, speed_sub)?;
+            }
+            let speed_max = if i + 1 == self.n_substeps {
+                speed_end_max
+            } else {
+                speed_sub_max
+            };
+            let f_applied_sub = f_applied
+                .min(res_net_sub + mass * (speed_max - speed_sub) / dt_sub)
+                .max(f_applied_min.min(f_applied));
+            let vel_change_sub = dt_sub / mass * (f_applied_sub - res_net_sub);
+            let dist_sub = dt_sub * (speed_sub + 0.5 * vel_change_sub);
+            work_res += res_net_sub * dist_sub;
+            work_applied += f_applied_sub * dist_sub;
+            dist += dist_sub;
+            speed_sub += vel_change_sub;
+        }
+        let (res_avg, f_applied_avg) = if dist != si::Length::ZERO {
+            (work_res / dist, work_applied / dist)
+        } else {
+            (res_net, f_applied)
+        };
+        Ok((speed_sub - speed, dist, res_avg, f_applied_avg))
+    }
+
     fn recalc_braking_points(&mut self) -> anyhow::Result<()> {
         self.braking_points.recalc(
             &self.state,
             &self.fric_brake,
             &self.train_res,
             &self.path_tpc,
+            self.speed_limit_margin,
         )
     }
 }
@@ -1062,13 +2062,43 @@ impl CheckAndResetState for SpeedLimitTrainSim {
     }
 }
 impl SetCumulative for SpeedLimitTrainSim {
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
-        self.state
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        self.state.set_cumulative_with(dt, integration_method, || {
+            format!("{}\n{}", loc(), format_dbg!())
+        })?;
+        self.loco_con
+            .set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
+        self.fric_brake
+            .set_cumulative_with(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
+        Ok(())
+    }
+
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()> {
+        self.state.set_cumulative_prev(dt, integration_method, || {
+            format!("{}\n{}", loc(), format_dbg!())
+        })?;
         self.loco_con
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
+            .set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
         self.fric_brake
-            .set_cumulative(dt, || format!("{}\n{}", loc(), format_dbg!()))?;
+            .set_cumulative_prev(dt, integration_method, || {
+                format!("{}\n{}", loc(), format_dbg!())
+            })?;
         Ok(())
     }
 }
@@ -1082,9 +2112,16 @@ impl SaveState for SpeedLimitTrainSim {
                 % interval
                 == 0
             {
-                self.history.push(self.state.clone());
-                self.loco_con.save_state(|| format_dbg!())?;
-                self.fric_brake.save_state(|| format_dbg!())?;
+                // with a sink, nested histories are not saved so that memory
+                // use stays bounded
+                match &self.history_sink {
+                    Some(sink) => sink.write(&self.state).with_context(|| format_dbg!())?,
+                    None => {
+                        self.history.push(self.state.clone());
+                        self.loco_con.save_state(|| format_dbg!())?;
+                        self.fric_brake.save_state(|| format_dbg!())?;
+                    }
+                }
             }
         }
         Ok(())
@@ -1144,8 +2181,17 @@ impl Default for SpeedLimitTrainSim {
             train_res: TrainRes::valid(),
             path_tpc: PathTpc::default(),
             braking_points: Default::default(),
+            speed_limit_margin: si::Velocity::ZERO,
+            eco_drive: None,
+            integration_method: Default::default(),
+            max_jerk: None,
+            n_substeps: default_n_substeps(),
+            substep_accel_threshold: si::Acceleration::ZERO,
+            speed_violation_policy: Default::default(),
             fric_brake: Default::default(),
             history: Default::default(),
+            history_sink: None,
+            state_init: None,
             temp_trace: Default::default(),
             save_interval: None,
             simulation_days: None,
@@ -1157,6 +2203,19 @@ impl Default for SpeedLimitTrainSim {
     }
 }
 
+impl fmt::Display for SpeedLimitTrainSim {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SpeedLimitTrainSim(train_id={}, n_locos={}, train_mass_kilograms={}, pwr_rated_kilowatts={:.1})",
+            self.train_id,
+            self.loco_con.loco_vec.len(),
+            utils::mass_repr(self.state.mass()),
+            self.loco_con.pwr_rated().get::<si::kilowatt>(),
+        )
+    }
+}
+
 impl Valid for SpeedLimitTrainSim {
     fn valid() -> Self {
         let mut train_sim = Self {
@@ -1220,10 +2279,62 @@ pub fn speed_limit_train_sim_rev() -> SpeedLimitTrainSim {
     sltsr
 }
 
+/// Converts history of tracked values to SI floats via `f`
+fn tracked_to_vec<T: Copy + std::fmt::Debug + PartialEq + Default, F: Fn(T) -> f64>(
+    hist: &[TrackedState<T>],
+    f: F,
+) -> anyhow::Result<Vec<f64>> {
+    hist.iter()
+        .map(|x| Ok(f(*x.get_unchecked(|| format_dbg!())?)))
+        .collect()
+}
+
+/// Linearly interpolates `y` at each of the sorted points in `x_new`, holding
+/// end values constant outside of `x`, which must also be sorted
+fn interp_clamped(x_new: &[f64], x: &[f64], y: &[f64]) -> Vec<f64> {
+    let mut idx = 0;
+    x_new
+        .iter()
+        .map(|&xn| {
+            if xn <= x[0] {
+                return y[0];
+            }
+            if xn >= x[x.len() - 1] {
+                return y[y.len() - 1];
+            }
+            while x[idx + 1] < xn {
+                idx += 1;
+            }
+            y[idx] + (y[idx + 1] - y[idx]) * (xn - x[idx]) / (x[idx + 1] - x[idx])
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::testing::*;
+    use crate::track::Elev;
+
+    /// Returns default sim on a path through [Link::valid] with elevation
+    /// profile `elevs`, with braking points recalculated for that path
+    fn graded_train_sim(elevs: Vec<Elev>) -> SpeedLimitTrainSim {
+        let link = Link {
+            elevs,
+            ..Link::valid()
+        };
+        let mut path_tpc = PathTpc::default();
+        path_tpc
+            .extend(vec![Link::default(), link], [LinkIdx::valid()])
+            .unwrap();
+        path_tpc.finish();
+        let mut ts = SpeedLimitTrainSim {
+            path_tpc,
+            ..Default::default()
+        };
+        ts.recalc_braking_points().unwrap();
+        ts
+    }
 
     // TODO: Add more SpeedLimitTrainSim cases
     impl Cases for SpeedLimitTrainSim {}
@@ -1250,6 +2361,639 @@ mod tests {
         assert_eq!(ts_msgpack.to_yaml().unwrap(), ts0.to_yaml().unwrap());
     }
 
+    #[test]
+    fn test_curve_comfort() {
+        let train_sim = SpeedLimitTrainSim::valid();
+        let network = Vec::<Link>::valid();
+
+        let curve_comforts = train_sim
+            .get_curve_comfort(&network, si::Length::ZERO, 3.0 * 0.0254 * uc::M)
+            .unwrap();
+        // `Link::valid` has a straight segment followed by a single curve
+        assert_eq!(curve_comforts.len(), 1);
+        let curve_comfort = curve_comforts[0];
+        assert!(almost_eq_uom(
+            &curve_comfort.offset_start,
+            &(5000.0 * uc::M),
+            None
+        ));
+        assert!(almost_eq_uom(
+            &curve_comfort.offset_end,
+            &(10000.0 * uc::M),
+            None
+        ));
+        assert!(curve_comfort.speed_max.is_none());
+        assert!(!curve_comfort.is_uncomfortable());
+
+        let curve_comforts = train_sim
+            .get_curve_comfort(&network, si::Length::ZERO, 0.001 * uc::M)
+            .unwrap();
+        assert!(curve_comforts[0].is_uncomfortable());
+    }
+
+    #[test]
+    fn test_trapezoid_integration() {
+        let mut ts_rect = SpeedLimitTrainSim::valid();
+        ts_rect.walk().unwrap();
+
+        let mut ts_trapz = SpeedLimitTrainSim::valid();
+        ts_trapz.integration_method = IntegrationMethod::Trapezoid;
+        ts_trapz.walk().unwrap();
+
+        // same trajectory, so cumulative energies differ only by the
+        // integration rule
+        let energy_rect = ts_rect.get_energy_fuel(false).unwrap();
+        let energy_trapz = ts_trapz.get_energy_fuel(false).unwrap();
+        assert!(energy_trapz != energy_rect);
+        assert!(almost_eq_uom(&energy_trapz, &energy_rect, Some(1e-2)));
+    }
+
+    #[test]
+    fn test_speed_limit_margin() {
+        let mut ts_zero = SpeedLimitTrainSim::valid();
+        ts_zero.set_save_interval(Some(1));
+        ts_zero.walk().unwrap();
+
+        let mut ts_margin = SpeedLimitTrainSim::valid();
+        ts_margin.set_save_interval(Some(1));
+        ts_margin.set_speed_limit_margin(2.0 * uc::MPS).unwrap();
+        ts_margin.walk().unwrap();
+
+        let speed_max = |ts: &SpeedLimitTrainSim| {
+            ts.history
+                .speed
+                .iter()
+                .map(|speed| *speed.get_unchecked(|| format_dbg!()).unwrap())
+                .fold(si::Velocity::ZERO, |acc, speed| acc.max(speed))
+        };
+        assert!(speed_max(&ts_margin) < speed_max(&ts_zero));
+        assert!(
+            *ts_margin.state.time.get_fresh(|| format_dbg!()).unwrap()
+                > *ts_zero.state.time.get_fresh(|| format_dbg!()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_eco_drive() {
+        let mut ts_base = SpeedLimitTrainSim::valid();
+        ts_base.walk().unwrap();
+
+        let mut ts_eco = SpeedLimitTrainSim::valid();
+        ts_eco.set_eco_drive(Some(EcoDriveConfig::default()));
+        ts_eco.walk().unwrap();
+
+        // coasting into the stop at the end of the path saves fuel at the
+        // expense of trip time
+        assert!(ts_eco.get_energy_fuel(false).unwrap() < ts_base.get_energy_fuel(false).unwrap());
+        assert!(
+            *ts_eco.state.time.get_fresh(|| format_dbg!()).unwrap()
+                >= *ts_base.state.time.get_fresh(|| format_dbg!()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_speed_target_trace() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.walk().unwrap();
+
+        let df = ts.speed_target_trace().unwrap();
+        assert_eq!(df.width(), 4);
+        assert_eq!(df.height(), ts.history.len());
+        let speed = df
+            .column("speed_meters_per_second")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<f64>>();
+        assert_eq!(
+            speed,
+            tracked_to_vec(&ts.history.speed, |x| x.get::<si::meter_per_second>()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut ts_a = SpeedLimitTrainSim::valid();
+        ts_a.set_save_interval(Some(1));
+        ts_a.walk().unwrap();
+        let mut ts_b = SpeedLimitTrainSim::valid();
+        ts_b.set_save_interval(Some(1));
+        ts_b.set_speed_limit_margin(2.0 * uc::MPS).unwrap();
+        ts_b.walk().unwrap();
+
+        let df = ts_a.compare(&ts_b).unwrap();
+        assert_eq!(df.width(), 1 + 3 * 7);
+        assert!(df.height() >= ts_a.history.len().max(ts_b.history.len()));
+        let time = df
+            .column("time_seconds")
+            .unwrap()
+            .f64()
+            .unwrap()
+            .into_no_null_iter()
+            .collect::<Vec<f64>>();
+        assert!(time.windows(2).all(|w| w[0] < w[1]));
+
+        let summary = ts_a.compare_summary(&ts_b).unwrap();
+        assert_eq!(summary.height(), 8);
+        let delta = summary.column("delta").unwrap().f64().unwrap();
+        // slower trip with margin
+        assert!(delta.get(0).unwrap() > 0.0);
+
+        // comparing with self yields no deltas
+        let df = ts_a.compare(&ts_a).unwrap();
+        assert_eq!(
+            df.column("speed_meters_per_second_delta")
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_no_null_iter()
+                .fold(0.0, |acc: f64, x| acc.max(x.abs())),
+            0.0
+        );
+    }
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.init().unwrap();
+        ts.save_state(|| format_dbg!()).unwrap();
+        let i0 = *ts.state.i.get_fresh(|| format_dbg!()).unwrap();
+        for _ in 0..10 {
+            ts.step(|| format_dbg!()).unwrap();
+        }
+        let checkpoint = ts.checkpoint().unwrap();
+        assert_eq!(checkpoint.i().unwrap(), i0 + 10);
+        let history_len = ts.history.len();
+        let yaml_checkpoint = ts.to_yaml().unwrap();
+
+        for _ in 0..10 {
+            ts.step(|| format_dbg!()).unwrap();
+        }
+        assert_eq!(*ts.state.i.get_fresh(|| format_dbg!()).unwrap(), i0 + 20);
+        assert_ne!(ts.to_yaml().unwrap(), yaml_checkpoint);
+
+        ts.restore(&checkpoint).unwrap();
+        // `to_yaml` is probably needed to get around problems with NAN
+        assert_eq!(ts.to_yaml().unwrap(), yaml_checkpoint);
+        assert_eq!(ts.history.len(), history_len);
+        // restored sim can continue
+        ts.step(|| format_dbg!()).unwrap();
+    }
+
+    #[test]
+    fn test_checkpoint_restore_history_sink() {
+        let tempdir = tempfile::tempdir().unwrap();
+        let path = tempdir.path().join("history.csv");
+        let path_ref = tempdir.path().join("history_ref.csv");
+        let n_steps = 15;
+
+        let mut ts_ref = SpeedLimitTrainSim::valid();
+        ts_ref.set_save_interval(Some(1));
+        ts_ref.init().unwrap();
+        let mut ts = ts_ref.clone();
+
+        ts_ref.set_history_sink(Some(HistorySink::new(&path_ref).unwrap()));
+        for _ in 0..n_steps {
+            ts_ref.step(|| format_dbg!()).unwrap();
+        }
+        ts_ref.set_history_sink(None);
+
+        ts.set_history_sink(Some(HistorySink::new(&path).unwrap()));
+        for _ in 0..10 {
+            ts.step(|| format_dbg!()).unwrap();
+        }
+        let checkpoint = ts.checkpoint().unwrap();
+        for _ in 0..10 {
+            ts.step(|| format_dbg!()).unwrap();
+        }
+        ts.restore(&checkpoint).unwrap();
+        assert_eq!(ts.history_sink.as_ref().unwrap().path(), path);
+        for _ in 10..n_steps {
+            ts.step(|| format_dbg!()).unwrap();
+        }
+        ts.set_history_sink(None);
+
+        // rows written after the checkpoint are discarded and the remaining
+        // rows go to the original file
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            std::fs::read_to_string(&path_ref).unwrap()
+        );
+        assert_eq!(std::fs::read_dir(tempdir.path()).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_reset() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.init().unwrap();
+        // nothing to reset before walking
+        assert!(ts.clone().reset().is_err());
+        let yaml_init = ts.to_yaml().unwrap();
+
+        ts.walk().unwrap();
+        let yaml_walked = ts.to_yaml().unwrap();
+        let energy_fuel = ts.get_energy_fuel(false).unwrap();
+
+        ts.reset().unwrap();
+        // `to_yaml` is probably needed to get around problems with NAN
+        assert_eq!(ts.to_yaml().unwrap(), yaml_init);
+        assert!(ts.history.is_empty());
+
+        ts.walk().unwrap();
+        assert_eq!(ts.to_yaml().unwrap(), yaml_walked);
+        assert_eq!(ts.get_energy_fuel(false).unwrap(), energy_fuel);
+    }
+
+    #[test]
+    fn test_strip_history() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.init().unwrap();
+        ts.walk().unwrap();
+        assert!(!ts.history.is_empty());
+        let yaml_full = ts.to_yaml().unwrap();
+
+        ts.strip_history();
+        assert!(ts.history.is_empty());
+        assert!(ts.loco_con.history.is_empty());
+        assert!(ts.fric_brake.history.is_empty());
+        for loco in ts.loco_con.loco_vec.iter() {
+            assert!(loco.history.is_empty());
+        }
+        let yaml_stripped = ts.to_yaml().unwrap();
+        assert!(yaml_stripped.len() * 10 < yaml_full.len());
+
+        // stripped output is still usable as an input
+        SpeedLimitTrainSim::from_yaml(yaml_stripped, false).unwrap();
+    }
+
+    #[test]
+    fn test_grade_mean() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.walk().unwrap();
+
+        let hist = &ts.history;
+        let mut n_straddle = 0;
+        for ((grade_mean, grade_front), grade_back) in hist
+            .grade_mean
+            .iter()
+            .zip(&hist.grade_front)
+            .zip(&hist.grade_back)
+        {
+            let grade_mean = *grade_mean.get_unchecked(|| format_dbg!()).unwrap();
+            let grade_front = *grade_front.get_unchecked(|| format_dbg!()).unwrap();
+            let grade_back = *grade_back.get_unchecked(|| format_dbg!()).unwrap();
+            let tol = 1e-9 * uc::R;
+            assert!(grade_mean >= grade_front.min(grade_back) - tol);
+            assert!(grade_mean <= grade_front.max(grade_back) + tol);
+            if grade_mean > grade_front.min(grade_back) && grade_mean < grade_front.max(grade_back)
+            {
+                n_straddle += 1;
+            }
+        }
+        // train crosses the crest of the graded test link
+        assert!(n_straddle > 0);
+    }
+
+    #[test]
+    fn test_ruling_grade() {
+        // short 3% spike followed by a long 1% grade
+        let ts = graded_train_sim(vec![
+            Elev::new(si::Length::ZERO, 100.0 * uc::M),
+            Elev::new(1000.0 * uc::M, 100.0 * uc::M),
+            Elev::new(1200.0 * uc::M, 106.0 * uc::M),
+            Elev::new(2000.0 * uc::M, 106.0 * uc::M),
+            Elev::new(6000.0 * uc::M, 146.0 * uc::M),
+            Elev::new(Link::valid().length, 146.0 * uc::M),
+        ]);
+
+        assert!(almost_eq(
+            ts.max_grade_on_route().unwrap().get::<si::ratio>(),
+            0.03,
+            None
+        ));
+        assert!(almost_eq(
+            ts.ruling_grade(2000.0 * uc::M).unwrap().get::<si::ratio>(),
+            0.01,
+            None
+        ));
+        // window short enough to be dominated by the spike
+        assert!(ts.ruling_grade(200.0 * uc::M).unwrap() > 0.02 * uc::R);
+        assert!(ts.ruling_grade(si::Length::ZERO).is_err());
+        assert!(SpeedLimitTrainSim::default().max_grade_on_route().is_err());
+    }
+
+    #[test]
+    fn test_brake_blending() {
+        // flat approach followed by a steep downgrade
+        let link_length = Link::valid().length;
+        let mut ts = graded_train_sim(vec![
+            Elev::new(si::Length::ZERO, 150.0 * uc::M),
+            Elev::new(3000.0 * uc::M, 150.0 * uc::M),
+            Elev::new(
+                link_length,
+                150.0 * uc::M - 0.015 * (link_length - 3000.0 * uc::M),
+            ),
+        ]);
+        ts.set_save_interval(Some(1));
+        ts.walk().unwrap();
+
+        let hist = &ts.history;
+        let pwr_dyn_brake: Vec<si::Power> = hist
+            .pwr_dyn_brake
+            .iter()
+            .map(|p| *p.get_unchecked(|| format_dbg!()).unwrap())
+            .collect();
+        // friction brake force, recovered from power and speed
+        let force_fric_brake: Vec<si::Force> = hist
+            .pwr_fric_brake
+            .iter()
+            .zip(&hist.speed)
+            .map(|(p, v)| {
+                let speed = *v.get_unchecked(|| format_dbg!()).unwrap();
+                if speed > si::Velocity::ZERO {
+                    *p.get_unchecked(|| format_dbg!()).unwrap() / speed
+                } else {
+                    si::Force::ZERO
+                }
+            })
+            .collect();
+
+        let i_fric = force_fric_brake
+            .iter()
+            .position(|f| *f > si::Force::ZERO)
+            .expect("friction brakes should engage on steep downgrade");
+        // dynamic braking engages first
+        assert!(pwr_dyn_brake[..i_fric].iter().any(|p| *p > si::Power::ZERO));
+        // friction braking only increases once dynamic braking is saturated.
+        // Dynamic braking force is limited to max power at the highest speed
+        // reachable within the step, so the power delivered at the end of the
+        // step may fall slightly short of the limit.
+        let mut n_fric_increase = 0;
+        for i in 1..force_fric_brake.len() {
+            if force_fric_brake[i] > force_fric_brake[i - 1] * (1.0 + 1e-9) {
+                let pwr_dyn_brake_max = *ts.loco_con.history.pwr_dyn_brake_max[i]
+                    .get_unchecked(|| format_dbg!())
+                    .unwrap();
+                assert!(
+                    pwr_dyn_brake[i] >= 0.95 * pwr_dyn_brake_max,
+                    "step {i}: pwr_dyn_brake={:?}, pwr_dyn_brake_max={:?}",
+                    pwr_dyn_brake[i],
+                    pwr_dyn_brake_max
+                );
+                n_fric_increase += 1;
+            }
+        }
+        assert!(n_fric_increase > 0);
+    }
+
+    #[test]
+    fn test_time_in_state_histogram() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.init().unwrap();
+        ts.walk().unwrap();
+
+        let pwr_thresh = 10.0 * uc::KW;
+        let tis_vec = ts.time_in_state_histogram(pwr_thresh).unwrap();
+        assert_eq!(tis_vec.len(), ts.loco_con.loco_vec.len());
+        let time_total = *ts
+            .history
+            .time
+            .last()
+            .unwrap()
+            .get_unchecked(|| format_dbg!())
+            .unwrap()
+            - *ts.history.time[0].get_unchecked(|| format_dbg!()).unwrap();
+        for tis in tis_vec.iter() {
+            assert!(almost_eq_uom(
+                &(tis.motoring + tis.braking + tis.idling),
+                &time_total,
+                None
+            ));
+            assert!(tis.motoring > si::Time::ZERO);
+        }
+
+        ts.strip_history();
+        assert!(ts.time_in_state_histogram(pwr_thresh).is_err());
+    }
+
+    #[test]
+    fn test_traction_limit_cause() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.walk().unwrap();
+
+        let causes: Vec<TractionLimitCause> = ts
+            .history
+            .traction_limit_cause
+            .iter()
+            .map(|c| {
+                TractionLimitCause::try_from(*c.get_unchecked(|| format_dbg!()).unwrap()).unwrap()
+            })
+            .collect();
+        let speed = |i: usize| *ts.history.speed[i].get_unchecked(|| format_dbg!()).unwrap();
+
+        // accelerating from stop, traction is adhesion limited at low speed and
+        // power limited once speed is high enough
+        let i_adhesion = causes
+            .iter()
+            .position(|c| *c == TractionLimitCause::Adhesion)
+            .expect("traction should be adhesion limited at low speed");
+        let i_power = i_adhesion
+            + causes[i_adhesion..]
+                .iter()
+                .position(|c| *c == TractionLimitCause::Power)
+                .expect("traction should become power limited");
+        assert!(speed(i_power) > speed(i_adhesion));
+    }
+
+    #[test]
+    fn test_max_jerk() {
+        // largest change in applied force between steps, normalized by mass
+        // and time step, over steps in which the jerk limit is not overridden
+        // by slowing down, holding the train at rest, or reaching the speed
+        // limit
+        let max_jerk_achieved = |ts: &SpeedLimitTrainSim| -> si::Jerk {
+            let mass = ts.state.mass_compound().unwrap();
+            let hist = &ts.history;
+            let get = |x: &TrackedState<si::Velocity>| *x.get_unchecked(|| format_dbg!()).unwrap();
+            hist.force_applied
+                .windows(2)
+                .zip(&hist.dt[1..])
+                .enumerate()
+                .filter(|(i, _)| {
+                    get(&hist.speed_target[i + 1]) >= get(&hist.speed[*i])
+                        && get(&hist.speed[i + 1]) > si::Velocity::ZERO
+                        && get(&hist.speed[i + 1]) < get(&hist.speed_limit[i + 1])
+                })
+                .map(|(_, (f, dt))| {
+                    (*f[1].get_unchecked(|| format_dbg!()).unwrap()
+                        - *f[0].get_unchecked(|| format_dbg!()).unwrap())
+                    .abs()
+                        / mass
+                        / *dt.get_unchecked(|| format_dbg!()).unwrap()
+                })
+                .fold(si::Jerk::ZERO, |acc, j| acc.max(j))
+        };
+        let max_jerk = 0.02 * uc::MPS3;
+
+        let mut ts_ref = SpeedLimitTrainSim::valid();
+        ts_ref.set_save_interval(Some(1));
+        ts_ref.walk().unwrap();
+        assert!(max_jerk_achieved(&ts_ref) > max_jerk);
+
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.set_save_interval(Some(1));
+        ts.set_max_jerk(Some(max_jerk)).unwrap();
+        ts.walk().unwrap();
+        assert!(max_jerk_achieved(&ts) <= max_jerk * (1.0 + 1e-6));
+        assert!(ts
+            .history
+            .speed
+            .iter()
+            .all(|speed| *speed.get_unchecked(|| format_dbg!()).unwrap() >= si::Velocity::ZERO));
+
+        assert!(ts.set_max_jerk(Some(si::Jerk::ZERO)).is_err());
+    }
+
+    #[test]
+    fn test_substepping() {
+        // flat approach followed by a sharp break to a downgrade
+        let link_length = Link::valid().length;
+        let mut ts_ref = graded_train_sim(vec![
+            Elev::new(si::Length::ZERO, 150.0 * uc::M),
+            Elev::new(3000.0 * uc::M, 150.0 * uc::M),
+            Elev::new(
+                link_length,
+                150.0 * uc::M - 0.005 * (link_length - 3000.0 * uc::M),
+            ),
+        ]);
+        ts_ref.set_save_interval(Some(1));
+        let mut ts = ts_ref.clone();
+        ts.set_substepping(10, si::Acceleration::ZERO).unwrap();
+        ts_ref.walk().unwrap();
+        // completes under the default `SpeedViolationPolicy::Error`
+        ts.walk().unwrap();
+        assert!(ts.history.speed.iter().zip(&ts.history.speed_limit).all(
+            |(speed, speed_limit)| {
+                *speed.get_unchecked(|| format_dbg!()).unwrap()
+                    <= *speed_limit.get_unchecked(|| format_dbg!()).unwrap()
+            }
+        ));
+
+        // sum over steps of the mismatch between kinetic energy change and
+        // work done by applied force and resistance, with resistance averaged
+        // between the start and end of each step
+        let energy_balance_residual = |ts: &SpeedLimitTrainSim| -> si::Energy {
+            let hist = &ts.history;
+            let get = |x: &TrackedState<si::Force>| *x.get_unchecked(|| format_dbg!()).unwrap();
+            let res_net = |i: usize| -> si::Force {
+                get(&hist.res_rolling[i])
+                    + get(&hist.res_bearing[i])
+                    + get(&hist.res_davis_b[i])
+                    + get(&hist.res_aero[i])
+                    + get(&hist.res_grade[i])
+                    + get(&hist.res_curve[i])
+            };
+            (1..hist.len() - 1)
+                .map(|i| {
+                    let ke_change = *hist.pwr_accel[i].get_unchecked(|| format_dbg!()).unwrap()
+                        * *hist.dt[i].get_unchecked(|| format_dbg!()).unwrap();
+                    let dist = *hist.offset[i].get_unchecked(|| format_dbg!()).unwrap()
+                        - *hist.offset[i - 1].get_unchecked(|| format_dbg!()).unwrap();
+                    let work =
+                        (get(&hist.force_applied[i]) - 0.5 * (res_net(i) + res_net(i + 1))) * dist;
+                    (ke_change - work).abs()
+                })
+                .fold(si::Energy::ZERO, |acc, r| acc + r)
+        };
+        let residual_ref = energy_balance_residual(&ts_ref);
+        let residual = energy_balance_residual(&ts);
+        assert!(residual_ref > si::Energy::ZERO);
+        assert!(
+            residual < 0.5 * residual_ref,
+            "{residual:?} vs {residual_ref:?}"
+        );
+
+        assert!(ts.set_substepping(0, si::Acceleration::ZERO).is_err());
+        assert!(ts.set_substepping(2, -uc::MPS2).is_err());
+    }
+
+    #[test]
+    fn test_regen_recovery_fraction() {
+        use crate::consist::locomotive::Locomotive;
+
+        // flat approach followed by a downgrade
+        let link_length = Link::valid().length;
+        let ts_graded = graded_train_sim(vec![
+            Elev::new(si::Length::ZERO, 150.0 * uc::M),
+            Elev::new(3000.0 * uc::M, 150.0 * uc::M),
+            Elev::new(
+                link_length,
+                150.0 * uc::M - 0.01 * (link_length - 3000.0 * uc::M),
+            ),
+        ]);
+
+        // default consist includes battery electric and hybrid locomotives
+        let mut ts = ts_graded.clone();
+        // leave headroom for regen
+        ts.loco_con.set_initial_soc(0.6 * uc::R).unwrap();
+        ts.recalc_braking_points().unwrap();
+        ts.walk().unwrap();
+        assert!(ts.regen_recovery_fraction().unwrap() > 0.0);
+
+        let mut loco_con_conv =
+            Consist::new(vec![Locomotive::default(); 4], None, Default::default());
+        loco_con_conv.init().unwrap();
+        let mut ts_conv = ts_graded;
+        ts_conv.loco_con = loco_con_conv;
+        ts_conv.recalc_braking_points().unwrap();
+        ts_conv.walk().unwrap();
+        assert!(
+            *ts_conv
+                .state
+                .energy_whl_out_neg
+                .get_unchecked(|| format_dbg!())
+                .unwrap()
+                > si::Energy::ZERO
+        );
+        assert_eq!(ts_conv.regen_recovery_fraction().unwrap(), 0.0);
+    }
+
+    #[test]
+    fn test_objective() {
+        let mut ts = SpeedLimitTrainSim::valid();
+        ts.walk().unwrap();
+
+        let weights = ObjectiveWeights {
+            time: 1.0,
+            ..Default::default()
+        };
+        let obj = ts.objective(weights).unwrap();
+        let obj_fuel = ts
+            .objective(ObjectiveWeights {
+                energy_fuel: 2.0 * weights.energy_fuel,
+                ..weights
+            })
+            .unwrap();
+        assert!(ts.get_energy_fuel(false).unwrap() > si::Energy::ZERO);
+        assert!(obj_fuel > obj);
+
+        assert!(ts
+            .objective(ObjectiveWeights {
+                time_ref: si::Time::ZERO,
+                ..weights
+            })
+            .is_err());
+        assert!(ObjectiveWeights::from_dict(&HashMap::from([("bogus".into(), 1.0)])).is_err());
+    }
+
     lazy_static! {
         static ref SOLVED_SPEED_LIM_TRAIN_SIM: crate::prelude::SpeedLimitTrainSim = {
             let mut ts = crate::prelude::SpeedLimitTrainSim::valid();
@@ -107,6 +107,33 @@ impl TrainConfig {
         self.cd_area_vec = Some(new_val.iter().map(|x| *x * uc::M2).collect());
         Ok(())
     }
+
+    #[cfg(feature = "json")]
+    #[pyo3(name = "to_param_dict")]
+    fn to_param_dict_py(&self) -> anyhow::Result<HashMap<String, f64>> {
+        self.to_param_dict()
+    }
+
+    #[cfg(feature = "json")]
+    #[staticmethod]
+    #[pyo3(name = "from_param_dict")]
+    /// - `base` - config providing all values not specified in `param_dict`
+    /// - `param_dict` - parameters, as returned by `to_param_dict`, to be overridden
+    fn from_param_dict_py(base: Self, param_dict: HashMap<String, f64>) -> anyhow::Result<Self> {
+        Self::from_param_dict(&base, &param_dict)
+    }
+
+    #[staticmethod]
+    #[pyo3(name = "from_dataframe")]
+    /// - `df` - DataFrame with one row per train config, as described in
+    ///   [TrainConfig::from_dataframe]
+    /// - `rail_vehicles` - list of `RailVehicle` objects with 1 element for each _type_ of rail vehicle
+    fn from_dataframe_py(
+        df: PyDataFrame,
+        rail_vehicles: Vec<RailVehicle>,
+    ) -> anyhow::Result<Vec<Self>> {
+        Self::from_dataframe(&df.into(), &rail_vehicles)
+    }
 }
 
 impl Init for TrainConfig {
@@ -119,6 +146,26 @@ impl Init for TrainConfig {
                 ));
             }
         };
+        if let Some(rv) = self.rail_vehicles.iter().find(|rv| {
+            rv.drafting_factor
+                .is_some_and(|df| !(df > si::Ratio::ZERO && df <= uc::R))
+        }) {
+            return Err(Error::InitError(format!(
+                "{}
+`drafting_factor` for car type `{}` must be in (0, 1], got {:?}",
+                format_dbg!(),
+                rv.car_type,
+                rv.drafting_factor
+            )));
+        }
+        let missing_car_types = self.missing_car_types();
+        if !missing_car_types.is_empty() {
+            return Err(Error::InitError(format!(
+                "{}\n`n_cars_by_type` has car types with no matching `car_type` in `rail_vehicles`: {:?}",
+                format_dbg!(),
+                missing_car_types
+            )));
+        }
         Ok(())
     }
 }
@@ -145,10 +192,249 @@ impl TrainConfig {
         Ok(train_config)
     }
 
+    /// Returns all numeric parameters flattened into a map of name to value,
+    /// with units baked into the names (e.g. `rail_vehicles.[0].length_meters`),
+    /// for use with optimization frameworks.  Optional fields that are `None`
+    /// are not included.
+    #[cfg(feature = "json")]
+    pub fn to_param_dict(&self) -> anyhow::Result<HashMap<String, f64>> {
+        let value = serde_json::to_value(self).with_context(|| format_dbg!())?;
+        let mut param_dict = HashMap::new();
+        utils::flatten_numeric_params(&value, "", &mut param_dict);
+        Ok(param_dict)
+    }
+
+    /// Returns a copy of `base` with each parameter in `param_dict` -- keyed
+    /// as in [Self::to_param_dict] -- overridden.
+    /// # Arguments
+    /// - `base` - config providing all values not specified in `param_dict`
+    /// - `param_dict` - parameters to be overridden
+    #[cfg(feature = "json")]
+    pub fn from_param_dict(base: &Self, param_dict: &HashMap<String, f64>) -> anyhow::Result<Self> {
+        let mut value = serde_json::to_value(base).with_context(|| format_dbg!())?;
+        for (key, val) in param_dict {
+            utils::set_numeric_param(&mut value, key, *val)?;
+        }
+        let mut train_config: Self =
+            serde_json::from_value(value).with_context(|| format_dbg!())?;
+        train_config.init()?;
+        Ok(train_config)
+    }
+
+    /// Returns one config per row of `df`, each with all of `rail_vehicles`.
+    /// Recognized columns are:
+    /// - one integer column per car type, named after [RailVehicle::car_type],
+    ///   with the number of cars of that type, at least one of which is required
+    ///   and any missing one of which is taken to be zero
+    /// - `Train_Type` (optional) - string matching a [TrainType] variant, with
+    ///   [TrainType::default] if not provided
+    /// - `Train_Length_Meters` (optional) - numeric [Self::train_length]
+    /// - `Train_Mass_Kilograms` (optional) - numeric [Self::train_mass]
+    ///
+    /// Null values are only allowed in the optional numeric columns, and any
+    /// other column is an error.
+    pub fn from_dataframe(
+        df: &DataFrame,
+        rail_vehicles: &[RailVehicle],
+    ) -> anyhow::Result<Vec<Self>> {
+        const TRAIN_TYPE: &str = "Train_Type";
+        const TRAIN_LENGTH: &str = "Train_Length_Meters";
+        const TRAIN_MASS: &str = "Train_Mass_Kilograms";
+
+        let car_types: HashSet<&str> = rail_vehicles
+            .iter()
+            .map(|rv| rv.car_type.as_str())
+            .collect();
+        if let Some(name) = df.get_column_names().into_iter().find(|name| {
+            !car_types.contains(name.as_str())
+                && ![TRAIN_TYPE, TRAIN_LENGTH, TRAIN_MASS].contains(&name.as_str())
+        }) {
+            bail!(
+                "{}\nColumn `{}` is neither a car type in `rail_vehicles` nor one of {:?}",
+                format_dbg!(),
+                name,
+                [TRAIN_TYPE, TRAIN_LENGTH, TRAIN_MASS]
+            );
+        }
+
+        let mut n_cars_cols: Vec<(String, Series)> = Vec::new();
+        for car_type in rail_vehicles.iter().map(|rv| &rv.car_type) {
+            let Ok(col) = df.column(car_type) else {
+                continue;
+            };
+            ensure!(
+                col.dtype().is_integer(),
+                "{}\nColumn `{}` must have integer type, got {}",
+                format_dbg!(),
+                car_type,
+                col.dtype()
+            );
+            ensure!(
+                col.null_count() == 0,
+                "{}\nColumn `{}` must not have null values",
+                format_dbg!(),
+                car_type
+            );
+            n_cars_cols.push((
+                car_type.clone(),
+                col.as_materialized_series()
+                    .strict_cast(&DataType::UInt32)
+                    .with_context(|| {
+                        format!(
+                            "{}\nColumn `{}` must be non-negative and fit in `u32`",
+                            format_dbg!(),
+                            car_type
+                        )
+                    })?,
+            ));
+        }
+        ensure!(
+            !n_cars_cols.is_empty(),
+            "{}\nExpected at least one column named after a car type in `rail_vehicles`: {:?}",
+            format_dbg!(),
+            rail_vehicles
+                .iter()
+                .map(|rv| &rv.car_type)
+                .collect::<Vec<_>>()
+        );
+
+        let train_types: Option<Series> = match df.column(TRAIN_TYPE) {
+            Ok(col) => {
+                ensure!(
+                    col.dtype() == &DataType::String,
+                    "{}\nColumn `{}` must have string type, got {}",
+                    format_dbg!(),
+                    TRAIN_TYPE,
+                    col.dtype()
+                );
+                ensure!(
+                    col.null_count() == 0,
+                    "{}\nColumn `{}` must not have null values",
+                    format_dbg!(),
+                    TRAIN_TYPE
+                );
+                Some(col.as_materialized_series().clone())
+            }
+            Err(_) => None,
+        };
+        let float_col = |name: &str| -> anyhow::Result<Option<Series>> {
+            match df.column(name) {
+                Ok(col) => {
+                    ensure!(
+                        col.dtype().is_float() || col.dtype().is_integer(),
+                        "{}\nColumn `{}` must have numeric type, got {}",
+                        format_dbg!(),
+                        name,
+                        col.dtype()
+                    );
+                    Ok(Some(
+                        col.as_materialized_series()
+                            .cast(&DataType::Float64)
+                            .with_context(|| format_dbg!())?,
+                    ))
+                }
+                Err(_) => Ok(None),
+            }
+        };
+        let train_lengths = float_col(TRAIN_LENGTH)?;
+        let train_masses = float_col(TRAIN_MASS)?;
+
+        (0..df.height())
+            .map(|i| -> anyhow::Result<Self> {
+                let mut n_cars_by_type = n_cars_cols
+                    .iter()
+                    .map(|(car_type, n_cars)| -> anyhow::Result<(String, u32)> {
+                        Ok((
+                            car_type.clone(),
+                            n_cars
+                                .u32()
+                                .with_context(|| format_dbg!())?
+                                .get(i)
+                                .with_context(|| format_dbg!())?,
+                        ))
+                    })
+                    .collect::<anyhow::Result<HashMap<String, u32>>>()?;
+                for rv in rail_vehicles {
+                    n_cars_by_type.entry(rv.car_type.clone()).or_insert(0);
+                }
+                let train_type = match &train_types {
+                    Some(train_types) => TrainType::try_from(
+                        train_types
+                            .str()
+                            .with_context(|| format_dbg!())?
+                            .get(i)
+                            .with_context(|| format_dbg!())?,
+                    )?,
+                    None => TrainType::default(),
+                };
+                let get_f64 = |col: &Option<Series>| -> anyhow::Result<Option<f64>> {
+                    match col {
+                        Some(col) => Ok(col.f64().with_context(|| format_dbg!())?.get(i)),
+                        None => Ok(None),
+                    }
+                };
+                Self::new(
+                    rail_vehicles.to_vec(),
+                    n_cars_by_type,
+                    train_type,
+                    get_f64(&train_lengths)?.map(|l| l * uc::M),
+                    get_f64(&train_masses)?.map(|m| m * uc::KG),
+                    None,
+                )
+                .with_context(|| format!("{}\nFailed to build config for row {}", format_dbg!(), i))
+            })
+            .collect()
+    }
+
+    /// Returns total drag area of all railcars.  If provided,
+    /// [Self::cd_area_vec] is summed as is.  Otherwise, [RailVehicle::cd_area]
+    /// is summed over [Self::n_cars_by_type], with every car but the lead car
+    /// scaled by its [RailVehicle::drafting_factor].  The lead car is taken to
+    /// be of the first car type in [Self::rail_vehicles] with a nonzero count.
+    pub fn cd_area_total(&self) -> si::Area {
+        if let Some(dcv) = &self.cd_area_vec {
+            return dcv.iter().fold(si::Area::ZERO, |acc, dc| acc + *dc);
+        }
+        let mut lead_found = false;
+        self.rail_vehicles
+            .iter()
+            .fold(si::Area::ZERO, |acc, rv| -> si::Area {
+                let n_cars = *self.n_cars_by_type.get(&rv.car_type).unwrap_or(&0) as f64;
+                if n_cars == 0.0 {
+                    return acc;
+                }
+                let drafting_factor = rv.drafting_factor.unwrap_or(uc::R);
+                let n_cars_eff = if lead_found {
+                    n_cars * drafting_factor
+                } else {
+                    lead_found = true;
+                    uc::R + (n_cars - 1.0) * drafting_factor
+                };
+                acc + rv.cd_area * n_cars_eff
+            })
+    }
+
     pub fn cars_total(&self) -> u32 {
         self.n_cars_by_type.values().fold(0, |acc, n| *n + acc)
     }
 
+    /// Returns sorted keys of `self.n_cars_by_type` that do not have a matching
+    /// `car_type` in `self.rail_vehicles`
+    pub fn missing_car_types(&self) -> Vec<&String> {
+        let mut missing_car_types = self
+            .n_cars_by_type
+            .keys()
+            .filter(|car_type| {
+                !self
+                    .rail_vehicles
+                    .iter()
+                    .any(|rv| &rv.car_type == *car_type)
+            })
+            .collect::<Vec<&String>>();
+        missing_car_types.sort();
+        missing_car_types
+    }
+
     /// # Arguments
     /// - `rail_vehicles` - slice of `RailVehicle` objects with 1 element for each _type_ of rail vehicle
     /// # Important
@@ -228,7 +514,10 @@ impl TrainConfig {
 impl Valid for TrainConfig {
     fn valid() -> Self {
         Self {
-            rail_vehicles: vec![RailVehicle::default()],
+            rail_vehicles: vec![RailVehicle {
+                car_type: "Bulk".into(),
+                ..Default::default()
+            }],
             n_cars_by_type: HashMap::from([("Bulk".into(), 100_u32)]),
             train_type: TrainType::Freight,
             train_length: None,
@@ -384,6 +673,23 @@ impl TrainSimBuilder {
         Ok((train_sim, train_params, path_tpc, trw, fb))
     }
 
+    #[pyo3(
+        name = "build_set_speed_train_sim",
+        signature = (
+            speed_trace,
+            save_interval=None,
+            temp_trace=None,
+        )
+    )]
+    fn build_set_speed_train_sim_py(
+        &self,
+        speed_trace: SpeedTrace,
+        save_interval: Option<usize>,
+        temp_trace: Option<TemperatureTrace>,
+    ) -> anyhow::Result<SetSpeedTrainSim> {
+        self.build_set_speed_train_sim(speed_trace, save_interval, temp_trace)
+    }
+
     #[pyo3(
         name = "make_speed_limit_train_sim",
         signature = (
@@ -491,7 +797,7 @@ impl TrainSimBuilder {
                 * rv.axle_count as f64
         });
         let mass_freight = rvs.iter().fold(0. * uc::KG, |acc, rv| -> si::Mass {
-            acc + rv.mass_freight
+            acc + rv.freight_mass()
                 * *self.train_config.n_cars_by_type.get(&rv.car_type).unwrap() as f64
         });
         let max_fric_braking = uc::ACC_GRAV
@@ -548,14 +854,7 @@ impl TrainSimBuilder {
                             * uc::R)
                 },
             )?);
-            let res_aero =
-                res_kind::aerodynamic::Basic::new(match &self.train_config.cd_area_vec {
-                    Some(dave) => dave.iter().fold(0. * uc::M2, |acc, dc| *dc + acc),
-                    None => rvs.iter().fold(0.0 * uc::M2, |acc, rv| -> si::Area {
-                        acc + rv.cd_area
-                            * *self.train_config.n_cars_by_type.get(&rv.car_type).unwrap() as f64
-                    }),
-                });
+            let res_aero = res_kind::aerodynamic::Basic::new(self.train_config.cd_area_total());
             let res_grade = res_kind::path_res::Strap::new(path_tpc.grades(), &state)?;
             let res_curve = res_kind::path_res::Strap::new(path_tpc.curves(), &state)?;
             TrainRes::Strap(res_method::Strap::new(
@@ -568,7 +867,7 @@ impl TrainSimBuilder {
             ))
         };
 
-        let fric_brake = FricBrake::new(max_fric_braking, None, None, None, save_interval);
+        let fric_brake = FricBrake::new(max_fric_braking, None, None, None, None, save_interval);
 
         Ok((train_params, state, path_tpc, train_res, fric_brake))
     }
@@ -673,6 +972,35 @@ impl TrainSimBuilder {
         ))
     }
 
+    /// Builds a [SetSpeedTrainSim] from the same consist, resistance, and path
+    /// assembly used by [Self::make_speed_limit_train_sim], so that both sim
+    /// types can be built from one builder.  Unlike
+    /// [Self::make_set_speed_train_sim], `origin_id` and `destination_id` are
+    /// not checked and `path_tpc` is not extended over a link path.  The
+    /// friction brake model is not used because speed is prescribed.
+    pub fn build_set_speed_train_sim(
+        &self,
+        speed_trace: SpeedTrace,
+        save_interval: Option<usize>,
+        temp_trace: Option<TemperatureTrace>,
+    ) -> anyhow::Result<SetSpeedTrainSim> {
+        let (_, state, path_tpc, train_res, _fric_brake) = self
+            .make_train_sim_parts(save_interval)
+            .with_context(|| format_dbg!())?;
+
+        Ok(SetSpeedTrainSimBuilder {
+            loco_con: self.loco_con.clone(),
+            n_cars_by_type: self.train_config.n_cars_by_type.clone(),
+            state,
+            speed_trace,
+            train_res,
+            path_tpc,
+            save_interval,
+            temp_trace,
+        }
+        .into())
+    }
+
     pub fn make_speed_limit_train_sim(
         &self,
         location_map: &LocationMap,
@@ -1452,6 +1780,13 @@ impl SpeedLimitTrainSimVec {
     fn __new__(v: Vec<SpeedLimitTrainSim>) -> Self {
         Self(v)
     }
+
+    #[staticmethod]
+    #[pyo3(name = "from_dir")]
+    /// Loads every `*.yaml` and `*.json` file in `dirpath`, sorted by filename
+    fn from_dir_py(dirpath: &Bound<PyAny>) -> anyhow::Result<Self> {
+        Self::from_dir(PathBuf::extract_bound(dirpath)?)
+    }
 }
 
 impl SpeedLimitTrainSimVec {
@@ -1459,6 +1794,35 @@ impl SpeedLimitTrainSimVec {
         Self(value)
     }
 
+    /// Loads a [SpeedLimitTrainSim] from every `*.yaml` and `*.json` file in
+    /// `dirpath`, sorted by filename for determinism.  Other files and
+    /// subdirectories are skipped.
+    pub fn from_dir<P: AsRef<Path>>(dirpath: P) -> anyhow::Result<Self> {
+        let dirpath = dirpath.as_ref();
+        let mut filepaths = std::fs::read_dir(dirpath)
+            .with_context(|| format!("{}\nFailed to read directory {:?}", format_dbg!(), dirpath))?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<std::io::Result<Vec<PathBuf>>>()
+            .with_context(|| format_dbg!())?;
+        filepaths.retain(|fp| {
+            fp.is_file()
+                && matches!(
+                    fp.extension().and_then(OsStr::to_str),
+                    Some("yaml") | Some("json")
+                )
+        });
+        filepaths.sort();
+        Ok(Self(
+            filepaths
+                .iter()
+                .map(|fp| {
+                    SpeedLimitTrainSim::from_file(fp, false)
+                        .with_context(|| format!("{}\nFailed to load {:?}", format_dbg!(), fp))
+                })
+                .collect::<anyhow::Result<Vec<SpeedLimitTrainSim>>>()?,
+        ))
+    }
+
     pub fn get_energy_fuel(&self, annualize: bool) -> anyhow::Result<si::Energy> {
         self.0.iter().try_fold(si::Energy::ZERO, |acc, sim| {
             Ok(acc + sim.get_energy_fuel(annualize)?)
@@ -1519,3 +1883,406 @@ impl Init for SpeedLimitTrainSimVec {
     }
 }
 impl SerdeAPI for SpeedLimitTrainSimVec {}
+
+/// Returns DataFrame with one row per sim in `sims` and columns for train ID,
+/// trip time, distance, fuel energy, net RES energy, and max speed.  Every sim
+/// must already have been run with history saved.
+pub fn summarize_speed_limit_train_sims(sims: &SpeedLimitTrainSimVec) -> anyhow::Result<DataFrame> {
+    let mut train_id: Vec<String> = Vec::with_capacity(sims.0.len());
+    let mut time_trip: Vec<f64> = Vec::with_capacity(sims.0.len());
+    let mut dist: Vec<f64> = Vec::with_capacity(sims.0.len());
+    let mut energy_fuel: Vec<f64> = Vec::with_capacity(sims.0.len());
+    let mut energy_res: Vec<f64> = Vec::with_capacity(sims.0.len());
+    let mut speed_max: Vec<f64> = Vec::with_capacity(sims.0.len());
+    for sim in sims.0.iter() {
+        ensure!(
+            !sim.history.is_empty(),
+            "{}\nExpected non-empty history for train `{}`",
+            format_dbg!(),
+            sim.train_id
+        );
+        train_id.push(sim.train_id.clone());
+        time_trip.push(
+            (*sim
+                .history
+                .time
+                .last()
+                .unwrap()
+                .get_unchecked(|| format_dbg!())?
+                - *sim.history.time[0].get_unchecked(|| format_dbg!())?)
+            .get::<si::second>(),
+        );
+        dist.push(
+            sim.state
+                .total_dist
+                .get_unchecked(|| format_dbg!())?
+                .get::<si::meter>(),
+        );
+        energy_fuel.push(
+            sim.get_energy_fuel(false)
+                .with_context(|| format_dbg!())?
+                .get::<si::joule>(),
+        );
+        energy_res.push(
+            sim.get_net_energy_res(false)
+                .with_context(|| format_dbg!())?
+                .get::<si::joule>(),
+        );
+        speed_max.push(sim.history.speed.iter().try_fold(
+            0.0,
+            |acc, speed| -> anyhow::Result<f64> {
+                Ok(acc.max(
+                    speed
+                        .get_unchecked(|| format_dbg!())?
+                        .get::<si::meter_per_second>(),
+                ))
+            },
+        )?);
+    }
+    DataFrame::new(vec![
+        Column::new("train_id".into(), train_id),
+        Column::new("time_trip_seconds".into(), time_trip),
+        Column::new("distance_meters".into(), dist),
+        Column::new("energy_fuel_joules".into(), energy_fuel),
+        Column::new("energy_res_net_joules".into(), energy_res),
+        Column::new("speed_max_meters_per_second".into(), speed_max),
+    ])
+    .with_context(|| format_dbg!())
+}
+
+#[cfg(feature = "pyo3")]
+#[pyfunction(name = "summarize_speed_limit_train_sims")]
+/// Python wrapper for [summarize_speed_limit_train_sims]
+pub fn summarize_speed_limit_train_sims_py(
+    sims: SpeedLimitTrainSimVec,
+) -> anyhow::Result<PyDataFrame> {
+    Ok(PyDataFrame(summarize_speed_limit_train_sims(&sims)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_param_dict_round_trip() {
+        let train_config = TrainConfig::valid();
+        let mut param_dict = train_config.to_param_dict().unwrap();
+        assert_eq!(param_dict["n_cars_by_type.Bulk"], 100.0);
+        assert_eq!(
+            param_dict["rail_vehicles.[0].length_meters"],
+            train_config.rail_vehicles[0].length.get::<si::meter>()
+        );
+        assert!(!param_dict.contains_key("train_length_meters"));
+        assert_eq!(
+            TrainConfig::from_param_dict(&train_config, &param_dict).unwrap(),
+            train_config
+        );
+
+        param_dict.insert("n_cars_by_type.Bulk".into(), 50.0);
+        param_dict.insert("rail_vehicles.[0].length_meters".into(), 20.0);
+        let new_config = TrainConfig::from_param_dict(&train_config, &param_dict).unwrap();
+        assert_eq!(new_config.n_cars_by_type["Bulk"], 50);
+        assert_eq!(new_config.rail_vehicles[0].length, 20.0 * uc::M);
+
+        param_dict.insert("train_length_meters".into(), 1.0e3);
+        assert!(TrainConfig::from_param_dict(&train_config, &param_dict).is_err());
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msg_pack_round_trip() {
+        // message pack is the intermediate format of python `to_pydict` and
+        // `from_pydict`
+        let train_config = TrainConfig::valid();
+        assert_eq!(
+            TrainConfig::from_msg_pack(&train_config.to_msg_pack().unwrap(), false).unwrap(),
+            train_config
+        );
+    }
+
+    #[test]
+    fn test_summarize_speed_limit_train_sims() {
+        let mut sim_a = SpeedLimitTrainSim::valid();
+        sim_a.train_id = "a".into();
+        sim_a.set_save_interval(Some(1));
+        sim_a.walk().unwrap();
+        let mut sim_b = sim_a.clone();
+        sim_b.train_id = "b".into();
+        let sims = SpeedLimitTrainSimVec::new(vec![sim_a, sim_b]);
+
+        let df = summarize_speed_limit_train_sims(&sims).unwrap();
+        assert_eq!(df.height(), 2);
+        assert_eq!(df.width(), 6);
+        for col in [
+            "time_trip_seconds",
+            "distance_meters",
+            "energy_fuel_joules",
+            "speed_max_meters_per_second",
+        ] {
+            let vals: Vec<f64> = df
+                .column(col)
+                .unwrap()
+                .f64()
+                .unwrap()
+                .into_no_null_iter()
+                .collect();
+            assert!(vals.iter().all(|val| *val > 0.0), "{col}: {vals:?}");
+            assert_eq!(vals[0], vals[1], "{col}");
+        }
+
+        // sims without history cannot be summarized
+        assert!(
+            summarize_speed_limit_train_sims(&SpeedLimitTrainSimVec::new(vec![
+                SpeedLimitTrainSim::valid()
+            ]))
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_towed_mass_loaded_vs_empty() {
+        let rv_loaded = RailVehicle {
+            car_type: "Bulk".into(),
+            mass_static_base: 25.0 * uc::TON,
+            mass_freight: 100.0 * uc::TON,
+            brake_count: 1,
+            ..Default::default()
+        };
+        let rv_empty = RailVehicle {
+            empty: true,
+            ..rv_loaded.clone()
+        };
+        assert!(almost_eq_uom(
+            &rv_loaded.total_mass(true),
+            &(125.0 * uc::TON),
+            None
+        ));
+        assert!(almost_eq_uom(
+            &rv_loaded.total_mass(false),
+            &(25.0 * uc::TON),
+            None
+        ));
+        assert_eq!(rv_empty.freight_mass(), si::Mass::ZERO);
+        assert!(almost_eq_uom(
+            &rv_empty.total_mass(true),
+            &rv_empty.total_mass(false),
+            None
+        ));
+
+        let n_cars = 10;
+        let towed_mass_static = |rv: RailVehicle| {
+            TrainConfig {
+                rail_vehicles: vec![rv],
+                n_cars_by_type: HashMap::from([("Bulk".into(), n_cars)]),
+                ..TrainConfig::valid()
+            }
+            .make_train_params()
+            .unwrap()
+            .towed_mass_static
+        };
+        let mass_loaded = towed_mass_static(rv_loaded);
+        let mass_empty = towed_mass_static(rv_empty);
+        assert!(almost_eq_uom(
+            &mass_loaded,
+            &(n_cars as f64 * 125.0 * uc::TON),
+            None
+        ));
+        assert!(almost_eq_uom(
+            &(mass_loaded - mass_empty),
+            &(n_cars as f64 * 100.0 * uc::TON),
+            None
+        ));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_speed_limit_train_sim_vec_from_dir() {
+        let tempdir = tempfile::tempdir().unwrap();
+        for train_id in ["b", "a"] {
+            let mut sim = SpeedLimitTrainSim::valid();
+            sim.train_id = train_id.into();
+            sim.to_file(tempdir.path().join(format!("{train_id}.yaml")))
+                .unwrap();
+        }
+        std::fs::write(tempdir.path().join("notes.txt"), "not a sim").unwrap();
+
+        let sims = SpeedLimitTrainSimVec::from_dir(tempdir.path()).unwrap();
+        assert_eq!(sims.0.len(), 2);
+        assert_eq!(
+            sims.0
+                .iter()
+                .map(|s| s.train_id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "b"]
+        );
+
+        assert!(SpeedLimitTrainSimVec::from_dir(tempdir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_build_set_speed_train_sim() {
+        let train_config = TrainConfig {
+            rail_vehicles: vec![RailVehicle {
+                car_type: "Bulk".into(),
+                axle_count: 4,
+                mass_static_base: 25.0 * uc::TON,
+                mass_freight: 100.0 * uc::TON,
+                length: 20.0 * uc::M,
+                brake_count: 1,
+                ..Default::default()
+            }],
+            ..TrainConfig::valid()
+        };
+        let builder = TrainSimBuilder::new(
+            "test".into(),
+            train_config,
+            Consist::default(),
+            Some("A".into()),
+            Some("B".into()),
+            None,
+        );
+        let location_map = LocationMap::from([("A".into(), Vec::new()), ("B".into(), Vec::new())]);
+
+        let slts = builder
+            .make_speed_limit_train_sim(&location_map, None, None, None, None)
+            .unwrap();
+        let sts = builder
+            .build_set_speed_train_sim(SpeedTrace::default(), Some(1), None)
+            .unwrap();
+        assert_eq!(sts.path_tpc(), &slts.path_tpc);
+        assert_eq!(sts.n_cars_by_type, slts.n_cars_by_type);
+        assert_eq!(sts.get_save_interval(), Some(1));
+    }
+
+    #[test]
+    fn test_cd_area_total_drafting() {
+        let rv = RailVehicle {
+            car_type: "Bulk".into(),
+            cd_area: 5.0 * uc::M2,
+            ..Default::default()
+        };
+        let n_cars = 100;
+        let train_config = TrainConfig {
+            rail_vehicles: vec![rv.clone()],
+            n_cars_by_type: HashMap::from([("Bulk".into(), n_cars)]),
+            ..TrainConfig::valid()
+        };
+        let cd_area = train_config.cd_area_total();
+        assert_eq!(cd_area, n_cars as f64 * rv.cd_area);
+
+        let mut train_config_drafting = TrainConfig {
+            rail_vehicles: vec![RailVehicle {
+                drafting_factor: Some(0.6 * uc::R),
+                ..rv.clone()
+            }],
+            ..train_config.clone()
+        };
+        train_config_drafting.init().unwrap();
+        let cd_area_drafting = train_config_drafting.cd_area_total();
+        assert!(cd_area_drafting < cd_area);
+        // lead car sees full drag
+        assert!(almost_eq_uom(
+            &cd_area_drafting,
+            &(rv.cd_area * (1.0 + 0.6 * (n_cars - 1) as f64)),
+            None
+        ));
+
+        // resulting aerodynamic resistance is lower at speed
+        let res_aero = |cd_area: si::Area| {
+            let mut state = TrainState::valid();
+            state
+                .speed
+                .update_unchecked(20.0 * uc::MPS, || format_dbg!())
+                .unwrap();
+            res_kind::aerodynamic::Basic::new(cd_area)
+                .calc_res(&state)
+                .unwrap()
+        };
+        assert!(res_aero(cd_area_drafting) < res_aero(cd_area));
+
+        train_config_drafting.rail_vehicles[0].drafting_factor = Some(1.5 * uc::R);
+        assert!(train_config_drafting.init().is_err());
+    }
+
+    #[test]
+    fn test_missing_car_types() {
+        let train_config = TrainConfig::valid();
+        assert!(train_config.missing_car_types().is_empty());
+
+        let mut n_cars_by_type = train_config.n_cars_by_type.clone();
+        n_cars_by_type.insert("Bogus".into(), 10);
+        let err = TrainConfig::new(
+            train_config.rail_vehicles.clone(),
+            n_cars_by_type,
+            train_config.train_type,
+            None,
+            None,
+            None,
+        )
+        .unwrap_err();
+        let err_msg = err.to_string();
+        assert!(err_msg.contains("`n_cars_by_type` has car types with no matching `car_type`"));
+        assert!(err_msg.contains("Bogus"));
+        assert!(!err_msg.contains("Bulk"));
+    }
+
+    #[test]
+    fn test_from_dataframe() {
+        let rail_vehicles = vec![
+            RailVehicle {
+                car_type: "Bulk".into(),
+                ..Default::default()
+            },
+            RailVehicle {
+                car_type: "Intermodal".into(),
+                ..Default::default()
+            },
+        ];
+        let df = DataFrame::new(vec![
+            Column::new("Bulk".into(), [100_u32, 0]),
+            Column::new("Intermodal".into(), [20_i64, 80]),
+            Column::new("Train_Type".into(), ["Freight", "Intermodal"]),
+            Column::new("Train_Mass_Kilograms".into(), [Some(1e7), None]),
+        ])
+        .unwrap();
+        let train_configs = TrainConfig::from_dataframe(&df, &rail_vehicles).unwrap();
+        assert_eq!(train_configs.len(), 2);
+        assert_eq!(
+            train_configs[0].n_cars_by_type,
+            HashMap::from([("Bulk".into(), 100), ("Intermodal".into(), 20)])
+        );
+        assert_eq!(train_configs[0].train_type, TrainType::Freight);
+        assert_eq!(train_configs[0].train_mass, Some(1e7 * uc::KG));
+        assert_eq!(train_configs[0].train_length, None);
+        assert_eq!(
+            train_configs[1].n_cars_by_type,
+            HashMap::from([("Bulk".into(), 0), ("Intermodal".into(), 80)])
+        );
+        assert_eq!(train_configs[1].train_type, TrainType::Intermodal);
+        assert_eq!(train_configs[1].train_mass, None);
+
+        // car type without a column gets zero cars
+        let df_bulk = DataFrame::new(vec![Column::new("Bulk".into(), [100_u32])]).unwrap();
+        let train_configs = TrainConfig::from_dataframe(&df_bulk, &rail_vehicles).unwrap();
+        assert_eq!(
+            train_configs[0].n_cars_by_type,
+            HashMap::from([("Bulk".into(), 100), ("Intermodal".into(), 0)])
+        );
+        train_configs[0].make_train_params().unwrap();
+
+        // unrecognized column, e.g. a misspelled car type
+        let mut df_bad = df.clone();
+        df_bad.rename("Intermodal", "Intermodel".into()).unwrap();
+        assert!(TrainConfig::from_dataframe(&df_bad, &rail_vehicles).is_err());
+        // non-integer car count
+        let df_bad = DataFrame::new(vec![Column::new("Bulk".into(), [100.0, 50.0])]).unwrap();
+        assert!(TrainConfig::from_dataframe(&df_bad, &rail_vehicles).is_err());
+        // negative car count
+        let df_bad = DataFrame::new(vec![Column::new("Bulk".into(), [-1_i64])]).unwrap();
+        assert!(TrainConfig::from_dataframe(&df_bad, &rail_vehicles).is_err());
+        // no car count columns
+        let df_bad = DataFrame::new(vec![Column::new("Train_Type".into(), ["Freight"])]).unwrap();
+        assert!(TrainConfig::from_dataframe(&df_bad, &rail_vehicles).is_err());
+    }
+}
@@ -3,6 +3,9 @@
 pub(crate) use crate::imports::*;
 
 pub(crate) use super::resistance::{method, ResMethod, TrainRes};
-pub(crate) use super::{set_link_and_offset, TrainState, TrainStateHistoryVec};
+pub(crate) use super::{
+    set_link_and_offset, HistorySink, TractionLimitCause, TrainState, TrainStateHistoryVec,
+    GRADE_LOOKAHEAD_MILES,
+};
 pub(crate) use crate::consist::{Consist, LocoTrait};
 pub(crate) use crate::track::{Link, LinkIdx, PathTpc, TrainParams, TrainType};
@@ -128,6 +128,10 @@ pub struct TrainState {
     pub grade_front: TrackedState<si::Ratio>,
     /// Grade at back of train of train if strap method is used
     pub grade_back: TrackedState<si::Ratio>,
+    /// Length-weighted mean grade between back and front of train, i.e. net
+    /// elevation change over train length
+    #[serde(default)]
+    pub grade_mean: TrackedState<si::Ratio>,
     /// Elevation at front of train
     pub elev_front: TrackedState<si::Length>,
     /// Elevation at back of train
@@ -145,6 +149,70 @@ pub struct TrainState {
     pub energy_whl_out_pos: TrackedState<si::Energy>,
     /// Energy out during negative traction (positive value means negative traction)
     pub energy_whl_out_neg: TrackedState<si::Energy>,
+    /// Braking power provided by the locomotive consist, i.e. dynamic and
+    /// regenerative braking (positive value means braking)
+    #[serde(default)]
+    pub pwr_dyn_brake: TrackedState<si::Power>,
+    /// Braking power provided by friction brakes, which make up any deficit
+    /// beyond consist dynamic braking capability (positive value means braking)
+    #[serde(default)]
+    pub pwr_fric_brake: TrackedState<si::Power>,
+    /// What limited positive traction, as [TractionLimitCause] cast to `u8`
+    #[serde(default)]
+    pub traction_limit_cause: TrackedState<u8>,
+    /// Net force applied by consist and friction brakes to control train
+    /// speed, i.e. total force less train resistance
+    #[serde(default)]
+    pub force_applied: TrackedState<si::Force>,
+}
+
+/// What limited positive traction in a time step, stored in
+/// [TrainState::traction_limit_cause] as `u8`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum TractionLimitCause {
+    /// Traction is not limited, e.g. train is following speed target,
+    /// coasting, or braking
+    Unlimited = 0,
+    /// Limited by consist tractive power capability
+    Power = 1,
+    /// Limited by adhesion, i.e. consist max tractive force
+    Adhesion = 2,
+    /// Max available traction does not exceed train resistance, so train
+    /// cannot accelerate
+    Resistance = 3,
+}
+
+impl From<TractionLimitCause> for u8 {
+    fn from(cause: TractionLimitCause) -> Self {
+        cause as u8
+    }
+}
+
+impl TryFrom<u8> for TractionLimitCause {
+    type Error = anyhow::Error;
+    fn try_from(value: u8) -> anyhow::Result<Self> {
+        match value {
+            0 => Ok(Self::Unlimited),
+            1 => Ok(Self::Power),
+            2 => Ok(Self::Adhesion),
+            3 => Ok(Self::Resistance),
+            _ => bail!("{}\nInvalid traction limit cause: {}", format_dbg!(), value),
+        }
+    }
+}
+
+#[pyo3_api]
+impl TrainState {
+    #[getter("kinetic_energy_joules")]
+    fn get_kinetic_energy_py(&self) -> anyhow::Result<f64> {
+        Ok(self.kinetic_energy()?.get::<si::joule>())
+    }
+
+    #[getter("res_net_newtons")]
+    fn get_res_net_py(&self) -> anyhow::Result<f64> {
+        Ok(self.res_net()?.get::<si::newton>())
+    }
 }
 
 impl Init for TrainState {}
@@ -173,6 +241,7 @@ impl Default for TrainState {
             energy_whl_out: Default::default(),
             grade_front: Default::default(),
             grade_back: Default::default(),
+            grade_mean: Default::default(),
             speed_target: Default::default(),
             weight_static: Default::default(),
             res_rolling: Default::default(),
@@ -186,6 +255,10 @@ impl Default for TrainState {
             pwr_whl_out: Default::default(),
             energy_whl_out_pos: Default::default(),
             energy_whl_out_neg: Default::default(),
+            pwr_dyn_brake: Default::default(),
+            pwr_fric_brake: Default::default(),
+            traction_limit_cause: Default::default(),
+            force_applied: Default::default(),
         }
     }
 }
@@ -245,6 +318,9 @@ impl TrainState {
         }
     }
 
+    /// Net train resistance, i.e. sum of [Self::res_rolling],
+    /// [Self::res_bearing], [Self::res_davis_b], [Self::res_aero],
+    /// [Self::res_grade], and [Self::res_curve]
     pub fn res_net(&self) -> anyhow::Result<si::Force> {
         Ok(*self.res_rolling.get_fresh(|| format_dbg!())?
             + *self.res_bearing.get_fresh(|| format_dbg!())?
@@ -254,6 +330,35 @@ impl TrainState {
             + *self.res_curve.get_fresh(|| format_dbg!())?)
     }
 
+    /// Returns time, link, and offset of the front of the train, for adding
+    /// location context to errors
+    pub fn loc_summary(&self) -> anyhow::Result<String> {
+        Ok(format!(
+            "{}
+time: {} s
+link_idx_front: {}
+offset: {} m",
+            format_dbg!(),
+            self.time
+                .get_unchecked(|| format_dbg!())?
+                .get::<si::second>()
+                .format_eng(Some(5)),
+            self.link_idx_front.get_unchecked(|| format_dbg!())?,
+            self.offset
+                .get_unchecked(|| format_dbg!())?
+                .get::<si::meter>()
+                .format_eng(Some(5)),
+        ))
+    }
+
+    /// Adds [Self::loc_summary] context to the error in `result`, if any
+    pub fn with_loc_context<T>(&self, result: anyhow::Result<T>) -> anyhow::Result<T> {
+        match result {
+            Ok(val) => Ok(val),
+            Err(err) => Err(err.context(self.loc_summary()?)),
+        }
+    }
+
     /// All base, freight, and rotational mass
     pub fn mass_compound(&self) -> anyhow::Result<si::Mass> {
         Ok(self
@@ -262,6 +367,13 @@ impl TrainState {
             .with_context(|| format!("{}\nExpected `Some`", format_dbg!()))? // extract option
             + *self.mass_rot.get_unchecked(|| format_dbg!())?)
     }
+
+    /// Kinetic energy at current speed, including effective rotational mass
+    /// via [Self::mass_compound]
+    pub fn kinetic_energy(&self) -> anyhow::Result<si::Energy> {
+        let speed = *self.speed.get_unchecked(|| format_dbg!())?;
+        Ok(0.5 * self.mass_compound().with_context(|| format_dbg!())? * speed * speed)
+    }
 }
 
 impl Valid for TrainState {
@@ -308,6 +420,10 @@ impl ObjState for TrainState {
     }
 }
 
+/// Distance in front of the train, in miles, over which grade is averaged for
+/// anticipatory powertrain controls
+pub const GRADE_LOOKAHEAD_MILES: f64 = 2.0;
+
 /// Sets `link_idx_front` and `offset_in_link` based on `state` and `path_tpc`
 ///
 /// Assumes that `offset` in `link_points()` is monotically increasing, which may not always be true.
@@ -322,7 +438,8 @@ pub fn set_link_and_offset(state: &mut TrainState, path_tpc: &PathTpc) -> anyhow
         .position(|&lp| lp.offset > offset)
         // if None, assume that it's the last element
         .unwrap_or_else(|| path_tpc.link_points().len())
-        - 1;
+        .checked_sub(1)
+        .with_context(|| format_dbg!("`offset` is before the start of `path_tpc`"))?;
     let link_point = path_tpc
         .link_points()
         .get(idx_curr_link)
@@ -343,7 +460,8 @@ pub fn set_link_and_offset(state: &mut TrainState, path_tpc: &PathTpc) -> anyhow
         .position(|&lp| lp.offset > offset_back)
         // if None, assume that it's the last element
         .unwrap_or_else(|| path_tpc.link_points().len())
-        - 1;
+        .checked_sub(1)
+        .with_context(|| format_dbg!("`offset_back` is before the start of `path_tpc`"))?;
     state.link_idx_back.update(
         path_tpc
             .link_points()
@@ -354,5 +472,105 @@ pub fn set_link_and_offset(state: &mut TrainState, path_tpc: &PathTpc) -> anyhow
         || format_dbg!(),
     )?;
 
+    // length-weighted mean grade under train
+    let grades = path_tpc.grades();
+    ensure!(
+        !grades.is_empty(),
+        "{}\n`path_tpc` has no grades",
+        format_dbg!()
+    );
+    let grade_mean = if offset > offset_back {
+        (path_tpc.elev_at(offset) - path_tpc.elev_at(offset_back)) / (offset - offset_back)
+    } else {
+        grades[grades
+            .partition_point(|grade| grade.offset <= offset)
+            .max(1)
+            - 1]
+        .res_coeff
+    };
+    state.grade_mean.update(grade_mean, || format_dbg!())?;
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kinetic_energy() {
+        let mut state = TrainState::valid();
+        assert_eq!(state.kinetic_energy().unwrap(), si::Energy::ZERO);
+
+        let speed = 20.0 * uc::MPS;
+        state
+            .speed
+            .update_unchecked(speed, || format_dbg!())
+            .unwrap();
+        // 0.5 * (6000 ton static + 200 ton rotational) * (20 m/s)^2
+        let ke_expected = 0.5 * 6_200.0 * 9.071_847e2 * 400.0 * uc::J;
+        assert!(almost_eq_uom(
+            &state.kinetic_energy().unwrap(),
+            &ke_expected,
+            None
+        ));
+        assert!(state.kinetic_energy().unwrap() > 0.5 * 6_000.0 * uc::TON * speed * speed);
+    }
+
+    #[test]
+    fn test_set_link_and_offset_without_grades() {
+        let mut path_tpc = serde_yaml::to_value(PathTpc::valid()).unwrap();
+        path_tpc["grades"] = serde_yaml::Value::Sequence(vec![]);
+        let path_tpc: PathTpc = serde_yaml::from_value(path_tpc).unwrap();
+
+        let mut state = TrainState::valid();
+        state.check_and_reset(|| format_dbg!()).unwrap();
+        state
+            .offset_back
+            .update(si::Length::ZERO, || format_dbg!())
+            .unwrap();
+        assert!(set_link_and_offset(&mut state, &path_tpc)
+            .unwrap_err()
+            .to_string()
+            .contains("`path_tpc` has no grades"));
+    }
+
+    #[test]
+    fn test_res_breakdown_in_history() {
+        let mut sim = crate::train::SpeedLimitTrainSim::valid();
+        sim.set_save_interval(Some(1));
+        sim.walk().unwrap();
+        let hist = &sim.history;
+        let get = |vals: &[TrackedState<si::Force>], i: usize| -> si::Force {
+            *vals[i].get_unchecked(|| format_dbg!()).unwrap()
+        };
+
+        let mut n_checked = 0;
+        for i in 1..hist.len() {
+            // `pwr_res` is `res_net` times mean speed over the time step
+            let speed_avg = 0.5
+                * (*hist.speed[i - 1].get_unchecked(|| format_dbg!()).unwrap()
+                    + *hist.speed[i].get_unchecked(|| format_dbg!()).unwrap());
+            if speed_avg < uc::MPS {
+                continue;
+            }
+            let res_net = *hist.pwr_res[i].get_unchecked(|| format_dbg!()).unwrap() / speed_avg;
+            let res_sum = get(&hist.res_rolling, i)
+                + get(&hist.res_bearing, i)
+                + get(&hist.res_davis_b, i)
+                + get(&hist.res_aero, i)
+                + get(&hist.res_curve, i);
+            assert!(
+                almost_eq_uom(&res_sum, &(res_net - get(&hist.res_grade, i)), None),
+                "step {i}: {res_sum:?} != {:?}",
+                res_net - get(&hist.res_grade, i)
+            );
+            n_checked += 1;
+        }
+        assert!(n_checked > 0);
+        assert!(hist
+            .res_aero
+            .iter()
+            .any(|res| *res.get_unchecked(|| format_dbg!()).unwrap() > si::Force::ZERO));
+    }
+}
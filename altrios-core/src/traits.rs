@@ -182,10 +182,46 @@ pub trait Mass {
 /// Super trait to ensure that related traits are implemented together
 pub trait StateMethods: SetCumulative + SaveState + Step + CheckAndResetState {}
 
+/// Numerical integration method used to accumulate `energy_*` fields from
+/// matching `pwr_*` fields
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "pyo3", pyclass(eq))]
+pub enum IntegrationMethod {
+    /// Right-rectangle (backward) rule, i.e. `pwr * dt` using power at end of
+    /// time step
+    #[default]
+    Rectangle,
+    /// Trapezoidal rule, i.e. `0.5 * (pwr_prev + pwr) * dt`, which is more
+    /// accurate for variable time step traces
+    Trapezoid,
+}
+
 /// Trait for setting cumulative values based on rate values
 pub trait SetCumulative {
-    /// Sets cumulative values based on rate values
-    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()>;
+    /// Sets cumulative values based on rate values using
+    /// [IntegrationMethod::Rectangle]
+    fn set_cumulative<F: Fn() -> String>(&mut self, dt: si::Time, loc: F) -> anyhow::Result<()> {
+        self.set_cumulative_with(dt, IntegrationMethod::Rectangle, loc)
+    }
+
+    /// Sets cumulative values based on rate values using `integration_method`
+    fn set_cumulative_with<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()>;
+
+    /// For [IntegrationMethod::Trapezoid], adds the contribution of rate values
+    /// from the end of the previous time step, which [Self::set_cumulative_with]
+    /// completes at the end of the current time step.  Must be called after
+    /// states are reset and before any rate values are updated.
+    fn set_cumulative_prev<F: Fn() -> String>(
+        &mut self,
+        dt: si::Time,
+        integration_method: IntegrationMethod,
+        loc: F,
+    ) -> anyhow::Result<()>;
 }
 
 /// Provides method that saves `self.state` to `self.history` and propagates to any fields with
@@ -218,3 +254,52 @@ pub trait HistoryMethods: SaveState {
     /// Remove all history
     fn clear(&mut self);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default, Clone, PartialEq, SetCumulative)]
+    struct RampState {
+        pwr_ramp: TrackedState<si::Power>,
+        energy_ramp: TrackedState<si::Energy>,
+    }
+
+    #[test]
+    fn test_integration_method_on_ramp() {
+        // power ramps linearly with time over a variable time step trace
+        let pwr_rate = 1e3 * uc::W / uc::S;
+        let times: Vec<si::Time> = [0.0, 1.0, 3.0, 4.0, 7.0, 8.0, 10.0]
+            .iter()
+            .map(|t| *t * uc::S)
+            .collect();
+        let energy_analytic = 0.5 * pwr_rate * *times.last().unwrap() * *times.last().unwrap();
+
+        let integrate = |integration_method: IntegrationMethod| -> si::Energy {
+            let mut state = RampState::default();
+            for w in times.windows(2) {
+                state.pwr_ramp.mark_stale();
+                state.energy_ramp.mark_stale();
+                state
+                    .set_cumulative_prev(w[1] - w[0], integration_method, || format_dbg!())
+                    .unwrap();
+                state
+                    .pwr_ramp
+                    .update(pwr_rate * w[1], || format_dbg!())
+                    .unwrap();
+                state
+                    .set_cumulative_with(w[1] - w[0], integration_method, || format_dbg!())
+                    .unwrap();
+            }
+            *state.energy_ramp.get_fresh(|| format_dbg!()).unwrap()
+        };
+
+        let energy_trapz = integrate(IntegrationMethod::Trapezoid);
+        let energy_rect = integrate(IntegrationMethod::Rectangle);
+        // trapezoidal rule is exact for a linear ramp
+        assert!(almost_eq_uom(&energy_trapz, &energy_analytic, None));
+        // rectangle rule with end-of-step power overestimates a rising ramp
+        assert!(energy_rect > energy_analytic);
+        assert!((energy_rect - energy_analytic) > 10.0 * (energy_trapz - energy_analytic).abs());
+    }
+}
@@ -9,6 +9,49 @@ pub trait Init {
     }
 }
 
+/// Returns the serialization format of `filepath` and whether the file is
+/// gzip-compressed.  For a path ending in `.gz`, the format is taken from the
+/// inner extension, e.g. `("yaml", true)` for `network.yaml.gz`.
+pub fn file_format(filepath: &Path) -> Result<(&str, bool), Error> {
+    let ext_err = || Error::SerdeError(format!("File extension could not be parsed: {filepath:?}"));
+    let extension = filepath
+        .extension()
+        .and_then(OsStr::to_str)
+        .ok_or_else(ext_err)?;
+    if extension.eq_ignore_ascii_case("gz") {
+        let extension_inner = filepath
+            .file_stem()
+            .map(Path::new)
+            .and_then(Path::extension)
+            .and_then(OsStr::to_str)
+            .ok_or_else(ext_err)?;
+        Ok((extension_inner, true))
+    } else {
+        Ok((extension, false))
+    }
+}
+
+/// Opens `filepath` for reading, transparently decompressing gzip-compressed
+/// (i.e. `.gz`) files
+pub fn open_file_reader(filepath: &Path) -> Result<Box<dyn std::io::Read>, Error> {
+    let (_, is_gz) = file_format(filepath)?;
+    let file = File::open(filepath).map_err(|err| {
+        Error::SerdeError(format!(
+            "{err}\n{}",
+            if !filepath.exists() {
+                format!("File not found: {filepath:?}")
+            } else {
+                format!("Could not open file: {filepath:?}")
+            }
+        ))
+    })?;
+    Ok(if is_gz {
+        Box::new(flate2::read::GzDecoder::new(file))
+    } else {
+        Box::new(file)
+    })
+}
+
 pub trait SerdeAPI: Serialize + for<'a> Deserialize<'a> + Init {
     const ACCEPTED_BYTE_FORMATS: &'static [&'static str] = &[
         #[cfg(feature = "yaml")]
@@ -88,7 +131,8 @@ pub trait SerdeAPI: Serialize + for<'a> Deserialize<'a> + Init {
     }
 
     /// Write (serialize) an object to a file.
-    /// Supported file extensions are listed in [`ACCEPTED_BYTE_FORMATS`](`SerdeAPI::ACCEPTED_BYTE_FORMATS`).
+    /// Supported file extensions are listed in [`ACCEPTED_BYTE_FORMATS`](`SerdeAPI::ACCEPTED_BYTE_FORMATS`),
+    /// optionally followed by `.gz` for gzip compression (e.g. `network.yaml.gz`).
     /// Creates a new file if it does not already exist, otherwise truncates the existing file.
     ///
     /// # Arguments
@@ -97,15 +141,21 @@ pub trait SerdeAPI: Serialize + for<'a> Deserialize<'a> + Init {
     ///
     fn to_file<P: AsRef<Path>>(&self, filepath: P) -> anyhow::Result<()> {
         let filepath = filepath.as_ref();
-        let extension = filepath
-            .extension()
-            .and_then(OsStr::to_str)
-            .with_context(|| format!("File extension could not be parsed: {filepath:?}"))?;
-        self.to_writer(File::create(filepath)?, extension)
+        let (extension, is_gz) = file_format(filepath)?;
+        let file = File::create(filepath)?;
+        if is_gz {
+            let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            self.to_writer(&mut encoder, extension)?;
+            encoder.finish()?;
+            Ok(())
+        } else {
+            self.to_writer(file, extension)
+        }
     }
 
     /// Read (deserialize) an object from a file.
-    /// Supported file extensions are listed in [`ACCEPTED_BYTE_FORMATS`](`SerdeAPI::ACCEPTED_BYTE_FORMATS`).
+    /// Supported file extensions are listed in [`ACCEPTED_BYTE_FORMATS`](`SerdeAPI::ACCEPTED_BYTE_FORMATS`),
+    /// optionally followed by `.gz` for gzip-compressed files (e.g. `network.yaml.gz`).
     ///
     /// # Arguments:
     ///
@@ -113,23 +163,9 @@ pub trait SerdeAPI: Serialize + for<'a> Deserialize<'a> + Init {
     ///
     fn from_file<P: AsRef<Path>>(filepath: P, skip_init: bool) -> Result<Self, Error> {
         let filepath = filepath.as_ref();
-        let extension = filepath
-            .extension()
-            .and_then(OsStr::to_str)
-            .ok_or_else(|| {
-                Error::SerdeError(format!("File extension could not be parsed: {filepath:?}"))
-            })?;
-        let mut file = File::open(filepath).map_err(|err| {
-            Error::SerdeError(format!(
-                "{err}\n{}",
-                if !filepath.exists() {
-                    format!("File not found: {filepath:?}")
-                } else {
-                    format!("Could not open file: {filepath:?}")
-                }
-            ))
-        })?;
-        Self::from_reader(&mut file, extension, skip_init)
+        let (extension, _) = file_format(filepath)?;
+        let mut rdr = open_file_reader(filepath)?;
+        Self::from_reader(&mut rdr, extension, skip_init)
     }
 
     /// Write (serialize) an object into anything that implements [`std::io::Write`]
@@ -55,13 +55,24 @@ unit_const!(LBF, Force, 4.448_222_E0);
 unit_const!(W, Power, 1.0);
 unit_const!(KW, Power, 1.0E3);
 unit_const!(MW, Power, 1.0E6);
+unit_const!(
+    /// mechanical horsepower
+    HP,
+    Power,
+    7.456_998_715_822_702_E2
+);
 unit_const!(J, Energy, 1.0);
 
+unit_const!(V, ElectricPotential, 1.0);
+unit_const!(A, ElectricCurrent, 1.0);
+unit_const!(OHM, ElectricalResistance, 1.0);
+
 unit_const!(KGPM3, MassDensity, 1.0);
 
 unit_const!(MPS, Velocity, 1.0);
 unit_const!(MPH, Velocity, 4.470_4_E-1);
 unit_const!(MPS2, Acceleration, 1.0);
+unit_const!(MPS3, Jerk, 1.0);
 unit_const!(SPM, InverseVelocity, 1.0);
 unit_const!(SPEED_DIFF_JOIN, Velocity, 4.470_4_E-2);
 
@@ -76,10 +87,26 @@ unit_const!(
     9.801_548_494_963_14
 );
 
+unit_const!(
+    /// Effective gauge (i.e. distance between rail head centerlines) of standard gauge track,
+    /// used for relating superelevation to bank angle
+    GAUGE_EFF,
+    Length,
+    1.511_3
+);
+
+unit_const!(
+    /// Mean radius of the Earth, used for great-circle distance
+    EARTH_RADIUS,
+    Length,
+    6.371_008_8_E6
+);
+
 pub const CELSIUS_TO_KELVIN: f64 = 273.15;
 unit_const!(KELVIN, ThermodynamicTemperature, 1.0);
 unit_const!(KELVIN_INT, TemperatureInterval, 1.0);
 unit_const!(M2PS2K, SpecificHeatCapacity, 1.0);
+unit_const!(JPK, HeatCapacity, 1.0);
 unit_const!(PASCAL, Pressure, 1.0);
 
 // TODO: make this variable
@@ -0,0 +1,84 @@
+//! Conversions between US customary and SI units as plain floats, built on the
+//! constants in [crate::uc], for use where [crate::si] quantities are not
+//! available (e.g. in Python)
+
+use crate::imports::*;
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts miles per hour to meters per second
+pub fn mph_to_mps(mph: f64) -> f64 {
+    (mph * uc::MPH).get::<si::meter_per_second>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts meters per second to miles per hour
+pub fn mps_to_mph(mps: f64) -> f64 {
+    mps / uc::MPH.get::<si::meter_per_second>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts short tons to kilograms
+pub fn ton_to_kg(ton: f64) -> f64 {
+    (ton * uc::TON).get::<si::kilogram>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts kilograms to short tons
+pub fn kg_to_ton(kg: f64) -> f64 {
+    kg / uc::TON.get::<si::kilogram>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts mechanical horsepower to watts
+pub fn hp_to_watt(hp: f64) -> f64 {
+    (hp * uc::HP).get::<si::watt>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts watts to mechanical horsepower
+pub fn watt_to_hp(watt: f64) -> f64 {
+    watt / uc::HP.get::<si::watt>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts pounds-force to newtons
+pub fn lbf_to_newton(lbf: f64) -> f64 {
+    (lbf * uc::LBF).get::<si::newton>()
+}
+
+#[cfg_attr(feature = "pyo3", pyfunction)]
+/// Converts newtons to pounds-force
+pub fn newton_to_lbf(newton: f64) -> f64 {
+    newton / uc::LBF.get::<si::newton>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversion_factors() {
+        assert!(almost_eq(mph_to_mps(1.0), 0.44704, None));
+        assert!(almost_eq(ton_to_kg(1.0), 907.1847, None));
+        assert!(almost_eq(hp_to_watt(1.0), 745.6999, Some(1e-6)));
+        assert!(almost_eq(lbf_to_newton(1.0), 4.448222, None));
+        // `uc` constants agree with `uom` units
+        assert!(almost_eq(
+            mph_to_mps(1.0),
+            si::Velocity::new::<si::mile_per_hour>(1.0).get::<si::meter_per_second>(),
+            None
+        ));
+        assert!(almost_eq(
+            hp_to_watt(1.0),
+            si::Power::new::<si::horsepower>(1.0).get::<si::watt>(),
+            Some(1e-6)
+        ));
+
+        for val in [0.0, 1.0, 60.0, 4400.0] {
+            assert!(almost_eq(mps_to_mph(mph_to_mps(val)), val, None));
+            assert!(almost_eq(kg_to_ton(ton_to_kg(val)), val, None));
+            assert!(almost_eq(watt_to_hp(hp_to_watt(val)), val, None));
+            assert!(almost_eq(newton_to_lbf(lbf_to_newton(val)), val, None));
+        }
+    }
+}
@@ -11,6 +11,11 @@ pub fn return_true() -> bool {
     true
 }
 
+/// returns ratio of 1.0 for use with serde default
+pub fn return_one_ratio() -> si::Ratio {
+    uc::R
+}
+
 /// Function for sorting a slice that implements `std::cmp::PartialOrd`.
 /// Remove this once is_sorted is stabilized in std
 pub fn is_sorted<T: std::cmp::PartialOrd>(data: &[T]) -> bool {
@@ -170,6 +175,108 @@ pub fn almost_le(val1: f64, val2: f64, epsilon: Option<f64>) -> bool {
     val1 < val2 * (1.0 + epsilon) || val1 < val2 + epsilon
 }
 
+/// Formats `mass` in kilograms for concise summaries, e.g. Python `__repr__`,
+/// or `None` if mass is not set or cannot be derived
+pub fn mass_repr(mass: anyhow::Result<Option<si::Mass>>) -> String {
+    match mass {
+        Ok(Some(mass)) => format!("{:.0}", mass.get::<si::kilogram>()),
+        _ => "None".into(),
+    }
+}
+
+/// Recursively collects all numeric values in `value` into `param_dict`, with
+/// keys formed by joining nested field names and `[idx]`-style list indices
+/// with `"."` (e.g. `rail_vehicles.[0].length_meters`).  Because unit names are
+/// baked into serialized field names, keys carry their units.
+/// # Arguments
+/// - `value`: serialized object
+/// - `prefix`: key of `value` within the top-level object -- empty for the top level
+/// - `param_dict`: map into which parameters are collected
+#[cfg(feature = "json")]
+pub fn flatten_numeric_params(
+    value: &serde_json::Value,
+    prefix: &str,
+    param_dict: &mut HashMap<String, f64>,
+) {
+    let join = |key: String| -> String {
+        if prefix.is_empty() {
+            key
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+    match value {
+        serde_json::Value::Number(num) => {
+            if let Some(num) = num.as_f64() {
+                param_dict.insert(prefix.to_string(), num);
+            }
+        }
+        serde_json::Value::Array(arr) => {
+            for (idx, val) in arr.iter().enumerate() {
+                flatten_numeric_params(val, &join(format!("[{idx}]")), param_dict);
+            }
+        }
+        serde_json::Value::Object(obj) => {
+            for (key, val) in obj {
+                flatten_numeric_params(val, &join(key.clone()), param_dict);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Sets the existing numeric value at `key`, formatted as in
+/// [flatten_numeric_params], to `new_val`, preserving integer types.
+/// # Arguments
+/// - `value`: serialized object
+/// - `key`: flattened key of numeric value to be set
+/// - `new_val`: new value
+#[cfg(feature = "json")]
+pub fn set_numeric_param(
+    value: &mut serde_json::Value,
+    key: &str,
+    new_val: f64,
+) -> anyhow::Result<()> {
+    let mut target = value;
+    for seg in key.split('.') {
+        target = match seg
+            .strip_prefix('[')
+            .and_then(|seg| seg.strip_suffix(']'))
+            .and_then(|idx| idx.parse::<usize>().ok())
+        {
+            Some(idx) => target.get_mut(idx),
+            None => target.get_mut(seg),
+        }
+        .with_context(|| format!("{}\n`{key}` not found at `{seg}`", format_dbg!()))?;
+    }
+    let (is_f64, is_u64) = match &*target {
+        serde_json::Value::Number(num) => (num.is_f64(), num.is_u64()),
+        _ => bail!("{}\n`{key}` is not a numeric parameter", format_dbg!()),
+    };
+    *target = if is_f64 {
+        serde_json::Number::from_f64(new_val)
+            .with_context(|| format!("{}\nInvalid value for `{key}`: {new_val}", format_dbg!()))?
+            .into()
+    } else {
+        ensure!(
+            new_val.fract() == 0.0,
+            "{}\n`{key}` must be an integer, got {new_val}",
+            format_dbg!()
+        );
+        if is_u64 {
+            ensure!(
+                new_val >= 0.0,
+                "{}\n`{key}` must be non-negative, got {new_val}",
+                format_dbg!()
+            );
+            (new_val as u64).into()
+        } else {
+            (new_val as i64).into()
+        }
+    };
+    Ok(())
+}
+
 make_uom_cmp_fn!(almost_eq);
 make_uom_cmp_fn!(almost_gt);
 make_uom_cmp_fn!(almost_lt);
@@ -402,6 +509,31 @@ mod tests {
         assert!(almost_le(1e9, 1e9 * (1.0 + 1e-7), None));
     }
 
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_flatten_and_set_numeric_params() {
+        let mut value = serde_json::json!({
+            "name": "test",
+            "mass_kilograms": 1.5e3,
+            "count": 2,
+            "nested": {"vals_meters": [1.0, 2.0]}
+        });
+        let mut param_dict = HashMap::new();
+        flatten_numeric_params(&value, "", &mut param_dict);
+        assert_eq!(param_dict.len(), 4);
+        assert_eq!(param_dict["mass_kilograms"], 1.5e3);
+        assert_eq!(param_dict["count"], 2.0);
+        assert_eq!(param_dict["nested.vals_meters.[1]"], 2.0);
+
+        set_numeric_param(&mut value, "nested.vals_meters.[1]", 3.0).unwrap();
+        set_numeric_param(&mut value, "count", 4.0).unwrap();
+        assert_eq!(value["nested"]["vals_meters"][1], 3.0);
+        assert!(value["count"].is_u64());
+        assert!(set_numeric_param(&mut value, "count", 4.5).is_err());
+        assert!(set_numeric_param(&mut value, "name", 1.0).is_err());
+        assert!(set_numeric_param(&mut value, "missing", 1.0).is_err());
+    }
+
     #[test]
     fn test_almost_lt_large() {
         assert!(!almost_lt(1e9 * (1.0 + 1e-9), 1e9, None));
@@ -172,6 +172,18 @@ impl<T: std::fmt::Debug + Clone + PartialEq + Default + std::ops::AddAssign> Tra
         self.1 = StateStatus::Fresh;
         Ok(())
     }
+
+    /// Add to the value of the tracked state after verifying that it has not
+    /// already been updated, leaving it [StateStatus::Stale] so that it can
+    /// still be updated once in the current time step
+    /// # Arguments
+    /// - `value`: value to add
+    /// - `loc`: closure that returns file and line number where called
+    pub fn increment_stale<F: Fn() -> String>(&mut self, value: T, loc: F) -> anyhow::Result<()> {
+        self.ensure_stale(|| format!("{}\n{}", loc(), format_dbg!()))?;
+        self.0 += value;
+        Ok(())
+    }
 }
 
 // Custom serialization
@@ -227,6 +239,17 @@ mod test_tracked_state {
         pwr.get_stale(|| format_dbg!()).unwrap();
     }
 
+    #[test]
+    fn test_increment_stale() {
+        let mut energy = TrackedState::new(uc::J * 5.0);
+        energy.mark_stale();
+        energy
+            .increment_stale(uc::J * 2.0, || format_dbg!())
+            .unwrap();
+        energy.increment(uc::J * 3.0, || format_dbg!()).unwrap();
+        assert_eq!(*energy.get_fresh(|| format_dbg!()).unwrap(), uc::J * 10.0);
+    }
+
     #[test]
     #[should_panic]
     fn test_get_stale_fail() {
@@ -16,13 +16,21 @@ pub use pyo3_polars::PyDataFrame;
 
 #[pymodule]
 fn altrios_pyo3(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
+    m.add_class::<AxleLoadTransfer>()?;
     m.add_class::<BatteryElectricLoco>()?;
     m.add_class::<Consist>()?;
     m.add_class::<ConsistSimulation>()?;
+    m.add_class::<ConsistSimulationVec>()?;
     m.add_class::<ConsistState>()?;
     m.add_class::<ConsistStateHistoryVec>()?;
     m.add_class::<ConventionalLoco>()?;
+    m.add_class::<CurveComfort>()?;
+    m.add_class::<CurveSpeedModel>()?;
+    m.add_class::<DispatchObjective>()?;
+    m.add_class::<DispatchResult>()?;
     m.add_class::<DummyLoco>()?;
+    m.add_class::<DynBrakeThermal>()?;
+    m.add_class::<EcoDriveConfig>()?;
     m.add_class::<ElectricDrivetrain>()?;
     m.add_class::<ElectricDrivetrainState>()?;
     m.add_class::<ElectricDrivetrainStateHistoryVec>()?;
@@ -31,12 +39,15 @@ fn altrios_pyo3(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<FuelConverter>()?;
     m.add_class::<FuelConverterState>()?;
     m.add_class::<FuelConverterStateHistoryVec>()?;
+    m.add_class::<FuelConverterThermal>()?;
+    m.add_class::<FuelType>()?;
     m.add_class::<Generator>()?;
     m.add_class::<GeneratorState>()?;
     m.add_class::<GeneratorStateHistoryVec>()?;
     m.add_class::<Heading>()?;
     m.add_class::<HybridLoco>()?;
     m.add_class::<InitTrainState>()?;
+    m.add_class::<IntegrationMethod>()?;
     m.add_class::<Link>()?;
     m.add_class::<LinkIdx>()?;
     m.add_class::<LinkIdxTime>()?;
@@ -48,25 +59,30 @@ fn altrios_pyo3(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<LocomotiveSimulation>()?;
     m.add_class::<LocomotiveState>()?;
     m.add_class::<LocomotiveStateHistoryVec>()?;
+    m.add_class::<Meet>()?;
     m.add_class::<Network>()?;
     m.add_class::<PathTpc>()?;
     m.add_class::<PowerTrace>()?;
     m.add_class::<RESGreedyWithDynamicBuffers>()?;
     m.add_class::<RESGreedyWithDynamicBuffersBEL>()?;
     m.add_class::<RailVehicle>()?;
+    m.add_class::<ResVoltageModel>()?;
     m.add_class::<ReversibleEnergyStorage>()?;
     m.add_class::<ReversibleEnergyStorageState>()?;
     m.add_class::<ReversibleEnergyStorageStateHistoryVec>()?;
     m.add_class::<SavedSim>()?;
     m.add_class::<SetSpeedTrainSim>()?;
+    m.add_class::<SimCheckpoint>()?;
     m.add_class::<SpeedLimitTrainSim>()?;
     m.add_class::<SpeedLimitTrainSimVec>()?;
     m.add_class::<SpeedSet>()?;
     m.add_class::<SpeedTrace>()?;
+    m.add_class::<SpeedViolationPolicy>()?;
     m.add_class::<TemperatureTraceBuilder>()?;
     m.add_class::<TemperatureTrace>()?;
     m.add_class::<TimedLinkPath>()?;
     m.add_class::<TrainConfig>()?;
+    m.add_class::<TrainDispatchSummary>()?;
     m.add_class::<TrainParams>()?;
     m.add_class::<TrainResWrapper>()?;
     m.add_class::<TrainSimBuilder>()?;
@@ -75,8 +91,19 @@ fn altrios_pyo3(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<TrainType>()?;
     m.add_function(wrap_pyfunction!(import_locations_py, m)?)?;
     m.add_function(wrap_pyfunction!(make_est_times_py, m)?)?;
+    m.add_function(wrap_pyfunction!(make_est_times_cached_py, m)?)?;
     m.add_function(wrap_pyfunction!(run_dispatch_py, m)?)?;
     m.add_function(wrap_pyfunction!(check_od_pair_valid, m)?)?;
     m.add_function(wrap_pyfunction!(run_speed_limit_train_sims, m)?)?;
+    m.add_function(wrap_pyfunction!(compare_sims_py, m)?)?;
+    m.add_function(wrap_pyfunction!(summarize_speed_limit_train_sims_py, m)?)?;
+    m.add_function(wrap_pyfunction!(mph_to_mps, m)?)?;
+    m.add_function(wrap_pyfunction!(mps_to_mph, m)?)?;
+    m.add_function(wrap_pyfunction!(ton_to_kg, m)?)?;
+    m.add_function(wrap_pyfunction!(kg_to_ton, m)?)?;
+    m.add_function(wrap_pyfunction!(hp_to_watt, m)?)?;
+    m.add_function(wrap_pyfunction!(watt_to_hp, m)?)?;
+    m.add_function(wrap_pyfunction!(lbf_to_newton, m)?)?;
+    m.add_function(wrap_pyfunction!(newton_to_lbf, m)?)?;
     Ok(())
 }
@@ -2,7 +2,7 @@ import altrios.altrios_pyo3 as altpy
 from typing import Any, Dict, List, Optional
 import polars as pl
 from typing_extensions import Self
-from typing import Union, Tuple
+from typing import Callable, Union, Tuple
 from dataclasses import dataclass
 from pathlib import Path
 
@@ -32,16 +32,43 @@ class Consist(SerdeAPI):
     assert_limits: bool
     history: ConsistStateHistoryVec
     loco_vec: list[Locomotive]
+    pwr_rate_out_max_watts_per_second: Optional[float]
+    command_delay_seconds: float
     save_interval: int
     state: ConsistState
-    def __init__(self, loco_vec: List[Locomotive]): ...
+    def __init__(
+        self,
+        loco_vec: List[Locomotive],
+        save_interval: Optional[int] = None,
+        pwr_rate_out_max_watts_per_second: Optional[float] = None,
+        command_delay_seconds: Optional[float] = None,
+    ): ...
     def clone(self) -> Self: ...
     @classmethod
     def default(cls) -> Self: ...
+    def energy_by_loco_type(self) -> Dict[str, Tuple[float, float]]: ...
+    def get_co2_mass_kg(self) -> float: ...
+    def get_fuel_volume_cubic_meters(self) -> float: ...
     def get_save_interval(self) -> int: ...
+    def check_save_interval_consistency(self) -> None: ...
     def set_pdct_prop(self) -> None: ...
+    def describe_topology(self) -> str: ...
+    def set_adhesion_factor(self, adhesion_factor: float) -> None: ...
+    def set_dyn_brake_thermal(
+        self, dyn_brake_thermal: Optional[DynBrakeThermal] = None
+    ) -> None: ...
+    def set_initial_soc(self, soc: float) -> None: ...
+    def insert_locomotive(self, idx: int, loco: Locomotive) -> None: ...
+    def remove_locomotive(self, idx: int) -> Locomotive: ...
     def set_pdct_resgreedy(self) -> None: ...
     def set_save_interval(self, save_interval: int): ...
+    def strip_history(self) -> None: ...
+    def tractive_effort_curve_newtons(
+        self, speeds_meters_per_second: list[float], train_mass_kilograms: float
+    ) -> list[float]: ...
+    def can_hold_grade(
+        self, grade: float, speed_meters_per_second: float, train_mass_kilograms: float
+    ) -> bool: ...
     def __copy__(self) -> Self: ...
 
 class ConsistSimulation(SerdeAPI):
@@ -58,16 +85,27 @@ class ConsistSimulation(SerdeAPI):
     def walk(self) -> None: ...
     def __copy__(self) -> Self: ...
 
+class ConsistSimulationVec(SerdeAPI):
+    def __init__(self, v: List[ConsistSimulation]) -> None: ...
+    def clone(self) -> Self: ...
+    @classmethod
+    def default(cls) -> Self: ...
+    def walk(self, b_parallelize: Optional[bool] = None) -> None: ...
+    def __copy__(self) -> Self: ...
+
 class ConsistState(SerdeAPI):
     energy_fuel_joules: float
+    energy_out_delay_deficit_joules: float
     energy_out_joules: float
     energy_res_joules: float
+    eta_consist: float
     i: int
     pwr_dyn_brake_max_watts: float
     pwr_fuel_watts: float
     pwr_out_max_non_reves_watts: float
     pwr_out_max_reves_watts: float
     pwr_out_max_watts: float
+    pwr_out_delay_deficit_watts: float
     pwr_out_req_watts: float
     pwr_out_unfulfilled_watts: float
     pwr_out_watts: float
@@ -84,14 +122,17 @@ class ConsistState(SerdeAPI):
 
 class ConsistStateHistoryVec(SerdeAPI):
     energy_fuel_joules: list[float]
+    energy_out_delay_deficit_joules: list[float]
     energy_out_joules: list[float]
     energy_res_joules: list[float]
+    eta_consist: list[float]
     i: list[int]
     pwr_dyn_brake_max_watts: list[float]
     pwr_fuel_watts: list[float]
     pwr_out_max_non_reves_watts: list[float]
     pwr_out_max_reves_watts: list[float]
     pwr_out_max_watts: list[float]
+    pwr_out_delay_deficit_watts: list[float]
     pwr_out_req_watts: list[float]
     pwr_out_unfulfilled_watts: list[float]
     pwr_out_watts: list[float]
@@ -113,6 +154,9 @@ class ElectricDrivetrain(SerdeAPI):
     pwr_in_frac_interp: list[float]
     pwr_out_frac_interp: list[float]
     pwr_out_max_watts: float
+    speed_field_weakening_meters_per_second: Optional[float]
+    dyn_brake_thermal: Optional[DynBrakeThermal]
+    pwr_dyn_brake_max_watts: float
     save_interval: Optional[int]
     state: ElectricDrivetrainState
     @classmethod
@@ -121,6 +165,28 @@ class ElectricDrivetrain(SerdeAPI):
     @classmethod
     def default(cls) -> Self: ...
     def __copy__(self) -> Self: ...
+    def set_speed_field_weakening(
+        self, speed_field_weakening_meters_per_second: Optional[float] = None
+    ) -> None: ...
+    def set_dyn_brake_thermal(
+        self, dyn_brake_thermal: Optional[DynBrakeThermal] = None
+    ) -> None: ...
+
+class DynBrakeThermal(SerdeAPI):
+    thermal_mass_joules_per_kelvin: float
+    time_constant_seconds: float
+    temp_ambient_kelvin: float
+    temp_fade_start_kelvin: float
+    temp_max_kelvin: float
+    def __init__(
+        self,
+        thermal_mass_joules_per_kelvin: Optional[float] = None,
+        time_constant_seconds: Optional[float] = None,
+        temp_ambient_kelvin: Optional[float] = None,
+        temp_fade_start_kelvin: Optional[float] = None,
+        temp_max_kelvin: Optional[float] = None,
+    ) -> None: ...
+    def fade_frac(self, temp_kelvin: float) -> float: ...
 
 class ElectricDrivetrainState(SerdeAPI):
     energy_elec_dyn_brake_joules: float
@@ -138,6 +204,7 @@ class ElectricDrivetrainState(SerdeAPI):
     pwr_mech_prop_out_watts: float
     pwr_mech_regen_max_watts: float
     pwr_rate_out_max_watts_per_second: float
+    temp_dyn_brake_kelvin: Optional[float]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -161,6 +228,7 @@ class ElectricDrivetrainStateHistoryVec(SerdeAPI):
     pwr_mech_prop_out_watts: list[float]
     pwr_mech_regen_max_watts: list[float]
     pwr_rate_out_max_watts_per_second: list[float]
+    temp_dyn_brake_kelvin: list[Optional[float]]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -173,6 +241,7 @@ class FuelConverter(SerdeAPI):
     eta_interp: list[float]
     eta_max: float
     eta_range: float
+    fuel_type: FuelType
     history: FuelConverterStateHistoryVec
     pwr_idle_fuel_watts: float
     pwr_out_frac_interp: list[float]
@@ -180,26 +249,65 @@ class FuelConverter(SerdeAPI):
     pwr_ramp_lag_seconds: float
     save_interval: Optional[int]
     state: FuelConverterState
+    thermal: Optional[FuelConverterThermal]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
     @classmethod
     def default(cls) -> Self: ...
     def __copy__(self) -> Self: ...
+    def set_thermal(self, thermal: Optional[FuelConverterThermal] = None) -> None: ...
+    def set_fuel_type(self, fuel_type: Optional[FuelType] = None) -> None: ...
+    def set_altitude_derate(self, elev_meters: List[float], pwr_frac: List[float]) -> None: ...
+    def derate_for_altitude(self, elev_meters: float) -> float: ...
+
+class FuelConverterThermal(SerdeAPI):
+    thermal_mass_joules_per_kelvin: float
+    heat_frac: float
+    temp_cold_kelvin: float
+    temp_warm_kelvin: float
+    eta_derate_cold: float
+    def __init__(
+        self,
+        thermal_mass_joules_per_kelvin: float,
+        heat_frac: Optional[float] = None,
+        temp_cold_kelvin: Optional[float] = None,
+        temp_warm_kelvin: Optional[float] = None,
+        eta_derate_cold: Optional[float] = None,
+    ) -> None: ...
+
+class FuelType(SerdeAPI):
+    name: str
+    lhv_joules_per_kilogram: float
+    density_kilograms_per_cubic_meter: float
+    co2_intensity_kilograms_per_joule: float
+    def __init__(
+        self,
+        name: str,
+        lhv_joules_per_kilogram: float,
+        density_kilograms_per_cubic_meter: float,
+        co2_intensity_kilograms_per_joule: float,
+    ) -> None: ...
 
 class FuelConverterState(SerdeAPI):
+    co2_mass_kilograms: float
+    co2_mass_rate_kilograms_per_second: float
     energy_brake_joules: float
     energy_fuel_joules: float
     energy_idle_fuel_joules: float
     energy_loss_joules: float
     engine_on: bool
+    fuel_volume_cubic_meters: float
+    fuel_volume_rate_cubic_meters_per_second: float
     eta: float
     i: int
+    notch: Optional[int]
     pwr_fuel_watts: float
     pwr_brake_watts: float
     pwr_idle_fuel_watts: float
     pwr_loss_watts: float
     pwr_out_max_watts: float
+    temp_kelvin: Optional[float]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -208,18 +316,24 @@ class FuelConverterState(SerdeAPI):
     def __copy__(self) -> Self: ...
 
 class FuelConverterStateHistoryVec(SerdeAPI):
+    co2_mass_kilograms: list[float]
+    co2_mass_rate_kilograms_per_second: list[float]
     energy_brake_joules: list[float]
     energy_fuel_joules: list[float]
     energy_idle_fuel_joules: list[float]
     energy_loss_joules: list[float]
     engine_on: list[bool]
+    fuel_volume_cubic_meters: list[float]
+    fuel_volume_rate_cubic_meters_per_second: list[float]
     eta: list[float]
     i: list[int]
+    notch: list[Optional[int]]
     pwr_brake_watts: list[float]
     pwr_fuel_watts: list[float]
     pwr_idle_fuel_watts: list[float]
     pwr_loss_watts: list[float]
     pwr_out_max_watts: list[float]
+    temp_kelvin: list[Optional[float]]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -247,6 +361,8 @@ class GeneratorState(SerdeAPI):
     energy_elec_aux_joules: float
     energy_elec_prop_out_joules: float
     energy_loss_joules: float
+    energy_loss_copper_joules: float
+    energy_loss_iron_joules: float
     energy_mech_in_joules: float
     eta: float
     i: int
@@ -254,6 +370,8 @@ class GeneratorState(SerdeAPI):
     pwr_elec_out_max_watts: float
     pwr_elec_prop_out_watts: float
     pwr_loss_watts: float
+    pwr_loss_copper_watts: float
+    pwr_loss_iron_watts: float
     pwr_mech_in_watts: float
     pwr_rate_out_max_watts_per_second: float
     @classmethod
@@ -267,6 +385,8 @@ class GeneratorStateHistoryVec(SerdeAPI):
     energy_elec_aux_joules: list[float]
     energy_elec_prop_out_joules: list[float]
     energy_loss_joules: list[float]
+    energy_loss_copper_joules: list[float]
+    energy_loss_iron_joules: list[float]
     energy_mech_in_joules: list[float]
     eta: list[float]
     i: list[int]
@@ -274,6 +394,8 @@ class GeneratorStateHistoryVec(SerdeAPI):
     pwr_elec_out_max_watts: list[float]
     pwr_elec_prop_out_watts: list[float]
     pwr_loss_watts: list[float]
+    pwr_loss_copper_watts: list[float]
+    pwr_loss_iron_watts: list[float]
     pwr_mech_in_watts: list[float]
     pwr_rate_out_max_watts_per_second: list[float]
     @classmethod
@@ -303,6 +425,7 @@ class ConventionalLoco(SerdeAPI):
     fc: FuelConverter
     gen: Generator
     edrv: ElectricDrivetrain
+    notch_pwr_fracs: Optional[list[float]]
 
 @dataclass
 class HybridLoco(SerdeAPI):
@@ -312,6 +435,17 @@ class HybridLoco(SerdeAPI):
     electric_drivetrain: ElectricDrivetrain
 
 @dataclass
+class AxleLoadTransfer(SerdeAPI):
+    transfer_coeff: float
+    drawbar_height_meters: float
+    truck_spacing_meters: float
+    def __init__(
+        self,
+        transfer_coeff: float,
+        drawbar_height_meters: Optional[float] = None,
+        truck_spacing_meters: Optional[float] = None,
+    ) -> None: ...
+
 class BatteryElectricLoco(SerdeAPI):
     res: ReversibleEnergyStorage
     edrv: ElectricDrivetrain
@@ -320,14 +454,19 @@ class BatteryElectricLoco(SerdeAPI):
 class DummyLoco(SerdeAPI): ...
 
 class Locomotive(SerdeAPI):
+    adhesion_factor: float
     assert_limits: bool
+    axle_load_transfer: AxleLoadTransfer
     edrv: ElectricDrivetrain
     fc: FuelConverter
+    force_max_effective_newtons: float
     fuel_res_ratio: float
     fuel_res_split: float
     gen: Generator
     history: LocomotiveStateHistoryVec
     pwr_aux_watts: float
+    pwr_aux_at_idle_watts: float
+    pwr_aux_idle_override_watts: Optional[float]
     res: ReversibleEnergyStorage
     save_interval: int
     state: LocomotiveState
@@ -355,7 +494,12 @@ class Locomotive(SerdeAPI):
     @classmethod
     def default(cls) -> Self: ...
     def get_save_interval(self) -> Any: ...
+    def derate_for_altitude(self, elev_meters: float) -> float: ...
+    def set_adhesion_factor(self, adhesion_factor: float) -> None: ...
+    def set_axle_load_transfer(self, axle_load_transfer: AxleLoadTransfer) -> None: ...
     def set_save_interval(self, save_interval: int): ...
+    def strip_history(self) -> None: ...
+    def summary(self) -> str: ...
     def __copy__(self) -> Self: ...
 
 class LocomotiveSimulation(SerdeAPI):
@@ -366,6 +510,7 @@ class LocomotiveSimulation(SerdeAPI):
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
     def get_save_interval(self) -> int: ...
+    def set_power_trace(self, power_trace: PowerTrace) -> None: ...
     def set_save_interval(self, save_interval: int): ...
     def walk(self) -> None: ...
     def __copy__(self) -> Self: ...
@@ -373,12 +518,14 @@ class LocomotiveSimulation(SerdeAPI):
 class LocomotiveState(SerdeAPI):
     energy_aux_joules: float
     energy_out_joules: float
+    force_max_curr_newtons: float
     i: int
     pwr_aux_watts: float
     pwr_out_max_watts: float
     pwr_out_watts: float
     pwr_rate_out_max_watts_per_second: float
     pwr_regen_max_watts: float
+    time_in_engine_state_seconds: float
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -389,12 +536,14 @@ class LocomotiveState(SerdeAPI):
 class LocomotiveStateHistoryVec(SerdeAPI):
     energy_aux_joules: list[float]
     energy_out_joules: list[float]
+    force_max_curr_newtons: list[float]
     i: list[int]
     pwr_aux_watts: list[float]
     pwr_out_max_watts: list[float]
     pwr_out_watts: list[float]
     pwr_rate_out_max_watts_per_second: list[float]
     pwr_regen_max_watts: list[float]
+    time_in_engine_state_seconds: list[float]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -408,6 +557,7 @@ class PowerTrace(SerdeAPI):
     time_seconds: list[float]
     pwr_watts: list[float]
     engine_on: Optional[list[bool]]
+    pwr_aux_watts: Optional[list[float]]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -417,6 +567,23 @@ class PowerTrace(SerdeAPI):
     def __len__(self) -> int: ...
     @classmethod
     def from_csv_file(cls, pathstr: str) -> Self: ...
+    def scale_power(self, factor: float) -> None: ...
+    def offset_time(self, dt_seconds: float) -> None: ...
+
+class ResVoltageModel(SerdeAPI):
+    ocv_soc_grid: list[float]
+    ocv_volts: list[float]
+    resistance_ohms: float
+    def __init__(
+        self,
+        ocv_soc_grid: list[float],
+        ocv_volts: list[float],
+        resistance_ohms: Optional[float] = None,
+    ) -> None: ...
+    def clone(self) -> Self: ...
+    @classmethod
+    def default(cls) -> Self: ...
+    def __copy__(self) -> Self: ...
 
 class ReversibleEnergyStorage(SerdeAPI):
     energy_capacity_joules: float
@@ -429,19 +596,28 @@ class ReversibleEnergyStorage(SerdeAPI):
     soc_hi_ramp_start: Optional[float]
     soc_lo_ramp_start: Optional[float]
     state: ReversibleEnergyStorageState
+    voltage_model: Optional[ResVoltageModel]
+    cycles_equivalent: float
+    energy_throughput_joules: float
+    energy_capacity_usable_joules: float
+    usable_energy_remaining_joules: float
+    usable_energy_headroom_joules: float
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
+    def set_voltage_model(self, voltage_model: Optional[ResVoltageModel] = None) -> None: ...
     @classmethod
     def default(cls) -> Self: ...
     def __copy__(self) -> Self: ...
 
 class ReversibleEnergyStorageState(SerdeAPI):
+    cycles_equivalent: float
     energy_aux_joules: float
     energy_loss_joules: float
     energy_out_chemical_joules: float
     energy_out_electrical_joules: float
     energy_out_propulsion_joules: float
+    energy_throughput_joules: float
     eta: float
     i: int
     max_soc: float
@@ -458,6 +634,8 @@ class ReversibleEnergyStorageState(SerdeAPI):
     soc_lo_ramp_start: float
     soh: float
     temperature_celsius: float
+    voltage_volts: float
+    current_amperes: float
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -466,11 +644,13 @@ class ReversibleEnergyStorageState(SerdeAPI):
     def __copy__(self) -> Self: ...
 
 class ReversibleEnergyStorageStateHistoryVec(SerdeAPI):
+    cycles_equivalent: list[float]
     energy_aux_joules: list[float]
     energy_loss_joules: list[float]
     energy_out_chemical_joules: list[float]
     energy_out_electrical_joules: list[float]
     energy_out_propulsion_joules: list[float]
+    energy_throughput_joules: list[float]
     eta: list[float]
     i: list[int]
     max_soc: list[float]
@@ -487,6 +667,8 @@ class ReversibleEnergyStorageStateHistoryVec(SerdeAPI):
     soc_lo_ramp_start: list[float]
     soh: list[float]
     temperature_celsius: list[float]
+    voltage_volts: list[float]
+    current_amperes: list[float]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -507,7 +689,21 @@ class SpeedTrace(SerdeAPI):
     def __len__(self) -> int: ...
     @classmethod
     def from_csv_file(cls, pathstr: str) -> Self: ...
+    @classmethod
+    def from_gps(
+        cls,
+        time_seconds: list[float],
+        lats_degrees: list[float],
+        lons_degrees: list[float],
+        smoothing_window: Optional[int] = None,
+    ) -> Self: ...
     def to_csv_file(self, pathstr: str): ...
+    def append(self, other: SpeedTrace): ...
+    def detect_stops(
+        self, speed_thresh_meters_per_second: float, min_duration_seconds: float
+    ) -> list[tuple[int, int]]: ...
+    def clip_speed(self, speed_max_meters_per_second: float) -> None: ...
+    def to_power_trace(self, sim: SetSpeedTrainSim) -> PowerTrace: ...
 
 class TemperatureTraceBuilder(SerdeAPI):
     time: list[float]
@@ -526,6 +722,9 @@ class TemperatureTrace(SerdeAPI):
     def default(cls) -> Self: ...
     def __copy__(self) -> Self: ...
     def get_temp_at_time_and_elev(self, time: float, elev: float) -> float: ...
+    @classmethod
+    def from_csv_file(cls, pathstr: str) -> Self: ...
+    def to_csv_file(self, pathstr: str): ...
 
 class TrainState:
     time_seconds: float
@@ -536,6 +735,7 @@ class TrainState:
     link_idx_front: int
     offset_in_link_meters: float
     grade_front: float
+    grade_mean: float
     speed_meters_per_second: float
     speed_limit_meters_per_second: float
     speed_target_meters_per_second: float
@@ -551,6 +751,7 @@ class TrainState:
     res_aero_newtons: float
     res_grade_newtons: float
     res_curve_newtons: float
+    res_net_newtons: float
     elev_front_meters: float
     pwr_res_watts: float
     pwr_accel_watts: float
@@ -558,6 +759,11 @@ class TrainState:
     energy_whl_out_joules: float
     energy_whl_out_pos_joules: float
     energy_whl_out_neg_joules: float
+    pwr_dyn_brake_watts: float
+    pwr_fric_brake_watts: float
+    traction_limit_cause: int
+    force_applied_newtons: float
+    kinetic_energy_joules: float
     @classmethod
     def default(cls) -> TrainState: ...
     @classmethod
@@ -585,6 +791,7 @@ class TrainStateHistoryVec(SerdeAPI):
     link_idx_front: list[int]
     offset_in_link_meters: list[float]
     grade_front: list[float]
+    grade_mean: list[float]
     speed_meters_per_second: list[float]
     speed_limit_meters_per_second: list[float]
     speed_target_meters_per_second: list[float]
@@ -603,6 +810,10 @@ class TrainStateHistoryVec(SerdeAPI):
     res_curve_newtons: list[float]
     pwr_whl_out_watts: list[float]
     energy_whl_out_joules: list[float]
+    pwr_dyn_brake_watts: list[float]
+    pwr_fric_brake_watts: list[float]
+    traction_limit_cause: list[int]
+    force_applied_newtons: list[float]
     @classmethod
     def __init__(cls) -> None: ...
     def clone(self) -> Self: ...
@@ -618,6 +829,7 @@ class SetSpeedTrainSim(SerdeAPI):
     history: TrainStateHistoryVec
     i: int
     save_interval: Optional[int]
+    integration_method: IntegrationMethod
 
     @classmethod
     def __init__(
@@ -635,6 +847,10 @@ class SetSpeedTrainSim(SerdeAPI):
     def default(cls) -> Self: ...
     def __copy__(self) -> Self: ...
     def set_save_interval(self, save_interval: int): ...
+    def strip_history(self) -> None: ...
+    def set_history_sink(self, path: Optional[str] = None) -> None: ...
+    def set_wind_speed(self, wind_speed_meters_per_second: float) -> None: ...
+    def set_integration_method(self, integration_method: IntegrationMethod) -> None: ...
 
 class LinkPoint(SerdeAPI):
     offset_meters: float
@@ -673,10 +889,24 @@ class PathTpc(SerdeAPI):
     link_points: List[LinkPoint]
     grades: List[PathResCoeff]
     curves: List[PathResCoeff]
-    speed_points: List[SpeedLimitPoint]
+    speed_points: Tuple[List[float], List[float]]
+    grade_points: Tuple[List[float], List[float]]
+    curve_points: Tuple[List[float], List[float]]
     cat_power_limits: List[CatPowerLimit]
     train_params: TrainParams
     is_finished: bool
+    curve_speed_limit: Optional[CurveSpeedModel]
+    def elevation_profile(self) -> Tuple[List[float], List[float]]: ...
+    def set_curve_speed_limit(
+        self, curve_speed_limit: Optional[CurveSpeedModel] = None
+    ) -> None: ...
+
+class CurveSpeedModel(SerdeAPI):
+    superelevation_meters: float
+    cant_deficiency_meters: float
+    def __init__(
+        self, superelevation_meters: float, cant_deficiency_meters: float
+    ) -> None: ...
 
 class BrakingPoint(SerdeAPI):
     offset_meters: float
@@ -691,20 +921,32 @@ class FricBrakeState(SerdeAPI):
     i: int
     force_newtons: float
     force_max_curr_newtons: float
+    force_min_curr_newtons: float
 
 class FricBrakeStateHistoryVec(SerdeAPI):
     i: List[int]
     force_newtons: List[float]
     force_max_curr_newtons: List[float]
+    force_min_curr_newtons: List[float]
 
 class FricBrake(SerdeAPI):
     force_max_newtons: float
     ramp_up_time_seconds: float
     ramp_up_coeff_ratio: float
+    ramp_down_time_seconds: float
     state: FricBrakeState
     history: FricBrakeStateHistoryVec
     save_interval: Optional[int]
 
+class CurveComfort(SerdeAPI):
+    offset_start_meters: float
+    offset_end_meters: float
+    curvature_radians_per_meter: float
+    speed_comfort_max_meters_per_second: float
+    speed_limit_max_meters_per_second: float
+    speed_max_meters_per_second: Optional[float]
+    def is_uncomfortable(self) -> bool: ...
+
 class SpeedLimitTrainSim(SerdeAPI):
     train_id: str
     origs: List[Location]
@@ -715,6 +957,12 @@ class SpeedLimitTrainSim(SerdeAPI):
     # train_res: TrainRes # not accessible in Python
     path_tpc: PathTpc
     braking_points: BrakingPoints
+    speed_limit_margin_meters_per_second: float
+    eco_drive: Optional[EcoDriveConfig]
+    max_jerk_meters_per_second_cubed: Optional[float]
+    n_substeps: int
+    substep_accel_threshold_meters_per_second_squared: float
+    speed_violation_policy: SpeedViolationPolicy
     fric_brake: FricBrake
     history: TrainStateHistoryVec
     save_interval: Optional[int]
@@ -738,8 +986,51 @@ class SpeedLimitTrainSim(SerdeAPI):
     def default(cls) -> Self: ...
     def __copy__(self) -> Self: ...
     def set_save_interval(self, save_interval: int): ...
+    def strip_history(self) -> None: ...
     def walk(self): ...
     def walk_timed_path(self, network: Network, timed_path: List[LinkIdxTime]): ...
+    def get_curve_comfort(
+        self,
+        network: Network | List[Link],
+        superelevation_meters: float,
+        cant_deficiency_meters: float,
+    ) -> List[CurveComfort]: ...
+    def set_speed_limit_offset(self, speed_limit_margin_meters_per_second: float): ...
+    def compare(self, other: SpeedLimitTrainSim) -> pl.DataFrame: ...
+    def compare_summary(self, other: SpeedLimitTrainSim) -> pl.DataFrame: ...
+    def speed_target_trace(self) -> pl.DataFrame: ...
+    def checkpoint(self) -> SimCheckpoint: ...
+    def restore(self, checkpoint: SimCheckpoint) -> None: ...
+    def reset(self) -> None: ...
+    def time_in_state_histogram(self, pwr_thresh_watts: float) -> Dict[int, Dict[str, float]]: ...
+    def set_eco_drive(self, eco_drive: Optional[EcoDriveConfig] = None) -> None: ...
+    def set_max_jerk(self, max_jerk_meters_per_second_cubed: Optional[float] = None) -> None: ...
+    def set_substepping(
+        self, n_substeps: int, accel_threshold_meters_per_second_squared: float = 0.0
+    ) -> None: ...
+    def set_speed_violation_policy(self, speed_violation_policy: SpeedViolationPolicy) -> None: ...
+    def set_history_sink(self, path: Optional[str] = None) -> None: ...
+    def set_wind_speed(self, wind_speed_meters_per_second: float) -> None: ...
+    def set_integration_method(self, integration_method: IntegrationMethod) -> None: ...
+    def max_grade_on_route(self) -> float: ...
+    def regen_recovery_fraction(self) -> float: ...
+    def objective(self, weights: Dict[str, float]) -> float: ...
+    def ruling_grade(self, length_min_meters: float) -> float: ...
+
+class EcoDriveConfig(SerdeAPI):
+    lookahead_meters: float
+    grade_coast: float
+    speed_coast_min_meters_per_second: float
+    def __init__(
+        self,
+        lookahead_meters: float,
+        grade_coast: Optional[float] = None,
+        speed_coast_min_meters_per_second: Optional[float] = None,
+    ) -> None: ...
+
+class SimCheckpoint(SerdeAPI):
+    train_sim: SpeedLimitTrainSim
+    i: int
 
 @dataclass
 class SpeedLimitTrainSimVec(SerdeAPI):
@@ -748,6 +1039,8 @@ class SpeedLimitTrainSimVec(SerdeAPI):
     def default(cls) -> Self: ...
     def tolist(self) -> List[SpeedLimitTrainSim]: ...
     def set_save_interval(self, save_interval: int): ...
+    @classmethod
+    def from_dir(cls, dirpath: Union[str, Path]) -> Self: ...
 
 @dataclass
 class LinkIdx(SerdeAPI):
@@ -798,6 +1091,12 @@ class TrainSimBuilder(SerdeAPI):
         scenario_year: Optional[int],
         temp_trace: Optional[TemperatureTrace] = None,
     ) -> SpeedLimitTrainSim: ...
+    def build_set_speed_train_sim(
+        self,
+        speed_trace: SpeedTrace,
+        save_interval: Optional[int] = None,
+        temp_trace: Optional[TemperatureTrace] = None,
+    ) -> SetSpeedTrainSim: ...
 
 @dataclass
 class TrainConfig(SerdeAPI):
@@ -809,6 +1108,11 @@ class TrainConfig(SerdeAPI):
     cd_area_vec: Optional[List[float]]
     @classmethod
     def default(cls) -> Self: ...
+    def to_param_dict(self) -> Dict[str, float]: ...
+    @staticmethod
+    def from_param_dict(base: TrainConfig, param_dict: Dict[str, float]) -> TrainConfig: ...
+    @staticmethod
+    def from_dataframe(df: pl.DataFrame, rail_vehicles: List[RailVehicle]) -> List[TrainConfig]: ...
 
 class RailVehicle(SerdeAPI):
     axle_count: int
@@ -821,6 +1125,7 @@ class RailVehicle(SerdeAPI):
     davis_b_seconds_per_meter: float
     cd_area_empty_square_meters: float
     cd_area_loaded_square_meters: float
+    drafting_factor: Optional[float]
     length_meters: float
     mass_rot_per_axle_kilograms: float
     mass_static_empty_kilograms: float
@@ -828,8 +1133,15 @@ class RailVehicle(SerdeAPI):
     rolling_ratio: float
     speed_max_empty_meters_per_second: float
     speed_max_loaded_meters_per_second: float
+    empty: bool
+    freight_mass_kilograms: float
     @classmethod
     def default(cls) -> Self: ...
+    @classmethod
+    def map_from_file(cls, filepath: Union[str, Path]) -> Dict[str, RailVehicle]: ...
+    @classmethod
+    def from_aar_type(cls, code: str) -> RailVehicle: ...
+    def total_mass_kilograms(self, loaded: bool) -> float: ...
 
 class Location(SerdeAPI):
     location_id: str
@@ -846,11 +1158,22 @@ class EstTimeNet(SerdeAPI):
     @classmethod
     def default(cls) -> Self: ...
     def get_running_time_hours(self) -> float: ...
+    def est_time_at_link_seconds(self, link_idx: LinkIdx) -> Optional[float]: ...
+    @property
+    def total_time_seconds(self) -> float: ...
+    def to_dataframe(self) -> pl.DataFrame: ...
 
 class Link(SerdeAPI):
     length_meters: float
     @classmethod
     def default(cls) -> Self: ...
+    def set_elev_from_grade(
+        self,
+        offsets_meters: List[float],
+        grades: List[float],
+        elev_start_meters: float,
+    ) -> None: ...
+    def speed_limit_at_offset(self, offset_meters: float) -> float: ...
 
 class Elev(SerdeAPI):
     offset_meters: float
@@ -887,19 +1210,76 @@ def run_speed_limit_train_sims(
     refuel_facilities: pl.DataFrame,
     timed_paths: List[List[LinkIdxTime]],
 ) -> Tuple[SpeedLimitTrainSimVec, pl.DataFrame]: ...
+def compare_sims(
+    sls: SpeedLimitTrainSim,
+    sts: SetSpeedTrainSim,
+) -> Dict[str, float]: ...
+def summarize_speed_limit_train_sims(
+    sims: SpeedLimitTrainSimVec,
+) -> pl.DataFrame: ...
+def mph_to_mps(mph: float) -> float: ...
+def mps_to_mph(mps: float) -> float: ...
+def ton_to_kg(ton: float) -> float: ...
+def kg_to_ton(kg: float) -> float: ...
+def hp_to_watt(hp: float) -> float: ...
+def watt_to_hp(watt: float) -> float: ...
+def lbf_to_newton(lbf: float) -> float: ...
+def newton_to_lbf(newton: float) -> float: ...
 def run_dispatch(
     network: List[Link],
     speed_limit_train_sims: SpeedLimitTrainSimVec,
     est_time_nets: List[EstTimeNet],
     print_train_move: bool,
     print_train_exit: bool,
-) -> List[TimedLinkPath]: ...
+    return_dispatch_result: bool = False,
+    objective: Optional[DispatchObjective] = None,
+) -> Union[List[TimedLinkPath], DispatchResult]: ...
 def make_est_times(
     speed_limit_train_sim: SpeedLimitTrainSim,
     network: List[Link],
     path_for_failed_sim: Optional[Path] = None,
+    progress: Optional[Callable[[float], None]] = None,
+) -> Tuple[EstTimeNet, Consist]: ...
+def make_est_times_cached(
+    speed_limit_train_sim: SpeedLimitTrainSim,
+    network: List[Link],
+    cache_path: Path,
+    path_for_failed_sim: Optional[Path] = None,
 ) -> Tuple[EstTimeNet, Consist]: ...
 @dataclass
+class TrainDispatchSummary(SerdeAPI):
+    train_id: str
+    time_depart_seconds: float
+    time_arrive_seconds: float
+    time_running_est_seconds: Optional[float]
+    time_delay_seconds: Optional[float]
+    timed_path: TimedLinkPath
+    energy_fuel_joules: Optional[float]
+    energy_res_joules: Optional[float]
+    co2_mass_kilograms: Optional[float]
+
+class Meet(SerdeAPI):
+    train_id_a: str
+    train_id_b: str
+    link_idx: LinkIdx
+    time_seconds: float
+
+class DispatchObjective:
+    MinTime = (altpy.DispatchObjective.MinTime,)  # type: ignore[has-type]
+    MinEnergy = (altpy.DispatchObjective.MinEnergy,)  # type: ignore[has-type]
+
+class DispatchResult(SerdeAPI):
+    train_summaries: List[TrainDispatchSummary]
+    meets: List[Meet]
+    time_running_total_seconds: float
+    time_delay_total_seconds: float
+    energy_fuel_total_joules: Optional[float]
+    energy_res_total_joules: Optional[float]
+    co2_mass_total_kilograms: Optional[float]
+    @property
+    def meet_count(self) -> int: ...
+    def set_energy(self, speed_limit_train_sims: SpeedLimitTrainSimVec) -> None: ...
+
 class TimedLinkPath(SerdeAPI):
     @classmethod
     def __new__(cls, v: List[LinkIdxTime]) -> Self: ...
@@ -927,6 +1307,19 @@ class Network(SerdeAPI):
     def default(cls) -> Self: ...
     def is_empty(self) -> bool: ...
     def tolist(self) -> List[Link]: ...
+    def validate_speed_sets(self) -> None: ...
+    def validate_connectivity(self) -> None: ...
+    def link_length(self, idx: int) -> float: ...
+    @property
+    def total_track_length_meters(self) -> float: ...
+    def adjacency(self) -> List[Tuple[int, List[int]]]: ...
+    def find_location(
+        self, location_map: Dict[str, List[Location]], name: str
+    ) -> Optional[Location]: ...
+    def path_between_locations(
+        self, location_map: Dict[str, List[Location]], origin: str, dest: str
+    ) -> LinkPath: ...
+    def merge(self, other: Network, link_joins: List[Tuple[int, int]]) -> None: ...
     def __copy__(self) -> Self: ...
     def __delitem__(self, other) -> None: ...
     def __getitem__(self, index) -> Link: ...
@@ -960,6 +1353,17 @@ class InitTrainState(SerdeAPI):
     @classmethod
     def default(cls) -> Self: ...
 
+@dataclass
+class IntegrationMethod:
+    Rectangle = (altpy.IntegrationMethod.Rectangle,)  # type: ignore[has-type]
+    Trapezoid = (altpy.IntegrationMethod.Trapezoid,)  # type: ignore[has-type]
+
+@dataclass
+class SpeedViolationPolicy:
+    Error = (altpy.SpeedViolationPolicy.Error,)  # type: ignore[has-type]
+    Clamp = (altpy.SpeedViolationPolicy.Clamp,)  # type: ignore[has-type]
+    Log = (altpy.SpeedViolationPolicy.Log,)  # type: ignore[has-type]
+
 @dataclass
 class TrainType(SerdeAPI):
     Freight = (altpy.TrainType.Freight,)  # type: ignore[has-type]
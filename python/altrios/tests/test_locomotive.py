@@ -1 +1,16 @@
+import unittest
 
+from .mock_resources import *
+
+
+class TestLocomotive(unittest.TestCase):
+    def test_repr(self):
+        loco = mock_conventional_loco()
+        loco_repr = repr(loco)
+        assert loco_repr.startswith("Locomotive(")
+        assert f"pwr_rated_kilowatts={loco.pwr_rated_kilowatts:.1f}" in loco_repr
+
+        consist = mock_consist()
+        consist_repr = repr(consist)
+        assert consist_repr.startswith("Consist(")
+        assert f"n_locos={len(consist.loco_vec)}" in consist_repr
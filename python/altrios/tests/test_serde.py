@@ -132,5 +132,26 @@ def test_pydict():
     assert ts_yaml.to_pydict() == ts.to_pydict()
 
 
+def test_pydict_round_trip():
+    train_config = alt.TrainConfig(
+        rail_vehicles=[
+            alt.RailVehicle.from_file(
+                alt.resources_root() / "rolling_stock/Manifest_Loaded.yaml"
+            )
+        ],
+        n_cars_by_type={"Manifest_Loaded": 50},
+        train_length_meters=None,
+        train_mass_kilograms=None,
+    )
+    train_config_dict = train_config.to_pydict()
+    assert train_config_dict["n_cars_by_type"] == {"Manifest_Loaded": 50}
+    assert alt.TrainConfig.from_pydict(train_config_dict) == train_config
+
+    fc = alt.FuelConverter.default()
+    fc_dict = fc.to_pydict()
+    assert isinstance(fc_dict, dict)
+    assert alt.FuelConverter.from_pydict(fc_dict).to_pydict() == fc_dict
+
+
 if __name__ == "__main__":
     test_pydict()